@@ -0,0 +1,181 @@
+// Gas and storage benchmark of the goods contracts on a local sandbox.
+//
+//   npm run compile            # build wasm into ./compilers
+//   npm run bench [report.json]
+//
+// near-workspaces is installed by `npm run bench` with --no-save, it is not a devDependency
+// so package-lock.json and `npm ci` stay unaffected by the sandbox binaries it pulls in.
+//
+// Every scenario records the gas burnt by the transaction and all its receipts and the
+// storage_usage delta of the contract account, the report is written as JSON so runs
+// before and after a storage change can be diffed.
+const fs = require('fs');
+const path = require('path');
+const { Worker, NEAR } = require('near-workspaces');
+
+const WASM_DIR = path.join(__dirname, '..', 'compilers', 'contracts', 'goods');
+const ROCKS_WASM = path.join(WASM_DIR, 'rocks', 'rocks.wasm');
+const HOLDER_WASM = path.join(WASM_DIR, 'rockNFTCollectionHolder', 'rock_nft_collection_holder.wasm');
+const ENVIRONMENTS_WASM = path.join(WASM_DIR, 'environments', 'environments.wasm');
+
+const REPORT_PATH = process.argv[2] || path.join(__dirname, 'report.json');
+const GAS = '300000000000000';
+const INIT_IMO_FEE = NEAR.parse('0.0001');
+const ROCK_PRICE = NEAR.parse('1');
+
+const CONTRACT_METADATA = { spec: 'nft-1.0.0', name: 'Rove bench', symbol: 'ROVE' };
+const TOKEN_METADATA = { title: 'bench', description: null, media: null, copies: 1 };
+
+function gasBurnt(result) {
+  const outcome = result.result;
+  return outcome.receipts_outcome.reduce(
+    (total, receipt) => total + BigInt(receipt.outcome.gas_burnt),
+    BigInt(outcome.transaction_outcome.outcome.gas_burnt),
+  );
+}
+
+// run a call and record its gas and the storage delta of `contract`
+async function measure(report, name, contract, signer, method, args, attachedDeposit) {
+  const storageBefore = (await contract.accountView()).storage_usage;
+  const result = await signer.callRaw(contract, method, args, { gas: GAS, attachedDeposit });
+  if (result.failed) {
+    throw new Error(`${name} failed: ${JSON.stringify(result.Failure)}`);
+  }
+  const storageAfter = (await contract.accountView()).storage_usage;
+  report[name] = {
+    method,
+    gas_burnt: gasBurnt(result).toString(),
+    storage_bytes: storageAfter - storageBefore,
+  };
+  console.log(`${name}: ${report[name].gas_burnt} gas, ${report[name].storage_bytes} bytes`);
+}
+
+function publicZone(zoneIndex, from, to) {
+  return {
+    zone_index: zoneIndex,
+    price: ROCK_PRICE.toString(),
+    core_team_addr: '',
+    collection_addr: '',
+    mint_policy: 'public_fixed_price',
+    rock_index_from: from,
+    rock_index_to: to,
+  };
+}
+
+async function benchRocks(root, report) {
+  const rocks = await root.devDeploy(ROCKS_WASM, { initialBalance: NEAR.parse('100').toJSON() });
+  await rocks.call(rocks, 'new', {
+    admin_id: root.accountId,
+    operator_id: root.accountId,
+    treasury_id: root.accountId,
+    init_imo_fee: INIT_IMO_FEE.toString(),
+    rock_purchase_fee: 500,
+    metadata: CONTRACT_METADATA,
+  });
+  const owner = await root.createSubAccount('owner', { initialBalance: NEAR.parse('100').toJSON() });
+  const buyer = await root.createSubAccount('buyer', { initialBalance: NEAR.parse('100').toJSON() });
+
+  for (const rocksCount of [500, 5000]) {
+    const metaverseId = `bench-${rocksCount}`;
+    await measure(
+      report, `rocks.init_metaverse.${rocksCount}`, rocks, owner, 'init_metaverse',
+      { metaverse_id: metaverseId, zone3: publicZone(3, 2, rocksCount + 1) },
+      NEAR.parse('10').toJSON(),
+    );
+  }
+  await measure(
+    report, 'rocks.add_zone.500', rocks, owner, 'add_zone',
+    { metaverse_id: 'bench-500', _zone: publicZone(4, 502, 1001) },
+    NEAR.parse('10').toJSON(),
+  );
+  await measure(
+    report, 'rocks.mint_rock.public', rocks, buyer, 'mint_rock',
+    {
+      metaverse_id: 'bench-500',
+      zone_index: 3,
+      rock_index: '2',
+      receiver_id: buyer.accountId,
+      token_metadata: TOKEN_METADATA,
+    },
+    NEAR.parse('1.1').toJSON(),
+  );
+}
+
+async function benchHolder(root, report) {
+  const collection = await root.devDeploy(ENVIRONMENTS_WASM, { initialBalance: NEAR.parse('50').toJSON() });
+  await collection.call(collection, 'new', {
+    admin_id: root.accountId,
+    operator_id: root.accountId,
+    treasury_id: root.accountId,
+    metadata: CONTRACT_METADATA,
+  });
+  const holderAccount = await root.createSubAccount('holder', { initialBalance: NEAR.parse('100').toJSON() });
+  await root.call(collection, 'create_nft', {
+    nft_type_id: 'pass',
+    price: '0',
+    token_metadata: TOKEN_METADATA,
+    max_supply: 100,
+  }, { attachedDeposit: '1' });
+  await root.call(collection, 'user_mint', { nft_type_id: 'pass', receiver_id: holderAccount.accountId }, { attachedDeposit: '1' });
+
+  const holder = await root.devDeploy(HOLDER_WASM, { initialBalance: NEAR.parse('100').toJSON() });
+  await holder.call(holder, 'new', {
+    admin_id: root.accountId,
+    operator_id: root.accountId,
+    treasury_id: root.accountId,
+    init_imo_fee: INIT_IMO_FEE.toString(),
+    rock_purchase_fee: 500,
+    init_imo_nft_holder_size: 500,
+    metadata: CONTRACT_METADATA,
+  });
+  await measure(
+    report, 'holder.init_metaverse.500', holder, holderAccount, 'init_metaverse',
+    {
+      metaverse_id: 'bench-holder',
+      _zone2: {
+        zone_index: 2,
+        price: '0',
+        core_team_addr: '',
+        collection_addr: collection.accountId,
+        mint_policy: 'holder_gated',
+        rock_index_from: 2,
+        rock_index_to: 501,
+      },
+    },
+    NEAR.parse('10').toJSON(),
+  );
+  await measure(
+    report, 'holder.add_zone.500', holder, holderAccount, 'add_zone',
+    { metaverse_id: 'bench-holder', _zone: publicZone(3, 502, 1001) },
+    NEAR.parse('10').toJSON(),
+  );
+  await measure(
+    report, 'holder.mint_rock.holder_gated', holder, holderAccount, 'mint_rock',
+    {
+      metaverse_id: 'bench-holder',
+      zone_index: 2,
+      rock_index: '2',
+      receiver_id: holderAccount.accountId,
+      token_metadata: TOKEN_METADATA,
+    },
+    NEAR.parse('0.1').toJSON(),
+  );
+}
+
+async function main() {
+  const worker = await Worker.init();
+  const report = {};
+  try {
+    await benchRocks(worker.rootAccount, report);
+    await benchHolder(worker.rootAccount, report);
+  } finally {
+    await worker.tearDown();
+  }
+  fs.writeFileSync(REPORT_PATH, JSON.stringify({ generated_at: new Date().toISOString(), scenarios: report }, null, 2));
+  console.log(`report written to ${REPORT_PATH}`);
+}
+
+main().catch((error) => {
+  console.error(error);
+  process.exit(1);
+});
@@ -0,0 +1,30 @@
+use crate::*;
+
+// version of the public method set, bumped when methods are deprecated or removed
+pub const API_VERSION: u32 = 1;
+
+// same shape as on the rock contracts, which still serve deprecated methods
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DeprecatedMethod {
+    pub method: String,
+    pub replacement: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ApiVersion {
+    pub version: u32,
+    pub deprecated_methods: Vec<DeprecatedMethod>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// api version for clients to negotiate with, no method of this contract is deprecated yet
+    pub fn api_version(&self) -> ApiVersion {
+        ApiVersion {
+            version: API_VERSION,
+            deprecated_methods: vec![],
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use near_contract_standards::non_fungible_token::approval::NonFungibleTokenApproval;
+use near_contract_standards::non_fungible_token::core::{
+    NonFungibleTokenCore, NonFungibleTokenResolver,
+};
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+// one approval of nft_approvals
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenApproval {
+    pub approval_id: u64,
+    // approvals of this contract do not expire, always None
+    pub expires_at: Option<U64>,
+    pub expired: bool,
+}
+
+// standard core and approval methods, transfers and approvals also log AccountActivity
+#[near_bindgen]
+impl NonFungibleTokenCore for Contract {
+    #[payable]
+    fn nft_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) {
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        self.tokens
+            .nft_transfer(receiver_id.clone(), token_id.clone(), approval_id, memo);
+        emit_account_activity(vec![AccountActivityLog::new(
+            &owner_id,
+            ActivityAction::Transfer,
+            &token_id,
+            Some(&receiver_id),
+            None,
+        )]);
+    }
+
+    #[payable]
+    fn nft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        // nft_transfer_call transfers before calling the receiver, nft_resolve_transfer logs a
+        // returned token
+        emit_account_activity(vec![AccountActivityLog::new(
+            &owner_id,
+            ActivityAction::Transfer,
+            &token_id,
+            Some(&receiver_id),
+            None,
+        )]);
+        self.tokens
+            .nft_transfer_call(receiver_id, token_id, approval_id, memo, msg)
+    }
+
+    fn nft_token(&self, token_id: TokenId) -> Option<Token> {
+        self.tokens.nft_token(token_id)
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenResolver for Contract {
+    #[private]
+    fn nft_resolve_transfer(
+        &mut self,
+        previous_owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approved_account_ids: Option<HashMap<AccountId, u64>>,
+    ) -> bool {
+        let transferred = self.tokens.nft_resolve_transfer(
+            previous_owner_id.clone(),
+            receiver_id.clone(),
+            token_id.clone(),
+            approved_account_ids,
+        );
+        // the receiver returned the token
+        if !transferred && self.tokens.owner_by_id.get(&token_id) == Some(previous_owner_id.clone())
+        {
+            emit_account_activity(vec![AccountActivityLog::new(
+                &receiver_id,
+                ActivityAction::Transfer,
+                &token_id,
+                Some(&previous_owner_id),
+                None,
+            )]);
+        }
+        transferred
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenApproval for Contract {
+    #[payable]
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        msg: Option<String>,
+    ) -> Option<Promise> {
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        let promise = self
+            .tokens
+            .nft_approve(token_id.clone(), account_id.clone(), msg);
+        emit_account_activity(vec![AccountActivityLog::new(
+            &owner_id,
+            ActivityAction::List,
+            &token_id,
+            Some(&account_id),
+            None,
+        )]);
+        promise
+    }
+
+    #[payable]
+    fn nft_revoke(&mut self, token_id: TokenId, account_id: AccountId) {
+        self.tokens.nft_revoke(token_id, account_id);
+    }
+
+    #[payable]
+    fn nft_revoke_all(&mut self, token_id: TokenId) {
+        self.tokens.nft_revoke_all(token_id);
+    }
+
+    fn nft_is_approved(
+        &self,
+        token_id: TokenId,
+        approved_account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool {
+        self.tokens
+            .nft_is_approved(token_id, approved_account_id, approval_id)
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// every approval of a token by approved account with its approval_id, None if the token
+    /// does not exist. Same shape as on the rock contracts
+    pub fn nft_approvals(&self, token_id: TokenId) -> Option<HashMap<AccountId, TokenApproval>> {
+        self.tokens.owner_by_id.get(&token_id)?;
+        Some(
+            self.tokens
+                .approvals_by_id
+                .as_ref()
+                .and_then(|by_id| by_id.get(&token_id))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(account_id, approval_id)| {
+                    (
+                        account_id,
+                        TokenApproval {
+                            approval_id,
+                            expires_at: None,
+                            expired: false,
+                        },
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// whether `account_id` is approved for the token (matching `approval_id` when given),
+    /// false for unknown tokens instead of panicking like nft_is_approved
+    pub fn is_approved(
+        &self,
+        token_id: TokenId,
+        account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool {
+        self.nft_approvals(token_id)
+            .and_then(|approvals| approvals.get(&account_id).cloned())
+            .map(|approval| {
+                approval_id
+                    .map(|approval_id| approval_id == approval.approval_id)
+                    .unwrap_or(true)
+            })
+            .unwrap_or(false)
+    }
+}
@@ -1,6 +1,9 @@
 use std::fmt;
 
+use near_sdk::json_types::U128;
+use near_sdk::env;
 use near_sdk::serde::{Deserialize, Serialize};
+use rove_contracts_interface::next_event_nonce;
 
 /// Enum that represents the data type of the EventLog.
 /// The enum can either be an NftMint or an NftTransfer.
@@ -12,6 +15,14 @@ use near_sdk::serde::{Deserialize, Serialize};
 pub enum EventLogVariant {
     NftMint(Vec<NftMintLog>),
     NftTransfer(Vec<NftTransferLog>),
+    NftBurn(Vec<NftBurnLog>),
+    PayoutFailed(Vec<PayoutLog>),
+    PayoutRetried(Vec<PayoutLog>),
+    ParametersUpdate(Vec<ParametersUpdateLog>),
+    SaleStateUpdate(Vec<SaleStateLog>),
+    EnvironmentUpgrade(Vec<EnvironmentUpgradeLog>),
+    MetadataFrozen(Vec<MetadataFrozenLog>),
+    NftGifted(Vec<NftGiftedLog>),
 }
 
 /// Interface to capture data about an event
@@ -40,6 +51,17 @@ impl fmt::Display for EventLog {
     }
 }
 
+impl EventLog {
+    /// log the event with the next `event_nonce` of this contract, nonces increase by one
+    /// for every emitted event so indexers can detect missed or duplicated receipts
+    pub fn emit(&self) {
+        let event_nonce = next_event_nonce();
+        let mut event_json = serde_json::to_value(self).unwrap();
+        event_json["event_nonce"] = event_nonce.into();
+        env::log_str(&format!("EVENT_JSON:{}", event_json));
+    }
+}
+
 /// An event log to capture token minting
 ///
 /// Arguments
@@ -77,3 +99,91 @@ pub struct NftTransferLog {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memo: Option<String>,
 }
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PayoutLog {
+    pub payout_id: u64,
+    pub receiver_id: String,
+    pub amount: U128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ParamChangeLog {
+    pub name: String,
+    // JSON encoded values
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ParametersUpdateLog {
+    pub admin_id: String,
+    pub changes: Vec<ParamChangeLog>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SaleStateLog {
+    pub nft_type_id: String,
+    // None for a newly created nft type
+    pub from: Option<String>,
+    pub to: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftBurnLog {
+    pub owner_id: String,
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EnvironmentUpgradeLog {
+    pub owner_id: String,
+    pub source_type_id: String,
+    pub target_type_id: String,
+    pub burned_token_ids: Vec<String>,
+    pub token_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetadataFrozenLog {
+    pub token_id: String,
+    pub frozen_by: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftGiftedLog {
+    pub payer_id: String,
+    pub receiver_id: String,
+    pub token_ids: Vec<String>,
+    pub price: U128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
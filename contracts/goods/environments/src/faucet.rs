@@ -0,0 +1,57 @@
+use crate::*;
+
+// testnet faucet: anyone mints up to max_per_account free tokens, paying storage only
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FaucetMode {
+    pub max_per_account: u32,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// admin enables (or disables with None) the faucet on a .testnet deployment, for QA and
+    /// partner integrations to mint environments without funded accounts or operator keys
+    #[payable]
+    pub fn set_faucet_mode(&mut self, max_per_account: Option<u32>) {
+        self.assert_admin_only();
+        self.faucet_mode = max_per_account.map(|max_per_account| {
+            require!(
+                env::current_account_id().as_str().ends_with(".testnet"),
+                RoveError::InvalidArgument.detail("faucet mode is only for testnet deployments")
+            );
+            require!(
+                max_per_account > 0,
+                RoveError::InvalidArgument.detail("max_per_account must be > 0")
+            );
+            FaucetMode { max_per_account }
+        });
+    }
+
+    pub fn get_faucet_mode(&self) -> Option<FaucetMode> {
+        self.faucet_mode.clone()
+    }
+
+    pub fn get_faucet_mints(&self, account_id: AccountId) -> u32 {
+        self.faucet_mints.get(&account_id).unwrap_or_default()
+    }
+
+    /// faucet mode only: mints a token of a live nft type to the caller without its price, the
+    /// caller attaches the storage of the token and gets back the rest
+    #[payable]
+    pub fn faucet_mint(&mut self, nft_type_id: String) -> Token {
+        let account_id = env::predecessor_account_id();
+        let faucet_mode = self
+            .faucet_mode
+            .clone()
+            .unwrap_or_else(|| env::panic_str(RoveError::FaucetNotEnabled.as_str()));
+        let faucet_mints = self.faucet_mints.get(&account_id).unwrap_or_default();
+        require!(
+            faucet_mints < faucet_mode.max_per_account,
+            RoveError::FaucetLimitReached.as_str()
+        );
+        self.faucet_mints.insert(&account_id, &(faucet_mints + 1));
+
+        self.internal_user_mint(nft_type_id, account_id, 1, true)
+            .remove(0)
+    }
+}
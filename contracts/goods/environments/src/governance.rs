@@ -0,0 +1,64 @@
+use near_sdk::serde_json;
+
+use crate::*;
+
+// configuration changes applied together by set_parameters, None keeps the current value
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ParamsPatch {
+    pub admin_id: Option<AccountId>,
+    pub operator_id: Option<AccountId>,
+    pub treasury_id: Option<AccountId>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// apply a batch of configuration changes in one admin call (e.g. one DAO proposal),
+    /// values equal to the current ones are skipped so the call is idempotent
+    #[payable]
+    pub fn set_parameters(&mut self, patch: ParamsPatch) {
+        self.assert_admin_only();
+        let mut changes: Vec<ParamChangeLog> = vec![];
+        if let Some(admin_id) = patch.admin_id {
+            record_param_change(&mut changes, "admin_id", &self.admin_id, &admin_id);
+            self.admin_id = admin_id;
+        }
+        if let Some(operator_id) = patch.operator_id {
+            record_param_change(&mut changes, "operator_id", &self.operator_id, &operator_id);
+            self.internal_change_operator(operator_id);
+        }
+        if let Some(treasury_id) = patch.treasury_id {
+            record_param_change(&mut changes, "treasury_id", &self.treasury_id, &treasury_id);
+            self.treasury_id = treasury_id;
+        }
+        if changes.is_empty() {
+            return;
+        }
+        let parameters_update_log: EventLog = EventLog {
+            standard: "governance".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::ParametersUpdate(vec![ParametersUpdateLog {
+                admin_id: env::predecessor_account_id().to_string(),
+                changes,
+                memo: None,
+            }]),
+        };
+
+        parameters_update_log.emit();
+    }
+}
+
+fn record_param_change<T: Serialize + PartialEq>(
+    changes: &mut Vec<ParamChangeLog>,
+    name: &str,
+    before: &T,
+    after: &T,
+) {
+    if before != after {
+        changes.push(ParamChangeLog {
+            name: name.to_string(),
+            before: serde_json::to_string(before).unwrap(),
+            after: serde_json::to_string(after).unwrap(),
+        });
+    }
+}
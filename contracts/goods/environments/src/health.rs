@@ -0,0 +1,60 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+// get_health, amounts in yoctoNEAR
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractHealth {
+    pub storage_usage: U64,
+    // balance locked by the storage of the contract
+    pub storage_stake: U128,
+    pub account_balance: U128,
+    // account balance above the storage stake, refunds and payouts fail once it is spent
+    pub available_balance: U128,
+    pub nft_type_count: u64,
+    pub token_count: U64,
+    // payouts that failed and wait for retry_payout
+    pub failed_payout_count: u64,
+    pub failed_payout_amount: U128,
+    // nft types whose sale is paused
+    pub paused_nft_types: Vec<String>,
+    // tokens of a replaced operator are still migrating
+    pub operator_migration_pending: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// storage, balance, pending payouts and paused sales of the contract in one view, for
+    /// monitoring to alert when the available balance runs low
+    pub fn get_health(&self) -> ContractHealth {
+        let storage_usage = env::storage_usage();
+        let storage_stake = env::storage_byte_cost() * Balance::from(storage_usage);
+        let account_balance = env::account_balance();
+        let failed_payout_amount: Balance = self
+            .failed_payouts
+            .values()
+            .map(|failed_payout| failed_payout.amount.0)
+            .sum();
+        let mut paused_nft_types: Vec<String> = self
+            .sale_states
+            .iter()
+            .filter(|(_, sale_state)| *sale_state == SaleState::Paused)
+            .map(|(nft_type_id, _)| nft_type_id)
+            .collect();
+        paused_nft_types.sort();
+
+        ContractHealth {
+            storage_usage: U64(storage_usage),
+            storage_stake: U128(storage_stake),
+            account_balance: U128(account_balance),
+            available_balance: U128(account_balance.saturating_sub(storage_stake)),
+            nft_type_count: self.tokens_price.len(),
+            token_count: U64(self.tokens.owner_by_id.len()),
+            failed_payout_count: self.failed_payouts.len(),
+            failed_payout_amount: U128(failed_payout_amount),
+            paused_nft_types,
+            operator_migration_pending: self.operator_migration.is_some(),
+        }
+    }
+}
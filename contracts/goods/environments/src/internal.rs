@@ -1,17 +1,27 @@
-use near_sdk::json_types::U128;
 use near_sdk::require;
 use crate::*;
 
-//convert the royalty percentage and amount to pay into a payout (U128)
-pub(crate) fn royalty_to_payout(royalty_percentage: u16, amount_to_pay: Balance) -> U128 {
-    U128(royalty_percentage as u128 * amount_to_pay / ONE_HUNDRED_PERCENT_IN_BPS as u128)
-}
-
 pub(crate) fn assert_at_least_one_yocto() {
-    require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR")
+    require!(
+        env::attached_deposit() >= 1,
+        RoveError::DepositRequired.as_str()
+    )
 }
 
+// token id has format {nft_type_id}:{token_count}, token_count comes from the
+// monotonic tokens_minted counter of the type so ids are never reused
 pub(crate) fn gen_token_id(nft_type_id: &String, token_count: &u64) -> String {
     let token_id = format!("{}:{}", nft_type_id, token_count);
     token_id
 }
+
+// inverse of gen_token_id, nft_type_id may itself contain ':'
+pub(crate) fn parse_token_id(token_id: &TokenId) -> (String, u64) {
+    let (nft_type_id, token_count) = token_id
+        .rsplit_once(':')
+        .unwrap_or_else(|| env::panic_str(RoveError::InvalidTokenId.as_str()));
+    let token_count: u64 = token_count
+        .parse()
+        .unwrap_or_else(|_| env::panic_str(RoveError::InvalidTokenId.as_str()));
+    (nft_type_id.to_string(), token_count)
+}
@@ -23,26 +23,61 @@ use near_contract_standards::non_fungible_token::{Token, TokenId};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LazyOption, UnorderedMap};
 use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
     assert_one_yocto, env, near_bindgen, require, AccountId, Balance, BorshStorageKey,
     PanicOnDefault, Promise, PromiseOrValue,
 };
+use rove_contracts_interface::{
+    compute_payout, emit_account_activity, royalty_to_payout, AccountActivityLog,
+    ActivityAction, Payout, ONE_HUNDRED_PERCENT_IN_BPS,
+};
 use std::collections::HashMap;
 
+pub use crate::api_version::*;
+pub use crate::approval::*;
+pub use crate::deposit_guard::*;
+pub use crate::errors::*;
 pub use crate::events::*;
+pub use crate::faucet::*;
+pub use crate::governance::*;
+pub use crate::health::*;
+pub use crate::migrate::*;
+pub use crate::operator_migration::*;
 use crate::internal::*;
+pub use crate::primary_split::*;
 pub use crate::royalty::*;
+pub use crate::sale_state::*;
+pub use crate::settlement::*;
+pub use crate::treasury_split::*;
 pub use crate::types::*;
+pub use crate::upgrade::*;
 
+mod api_version;
+mod approval;
+mod deposit_guard;
+#[path = "../../shared/errors.rs"]
+mod errors;
 mod events;
+mod faucet;
+mod governance;
+mod health;
 mod internal;
+mod metadata_freeze;
+mod migrate;
+mod operator_migration;
+mod primary_split;
 mod royalty;
+mod sale_state;
+#[path = "../../shared/settlement.rs"]
+mod settlement;
+mod treasury_split;
 mod types;
+mod upgrade;
 
-const ONE_HUNDRED_PERCENT_IN_BPS: u16 = 10_000;
 pub const NFT_METADATA_SPEC: &str = "1.0.0";
 pub const NFT_STANDARD_NAME: &str = "nep171";
-pub const NOT_FOUND_NFT_TYPE_ID_ERROR: &str = "Not found nft_type_id";
+pub const MAX_MINT_QUANTITY: u32 = 20;
 
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
@@ -59,6 +94,38 @@ pub struct Contract {
     pub tokens_price: UnorderedMap<String, u128>,
     pub tokens_metadata: UnorderedMap<String, TokenMetadata>,
     pub tokens_minted: UnorderedMap<String, u64>,
+
+    // Map payout_id => transfer that failed and can be retried
+    pub failed_payouts: UnorderedMap<u64, FailedPayout>,
+    pub next_payout_id: u64,
+    pub treasury_settlement: TreasurySettlement,
+
+    // Map nft_type_id => how primary sale proceeds are shared
+    pub primary_splits: UnorderedMap<String, PrimarySplit>,
+
+    // Map nft_type_id => sale state
+    pub sale_states: UnorderedMap<String, SaleState>,
+
+    // Map target nft_type_id => recipe to upgrade into it
+    pub upgrade_recipes: UnorderedMap<String, UpgradeRecipe>,
+    // Map target nft_type_id => upgrade statistics
+    pub upgrade_stats: UnorderedMap<String, UpgradeStats>,
+
+    // Map token_id => timestamp its metadata was frozen at
+    pub frozen_metadata: UnorderedMap<TokenId, u64>,
+
+    // platform wallets sharing treasury payouts, empty pays treasury_id
+    pub treasury_beneficiaries: Vec<TreasuryBeneficiary>,
+    // tokens of a replaced operator that still have to be moved to operator_id
+    pub operator_migration: Option<OperatorMigration>,
+
+    // mints attaching more than this many times their price plus storage are rejected, 0: off
+    pub max_deposit_multiplier: u32,
+
+    // testnet faucet minting free tokens, None: off
+    pub faucet_mode: Option<FaucetMode>,
+    // Map account_id => tokens minted with faucet_mint
+    pub faucet_mints: UnorderedMap<AccountId, u32>,
 }
 
 #[derive(BorshSerialize, BorshStorageKey)]
@@ -73,6 +140,13 @@ enum StorageKey {
     TokensMetadata,
     TokensMinted,
     Royalties,
+    FailedPayouts,
+    PrimarySplits,
+    SaleStates,
+    UpgradeRecipes,
+    UpgradeStats,
+    FrozenMetadata,
+    FaucetMints,
 }
 
 #[near_bindgen]
@@ -84,42 +158,75 @@ impl Contract {
         treasury_id: AccountId,
         metadata: NFTContractMetadata,
     ) -> Self {
-        assert!(!env::state_exists(), "Already initialized");
+        require!(!env::state_exists(), RoveError::AlreadyInitialized.as_str());
         metadata.assert_valid();
 
-        Self {
-            admin_id: admin_id.into(),
-            operator_id: operator_id.clone().into(),
-            treasury_id: treasury_id.into(),
-            royalties: UnorderedMap::new(StorageKey::Royalties),
-            max_supplies: UnorderedMap::new(StorageKey::MaxSupplies),
-            tokens_price: UnorderedMap::new(StorageKey::TokensPrice),
-            tokens_metadata: UnorderedMap::new(StorageKey::TokensMetadata),
+        Self::from_old_state(OldContract {
             tokens: NonFungibleToken::new(
                 StorageKey::NonFungibleToken,
-                operator_id.clone().into(),
+                operator_id.clone(),
                 Some(StorageKey::TokenMetadata),
                 Some(StorageKey::Enumeration),
                 Some(StorageKey::Approval),
             ),
             metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
+            admin_id,
+            operator_id: operator_id.clone(),
+            treasury_id,
+            royalties: UnorderedMap::new(StorageKey::Royalties),
+            max_supplies: UnorderedMap::new(StorageKey::MaxSupplies),
+            tokens_price: UnorderedMap::new(StorageKey::TokensPrice),
+            tokens_metadata: UnorderedMap::new(StorageKey::TokensMetadata),
             tokens_minted: UnorderedMap::new(StorageKey::TokensMinted),
+        })
+    }
+
+    // state of a new contract or of a migrated 1.0 one, the fields added since 1.0 start empty
+    // or at their default. New state is added here so both paths initialize it
+    fn from_old_state(old: OldContract) -> Self {
+        Self {
+            tokens: old.tokens,
+            metadata: old.metadata,
+            admin_id: old.admin_id,
+            operator_id: old.operator_id,
+            treasury_id: old.treasury_id,
+            royalties: old.royalties,
+            max_supplies: old.max_supplies,
+            tokens_price: old.tokens_price,
+            tokens_metadata: old.tokens_metadata,
+            tokens_minted: old.tokens_minted,
+
+            failed_payouts: UnorderedMap::new(StorageKey::FailedPayouts),
+            next_payout_id: 0,
+            treasury_settlement: TreasurySettlement::Native,
+            primary_splits: UnorderedMap::new(StorageKey::PrimarySplits),
+            sale_states: UnorderedMap::new(StorageKey::SaleStates),
+            upgrade_recipes: UnorderedMap::new(StorageKey::UpgradeRecipes),
+            upgrade_stats: UnorderedMap::new(StorageKey::UpgradeStats),
+            frozen_metadata: UnorderedMap::new(StorageKey::FrozenMetadata),
+            treasury_beneficiaries: Vec::new(),
+            operator_migration: None,
+            max_deposit_multiplier: 0,
+            faucet_mode: None,
+            faucet_mints: UnorderedMap::new(StorageKey::FaucetMints),
         }
     }
 
     fn assert_admin_only(&mut self) {
         // assert that the user attached greater than or equal 1 yoctoNEAR. This is for security and so that user will be redirected to the NEAR wallet
         assert_at_least_one_yocto();
-        assert_eq!(env::predecessor_account_id(), self.admin_id, "Unauthorized");
+        require!(
+            env::predecessor_account_id() == self.admin_id,
+            RoveError::Unauthorized.as_str()
+        );
     }
 
     fn assert_operator_only(&mut self) {
         // assert that the user attached greater than or equal 1 yoctoNEAR. This is for security and so that user will be redirected to the NEAR wallet
         assert_at_least_one_yocto();
-        assert_eq!(
-            env::predecessor_account_id(),
-            self.tokens.owner_id,
-            "Unauthorized"
+        require!(
+            env::predecessor_account_id() == self.tokens.owner_id,
+            RoveError::Unauthorized.as_str()
         );
     }
 
@@ -127,23 +234,22 @@ impl Contract {
     #[payable]
     pub fn change_admin(&mut self, new_admin_id: AccountId) {
         self.assert_admin_only();
-        self.admin_id = new_admin_id.into();
+        self.admin_id = new_admin_id;
     }
 
     /// change tokens.owner_id and operator_id to new_operator_id
-    /// move all tokens of current operator to new operator
+    /// tokens of current operator are then moved to new operator by migrate_operator_tokens
     #[payable]
     pub fn change_operator(&mut self, new_operator_id: AccountId) {
         self.assert_admin_only();
 
-        self.tokens.owner_id = new_operator_id.clone();
-        self.operator_id = new_operator_id.into();
+        self.internal_change_operator(new_operator_id);
     }
 
     #[payable]
     pub fn change_treasury(&mut self, new_treasury_id: AccountId) {
         self.assert_admin_only();
-        self.treasury_id = new_treasury_id.into();
+        self.treasury_id = new_treasury_id;
     }
 
     #[payable]
@@ -153,6 +259,14 @@ impl Contract {
         updated_royalties: HashMap<AccountId, u16>,
     ) {
         self.assert_admin_only();
+        require!(
+            updated_royalties
+                .values()
+                .map(|royalty| u32::from(*royalty))
+                .sum::<u32>()
+                <= u32::from(ONE_HUNDRED_PERCENT_IN_BPS),
+            RoveError::InvalidArgument.detail("total royalties must <= 10_000")
+        );
         let initial_storage_usage = env::storage_usage();
         self.royalties.insert(&nft_type_id, &updated_royalties);
         if env::storage_usage() > initial_storage_usage {
@@ -175,6 +289,26 @@ impl Contract {
         self.treasury_id
     }
 
+    pub fn contract_version(&self) -> VersionInfo {
+        VersionInfo {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("BUILD_GIT_COMMIT").to_string(),
+            rustc_version: env!("BUILD_RUSTC_VERSION").to_string(),
+            build_target: env!("BUILD_TARGET").to_string(),
+            build_profile: env!("BUILD_PROFILE").to_string(),
+        }
+    }
+
+    pub fn get_config(&self) -> ContractConfig {
+        ContractConfig {
+            admin_id: self.admin_id.clone(),
+            operator_id: self.tokens.owner_id.clone(),
+            treasury_id: self.treasury_id.clone(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
     #[payable]
     pub fn create_nft(
         &mut self,
@@ -190,74 +324,181 @@ impl Contract {
         self.max_supplies.insert(&nft_type_id, &max_supply);
         self.tokens_minted.insert(&nft_type_id, &0);
         self.royalties.insert(&nft_type_id, &HashMap::new());
+        // a new type starts live, admin can move it to NotStarted, Paused or Closed
+        if self.sale_states.get(&nft_type_id).is_none() {
+            self.internal_set_sale_state(&nft_type_id, SaleState::Live);
+        }
     }
 
+    /// the caller pays the price and storage, receiver_id gets the token. Minting for another
+    /// account is logged as a gift naming the payer, which also gets back any excess deposit
     #[payable]
     pub fn user_mint(&mut self, nft_type_id: String, receiver_id: AccountId) -> Token {
+        self.internal_user_mint(nft_type_id, receiver_id, 1, false)
+            .remove(0)
+    }
+
+    /// mint `quantity` tokens of an nft type in one call, deposit covers quantity * price
+    #[payable]
+    pub fn user_mint_batch(
+        &mut self,
+        nft_type_id: String,
+        receiver_id: AccountId,
+        quantity: u32,
+    ) -> Vec<Token> {
+        self.internal_user_mint(nft_type_id, receiver_id, quantity, false)
+    }
+
+    // a faucet mint is free, its payer attaches the storage only
+    fn internal_user_mint(
+        &mut self,
+        nft_type_id: String,
+        receiver_id: AccountId,
+        quantity: u32,
+        is_faucet_mint: bool,
+    ) -> Vec<Token> {
+        require!(
+            quantity > 0 && quantity <= MAX_MINT_QUANTITY,
+            RoveError::InvalidArgument.detail("quantity must be > 0 and <= 20")
+        );
+        let payer_id = env::predecessor_account_id();
+        require!(
+            receiver_id != env::current_account_id(),
+            RoveError::InvalidArgument.detail("receiver_id can not be the contract")
+        );
         let initial_storage_usage = env::storage_usage();
         let max_supply = self
             .max_supplies
             .get(&nft_type_id)
-            .expect(NOT_FOUND_NFT_TYPE_ID_ERROR);
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()));
         let token_metadata = self
             .tokens_metadata
             .get(&nft_type_id)
-            .expect(NOT_FOUND_NFT_TYPE_ID_ERROR);
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()));
         let token_price = self
             .tokens_price
             .get(&nft_type_id)
-            .expect(NOT_FOUND_NFT_TYPE_ID_ERROR);
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()));
         let token_minted = self
             .tokens_minted
             .get(&nft_type_id)
-            .expect(NOT_FOUND_NFT_TYPE_ID_ERROR);
-        require!(token_minted < max_supply, "REACH MAX SUPPLY");
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()));
+        require!(
+            self.internal_sale_state(&nft_type_id) == SaleState::Live,
+            RoveError::SaleNotLive.as_str()
+        );
+        require!(
+            token_minted + quantity as u64 <= max_supply,
+            RoveError::MaxSupplyReached.as_str()
+        );
         let mut is_operator_mint = false;
-        if env::predecessor_account_id() == self.operator_id {
+        if payer_id == self.operator_id {
             self.assert_operator_only();
             is_operator_mint = true;
         }
 
-        let price: u128 = if is_operator_mint { 0 } else { token_price };
-
-        let token_id = gen_token_id(&nft_type_id, &(token_minted + 1));
-        let token = self.tokens.internal_mint_with_refund(
-            token_id.clone(),
-            receiver_id.clone(),
-            Some(token_metadata.clone()),
-            None,
-        );
+        let price: u128 = if is_operator_mint || is_faucet_mint {
+            0
+        } else {
+            token_price * quantity as u128
+        };
+        self.assert_deposit_accepted(price, quantity);
+
+        let mut tokens: Vec<Token> = vec![];
+        for token_count in token_minted + 1..=token_minted + quantity as u64 {
+            let token_id = gen_token_id(&nft_type_id, &token_count);
+            require!(
+                self.tokens.owner_by_id.get(&token_id).is_none(),
+                RoveError::TokenAlreadyExists.as_str()
+            );
+            tokens.push(self.tokens.internal_mint_with_refund(
+                token_id,
+                receiver_id.clone(),
+                Some(token_metadata.clone()),
+                None,
+            ));
+        }
 
-        self.tokens_minted.insert(&nft_type_id, &(token_minted + 1));
+        let token_minted = token_minted + quantity as u64;
+        self.tokens_minted.insert(&nft_type_id, &token_minted);
+        if token_minted == max_supply {
+            self.internal_set_sale_state(&nft_type_id, SaleState::SoldOut);
+        }
 
         let storage_used = env::storage_usage() - initial_storage_usage;
         let required_storage_cost = env::storage_byte_cost() * Balance::from(storage_used);
 
         require!(
             env::attached_deposit() >= price,
-            "NOT ATTACHING ENOUGH DEPOSIT"
+            RoveError::NotEnoughDeposit.as_str()
         );
+        if is_faucet_mint {
+            require!(
+                env::attached_deposit() >= required_storage_cost,
+                RoveError::NotEnoughDeposit.detail(format!(
+                    "Need {} yoctoNEAR to pay storage",
+                    required_storage_cost
+                ))
+            );
+        }
 
+        // storage is paid first, the payer gets back what remains above the price
         if !is_operator_mint && env::attached_deposit() > required_storage_cost {
-            Promise::new(self.treasury_id.clone())
-                .transfer(env::attached_deposit() - required_storage_cost);
+            let remain = env::attached_deposit() - required_storage_cost;
+            let proceeds = std::cmp::min(price, remain);
+            if proceeds > 0 {
+                self.internal_primary_payout(&nft_type_id, proceeds);
+            }
+            if remain > proceeds {
+                self.internal_payout(payer_id.clone(), remain - proceeds);
+            }
         }
 
-        // Construct the mint log as per the events standard.
+        // Construct the mint log as per the events standard, one log for the whole batch.
         let nft_mint_log: EventLog = EventLog {
             standard: NFT_STANDARD_NAME.to_string(),
             version: NFT_METADATA_SPEC.to_string(),
             event: EventLogVariant::NftMint(vec![NftMintLog {
                 owner_id: receiver_id.to_string(),
-                token_ids: vec![token_id.to_string()],
+                token_ids: tokens.iter().map(|token| token.token_id.clone()).collect(),
                 memo: None,
             }]),
         };
 
         // Log the serialized json.
-        env::log_str(&nft_mint_log.to_string());
+        nft_mint_log.emit();
+        let token_price = price / quantity as u128;
+        emit_account_activity(
+            tokens
+                .iter()
+                .map(|token| {
+                    AccountActivityLog::new(
+                        &receiver_id,
+                        ActivityAction::Mint,
+                        &token.token_id,
+                        Some(&payer_id).filter(|payer_id| **payer_id != receiver_id),
+                        Some(token_price).filter(|price| *price > 0),
+                    )
+                })
+                .collect(),
+        );
+
+        if !is_operator_mint && receiver_id != payer_id {
+            let nft_gifted_log: EventLog = EventLog {
+                standard: "nft_gift".to_string(),
+                version: "1.0.0".to_string(),
+                event: EventLogVariant::NftGifted(vec![NftGiftedLog {
+                    payer_id: payer_id.to_string(),
+                    receiver_id: receiver_id.to_string(),
+                    token_ids: tokens.iter().map(|token| token.token_id.clone()).collect(),
+                    price: U128(price),
+                    memo: None,
+                }]),
+            };
+            nft_gifted_log.emit();
+        }
 
-        token
+        tokens
     }
 
     #[payable]
@@ -267,12 +508,14 @@ impl Contract {
         self.tokens_price.insert(&nft_type_id, &price_u128);
     }
 
-    pub fn get_token_price(self, nft_type_id: String) -> u128 {
+    // U128 like the price of create_nft and update_token_price, a raw u128 loses precision in
+    // JS clients
+    pub fn get_token_price(self, nft_type_id: String) -> U128 {
         let price = self
             .tokens_price
             .get(&nft_type_id)
-            .expect(NOT_FOUND_NFT_TYPE_ID_ERROR);
-        price
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()));
+        U128(price)
     }
 
     // update default token_metadata
@@ -294,10 +537,11 @@ impl Contract {
         updated_token_metadata: TokenMetadata,
     ) {
         self.assert_operator_only();
+        self.assert_metadata_not_frozen(&token_id);
         if let Some(token_metadata_by_id) = &mut self.tokens.token_metadata_by_id {
             token_metadata_by_id.insert(&token_id, &updated_token_metadata);
         } else {
-            env::panic_str("token_metadata_by_id is null");
+            env::panic_str(&RoveError::TokenNotFound.detail("token_metadata_by_id is null"));
         }
     }
 
@@ -307,19 +551,25 @@ impl Contract {
         self.metadata.set(&updated_contract_metadata);
     }
 
-    pub fn get_current_supply(self, nft_type_id : String) -> u64 {
-        let max_supply = self.max_supplies.get(&nft_type_id).expect(NOT_FOUND_NFT_TYPE_ID_ERROR);
-        let token_minted = self.tokens_minted.get(&nft_type_id).expect(NOT_FOUND_NFT_TYPE_ID_ERROR);
+    pub fn get_current_supply(self, nft_type_id: String) -> u64 {
+        let max_supply = self
+            .max_supplies
+            .get(&nft_type_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()));
+        let token_minted = self
+            .tokens_minted
+            .get(&nft_type_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()));
         max_supply - token_minted
     }
 
     pub fn get_max_supply(self, nft_type_id: String) -> u64 {
-        self.max_supplies.get(&nft_type_id).expect(NOT_FOUND_NFT_TYPE_ID_ERROR)
+        self.max_supplies
+            .get(&nft_type_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()))
     }
 }
 
-near_contract_standards::impl_non_fungible_token_core!(Contract, tokens);
-near_contract_standards::impl_non_fungible_token_approval!(Contract, tokens);
 near_contract_standards::impl_non_fungible_token_enumeration!(Contract, tokens);
 
 #[near_bindgen]
@@ -328,3 +578,88 @@ impl NonFungibleTokenMetadataProvider for Contract {
         self.metadata.get().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use near_contract_standards::non_fungible_token::metadata::NFT_METADATA_SPEC as NFT_SPEC;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, ONE_NEAR};
+
+    use super::*;
+
+    const NFT_TYPE_ID: &str = "sword";
+
+    fn context(predecessor_id: AccountId, attached_deposit: Balance) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .predecessor_account_id(predecessor_id)
+            .attached_deposit(attached_deposit);
+        builder
+    }
+
+    // admin accounts(0), operator accounts(1), treasury accounts(2), nft type NFT_TYPE_ID
+    // costs 1 NEAR
+    fn setup() -> Contract {
+        testing_env!(context(accounts(1), 1).build());
+        let mut contract = Contract::new(
+            accounts(0),
+            accounts(1),
+            accounts(2),
+            NFTContractMetadata {
+                spec: NFT_SPEC.to_string(),
+                name: "Environments".to_string(),
+                symbol: "ENV".to_string(),
+                icon: None,
+                base_uri: None,
+                reference: None,
+                reference_hash: None,
+            },
+        );
+        contract.create_nft(
+            NFT_TYPE_ID.to_string(),
+            U128(ONE_NEAR),
+            TokenMetadata {
+                title: Some("Sword".to_string()),
+                description: None,
+                media: None,
+                media_hash: None,
+                copies: None,
+                issued_at: None,
+                expires_at: None,
+                starts_at: None,
+                updated_at: None,
+                extra: None,
+                reference: None,
+                reference_hash: None,
+            },
+            10,
+        );
+        contract
+    }
+
+    fn user_mint(contract: &mut Contract) -> TokenId {
+        testing_env!(context(accounts(3), 2 * ONE_NEAR).build());
+        contract
+            .user_mint(NFT_TYPE_ID.to_string(), accounts(3))
+            .token_id
+    }
+
+    #[test]
+    fn mint_after_burn_does_not_reuse_token_id() {
+        let mut contract = setup();
+        assert_eq!(user_mint(&mut contract), "sword:1");
+        assert_eq!(user_mint(&mut contract), "sword:2");
+
+        contract.internal_burn(&"sword:2".to_string(), &accounts(3));
+        assert_eq!(user_mint(&mut contract), "sword:3");
+        assert!(contract
+            .tokens
+            .owner_by_id
+            .get(&"sword:2".to_string())
+            .is_none());
+        assert_eq!(
+            parse_token_id(&"sword:3".to_string()),
+            (NFT_TYPE_ID.to_string(), 3)
+        );
+    }
+}
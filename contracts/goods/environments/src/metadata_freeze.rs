@@ -0,0 +1,51 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// operator permanently freezes the metadata of a minted token,
+    /// update_minted_token_metadata is rejected afterwards
+    #[payable]
+    pub fn freeze_token_metadata(&mut self, token_id: TokenId) {
+        self.assert_operator_only();
+        require!(
+            self.tokens.owner_by_id.get(&token_id).is_some(),
+            RoveError::TokenNotFound.as_str()
+        );
+        self.assert_metadata_not_frozen(&token_id);
+
+        let initial_storage_usage = env::storage_usage();
+        self.frozen_metadata
+            .insert(&token_id, &env::block_timestamp());
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+
+        let metadata_frozen_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::MetadataFrozen(vec![MetadataFrozenLog {
+                token_id,
+                frozen_by: env::predecessor_account_id().to_string(),
+                memo: None,
+            }]),
+        };
+        metadata_frozen_log.emit();
+    }
+
+    /// timestamp the metadata of a token was frozen at, None if it is still editable
+    pub fn get_metadata_frozen_at(&self, token_id: TokenId) -> Option<U64> {
+        self.frozen_metadata.get(&token_id).map(U64)
+    }
+
+    pub(crate) fn assert_metadata_not_frozen(&self, token_id: &TokenId) {
+        require!(
+            self.frozen_metadata.get(token_id).is_none(),
+            RoveError::MetadataFrozen.as_str()
+        );
+    }
+}
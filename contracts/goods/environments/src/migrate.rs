@@ -0,0 +1,29 @@
+use crate::*;
+
+// Contract state of the 1.0 release, read once by migrate. Keep it as deployed
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldContract {
+    pub tokens: NonFungibleToken,
+    pub metadata: LazyOption<NFTContractMetadata>,
+    pub admin_id: AccountId,
+    pub operator_id: AccountId,
+    pub treasury_id: AccountId,
+    pub royalties: UnorderedMap<String, HashMap<AccountId, u16>>,
+    pub max_supplies: UnorderedMap<String, u64>,
+    pub tokens_price: UnorderedMap<String, u128>,
+    pub tokens_metadata: UnorderedMap<String, TokenMetadata>,
+    pub tokens_minted: UnorderedMap<String, u64>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// upgrade the state of a 1.0 deployment in place, called by the upgrade script in the
+    /// deploy transaction. Fails on an already migrated state, which does not read as 1.0
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old_state: OldContract =
+            env::state_read().unwrap_or_else(|| env::panic_str(RoveError::NotInitialized.as_str()));
+        Self::from_old_state(old_state)
+    }
+}
@@ -0,0 +1,144 @@
+use near_contract_standards::non_fungible_token::refund_approved_account_ids;
+
+use crate::*;
+
+// gas bounds the number of tokens moved by one migrate_operator_tokens call
+pub const MAX_OPERATOR_MIGRATION_BATCH: u64 = 100;
+pub const DEFAULT_OPERATOR_MIGRATION_BATCH: u64 = 50;
+
+// tokens owned by the previous operator, moved to the new one by migrate_operator_tokens
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorMigration {
+    pub from_id: AccountId,
+    pub to_id: AccountId,
+    pub migrated: u64,
+}
+
+// progress returned by get_operator_migration
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorMigrationStatus {
+    pub from_id: AccountId,
+    pub to_id: AccountId,
+    pub migrated: u64,
+    pub remaining: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// admin moves up to `limit` tokens of the previous operator to the current one, call it
+    /// again until get_operator_migration returns None. Returns the number of tokens left
+    #[payable]
+    pub fn migrate_operator_tokens(&mut self, limit: Option<u64>) -> u64 {
+        self.assert_admin_only();
+        let mut migration = self
+            .operator_migration
+            .clone()
+            .unwrap_or_else(|| env::panic_str(RoveError::OperatorMigrationNotFound.as_str()));
+        let limit = limit.unwrap_or(DEFAULT_OPERATOR_MIGRATION_BATCH);
+        require!(
+            0 < limit && limit <= MAX_OPERATOR_MIGRATION_BATCH,
+            RoveError::InvalidArgument.detail("limit must be > 0 and <= 100")
+        );
+
+        let token_ids: Vec<TokenId> = self
+            .tokens
+            .tokens_per_owner
+            .as_ref()
+            .and_then(|by_owner| by_owner.get(&migration.from_id))
+            .map(|token_ids| token_ids.iter().take(limit as usize).collect())
+            .unwrap_or_default();
+        for token_id in token_ids.iter() {
+            if let Some(approved_account_ids) = self
+                .tokens
+                .approvals_by_id
+                .as_mut()
+                .and_then(|by_id| by_id.remove(token_id))
+            {
+                refund_approved_account_ids(migration.from_id.clone(), &approved_account_ids);
+            }
+            self.tokens
+                .internal_transfer_unguarded(token_id, &migration.from_id, &migration.to_id);
+        }
+
+        if !token_ids.is_empty() {
+            let nft_transfer_log: EventLog = EventLog {
+                standard: NFT_STANDARD_NAME.to_string(),
+                version: NFT_METADATA_SPEC.to_string(),
+                event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                    authorized_id: None,
+                    old_owner_id: migration.from_id.to_string(),
+                    new_owner_id: migration.to_id.to_string(),
+                    token_ids: token_ids.clone(),
+                    memo: Some(String::from("migrate_operator_tokens")),
+                }]),
+            };
+            nft_transfer_log.emit();
+            emit_account_activity(
+                token_ids
+                    .iter()
+                    .map(|token_id| {
+                        AccountActivityLog::new(
+                            &migration.from_id,
+                            ActivityAction::Transfer,
+                            token_id,
+                            Some(&migration.to_id),
+                            None,
+                        )
+                    })
+                    .collect(),
+            );
+        }
+
+        migration.migrated += token_ids.len() as u64;
+        let remaining = self.internal_owner_token_count(&migration.from_id);
+        self.operator_migration = if remaining == 0 {
+            None
+        } else {
+            Some(migration)
+        };
+        remaining
+    }
+
+    /// migration of the tokens of the previous operator, None when there is nothing left to move
+    pub fn get_operator_migration(&self) -> Option<OperatorMigrationStatus> {
+        self.operator_migration
+            .clone()
+            .map(|migration| OperatorMigrationStatus {
+                remaining: self.internal_owner_token_count(&migration.from_id),
+                from_id: migration.from_id,
+                to_id: migration.to_id,
+                migrated: migration.migrated,
+            })
+    }
+
+    // used by change_operator and set_parameters, a migration must finish before the next change
+    pub(crate) fn internal_change_operator(&mut self, new_operator_id: AccountId) {
+        let operator_id = self.operator_id.clone();
+        if new_operator_id != operator_id {
+            require!(
+                self.operator_migration.is_none(),
+                RoveError::OperatorMigrationPending.as_str()
+            );
+            if self.internal_owner_token_count(&operator_id) > 0 {
+                self.operator_migration = Some(OperatorMigration {
+                    from_id: operator_id,
+                    to_id: new_operator_id.clone(),
+                    migrated: 0,
+                });
+            }
+        }
+        self.tokens.owner_id = new_operator_id.clone();
+        self.operator_id = new_operator_id;
+    }
+
+    fn internal_owner_token_count(&self, account_id: &AccountId) -> u64 {
+        self.tokens
+            .tokens_per_owner
+            .as_ref()
+            .and_then(|by_owner| by_owner.get(account_id))
+            .map(|token_ids| token_ids.len())
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,84 @@
+use crate::*;
+
+// gas bounds the number of payouts of a single mint
+pub const MAX_PRIMARY_SPLIT_RECEIVERS: usize = 10;
+
+// how primary sale proceeds of an nft type are shared, the rest goes to treasury
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum PrimarySplit {
+    // pay the royalty receivers of the nft type their royalty share
+    Royalties,
+    // pay each receiver its share in bps
+    Custom { split: HashMap<AccountId, u16> },
+}
+
+#[near_bindgen]
+impl Contract {
+    /// admin sets (or removes with None) the primary sale split of an nft type
+    #[payable]
+    pub fn set_primary_split(&mut self, nft_type_id: String, primary_split: Option<PrimarySplit>) {
+        self.assert_admin_only();
+        require!(
+            self.tokens_price.get(&nft_type_id).is_some(),
+            RoveError::NftTypeNotFound.as_str()
+        );
+        let initial_storage_usage = env::storage_usage();
+        match primary_split {
+            Some(primary_split) => {
+                if let PrimarySplit::Custom { split } = &primary_split {
+                    assert_valid_split(split);
+                }
+                self.primary_splits.insert(&nft_type_id, &primary_split);
+            }
+            None => {
+                self.primary_splits.remove(&nft_type_id);
+            }
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_primary_split(&self, nft_type_id: String) -> Option<PrimarySplit> {
+        self.primary_splits.get(&nft_type_id)
+    }
+
+    // pay primary sale proceeds to the split receivers, treasury gets the remainder
+    pub(crate) fn internal_primary_payout(&mut self, nft_type_id: &String, amount: Balance) {
+        let split = match self.primary_splits.get(nft_type_id) {
+            Some(PrimarySplit::Royalties) => self.royalties.get(nft_type_id).unwrap_or_default(),
+            Some(PrimarySplit::Custom { split }) => split,
+            None => HashMap::new(),
+        };
+        assert_valid_split(&split);
+
+        let mut treasury_amount = amount;
+        for (receiver_id, share) in split.iter() {
+            let receiver_amount = royalty_to_payout(*share, amount).0;
+            if receiver_amount > 0 {
+                treasury_amount -= receiver_amount;
+                self.internal_payout(receiver_id.clone(), receiver_amount);
+            }
+        }
+        if treasury_amount > 0 {
+            self.internal_pay_treasury(treasury_amount);
+        }
+    }
+}
+
+fn assert_valid_split(split: &HashMap<AccountId, u16>) {
+    require!(
+        split.len() <= MAX_PRIMARY_SPLIT_RECEIVERS,
+        RoveError::TooManyItems.detail("primary split has too many receivers")
+    );
+    let total: u32 = split.values().map(|share| *share as u32).sum();
+    require!(
+        total <= ONE_HUNDRED_PERCENT_IN_BPS as u32,
+        RoveError::InvalidArgument.detail("primary split total must <= 10000 bps")
+    );
+}
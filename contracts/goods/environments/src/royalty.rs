@@ -23,47 +23,20 @@ pub trait NonFungibleTokenRoyalty {
 impl NonFungibleTokenRoyalty for Contract {
     //calculates the payout for a token given the passed in balance. This is a view method
     fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: u32) -> Payout {
-        // token id has format {nft_type_id}:{token_count}
-        let token_id_parts: Vec<&str> = token_id.split(':').collect();
-
-        require!(token_id_parts.len() == 2, "token_id has wrong format");
-
-        let nft_type_id_str = token_id_parts.get(0).expect("token_id has wrong format");
-        let nft_type_id = format!("{}", nft_type_id_str);
-
-        let token_owner_id = self.tokens.owner_by_id.get(&token_id).expect("token not exist");
-        //keep track of the total perpetual royalties
-        let mut total_perpetual: u16 = 0;
-        //get the u128 version of the passed in balance (which was U128 before)
-        let balance_u128 = u128::from(balance);
-        //keep track of the payout object to send back
-        let mut payout_object = Payout {
-            payout: HashMap::new()
-        };
-
-        let royalties = self.royalties.get(&nft_type_id).expect(NOT_FOUND_NFT_TYPE_ID_ERROR);
-
-        //get the royalty object from token
-        //make sure we're not paying out to too many people (GAS limits this)
-        assert!(royalties.len() as u32 <= max_len_payout, "Market cannot payout to that many receivers");
-
-        //go through each key and value in the royalty object
-        for (k, v) in royalties.iter() {
-            //get the key
-            let key = k.clone();
-            //only insert into the payout if the key isn't the token owner (we add their payout at the end)
-            if key != token_owner_id {
-                //
-                payout_object.payout.insert(key, royalty_to_payout(*v, balance_u128));
-                total_perpetual += v;
-            }
-        }
-
-        // payout to previous owner who gets 100% - total perpetual royalties
-        payout_object.payout.insert(token_owner_id, royalty_to_payout(ONE_HUNDRED_PERCENT_IN_BPS - total_perpetual, balance_u128));
-
-        //return the payout object
-        payout_object
+        let (nft_type_id, _) = parse_token_id(&token_id);
+
+        let token_owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        let royalties = self
+            .royalties
+            .get(&nft_type_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()));
+
+        compute_payout(&royalties, token_owner_id, u128::from(balance), max_len_payout)
+            .unwrap_or_else(|| env::panic_str(RoveError::TooManyPayoutReceivers.as_str()))
     }
 
     //transfers the token to the receiver ID and returns the payout object that should be payed given the passed in balance.
@@ -81,8 +54,20 @@ impl NonFungibleTokenRoyalty for Contract {
         assert_one_yocto();
 
         let payout = self.nft_payout(token_id.clone(), balance, max_len_payout);
-
-        self.tokens.nft_transfer(receiver_id.try_into().unwrap(), token_id, Some(approval_id), memo);
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+
+        self.tokens.nft_transfer(receiver_id.clone(), token_id.clone(), Some(approval_id), memo);
+        emit_account_activity(vec![AccountActivityLog::new(
+            &owner_id,
+            ActivityAction::Transfer,
+            &token_id,
+            Some(&receiver_id),
+            Some(balance.0),
+        )]);
 
         payout
     }
@@ -0,0 +1,84 @@
+use crate::*;
+
+// sale lifecycle of an nft type, only Live types can be minted
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum SaleState {
+    NotStarted,
+    Live,
+    Paused,
+    // set by user_mint when the last token of max_supply is minted
+    SoldOut,
+    // final, the type can not be minted anymore
+    Closed,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// admin moves the sale of an nft type to a new state:
+    /// NotStarted -> Live | Closed, Live <-> Paused, Live | Paused | SoldOut -> Closed
+    #[payable]
+    pub fn set_sale_state(&mut self, nft_type_id: String, sale_state: SaleState) {
+        self.assert_admin_only();
+        let current_state = self.internal_sale_state(&nft_type_id);
+        let allowed = match (current_state, sale_state) {
+            (SaleState::NotStarted, SaleState::Live) => true,
+            (SaleState::Live, SaleState::Paused) => true,
+            (SaleState::Paused, SaleState::Live) => true,
+            (SaleState::Closed, _) => false,
+            (_, SaleState::Closed) => true,
+            _ => false,
+        };
+        require!(allowed, RoveError::InvalidSaleStateTransition.as_str());
+        self.internal_set_sale_state(&nft_type_id, sale_state);
+    }
+
+    pub fn get_sale_state(&self, nft_type_id: String) -> SaleState {
+        self.internal_sale_state(&nft_type_id)
+    }
+
+    pub fn get_sale_states(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<(String, SaleState)> {
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+        self.tokens_price
+            .keys()
+            .skip(start as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .map(|nft_type_id| {
+                let sale_state = self.internal_sale_state(&nft_type_id);
+                (nft_type_id, sale_state)
+            })
+            .collect()
+    }
+
+    pub(crate) fn internal_sale_state(&self, nft_type_id: &String) -> SaleState {
+        require!(
+            self.tokens_price.get(nft_type_id).is_some(),
+            RoveError::NftTypeNotFound.as_str()
+        );
+        // types created before sale states existed stay live
+        self.sale_states.get(nft_type_id).unwrap_or(SaleState::Live)
+    }
+
+    pub(crate) fn internal_set_sale_state(&mut self, nft_type_id: &String, sale_state: SaleState) {
+        let current_state = self.sale_states.insert(nft_type_id, &sale_state);
+        let sale_state_log: EventLog = EventLog {
+            standard: "environment_sale".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::SaleStateUpdate(vec![SaleStateLog {
+                nft_type_id: nft_type_id.clone(),
+                from: current_state.map(|state| format!("{:?}", state)),
+                to: format!("{:?}", sale_state),
+                memo: None,
+            }]),
+        };
+
+        sale_state_log.emit();
+    }
+}
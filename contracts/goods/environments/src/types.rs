@@ -2,11 +2,25 @@ use crate::*;
 use near_sdk::{
     serde::{Deserialize, Serialize},
 };
-use near_sdk::json_types::U128;
 
-//defines the payout type we'll be returning as a part of the royalty standards.
+// contract configuration returned by get_config
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
-pub struct Payout {
-    pub payout: HashMap<AccountId, U128>,
+pub struct ContractConfig {
+    pub admin_id: AccountId,
+    pub operator_id: AccountId,
+    pub treasury_id: AccountId,
+    pub version: String,
+}
+
+// build metadata embedded at compile time by build.rs
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VersionInfo {
+    pub name: String,
+    pub version: String,
+    pub git_commit: String,
+    pub rustc_version: String,
+    pub build_target: String,
+    pub build_profile: String,
 }
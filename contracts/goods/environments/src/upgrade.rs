@@ -0,0 +1,227 @@
+use crate::*;
+
+pub const MAX_UPGRADE_BURN_COUNT: u32 = 20;
+
+// burn `burn_count` tokens of `source_type_id` to mint one token of the target type
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UpgradeRecipe {
+    pub source_type_id: String,
+    pub burn_count: u32,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UpgradeStats {
+    pub upgrades: u64,
+    pub tokens_burned: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator sets (or removes with None) the recipe to upgrade into target_type_id
+    #[payable]
+    pub fn set_upgrade_recipe(&mut self, target_type_id: String, recipe: Option<UpgradeRecipe>) {
+        self.assert_operator_only();
+        require!(
+            self.tokens_price.get(&target_type_id).is_some(),
+            RoveError::NftTypeNotFound.as_str()
+        );
+        let initial_storage_usage = env::storage_usage();
+        match recipe {
+            Some(recipe) => {
+                require!(
+                    self.tokens_price.get(&recipe.source_type_id).is_some(),
+                    RoveError::NftTypeNotFound.as_str()
+                );
+                require!(
+                    recipe.source_type_id != target_type_id,
+                    RoveError::InvalidArgument.detail("source and target type must differ")
+                );
+                require!(
+                    recipe.burn_count > 0 && recipe.burn_count <= MAX_UPGRADE_BURN_COUNT,
+                    RoveError::InvalidArgument.detail("burn_count must be > 0 and <= 20")
+                );
+                self.upgrade_recipes.insert(&target_type_id, &recipe);
+            }
+            None => {
+                self.upgrade_recipes.remove(&target_type_id);
+            }
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    /// burn the caller's environments listed in burn_token_ids and mint one
+    /// environment of target_type_id following its upgrade recipe
+    #[payable]
+    pub fn upgrade_environment(
+        &mut self,
+        burn_token_ids: Vec<TokenId>,
+        target_type_id: String,
+    ) -> Token {
+        assert_at_least_one_yocto();
+        let initial_storage_usage = env::storage_usage();
+        let owner_id = env::predecessor_account_id();
+        let recipe = self
+            .upgrade_recipes
+            .get(&target_type_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::NoUpgradeRecipe.as_str()));
+        require!(
+            burn_token_ids.len() == recipe.burn_count as usize,
+            RoveError::InvalidUpgradeTokens.detail("wrong number of tokens to burn")
+        );
+        require!(
+            self.internal_sale_state(&target_type_id) == SaleState::Live,
+            RoveError::SaleNotLive.as_str()
+        );
+        let max_supply = self
+            .max_supplies
+            .get(&target_type_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()));
+        let token_minted = self
+            .tokens_minted
+            .get(&target_type_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()));
+        require!(
+            token_minted < max_supply,
+            RoveError::MaxSupplyReached.as_str()
+        );
+
+        for token_id in burn_token_ids.iter() {
+            let (nft_type_id, _) = parse_token_id(token_id);
+            require!(
+                nft_type_id == recipe.source_type_id,
+                RoveError::InvalidUpgradeTokens.detail("token is not of the recipe source type")
+            );
+            self.internal_burn(token_id, &owner_id);
+        }
+
+        let token_metadata = self
+            .tokens_metadata
+            .get(&target_type_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::NftTypeNotFound.as_str()));
+        let token_id = gen_token_id(&target_type_id, &(token_minted + 1));
+        require!(
+            self.tokens.owner_by_id.get(&token_id).is_none(),
+            RoveError::TokenAlreadyExists.as_str()
+        );
+        let token = self.tokens.internal_mint_with_refund(
+            token_id.clone(),
+            owner_id.clone(),
+            Some(token_metadata),
+            None,
+        );
+        self.tokens_minted
+            .insert(&target_type_id, &(token_minted + 1));
+        if token_minted + 1 == max_supply {
+            self.internal_set_sale_state(&target_type_id, SaleState::SoldOut);
+        }
+
+        let mut upgrade_stats = self.upgrade_stats.get(&target_type_id).unwrap_or_default();
+        upgrade_stats.upgrades += 1;
+        upgrade_stats.tokens_burned += burn_token_ids.len() as u64;
+        self.upgrade_stats.insert(&target_type_id, &upgrade_stats);
+
+        // burned tokens free storage, only a net increase is charged
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                owner_id.clone(),
+            );
+        }
+
+        let nft_burn_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                owner_id: owner_id.to_string(),
+                token_ids: burn_token_ids.clone(),
+                memo: Some("upgrade_environment".to_string()),
+            }]),
+        };
+        nft_burn_log.emit();
+
+        let nft_mint_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftMint(vec![NftMintLog {
+                owner_id: owner_id.to_string(),
+                token_ids: vec![token_id.clone()],
+                memo: Some("upgrade_environment".to_string()),
+            }]),
+        };
+        nft_mint_log.emit();
+        emit_account_activity(vec![AccountActivityLog::new(
+            &owner_id,
+            ActivityAction::Mint,
+            &token_id,
+            None,
+            None,
+        )]);
+
+        let upgrade_log: EventLog = EventLog {
+            standard: "environment_upgrade".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::EnvironmentUpgrade(vec![EnvironmentUpgradeLog {
+                owner_id: owner_id.to_string(),
+                source_type_id: recipe.source_type_id,
+                target_type_id,
+                burned_token_ids: burn_token_ids,
+                token_id,
+                memo: None,
+            }]),
+        };
+        upgrade_log.emit();
+
+        token
+    }
+
+    pub fn get_upgrade_recipe(&self, target_type_id: String) -> Option<UpgradeRecipe> {
+        self.upgrade_recipes.get(&target_type_id)
+    }
+
+    pub fn get_upgrade_stats(&self, target_type_id: String) -> UpgradeStats {
+        self.upgrade_stats.get(&target_type_id).unwrap_or_default()
+    }
+
+    // remove a token owned by owner_id with its metadata, enumeration entry and approvals
+    pub(crate) fn internal_burn(&mut self, token_id: &TokenId, owner_id: &AccountId) {
+        let token_owner_id = self
+            .tokens
+            .owner_by_id
+            .get(token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        require!(
+            &token_owner_id == owner_id,
+            RoveError::NotTokenOwner.as_str()
+        );
+
+        self.tokens.owner_by_id.remove(token_id);
+        if let Some(token_metadata_by_id) = &mut self.tokens.token_metadata_by_id {
+            token_metadata_by_id.remove(token_id);
+        }
+        self.frozen_metadata.remove(token_id);
+        if let Some(tokens_per_owner) = &mut self.tokens.tokens_per_owner {
+            let mut owner_tokens = tokens_per_owner
+                .get(owner_id)
+                .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+            owner_tokens.remove(token_id);
+            if owner_tokens.is_empty() {
+                tokens_per_owner.remove(owner_id);
+            } else {
+                tokens_per_owner.insert(owner_id, &owner_tokens);
+            }
+        }
+        if let Some(approvals_by_id) = &mut self.tokens.approvals_by_id {
+            approvals_by_id.remove(token_id);
+        }
+        if let Some(next_approval_id_by_id) = &mut self.tokens.next_approval_id_by_id {
+            next_approval_id_by_id.remove(token_id);
+        }
+    }
+}
@@ -0,0 +1,385 @@
+// ext_contract generates a function per method that clippy checks against the argument
+// limit, mint_rock takes use_token_id and request_id on top of the rock arguments
+#![allow(clippy::too_many_arguments)]
+use std::collections::HashMap;
+
+use near_contract_standards::non_fungible_token::metadata::TokenMetadata;
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
+use near_sdk::{ext_contract, AccountId};
+
+use crate::*;
+
+/// nft collection holder rocks contract (contracts/goods/rockNFTCollectionHolder)
+#[ext_contract(ext_collection_holder)]
+pub trait CollectionHolder {
+    // payable, attach the zone price plus storage, nft holder zones verify use_token_id,
+    // retries with the same request_id return the minted token_id
+    fn mint_rock(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        receiver_id: AccountId,
+        token_metadata: TokenMetadata,
+        use_token_id: Option<TokenId>,
+        request_id: Option<String>,
+    ) -> Option<TokenId>;
+
+    // payable, attach the zone price plus storage
+    fn mint_rock_with_proof(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        receiver_id: AccountId,
+        token_metadata: TokenMetadata,
+        proof: Vec<Base58CryptoHash>,
+    );
+
+    // payable, metaverse owner attaches init_imo_fee per added rock plus storage
+    fn extend_zone(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        additional_rocks: U128,
+    ) -> Zone;
+
+    // payable, metaverse owner attaches slug_fee plus storage, None releases the slug
+    fn set_metaverse_slug(&mut self, metaverse_id: String, slug: Option<String>);
+
+    // payable, metaverse owner attaches the bid, the outbid bid is refunded
+    fn bid_slug(&mut self, slug: String, metaverse_id: String);
+
+    fn settle_slug_auction(&mut self, slug: String);
+
+    // payable, attach 1 yoctoNEAR plus storage, returns the region token id
+    fn wrap_region(&mut self, token_ids: Vec<TokenId>, token_metadata: TokenMetadata) -> TokenId;
+
+    // payable, attach 1 yoctoNEAR
+    fn unwrap_region(&mut self, region_id: TokenId) -> Vec<TokenId>;
+
+    // payable, attach 1 yoctoNEAR plus the storage of the message
+    fn gift_rock(&mut self, token_id: TokenId, receiver_id: AccountId, message: String);
+
+    // faucet mode (testnet) only, attach the storage of the rock, minted to the caller
+    fn faucet_mint_rock(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        token_metadata: TokenMetadata,
+    ) -> TokenId;
+
+    fn get_faucet_mode(&self) -> Option<FaucetMode>;
+
+    fn get_faucet_mints(&self, account_id: AccountId) -> u32;
+
+    // payable, called by the operator and the metaverse owner with the same new_addr
+    fn change_collection_address(&mut self, metaverse_id: String, new_addr: AccountId);
+
+    // payable, operator attaches 1 yoctoNEAR plus storage, until_timestamp in nanoseconds
+    fn hold_rocks(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        indices: Vec<u128>,
+        until_timestamp: U64,
+        reason: Option<String>,
+    );
+
+    // payable, operator attaches 1 yoctoNEAR
+    fn release_rocks(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        indices: Vec<u128>,
+        reason: Option<String>,
+    );
+
+    // payable, metaverse owner attaches 1 yoctoNEAR plus storage, holder_gated zones only.
+    // Non-holders attaching at least price mint instead of being refunded, None stops it
+    fn set_zone_non_holder_price(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        price: Option<U128>,
+    );
+
+    // payable, metaverse owner attaches 1 yoctoNEAR plus storage, None lifts the restriction
+    fn set_zone_receiver_restriction(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        restriction: Option<ZoneReceiverRestriction>,
+    );
+
+    // payable, metaverse owner attaches 1 yoctoNEAR plus storage
+    fn add_zone_receivers(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        account_ids: Vec<AccountId>,
+    );
+
+    // payable, metaverse owner attaches 1 yoctoNEAR
+    fn remove_zone_receivers(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        account_ids: Vec<AccountId>,
+    );
+
+    // payable, metaverse owner attaches 1 yoctoNEAR plus storage, at most 5 listeners
+    fn add_mint_listener(&mut self, metaverse_id: String, listener_id: AccountId);
+
+    // payable, metaverse owner attaches 1 yoctoNEAR
+    fn remove_mint_listener(&mut self, metaverse_id: String, listener_id: AccountId);
+
+    // payable, attach the storage of the approval, transfers with it fail after expires_at
+    fn nft_approve_with_expiry(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        expires_at: U64,
+        msg: Option<String>,
+    );
+
+    // payable, attach 1 yoctoNEAR
+    fn request_refund(&mut self, token_id: TokenId);
+
+    fn finalize_escrow(&mut self, token_id: TokenId);
+
+    // payable, called by an attribute writer of the metaverse
+    fn set_rock_attributes(&mut self, token_id: TokenId, attributes: Vec<(String, String)>);
+
+    fn contract_version(&self) -> VersionInfo;
+
+    // api version and the deprecated methods still served
+    fn api_version(&self) -> ApiVersion;
+
+    // storage deposit of a metaverse of zone_count zones (2 for init_metaverse), init_metaverse
+    // asks for the part of it the IMO fee does not cover
+    fn get_init_storage_quote(&self, zone_count: u16) -> U128;
+
+    // mints attaching more than get_max_accepted_deposit are rejected, None: no limit
+    fn get_max_accepted_deposit(&self, price: U128, token_count: u32) -> Option<U128>;
+
+    fn get_max_deposit_multiplier(&self) -> u32;
+
+    // operator caps checked by init_metaverse and add_zone, 0 disables a cap
+    fn get_metaverse_limits(&self) -> MetaverseLimits;
+
+    fn get_owned_metaverse_count(&self, account_id: AccountId) -> u32;
+
+    fn get_collection_for_metaverse(&self, metaverse_id: String) -> Option<String>;
+
+    fn get_metaverse_for_collection(&self, collection_addr: String) -> Option<String>;
+
+    fn get_pending_collection_change(
+        &self,
+        metaverse_id: String,
+    ) -> Option<PendingCollectionChange>;
+
+    fn check_holder_eligibility(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        account_id: AccountId,
+    ) -> HolderEligibility;
+
+    // deprecated, use get_zones
+    fn get_zone_info(&self, metaverse_id: String, zone_index: u16) -> String;
+
+    fn get_zone_remaining(&self, metaverse_id: String, zone_index: u16) -> U128;
+
+    fn is_zone_sold_out(&self, metaverse_id: String, zone_index: u16) -> bool;
+
+    // zones by zone_index with their minted count, price and sale phase
+    fn get_zone_summaries(&self, metaverse_id: String) -> Vec<ZoneSummary>;
+
+    // payable, metaverse owner attaches 1 yoctoNEAR
+    fn set_zone_visibility(&mut self, metaverse_id: String, zone_index: u16, visible: bool);
+
+    // zones by zone_index, hidden zones only with include_hidden
+    fn get_zones(&self, metaverse_id: String, include_hidden: Option<bool>) -> Vec<Zone>;
+
+    fn is_zone_visible(&self, metaverse_id: String, zone_index: u16) -> bool;
+
+    // owners of rocks from_rock..=to_rock of a zone (at most 500), None for unminted rocks
+    fn get_owners_for_range(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        from_rock: U128,
+        to_rock: U128,
+    ) -> Vec<Option<AccountId>>;
+
+    // community leaderboard: accounts holding the most rocks of the metaverse
+    fn get_top_holders(&self, metaverse_id: String, limit: Option<u64>) -> Vec<HolderCount>;
+
+    fn get_holder_count(&self, metaverse_id: String, account_id: AccountId) -> u64;
+
+    // tokens_minted entries that are not owned rocks of a zone range, or zones_minted counts
+    // that do not match them, of limit metaverses from from_index
+    fn verify_invariants(&self, from_index: Option<u64>, limit: Option<u64>) -> InvariantReport;
+
+    // collection tokens consumed by holder-gated mints, to carry over a redeployment
+    fn export_checker_usage(
+        &self,
+        metaverse_id: String,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> CheckerUsageChunk;
+
+    fn get_zone_non_holder_price(&self, metaverse_id: String, zone_index: u16) -> Option<U128>;
+
+    fn get_unminted_rocks(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<u128>;
+
+    fn get_purchases_for_account(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<Purchase>;
+
+    fn get_fee_tiers(&self) -> Vec<FeeTier>;
+
+    fn get_metaverse_volume(&self, metaverse_id: String) -> U128;
+
+    fn get_effective_fee(&self, metaverse_id: String) -> u32;
+
+    fn get_zone_royalties(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+    ) -> Option<HashMap<AccountId, u16>>;
+
+    // resale royalty the metaverse owner takes on top of the creator royalties, in bps
+    fn get_metaverse_owner_royalty(&self, metaverse_id: String) -> u16;
+
+    fn get_escrowed_sale(&self, token_id: TokenId) -> Option<EscrowedSale>;
+
+    fn get_rock_attributes(&self, token_id: TokenId) -> Vec<(String, String)>;
+
+    fn get_metaverse_themes(&self, metaverse_id: String) -> Vec<String>;
+
+    fn get_metaverse_tags(&self, metaverse_id: String) -> Vec<MetaverseTag>;
+
+    fn get_environment_compatibility(&self, class: CompatibilityClass) -> Vec<String>;
+
+    // whether an environment of nft_type_id can be attached to the rocks of the zone
+    fn is_environment_compatible(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        nft_type_id: String,
+    ) -> bool;
+
+    // world directory: sorted metaverse ids with the tag
+    fn find_metaverses_by_tag(
+        &self,
+        tag: MetaverseTag,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<String>;
+
+    fn verify_rock_in_map(
+        &self,
+        metaverse_id: String,
+        rock_index: u128,
+        position: String,
+        proof: Vec<Base58CryptoHash>,
+    ) -> bool;
+
+    fn get_mint_request(&self, account_id: AccountId, request_id: String) -> Option<TokenId>;
+
+    fn get_mint_listeners(&self, metaverse_id: String) -> Vec<AccountId>;
+
+    fn get_rock_hold(&self, metaverse_id: String, zone_index: u16, rock_index: u128)
+        -> Option<U64>;
+
+    fn get_held_rocks(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<u128>;
+
+    fn get_zone_receiver_restriction(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+    ) -> Option<ZoneReceiverRestriction>;
+
+    fn is_zone_receiver(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        account_id: AccountId,
+    ) -> bool;
+
+    fn compute_token_id(&self, metaverse_id: String, zone_index: u16, rock_index: u128) -> TokenId;
+
+    fn get_region_tokens(
+        &self,
+        region_id: TokenId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<TokenId>;
+
+    fn get_region_of_rock(&self, token_id: TokenId) -> Option<TokenId>;
+
+    fn get_gift_message(&self, token_id: TokenId) -> Option<GiftMessage>;
+
+    fn nft_token_detail(&self, token_id: TokenId) -> Option<TokenDetail>;
+
+    fn nft_tokens_detail_for_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<TokenDetail>;
+
+    fn is_denied(&self, account_id: AccountId) -> bool;
+
+    fn get_approval_expiry(&self, token_id: TokenId, account_id: AccountId) -> Option<U64>;
+
+    fn get_approval_expiries(&self, token_id: TokenId) -> HashMap<AccountId, U64>;
+
+    // approvals by approved account with their approval_id and expiry, None for unknown tokens
+    fn nft_approvals(&self, token_id: TokenId) -> Option<HashMap<AccountId, TokenApproval>>;
+
+    // false for unknown tokens and expired approvals
+    fn is_approved(
+        &self,
+        token_id: TokenId,
+        account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool;
+
+    fn get_metadata_frozen_at(&self, token_id: TokenId) -> Option<U64>;
+
+    fn get_lock_attestation(&self) -> Option<LockAttestation>;
+
+    fn resolve_slug(&self, slug: String) -> Option<String>;
+
+    fn get_metaverse_slug(&self, metaverse_id: String) -> Option<String>;
+
+    fn get_slug_fee(&self) -> U128;
+
+    fn get_slug_auction(&self, slug: String) -> Option<SlugAuction>;
+
+    fn get_slug_auctions(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<(String, SlugAuction)>;
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use near_contract_standards::non_fungible_token::{Token, TokenId};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{ext_contract, AccountId};
+
+use crate::*;
+
+/// environment nft contract (contracts/goods/environments)
+#[ext_contract(ext_environments)]
+pub trait Environments {
+    // payable, attach the token price plus storage, the excess is refunded to the caller and
+    // a receiver_id other than the caller is logged as a gift (NftGifted)
+    fn user_mint(&mut self, nft_type_id: String, receiver_id: AccountId) -> Token;
+
+    // payable, attach quantity times the token price plus storage
+    fn user_mint_batch(
+        &mut self,
+        nft_type_id: String,
+        receiver_id: AccountId,
+        quantity: u32,
+    ) -> Vec<Token>;
+
+    // payable, attach 1 yoctoNEAR plus storage
+    fn upgrade_environment(
+        &mut self,
+        burn_token_ids: Vec<TokenId>,
+        target_type_id: String,
+    ) -> Token;
+
+    fn contract_version(&self) -> VersionInfo;
+
+    // api version and the deprecated methods still served
+    fn api_version(&self) -> ApiVersion;
+
+    fn get_token_price(&self, nft_type_id: String) -> U128;
+
+    // mints attaching more than get_max_accepted_deposit are rejected, None: no limit
+    fn get_max_accepted_deposit(&self, price: U128, token_count: u32) -> Option<U128>;
+
+    fn get_max_deposit_multiplier(&self) -> u32;
+
+    fn get_current_supply(&self, nft_type_id: String) -> u64;
+
+    fn get_max_supply(&self, nft_type_id: String) -> u64;
+
+    fn get_sale_state(&self, nft_type_id: String) -> SaleState;
+
+    fn get_primary_split(&self, nft_type_id: String) -> Option<PrimarySplit>;
+
+    fn get_upgrade_recipe(&self, target_type_id: String) -> Option<UpgradeRecipe>;
+
+    fn get_upgrade_stats(&self, target_type_id: String) -> UpgradeStats;
+
+    fn get_metadata_frozen_at(&self, token_id: TokenId) -> Option<U64>;
+
+    // admin only, attach 1 yoctoNEAR. Moves the tokens of the operator replaced by
+    // change_operator or set_parameters, returns how many are left
+    fn migrate_operator_tokens(&mut self, limit: Option<u64>) -> u64;
+
+    fn get_operator_migration(&self) -> Option<OperatorMigrationStatus>;
+
+    // faucet mode (testnet) only, attach the storage of the token, minted to the caller
+    fn faucet_mint(&mut self, nft_type_id: String) -> Token;
+
+    fn get_faucet_mode(&self) -> Option<FaucetMode>;
+
+    fn get_faucet_mints(&self, account_id: AccountId) -> u32;
+
+    // approvals by approved account with their approval_id, None for unknown tokens
+    fn nft_approvals(&self, token_id: TokenId) -> Option<HashMap<AccountId, TokenApproval>>;
+
+    // false for unknown tokens
+    fn is_approved(
+        &self,
+        token_id: TokenId,
+        account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool;
+}
@@ -0,0 +1,544 @@
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, AccountId, Balance};
+
+/// Events emitted by the goods contracts that integrations react to.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+#[serde(crate = "near_sdk::serde")]
+pub enum EventLogVariant {
+    NftMint(Vec<NftMintLog>),
+    NftTransfer(Vec<NftTransferLog>),
+    NftBurn(Vec<NftBurnLog>),
+    NftMetadataUpdate(Vec<NftMetadataUpdateLog>),
+    ImoInit(Vec<ImoInitLog>),
+    ImoAddZone(Vec<ImoAddZoneLog>),
+    ZoneSoldOut(Vec<ZoneSoldOutLog>),
+    ZoneExtended(Vec<ZoneExtendedLog>),
+    RockAttributesUpdate(Vec<RockAttributesLog>),
+    EscrowHeld(Vec<EscrowLog>),
+    EscrowRefunded(Vec<EscrowLog>),
+    EscrowReleased(Vec<EscrowLog>),
+    RockGifted(Vec<RockGiftedLog>),
+    MetadataFrozen(Vec<MetadataFrozenLog>),
+    ContractLocked(Vec<ContractLockedLog>),
+    SaleStateUpdate(Vec<SaleStateLog>),
+    EnvironmentUpgrade(Vec<EnvironmentUpgradeLog>),
+    MetaverseSlugUpdate(Vec<MetaverseSlugLog>),
+    RegionWrapped(Vec<RegionLog>),
+    RegionUnwrapped(Vec<RegionLog>),
+    SlugAuctionStarted(Vec<SlugAuctionLog>),
+    SlugBid(Vec<SlugAuctionLog>),
+    SlugAuctionSettled(Vec<SlugAuctionLog>),
+    SlugAuctionCancelled(Vec<SlugAuctionLog>),
+    AccountsDenied(Vec<DenyListLog>),
+    AccountsAllowed(Vec<DenyListLog>),
+    CollectionChangeProposed(Vec<CollectionChangeLog>),
+    CollectionAddressChanged(Vec<CollectionChangeLog>),
+    CollectionChangeCancelled(Vec<CollectionChangeLog>),
+    InstallmentStarted(Vec<InstallmentLog>),
+    InstallmentPaid(Vec<InstallmentLog>),
+    InstallmentCompleted(Vec<InstallmentLog>),
+    InstallmentDefaulted(Vec<InstallmentLog>),
+    RaffleStarted(Vec<RaffleLog>),
+    RaffleEntered(Vec<RaffleLog>),
+    RaffleWon(Vec<RaffleLog>),
+    RaffleRefunded(Vec<RaffleLog>),
+    RaffleClaimed(Vec<RaffleLog>),
+    AirdropRootSet(Vec<AirdropLog>),
+    AirdropClaimed(Vec<AirdropLog>),
+    NftGifted(Vec<NftGiftedLog>),
+    RocksHeld(Vec<RockHoldLog>),
+    RocksReleased(Vec<RockHoldLog>),
+    CampaignCreated(Vec<CampaignLog>),
+    CampaignClaimed(Vec<CampaignLog>),
+    CampaignClosed(Vec<CampaignLog>),
+    ZoneVisibilityUpdate(Vec<ZoneVisibilityLog>),
+    AccountActivity(Vec<AccountActivityLog>),
+    DeprecatedCall(Vec<DeprecatedCallLog>),
+}
+
+/// Interface to capture data about an event
+///
+/// Arguments:
+/// * `standard`: name of standard e.g. nep171
+/// * `version`: e.g. 1.0.0
+/// * `event_nonce`: sequence number of the event in the emitting contract
+/// * `event`: associate event data
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EventLog {
+    pub standard: String,
+    pub version: String,
+    pub event_nonce: u64,
+
+    // `flatten` to not have "event": {<EventLogVariant>} in the JSON, just have the contents of {<EventLogVariant>}.
+    #[serde(flatten)]
+    pub event: EventLogVariant,
+}
+
+impl EventLog {
+    /// parse an `EVENT_JSON:` log line, None for any other log or an event not listed
+    /// in EventLogVariant
+    pub fn from_log(log: &str) -> Option<EventLog> {
+        log.strip_prefix("EVENT_JSON:")
+            .and_then(|event_json| serde_json::from_str(event_json).ok())
+    }
+}
+
+// raw storage key of the event nonce, outside the Contract state so emit needs no &mut self
+const EVENT_NONCE_KEY: &[u8] = b"event_nonce";
+
+/// take the next `event_nonce` of the calling contract, nonces increase by one for every
+/// emitted event so indexers can detect missed or duplicated receipts
+pub fn next_event_nonce() -> u64 {
+    let event_nonce = env::storage_read(EVENT_NONCE_KEY)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .unwrap_or(0);
+    env::storage_write(EVENT_NONCE_KEY, &(event_nonce + 1).to_le_bytes());
+    event_nonce
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftMintLog {
+    pub owner_id: String,
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftTransferLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftBurnLog {
+    pub owner_id: String,
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftMetadataUpdateLog {
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ImoInitLog {
+    pub metaverse_id: String,
+    pub owner_id: String,
+    pub rock_size: u128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ImoAddZoneLog {
+    pub metaverse_id: String,
+    pub owner_id: String,
+    pub zone_index: u16,
+    pub price: U128,
+    pub core_team_addr: String,
+    pub collection_addr: String,
+    pub type_zone: u8,
+    pub rock_index_from: u128,
+    pub rock_index_to: u128,
+    pub rock_size: u128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneSoldOutLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneExtendedLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    // range of the added rocks
+    pub rock_index_from: u128,
+    pub rock_index_to: u128,
+    pub additional_rocks: u128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetaverseSlugLog {
+    pub metaverse_id: String,
+    // None when the slug was released
+    pub slug: Option<String>,
+    pub previous_slug: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RegionLog {
+    pub region_id: String,
+    pub owner_id: String,
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SlugAuctionLog {
+    pub slug: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bidder_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metaverse_id: Option<String>,
+    // min_bid when started, the bid otherwise
+    pub amount: U128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InstallmentLog {
+    pub token_id: String,
+    pub buyer_id: String,
+    pub price: U128,
+    pub paid: U128,
+    pub due_at: U64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RaffleLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub account_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rock_index: Option<u128>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RockAttributesLog {
+    pub token_id: String,
+    pub writer_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscrowLog {
+    pub token_id: String,
+    pub buyer_id: String,
+    pub amount: U128,
+    pub release_at: U64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RockGiftedLog {
+    pub token_id: String,
+    pub sender_id: String,
+    pub receiver_id: String,
+    pub message: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetadataFrozenLog {
+    pub token_id: String,
+    pub frozen_by: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractLockedLog {
+    pub verified_by: String,
+    pub code_hash: Base58CryptoHash,
+    pub block_height: U64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SaleStateLog {
+    pub nft_type_id: String,
+    // None for a newly created nft type
+    pub from: Option<String>,
+    pub to: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EnvironmentUpgradeLog {
+    pub owner_id: String,
+    pub source_type_id: String,
+    pub target_type_id: String,
+    pub burned_token_ids: Vec<String>,
+    pub token_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CollectionChangeLog {
+    pub metaverse_id: String,
+    pub old_addr: String,
+    pub new_addr: String,
+    // operator or metaverse owner that proposed, confirmed or cancelled the change
+    pub account_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DenyListLog {
+    pub account_ids: Vec<String>,
+
+    // reason given by the admin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AirdropLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub account_id: String,
+    // set by AirdropRootSet, absent when the root is removed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub airdrop_root: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rock_index: Option<u128>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftGiftedLog {
+    pub payer_id: String,
+    pub receiver_id: String,
+    pub token_ids: Vec<String>,
+    pub price: U128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RockHoldLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub rock_indices: Vec<u128>,
+    // set by RocksHeld, the hold expires by itself at this timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until_timestamp: Option<U64>,
+
+    // reason given by the operator
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CampaignLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    // operator for CampaignCreated and CampaignClosed, claimer for CampaignClaimed
+    pub account_id: String,
+    // budget funded, zone price paid or unspent budget refunded
+    pub amount: U128,
+    // set by CampaignClaimed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rock_index: Option<u128>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneVisibilityLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub visible: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+// what the account of an AccountActivity did, a listing is an approval (e.g. of a marketplace)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityAction {
+    Mint,
+    Transfer,
+    List,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccountActivityLog {
+    // owner of the minted token, sender of a transfer or owner listing the token
+    pub account_id: String,
+    pub action: ActivityAction,
+    pub token_id: String,
+    // payer of a mint for someone else, receiver of a transfer or approved account of a listing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counterparty: Option<String>,
+    // price paid for a mint or balance of a payout transfer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<U128>,
+}
+
+impl AccountActivityLog {
+    pub fn new(
+        account_id: &AccountId,
+        action: ActivityAction,
+        token_id: &str,
+        counterparty: Option<&AccountId>,
+        amount: Option<Balance>,
+    ) -> Self {
+        AccountActivityLog {
+            account_id: account_id.to_string(),
+            action,
+            token_id: token_id.to_string(),
+            counterparty: counterparty.map(|account_id| account_id.to_string()),
+            amount: amount.map(U128),
+        }
+    }
+}
+
+/// log an AccountActivity event of the calling contract. It is logged next to the standard
+/// event of every mint, transfer and listing, so a profile activity feed filters one event on
+/// account_id and counterparty
+pub fn emit_account_activity(activities: Vec<AccountActivityLog>) {
+    if activities.is_empty() {
+        return;
+    }
+    let account_activity_log = EventLog {
+        standard: "account_activity".to_string(),
+        version: "1.0.0".to_string(),
+        event_nonce: next_event_nonce(),
+        event: EventLogVariant::AccountActivity(activities),
+    };
+    // through a Value like the events of the contracts, with the same key order
+    let event_json = serde_json::to_value(&account_activity_log).unwrap();
+    env::log_str(&format!("EVENT_JSON:{}", event_json));
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DeprecatedCallLog {
+    pub method: String,
+    pub replacement: String,
+    // None for view calls, which cannot read the caller
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caller_id: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    use super::*;
+
+    #[test]
+    fn account_activity_takes_the_contract_event_nonce() {
+        testing_env!(VMContextBuilder::new().build());
+        assert_eq!(next_event_nonce(), 0);
+        emit_account_activity(vec![]);
+        emit_account_activity(vec![AccountActivityLog::new(
+            &accounts(0),
+            ActivityAction::Mint,
+            "m1:2:1",
+            Some(&accounts(1)),
+            Some(5),
+        )]);
+
+        let logs = get_logs();
+        assert_eq!(logs.len(), 1);
+        let event_log = EventLog::from_log(&logs[0]).unwrap();
+        assert_eq!(event_log.standard, "account_activity");
+        assert_eq!(event_log.event_nonce, 1);
+        match event_log.event {
+            EventLogVariant::AccountActivity(activities) => {
+                assert_eq!(activities[0].account_id, accounts(0).to_string());
+                assert_eq!(activities[0].action, ActivityAction::Mint);
+                assert_eq!(activities[0].counterparty, Some(accounts(1).to_string()));
+                assert_eq!(activities[0].amount, Some(U128(5)));
+            }
+            _ => panic!("not an account activity"),
+        }
+        assert_eq!(next_event_nonce(), 2);
+    }
+}
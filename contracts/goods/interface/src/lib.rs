@@ -0,0 +1,33 @@
+/*!
+Interface of the Rove goods contracts for other NEAR contracts and near-workspaces tests.
+NOTES:
+  - `ext_rocks`, `ext_collection_holder` and `ext_environments` are the cross-contract clients
+    generated by `ext_contract` for the rocks, rockNFTCollectionHolder and environments contracts.
+  - The argument and return types mirror the JSON of the contracts, they are not shared with
+    the contract crates (except `Payout` and `DustPolicy`), keep them in sync when a method
+    signature changes.
+  - `EventLog::from_log` parses an `EVENT_JSON:` log line emitted by any of the contracts.
+  - `rock_token_id` / `parse_rock_token_id` build and split rock token ids, the format is the
+    same in both rock contracts.
+  - `MintListener` is the callback a contract registered with `add_mint_listener` implements.
+  - `compute_payout`, `split_purchase_fee`, `rock_token_id`, `verify_merkle_proof`,
+    `emit_account_activity` and `next_event_nonce` are called by the contract crates
+    themselves, a change here changes the contracts.
+ */
+pub use crate::collection_holder::*;
+pub use crate::environments::*;
+pub use crate::events::*;
+pub use crate::merkle::*;
+pub use crate::payout::*;
+pub use crate::rocks::*;
+pub use crate::token_id::*;
+pub use crate::types::*;
+
+mod collection_holder;
+mod environments;
+mod events;
+mod merkle;
+mod payout;
+mod rocks;
+mod token_id;
+mod types;
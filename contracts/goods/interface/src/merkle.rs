@@ -0,0 +1,20 @@
+use near_sdk::{env, CryptoHash};
+
+pub fn sha256_hash(value: &[u8]) -> CryptoHash {
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&env::sha256(value));
+    hash
+}
+
+/// check a sha256 Merkle proof where each pair of nodes is hashed in sorted order
+pub fn verify_merkle_proof(leaf: CryptoHash, proof: &[CryptoHash], root: &CryptoHash) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling {
+            (node, *sibling)
+        } else {
+            (*sibling, node)
+        };
+        sha256_hash(&[left, right].concat())
+    });
+    computed == *root
+}
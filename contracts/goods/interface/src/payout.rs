@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{AccountId, Balance};
+
+pub const ONE_HUNDRED_PERCENT_IN_BPS: u16 = 10_000;
+
+/// payout returned by `nft_payout` / `nft_transfer_payout` of every goods contract
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Payout {
+    pub payout: HashMap<AccountId, U128>,
+}
+
+/// receiver of the yoctoNEAR lost when rock_purchase_fee is rounded down
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum DustPolicy {
+    Treasury,
+    MetaverseOwner,
+}
+
+/// share of amount_to_pay for a royalty in basis points
+pub fn royalty_to_payout(royalty_percentage: u16, amount_to_pay: Balance) -> U128 {
+    U128(royalty_percentage as u128 * amount_to_pay / ONE_HUNDRED_PERCENT_IN_BPS as u128)
+}
+
+/// split balance between the royalty receivers and the token owner, the owner gets
+/// 100% - total perpetual royalties (none above 100%). None when there are more than
+/// max_len_payout receivers
+pub fn compute_payout(
+    royalties: &HashMap<AccountId, u16>,
+    token_owner_id: AccountId,
+    balance: Balance,
+    max_len_payout: u32,
+) -> Option<Payout> {
+    if royalties.len() as u32 > max_len_payout {
+        return None;
+    }
+
+    // summed in u32, royalties of several receivers can add up past u16::MAX
+    let mut total_perpetual: u32 = 0;
+    let mut payout_object = Payout {
+        payout: HashMap::new(),
+    };
+    for (receiver_id, royalty_percentage) in royalties.iter() {
+        // the token owner's share is added at the end
+        if *receiver_id != token_owner_id {
+            payout_object.payout.insert(
+                receiver_id.clone(),
+                royalty_to_payout(*royalty_percentage, balance),
+            );
+            total_perpetual += u32::from(*royalty_percentage);
+        }
+    }
+    // nothing is left to the owner when royalties reach 100%
+    let owner_percentage = u32::from(ONE_HUNDRED_PERCENT_IN_BPS).saturating_sub(total_perpetual);
+    payout_object.payout.insert(
+        token_owner_id,
+        royalty_to_payout(owner_percentage as u16, balance),
+    );
+    Some(payout_object)
+}
+
+/// split remain of a rock purchase into (treasury_amount, metaverse_owner_amount),
+/// the rounding remainder of the fee goes to the receiver chosen by dust_policy
+pub fn split_purchase_fee(
+    remain: Balance,
+    rock_purchase_fee: u32,
+    dust_policy: &DustPolicy,
+) -> (Balance, Balance) {
+    let fee_amount = remain * rock_purchase_fee as u128;
+    let mut treasury_amount = fee_amount / ONE_HUNDRED_PERCENT_IN_BPS as u128;
+    let has_dust = treasury_amount * ONE_HUNDRED_PERCENT_IN_BPS as u128 != fee_amount;
+    if *dust_policy == DustPolicy::Treasury && has_dust {
+        treasury_amount += 1;
+    }
+    (treasury_amount, remain - treasury_amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICIES: [DustPolicy; 2] = [DustPolicy::Treasury, DustPolicy::MetaverseOwner];
+
+    #[test]
+    fn compute_payout_of_royalties_above_100_percent_leaves_nothing_to_the_owner() {
+        let owner_id: AccountId = "owner.near".parse().unwrap();
+        let royalties: HashMap<AccountId, u16> = HashMap::from([
+            ("a.near".parse().unwrap(), 40_000),
+            ("b.near".parse().unwrap(), 40_000),
+        ]);
+        let payout = compute_payout(&royalties, owner_id.clone(), 10_000, 10).unwrap();
+        assert_eq!(payout.payout[&owner_id], U128(0));
+        assert_eq!(payout.payout.len(), 3);
+    }
+
+    #[test]
+    fn compute_payout_gives_the_owner_the_rest() {
+        let owner_id: AccountId = "owner.near".parse().unwrap();
+        let royalties: HashMap<AccountId, u16> =
+            HashMap::from([("a.near".parse().unwrap(), 1_000), (owner_id.clone(), 500)]);
+        let payout = compute_payout(&royalties, owner_id.clone(), 10_000, 10).unwrap();
+        assert_eq!(payout.payout[&"a.near".parse().unwrap()], U128(1_000));
+        assert_eq!(payout.payout[&owner_id], U128(9_000));
+    }
+
+    #[test]
+    fn split_purchase_fee_dust_goes_by_policy() {
+        assert_eq!(split_purchase_fee(1, 250, &DustPolicy::Treasury), (1, 0));
+        assert_eq!(
+            split_purchase_fee(1, 250, &DustPolicy::MetaverseOwner),
+            (0, 1)
+        );
+        assert_eq!(
+            split_purchase_fee(1_001, 250, &DustPolicy::Treasury),
+            (26, 975)
+        );
+        assert_eq!(
+            split_purchase_fee(1_001, 250, &DustPolicy::MetaverseOwner),
+            (25, 976)
+        );
+    }
+
+    #[test]
+    fn split_purchase_fee_zero_fee() {
+        for policy in POLICIES.iter() {
+            assert_eq!(split_purchase_fee(1, 0, policy), (0, 1));
+            assert_eq!(split_purchase_fee(1_000, 0, policy), (0, 1_000));
+        }
+    }
+
+    #[test]
+    fn split_purchase_fee_full_fee() {
+        for policy in POLICIES.iter() {
+            assert_eq!(split_purchase_fee(1, 10_000, policy), (1, 0));
+            assert_eq!(split_purchase_fee(1_000, 10_000, policy), (1_000, 0));
+        }
+    }
+}
@@ -0,0 +1,18 @@
+/// token_id of a rock, `{metaverse_id}:{zone_index}:{rock_index}` in both rocks and
+/// rockNFTCollectionHolder (the contracts' `compute_token_id` view)
+pub fn rock_token_id(metaverse_id: &str, zone_index: u16, rock_index: u128) -> String {
+    format!("{}:{}:{}", metaverse_id, zone_index, rock_index)
+}
+
+/// (metaverse_id, zone_index, rock_index) of a rock token_id, None for other token ids such as
+/// regions (`{metaverse_id}:region:{n}`)
+pub fn parse_rock_token_id(token_id: &str) -> Option<(String, u16, u128)> {
+    let mut parts = token_id.split(':');
+    let metaverse_id = parts.next()?;
+    let zone_index = parts.next()?.parse().ok()?;
+    let rock_index = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((metaverse_id.to_string(), zone_index, rock_index))
+}
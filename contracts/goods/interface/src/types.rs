@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+
+use near_contract_standards::non_fungible_token::metadata::TokenMetadata;
+use near_contract_standards::non_fungible_token::{Token, TokenId};
+use near_sdk::json_types::{Base58CryptoHash, Base64VecU8, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+
+// build metadata returned by contract_version
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VersionInfo {
+    pub name: String,
+    pub version: String,
+    pub git_commit: String,
+    pub rustc_version: String,
+    pub build_target: String,
+    pub build_profile: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Zone {
+    pub zone_index: u16,
+    pub price: U128,
+    pub core_team_addr: String,
+    pub collection_addr: String,
+    pub mint_policy: MintPolicy,
+    pub rock_index_from: u128,
+    pub rock_index_to: u128,
+    // hidden zones are only minted by the metaverse owner
+    #[serde(default = "default_zone_visible")]
+    pub visible: bool,
+}
+
+fn default_zone_visible() -> bool {
+    true
+}
+
+// how the rocks of a zone are sold, HolderGated zones only exist on the collection holder. Zone
+// events still carry the matching type_zone (1: core_team, 2: nft_holder, 3: public)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum MintPolicy {
+    CoreTeam,
+    HolderGated,
+    PublicFixedPrice,
+}
+
+// a rock bought by an account, returned by get_purchases_for_account
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Purchase {
+    pub token_id: TokenId,
+    pub metaverse_id: String,
+    pub price: U128,
+    pub timestamp: U64,
+}
+
+// rock_purchase_fee applied once the metaverse sale volume reaches volume_threshold
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeTier {
+    pub volume_threshold: U128,
+    pub rock_purchase_fee: u32,
+}
+
+// primary sale proceeds held during the refund window
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscrowedSale {
+    pub metaverse_id: String,
+    pub buyer_id: AccountId,
+    pub treasury_amount: U128,
+    pub metaverse_owner_amount: U128,
+    pub release_at: U64,
+}
+
+// message left by the last gift of a rock
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GiftMessage {
+    pub sender_id: AccountId,
+    pub message: String,
+    pub gifted_at: U64,
+}
+
+// record that the contract account was verified to have no access keys left
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct LockAttestation {
+    pub verified_by: AccountId,
+    pub verified_at: U64,
+    pub block_height: U64,
+    pub code_hash: Base58CryptoHash,
+}
+
+// installment terms a metaverse offers on its public zones
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InstallmentPlan {
+    pub min_price: U128,
+    pub deposit_bps: u16,
+    pub duration_sec: u32,
+    pub forfeit_bps: u16,
+}
+
+// a rock held by the rocks contract until its buyer pays the full price
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Installment {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub buyer_id: AccountId,
+    pub price: U128,
+    pub paid: U128,
+    pub storage_cost: U128,
+    pub forfeit_bps: u16,
+    pub due_at: U64,
+}
+
+// raffle of the unminted rocks of a public zone
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Raffle {
+    pub entry_price: U128,
+    pub closes_at: U64,
+    pub entrants: Vec<AccountId>,
+    pub winners: Option<HashMap<AccountId, u128>>,
+}
+
+// zone whose rocks can only be owned by the eligible receivers set by the metaverse owner
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneReceiverRestriction {
+    pub on_transfer: bool,
+}
+
+// Merkle airdrop of a public zone, leaves are sha256("{account_id}:{rock_index}")
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneAirdrop {
+    pub airdrop_root: Base58CryptoHash,
+    pub claimed: u64,
+}
+
+// highest bid of a slug auction, refunded when outbid
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SlugBid {
+    pub bidder_id: AccountId,
+    pub metaverse_id: String,
+    pub amount: U128,
+}
+
+// auction of a reserved premium slug
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SlugAuction {
+    pub min_bid: U128,
+    pub ends_at: U64,
+    pub highest_bid: Option<SlugBid>,
+}
+
+// collection change of a metaverse waiting for the operator or the owner to confirm it
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingCollectionChange {
+    pub new_addr: AccountId,
+    pub proposed_by: AccountId,
+    pub expires_at: U64,
+}
+
+// result of the last nft holder check of an account, returned by check_holder_eligibility
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HolderEligibility {
+    pub eligible: Option<bool>,
+    pub unused_token_ids: Vec<TokenId>,
+    pub checked_at: Option<U64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum SaleState {
+    NotStarted,
+    Live,
+    Paused,
+    SoldOut,
+    Closed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum PrimarySplit {
+    Royalties,
+    Custom { split: HashMap<AccountId, u16> },
+}
+
+// burn `burn_count` tokens of `source_type_id` to mint one token of the target type
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UpgradeRecipe {
+    pub source_type_id: String,
+    pub burn_count: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UpgradeStats {
+    pub upgrades: u64,
+    pub tokens_burned: u64,
+}
+
+// nft_token_detail of both rock contracts, installment is always None on the holder
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenDetail {
+    pub token: Token,
+    pub royalties: HashMap<AccountId, u16>,
+    pub mint_receipt: Option<Purchase>,
+    pub escrowed_sale: Option<EscrowedSale>,
+    pub installment: Option<Installment>,
+    pub region_id: Option<TokenId>,
+    pub metadata_frozen_at: Option<U64>,
+    pub attributes: Vec<(String, String)>,
+    pub gift_message: Option<GiftMessage>,
+}
+
+// get_operator_migration of the environments contract
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorMigrationStatus {
+    pub from_id: AccountId,
+    pub to_id: AccountId,
+    pub migrated: u64,
+    pub remaining: u64,
+}
+
+// what currently restricts minting the rocks of a zone, airdrop and raffle are rocks only
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneSalePhase {
+    SoldOut,
+    Airdrop,
+    Raffle,
+    Allowlist,
+    Open,
+}
+
+// one zone of get_zone_summaries
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneSummary {
+    pub zone_index: u16,
+    pub mint_policy: MintPolicy,
+    pub capacity: U128,
+    pub minted: U128,
+    pub remaining: U128,
+    pub price: U128,
+    pub price_usd_cents: Option<u64>,
+    pub phase: ZoneSalePhase,
+}
+
+// what verify_invariants found wrong with the minted rocks of a metaverse
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum InvariantViolation {
+    MalformedTokenId,
+    ZoneNotFound,
+    RockOutOfRange,
+    MissingOwner,
+    MintedCountMismatch,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InvariantDiscrepancy {
+    pub metaverse_id: String,
+    pub zone_index: Option<u16>,
+    pub token_id: Option<TokenId>,
+    pub violation: InvariantViolation,
+    pub detail: Option<String>,
+}
+
+// one page of verify_invariants
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InvariantReport {
+    pub from_index: u64,
+    pub total: u64,
+    pub discrepancies: Vec<InvariantDiscrepancy>,
+}
+
+// get_metaverse_limits, 0 disables a cap
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetaverseLimits {
+    pub max_zones_per_metaverse: u16,
+    pub max_rocks_per_zone: U128,
+    pub max_metaverses_per_owner: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum MetaverseTagKind {
+    Region,
+    Genre,
+    Language,
+}
+
+// discovery tag of a metaverse, values are lowercased by the contracts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetaverseTag {
+    pub kind: MetaverseTagKind,
+    pub value: String,
+}
+
+// get_top_holders, a region counts its rocks
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HolderCount {
+    pub account_id: AccountId,
+    pub count: u64,
+}
+
+// get_campaign, rocks contract only
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CampaignStats {
+    pub budget: U128,
+    pub spent: U128,
+    pub remaining: U128,
+    pub per_account: u32,
+    pub claimed: u64,
+    // rocks the remaining budget pays at the current zone price
+    pub claimable: u64,
+}
+
+// anti-sybil checks of campaign claims, the account age counts from register_for_campaigns
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CampaignConfig {
+    pub reject_implicit_accounts: bool,
+    pub min_account_age_sec: u32,
+}
+
+// one approval of nft_approvals, expires_at is set by nft_approve_with_expiry (rock contracts)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenApproval {
+    pub approval_id: u64,
+    pub expires_at: Option<U64>,
+    pub expired: bool,
+}
+
+// collection token consumed in the whole metaverse, or in zone_index when set
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CheckerUsage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zone_index: Option<u16>,
+    pub token_id: TokenId,
+}
+
+// one page of export_checker_usage (rockNFTCollectionHolder)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CheckerUsageChunk {
+    pub metaverse_id: String,
+    pub from_index: u64,
+    pub total: u64,
+    pub usage: Vec<CheckerUsage>,
+}
+
+// rocks an environment compatibility rule applies to: the zones of a mint policy or a metaverse
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "kind", content = "value")]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityClass {
+    Zone(MintPolicy),
+    Metaverse(String),
+}
+
+// testnet faucet of a rock or environments contract
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FaucetMode {
+    pub max_per_account: u32,
+}
+
+// rock minted by mint_rock_with_quote (rocks)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct QuoteMintArgs {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub rock_index: u128,
+    pub receiver_id: AccountId,
+    pub token_metadata: TokenMetadata,
+}
+
+// operator-signed price of one rock, expires_at in nanoseconds. The ed25519 signature is over
+// the borsh serialization of (rocks contract account, metaverse_id, zone_index, rock_index,
+// receiver_id, price, expires_at)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SignedQuote {
+    pub price: U128,
+    pub expires_at: U64,
+    pub signature: Base64VecU8,
+}
+
+// deprecated method of api_version, still served until a later upgrade removes it
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DeprecatedMethod {
+    pub method: String,
+    pub replacement: String,
+}
+
+// returned by api_version of every goods contract
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ApiVersion {
+    pub version: u32,
+    pub deprecated_methods: Vec<DeprecatedMethod>,
+}
@@ -0,0 +1,88 @@
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::CryptoHash;
+
+use crate::*;
+
+// Merkle allowlist of a zone, leaves are sha256(account_id)
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneAllowlist {
+    pub allowlist_root: Base58CryptoHash,
+    pub price: U128,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner sets (or removes with None) the Merkle allowlist of a public zone
+    #[payable]
+    pub fn set_zone_allowlist(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        allowlist: Option<ZoneAllowlist>,
+    ) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(
+            zone.mint_policy == MintPolicy::PublicFixedPrice,
+            RoveError::InvalidZoneType.as_str()
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        match allowlist {
+            Some(allowlist) => self.allowlists.insert(&zone_key, &allowlist),
+            None => self.allowlists.remove(&zone_key),
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_zone_allowlist(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+    ) -> Option<ZoneAllowlist> {
+        self.allowlists
+            .get(&gen_zone_key(&metaverse_id, zone_index))
+    }
+
+    /// mint at the allowlist price, proof shows the caller belongs to the zone allowlist
+    #[payable]
+    pub fn mint_rock_with_proof(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        receiver_id: AccountId,
+        token_metadata: TokenMetadata,
+        proof: Vec<Base58CryptoHash>,
+    ) {
+        let (_zone, token_id) = self.assert_rock_mintable(&metaverse_id, zone_index, rock_index);
+        let allowlist = self
+            .get_zone_allowlist(metaverse_id.clone(), zone_index)
+            .unwrap_or_else(|| env::panic_str(RoveError::NoZoneAllowlist.as_str()));
+        let leaf = sha256_hash(env::predecessor_account_id().as_bytes());
+        let proof: Vec<CryptoHash> = proof.into_iter().map(CryptoHash::from).collect();
+        require!(
+            verify_merkle_proof(leaf, &proof, &CryptoHash::from(allowlist.allowlist_root)),
+            RoveError::NotInAllowlist.as_str()
+        );
+
+        self._mint(
+            metaverse_id,
+            zone_index,
+            token_id,
+            receiver_id,
+            token_metadata,
+            allowlist.price,
+            MintPolicy::PublicFixedPrice,
+            "".to_string(),
+            env::attached_deposit(),
+        );
+    }
+}
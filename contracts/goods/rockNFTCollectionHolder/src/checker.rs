@@ -0,0 +1,317 @@
+use crate::*;
+
+// a pending holder-gated mint blocks its rock until the callback runs or this expires (5 minutes)
+pub const PENDING_MINT_TTL: u64 = 300_000_000_000;
+// gas cap for the heaviest collection nft_tokens_for_owner call
+pub const MAX_COLLECTION_GAS: Gas = Gas(100_000_000_000_000);
+// maximum rocks of the holder zone when neither the collection nor init_imo_nft_holder_size sets it
+pub const DEFAULT_HOLDER_ZONE_MAX_SIZE: u32 = 500;
+
+// how collection tokens are consumed by holder-gated mints of a zone
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum CheckerPolicy {
+    // a collection token mints one rock in the whole metaverse
+    ConsumePerMint,
+    // a collection token mints one rock in each zone
+    OnePerTokenPerZone,
+    // holding any collection token is enough, nothing is consumed
+    Unlimited,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator sets (or resets to the default with None) the gas of the
+    /// nft_tokens_for_owner call to a collection used by holder-gated zones
+    #[payable]
+    pub fn set_collection_gas(&mut self, collection_addr: AccountId, gas: Option<U64>) {
+        self.assert_operator_only();
+        match gas {
+            Some(gas) => {
+                require!(
+                    gas.0 > 0 && gas.0 <= MAX_COLLECTION_GAS.0,
+                    RoveError::InvalidArgument.detail("gas must be > 0 and <= 100 Tgas")
+                );
+                self.collection_gas.insert(&collection_addr, &gas.0);
+            }
+            None => {
+                self.collection_gas.remove(&collection_addr);
+            }
+        }
+    }
+
+    pub fn get_collection_gas(&self, collection_addr: AccountId) -> U64 {
+        U64(self.internal_collection_gas(&collection_addr).0)
+    }
+
+    pub(crate) fn internal_collection_gas(&self, collection_addr: &AccountId) -> Gas {
+        self.collection_gas
+            .get(collection_addr)
+            .map(Gas)
+            .unwrap_or(GAS_FOR_COMMON_OPERATIONS)
+    }
+
+    /// operator sets (or resets to init_imo_nft_holder_size with None) the maximum rocks
+    /// of the holder zone of a metaverse created for a collection
+    #[payable]
+    pub fn set_collection_max_zone_size(
+        &mut self,
+        collection_addr: AccountId,
+        max_size: Option<u32>,
+    ) {
+        self.assert_operator_only();
+        match max_size {
+            Some(max_size) => {
+                require!(
+                    max_size > 0,
+                    RoveError::InvalidArgument.detail("max_size must be > 0")
+                );
+                self.collection_max_zone_sizes
+                    .insert(&collection_addr, &max_size);
+            }
+            None => {
+                self.collection_max_zone_sizes.remove(&collection_addr);
+            }
+        }
+    }
+
+    pub fn get_collection_max_zone_size(&self, collection_addr: AccountId) -> u32 {
+        self.internal_collection_max_zone_size(&collection_addr)
+    }
+
+    pub(crate) fn internal_collection_max_zone_size(&self, collection_addr: &AccountId) -> u32 {
+        self.collection_max_zone_sizes
+            .get(collection_addr)
+            .unwrap_or(if self.init_imo_nft_holder_size > 0 {
+                self.init_imo_nft_holder_size
+            } else {
+                DEFAULT_HOLDER_ZONE_MAX_SIZE
+            })
+    }
+
+    pub fn get_checker_policy(&self, metaverse_id: String, zone_index: u16) -> CheckerPolicy {
+        self.assert_zone_exist(&metaverse_id, zone_index);
+        self.internal_checker_policy(&metaverse_id, zone_index)
+    }
+
+    pub(crate) fn internal_checker_policy(
+        &self,
+        metaverse_id: &String,
+        zone_index: u16,
+    ) -> CheckerPolicy {
+        self.checker_policies
+            .get(metaverse_id)
+            .and_then(|policies| policies.get(&zone_index).cloned())
+            .unwrap_or(CheckerPolicy::ConsumePerMint)
+    }
+
+    pub(crate) fn internal_set_checker_policy(
+        &mut self,
+        metaverse_id: &String,
+        zone_index: u16,
+        checker_policy: CheckerPolicy,
+    ) {
+        let mut policies = self.checker_policies.get(metaverse_id).unwrap_or_default();
+        policies.insert(zone_index, checker_policy);
+        self.checker_policies.insert(metaverse_id, &policies);
+    }
+
+    pub(crate) fn internal_is_checker_token_used(
+        &self,
+        metaverse_id: &String,
+        zone_index: u16,
+        token_id: &TokenId,
+    ) -> bool {
+        match self.internal_checker_policy(metaverse_id, zone_index) {
+            CheckerPolicy::ConsumePerMint => self
+                .nft_checker
+                .get(metaverse_id)
+                .unwrap_or_default()
+                .contains_key(token_id),
+            CheckerPolicy::OnePerTokenPerZone => self
+                .zone_nft_checker
+                .get(&gen_zone_key(metaverse_id, zone_index))
+                .unwrap_or_default()
+                .contains_key(token_id),
+            CheckerPolicy::Unlimited => false,
+        }
+    }
+
+    pub(crate) fn internal_use_checker_token(
+        &mut self,
+        metaverse_id: &String,
+        zone_index: u16,
+        token_id: TokenId,
+    ) {
+        match self.internal_checker_policy(metaverse_id, zone_index) {
+            CheckerPolicy::ConsumePerMint => {
+                let mut nft_checker = self.nft_checker.get(metaverse_id).unwrap_or_default();
+                nft_checker.insert(token_id.clone(), true);
+                self.nft_checker.insert(metaverse_id, &nft_checker);
+            }
+            CheckerPolicy::OnePerTokenPerZone => {
+                let zone_key = gen_zone_key(metaverse_id, zone_index);
+                let mut zone_nft_checker = self.zone_nft_checker.get(&zone_key).unwrap_or_default();
+                zone_nft_checker.insert(token_id.clone(), true);
+                self.zone_nft_checker.insert(&zone_key, &zone_nft_checker);
+            }
+            CheckerPolicy::Unlimited => return,
+        }
+
+        // the nft checker queried the tokens of the signer
+        let zone = self.assert_zone_exist(metaverse_id, zone_index);
+        let checker_token_used_log: EventLog = EventLog {
+            standard: "nft_collection_holder_checker".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::CheckerTokenUsed(vec![CheckerTokenUsedLog {
+                metaverse_id: metaverse_id.clone(),
+                collection_addr: zone.collection_addr,
+                token_id,
+                minter: env::signer_account_id().to_string(),
+                memo: None,
+            }]),
+        };
+
+        checker_token_used_log.emit();
+    }
+
+    pub(crate) fn internal_is_mint_pending(&self, token_id: &TokenId) -> bool {
+        self.pending_mints
+            .get(token_id)
+            .map(|expires_at| env::block_timestamp() < expires_at)
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn internal_lock_pending_mint(&mut self, token_id: &TokenId) {
+        self.pending_mints
+            .insert(token_id, &(env::block_timestamp() + PENDING_MINT_TTL));
+    }
+
+    pub(crate) fn internal_unlock_pending_mint(&mut self, token_id: &TokenId) {
+        self.pending_mints.remove(token_id);
+    }
+
+    // collection token consumed by a holder-gated mint, from the nft_tokens_for_owner result
+    pub(crate) fn internal_checked_token(
+        &mut self,
+        metaverse_id: &String,
+        zone_index: u16,
+        use_token_id: Option<TokenId>,
+    ) -> Result<TokenId, RoveError> {
+        let tokens = match env::promise_result(0) {
+            PromiseResult::NotReady => return Err(RoveError::CheckerNotReady),
+            PromiseResult::Failed => return Err(RoveError::CheckerFailed),
+            PromiseResult::Successful(result) => {
+                near_sdk::serde_json::from_slice::<Vec<Token>>(&result)
+                    .map_err(|_| RoveError::CheckerFailed)?
+            }
+        };
+        if tokens.is_empty() {
+            return Err(RoveError::NotCollectionHolder);
+        }
+        let use_token_id = match use_token_id {
+            // the buyer picked the collection token to consume
+            Some(use_token_id) => {
+                if !tokens.iter().any(|token| token.token_id == use_token_id) {
+                    return Err(RoveError::NotTokenOwner);
+                }
+                if self.internal_is_checker_token_used(metaverse_id, zone_index, &use_token_id) {
+                    return Err(RoveError::CollectionTokenUsed);
+                }
+                use_token_id
+            }
+            // first collection token not used yet
+            None => tokens
+                .iter()
+                .map(|token| token.token_id.clone())
+                .find(|token_id| {
+                    !self.internal_is_checker_token_used(metaverse_id, zone_index, token_id)
+                })
+                .ok_or(RoveError::NotCollectionHolder)?,
+        };
+
+        self.internal_record_holder_check(
+            metaverse_id,
+            &env::signer_account_id(),
+            tokens.into_iter().map(|token| token.token_id).collect(),
+        );
+        Ok(use_token_id)
+    }
+
+    // checks of _mint that can fail in the checker and USD price callbacks, run before it so
+    // the deposit is refunded instead of kept by a panic. The storage of a deposit-paid rock is
+    // estimated with MINT_STORAGE_ALLOWANCE_BYTES, a rock put on hold while the callback was
+    // pending is not minted
+    pub(crate) fn internal_check_checker_mint(
+        &self,
+        metaverse_id: &String,
+        zone_index: u16,
+        token_id: &TokenId,
+        receiver_id: &AccountId,
+        price: Balance,
+        mint_policy: MintPolicy,
+    ) -> Result<(), RoveError> {
+        if self.denied_accounts.contains(receiver_id)
+            || self.denied_accounts.contains(&env::signer_account_id())
+        {
+            return Err(RoveError::AccountDenied);
+        }
+        if !self.is_zone_receiver(metaverse_id.clone(), zone_index, receiver_id.clone()) {
+            return Err(RoveError::NotZoneReceiver);
+        }
+        if self.internal_rock_hold(token_id).is_some()
+            && env::signer_account_id() != self.tokens.owner_id
+        {
+            return Err(RoveError::RockOnHold);
+        }
+        self.internal_check_mint_throttle(metaverse_id, zone_index, receiver_id)?;
+        let attached_deposit = env::attached_deposit();
+        if price > attached_deposit {
+            return Err(RoveError::NotEnoughDeposit);
+        }
+        let storage_cost = env::storage_byte_cost() * Balance::from(MINT_STORAGE_ALLOWANCE_BYTES);
+        let is_sponsored = self
+            .sponsored_zones
+            .get(&gen_zone_key(metaverse_id, zone_index))
+            .is_some()
+            && self.sponsorship_balance >= storage_cost;
+        if price == 0
+            && mint_policy == MintPolicy::HolderGated
+            && !is_sponsored
+            && storage_cost > attached_deposit
+        {
+            return Err(RoveError::NotEnoughDeposit);
+        }
+        Ok(())
+    }
+
+    // a holder-gated mint that cannot happen in its callback returns the deposit to the signer
+    pub(crate) fn internal_refund_checker_mint(
+        &mut self,
+        metaverse_id: &str,
+        zone_index: u16,
+        rock_index: u128,
+        error: RoveError,
+    ) {
+        let attached_deposit = env::attached_deposit();
+        if attached_deposit > 0 {
+            self.internal_payout(env::signer_account_id(), attached_deposit);
+        }
+
+        let mint_refunded_log: EventLog = EventLog {
+            standard: "nft_collection_holder_checker".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::MintRefunded(vec![MintRefundedLog {
+                metaverse_id: metaverse_id.to_string(),
+                zone_index,
+                rock_index,
+                minter: env::signer_account_id().to_string(),
+                amount: U128(attached_deposit),
+                memo: Some(error.as_str().to_string()),
+            }]),
+        };
+
+        mint_refunded_log.emit();
+    }
+}
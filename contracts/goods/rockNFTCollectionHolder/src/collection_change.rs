@@ -0,0 +1,155 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+// time the other party has to confirm a proposed collection change: 72h in nanoseconds
+pub const COLLECTION_CHANGE_WINDOW: u64 = 72 * 60 * 60 * 1_000_000_000;
+
+// collection change proposed by the operator or the metaverse owner, waiting for the other one
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingCollectionChange {
+    pub new_addr: AccountId,
+    pub proposed_by: AccountId,
+    pub expires_at: U64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator and metaverse owner both call this with the same `new_addr` within
+    /// COLLECTION_CHANGE_WINDOW to relink the nft holder zones of a metaverse, e.g. when the
+    /// partner collection migrates accounts. The first call proposes the change, the call of
+    /// the other party applies it. Used checker tokens, collection gas and zone size caps are kept
+    #[payable]
+    pub fn change_collection_address(&mut self, metaverse_id: String, new_addr: AccountId) {
+        assert_at_least_one_yocto();
+        self.assert_metaverse_exist(&metaverse_id);
+        let account_id = env::predecessor_account_id();
+        let owner_id = self
+            .metaverse_owners
+            .get(&metaverse_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::MetaverseNotFound.as_str()));
+        let operator_id = self.tokens.owner_id.clone();
+        require!(
+            account_id == owner_id || account_id == operator_id,
+            RoveError::Unauthorized.as_str()
+        );
+        let old_addr = self
+            .get_collection_for_metaverse(metaverse_id.clone())
+            .unwrap_or_else(|| {
+                env::panic_str(
+                    &RoveError::InvalidCollection.detail("metaverse does not have zone_index 2"),
+                )
+            });
+        require!(
+            new_addr.to_string() != old_addr,
+            RoveError::InvalidArgument.detail("collection address is unchanged")
+        );
+        require!(
+            self.metaverse_nft_collections
+                .get(&new_addr.to_string())
+                .is_none(),
+            RoveError::InvalidCollection.detail("this collection address is already used")
+        );
+
+        // an operator owning the metaverse confirms its own change
+        let is_confirmed = owner_id == operator_id
+            || match self.pending_collection_changes.get(&metaverse_id) {
+                Some(pending) => {
+                    pending.new_addr == new_addr
+                        && pending.proposed_by != account_id
+                        && env::block_timestamp() < pending.expires_at.0
+                }
+                None => false,
+            };
+
+        let initial_storage_usage = env::storage_usage();
+        let variant: fn(Vec<CollectionChangeLog>) -> EventLogVariant = if is_confirmed {
+            self.pending_collection_changes.remove(&metaverse_id);
+            let mut metaverse = self.metaverses.get(&metaverse_id).unwrap();
+            for zone in metaverse.zones.values_mut() {
+                if zone.mint_policy == MintPolicy::HolderGated && zone.collection_addr == old_addr {
+                    zone.collection_addr = new_addr.to_string();
+                }
+            }
+            self.metaverses.insert(&metaverse_id, &metaverse);
+            self.metaverse_nft_collections.remove(&old_addr);
+            self.metaverse_nft_collections
+                .insert(&new_addr.to_string(), &metaverse_id);
+            EventLogVariant::CollectionAddressChanged
+        } else {
+            self.pending_collection_changes.insert(
+                &metaverse_id,
+                &PendingCollectionChange {
+                    new_addr: new_addr.clone(),
+                    proposed_by: account_id.clone(),
+                    expires_at: U64(env::block_timestamp() + COLLECTION_CHANGE_WINDOW),
+                },
+            );
+            EventLogVariant::CollectionChangeProposed
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                account_id.clone(),
+            );
+        }
+
+        emit_collection_change_log(variant, metaverse_id, old_addr, &new_addr, &account_id);
+    }
+
+    /// operator or metaverse owner withdraws the pending collection change of a metaverse
+    #[payable]
+    pub fn cancel_collection_change(&mut self, metaverse_id: String) {
+        assert_at_least_one_yocto();
+        let account_id = env::predecessor_account_id();
+        require!(
+            Some(account_id.clone()) == self.metaverse_owners.get(&metaverse_id)
+                || account_id == self.tokens.owner_id,
+            RoveError::Unauthorized.as_str()
+        );
+        let pending = self
+            .pending_collection_changes
+            .remove(&metaverse_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::CollectionChangeNotFound.as_str()));
+        let old_addr = self
+            .get_collection_for_metaverse(metaverse_id.clone())
+            .unwrap_or_default();
+
+        emit_collection_change_log(
+            EventLogVariant::CollectionChangeCancelled,
+            metaverse_id,
+            old_addr,
+            &pending.new_addr,
+            &account_id,
+        );
+    }
+
+    pub fn get_pending_collection_change(
+        &self,
+        metaverse_id: String,
+    ) -> Option<PendingCollectionChange> {
+        self.pending_collection_changes.get(&metaverse_id)
+    }
+}
+
+fn emit_collection_change_log(
+    variant: fn(Vec<CollectionChangeLog>) -> EventLogVariant,
+    metaverse_id: String,
+    old_addr: String,
+    new_addr: &AccountId,
+    account_id: &AccountId,
+) {
+    let collection_change_log: EventLog = EventLog {
+        standard: "nft_collection_holder_checker".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![CollectionChangeLog {
+            metaverse_id,
+            old_addr,
+            new_addr: new_addr.to_string(),
+            account_id: account_id.to_string(),
+            memo: None,
+        }]),
+    };
+    collection_change_log.emit();
+}
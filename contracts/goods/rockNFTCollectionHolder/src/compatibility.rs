@@ -0,0 +1,100 @@
+use crate::*;
+
+pub const MAX_COMPATIBLE_NFT_TYPES: usize = 64;
+pub const MAX_NFT_TYPE_ID_LEN: usize = 64;
+
+// rocks an environment compatibility rule applies to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "kind", content = "value")]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityClass {
+    // rocks of every zone minted under this policy
+    Zone(MintPolicy),
+    // rocks of one metaverse
+    Metaverse(String),
+}
+
+impl CompatibilityClass {
+    fn key(&self) -> String {
+        match self {
+            CompatibilityClass::Zone(mint_policy) => format!("zone:{}", mint_policy.type_zone()),
+            CompatibilityClass::Metaverse(metaverse_id) => format!("metaverse:{}", metaverse_id),
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator sets the environment nft_type_ids that can be attached to the rocks of a class,
+    /// an empty list removes the rule. A rock accepts an environment type allowed by both the
+    /// rule of its zone policy and the rule of its metaverse, a missing rule allows every type
+    #[payable]
+    pub fn set_environment_compatibility(
+        &mut self,
+        class: CompatibilityClass,
+        nft_type_ids: Vec<String>,
+    ) {
+        self.assert_operator_only();
+        if let CompatibilityClass::Metaverse(metaverse_id) = &class {
+            self.assert_metaverse_exist(metaverse_id);
+        }
+        require!(
+            nft_type_ids.len() <= MAX_COMPATIBLE_NFT_TYPES,
+            RoveError::TooManyItems.detail("too many nft_type_ids")
+        );
+        require!(
+            nft_type_ids
+                .iter()
+                .all(|nft_type_id| !nft_type_id.is_empty()
+                    && nft_type_id.len() <= MAX_NFT_TYPE_ID_LEN),
+            RoveError::InvalidArgument.detail("nft_type_id must not be empty and <= 64 bytes")
+        );
+
+        let mut nft_type_ids = nft_type_ids;
+        nft_type_ids.sort();
+        nft_type_ids.dedup();
+        let initial_storage_usage = env::storage_usage();
+        if nft_type_ids.is_empty() {
+            self.environment_compatibility.remove(&class.key());
+        } else {
+            self.environment_compatibility
+                .insert(&class.key(), &nft_type_ids);
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    /// environment nft_type_ids allowed on the rocks of a class, empty when it has no rule
+    pub fn get_environment_compatibility(&self, class: CompatibilityClass) -> Vec<String> {
+        self.environment_compatibility
+            .get(&class.key())
+            .unwrap_or_default()
+    }
+
+    /// whether an environment of `nft_type_id` can be attached to the rocks of a zone, checked
+    /// by the attachment flow before an environment is placed on a rock
+    pub fn is_environment_compatible(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        nft_type_id: String,
+    ) -> bool {
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        [
+            CompatibilityClass::Zone(zone.mint_policy),
+            CompatibilityClass::Metaverse(metaverse_id),
+        ]
+        .iter()
+        .all(|class| {
+            self.environment_compatibility
+                .get(&class.key())
+                .map(|nft_type_ids| nft_type_ids.binary_search(&nft_type_id).is_ok())
+                .unwrap_or(true)
+        })
+    }
+}
@@ -0,0 +1,51 @@
+use crate::*;
+
+// storage a mint may attach per token on top of its price, a token with its metadata
+pub const MINT_STORAGE_ALLOWANCE_BYTES: u64 = 2_000;
+
+#[near_bindgen]
+impl Contract {
+    /// operator rejects mints attaching more than `multiplier` times their price plus the
+    /// storage allowance, instead of refunding the excess (a refund to a deleted account is
+    /// lost). 0 disables the guard
+    #[payable]
+    pub fn set_max_deposit_multiplier(&mut self, multiplier: u32) {
+        self.assert_operator_only();
+        self.max_deposit_multiplier = multiplier;
+    }
+
+    pub fn get_max_deposit_multiplier(&self) -> u32 {
+        self.max_deposit_multiplier
+    }
+
+    /// largest deposit a mint of `token_count` tokens for `price` accepts, None without a guard
+    pub fn get_max_accepted_deposit(&self, price: U128, token_count: u32) -> Option<U128> {
+        self.internal_max_accepted_deposit(price.0, token_count)
+            .map(U128)
+    }
+
+    // panics when the attached deposit is above the max accepted deposit of the mint
+    pub(crate) fn assert_deposit_accepted(&self, price: Balance, token_count: u32) {
+        if let Some(max_accepted_deposit) = self.internal_max_accepted_deposit(price, token_count) {
+            require!(
+                env::attached_deposit() <= max_accepted_deposit,
+                RoveError::DepositTooLarge
+                    .detail(format!("attach at most {} yoctoNEAR", max_accepted_deposit))
+            );
+        }
+    }
+
+    fn internal_max_accepted_deposit(&self, price: Balance, token_count: u32) -> Option<Balance> {
+        if self.max_deposit_multiplier == 0 {
+            return None;
+        }
+        let storage_allowance = env::storage_byte_cost()
+            * Balance::from(MINT_STORAGE_ALLOWANCE_BYTES)
+            * Balance::from(token_count);
+        Some(
+            price
+                .saturating_add(storage_allowance)
+                .saturating_mul(Balance::from(self.max_deposit_multiplier)),
+        )
+    }
+}
@@ -0,0 +1,115 @@
+use crate::*;
+
+// collection tokens seen for an account by the last nft checker call
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HolderCheck {
+    pub token_ids: Vec<TokenId>,
+    pub checked_at: U64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HolderEligibility {
+    // None when the account was never checked
+    pub eligible: Option<bool>,
+    pub unused_token_ids: Vec<TokenId>,
+    pub checked_at: Option<U64>,
+}
+
+fn gen_holder_check_key(metaverse_id: &String, account_id: &AccountId) -> String {
+    format!("{}:{}", metaverse_id, account_id)
+}
+
+#[near_bindgen]
+impl Contract {
+    /// eligibility of account_id for a holder zone, computed from the tokens seen by the last
+    /// nft checker call against the current checker usage (views can not query the collection)
+    pub fn check_holder_eligibility(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        account_id: AccountId,
+    ) -> HolderEligibility {
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(
+            zone.mint_policy == MintPolicy::HolderGated,
+            RoveError::InvalidZoneType.as_str()
+        );
+
+        match self
+            .holder_checks
+            .get(&gen_holder_check_key(&metaverse_id, &account_id))
+        {
+            Some(holder_check) => {
+                let unused_token_ids: Vec<TokenId> = holder_check
+                    .token_ids
+                    .into_iter()
+                    .filter(|token_id| {
+                        !self.internal_is_checker_token_used(&metaverse_id, zone_index, token_id)
+                    })
+                    .collect();
+                HolderEligibility {
+                    eligible: Some(!unused_token_ids.is_empty()),
+                    unused_token_ids,
+                    checked_at: Some(holder_check.checked_at),
+                }
+            }
+            None => HolderEligibility {
+                eligible: None,
+                unused_token_ids: vec![],
+                checked_at: None,
+            },
+        }
+    }
+
+    // collection tokens of account_id seen by the last nft checker call that can still mint in the zone
+    pub fn get_unused_checker_tokens(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        account_id: AccountId,
+    ) -> Vec<TokenId> {
+        self.check_holder_eligibility(metaverse_id, zone_index, account_id)
+            .unused_token_ids
+    }
+
+    // collection token ids already consumed to mint a rock in metaverse_id, sorted
+    pub fn get_used_checker_tokens(
+        &self,
+        metaverse_id: String,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<TokenId> {
+        self.assert_metaverse_exist(&metaverse_id);
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        let mut token_ids: Vec<TokenId> = self
+            .nft_checker
+            .get(&metaverse_id)
+            .unwrap_or_default()
+            .into_keys()
+            .collect();
+        token_ids.sort();
+        token_ids
+            .into_iter()
+            .skip(start_index as usize)
+            .take(limit)
+            .collect()
+    }
+
+    pub(crate) fn internal_record_holder_check(
+        &mut self,
+        metaverse_id: &String,
+        account_id: &AccountId,
+        token_ids: Vec<TokenId>,
+    ) {
+        self.holder_checks.insert(
+            &gen_holder_check_key(metaverse_id, account_id),
+            &HolderCheck {
+                token_ids,
+                checked_at: U64(env::block_timestamp()),
+            },
+        );
+    }
+}
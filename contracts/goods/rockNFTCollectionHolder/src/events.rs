@@ -1,7 +1,9 @@
 use std::fmt;
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
 
+use near_sdk::env;
 use near_sdk::serde::{Deserialize, Serialize};
+use rove_contracts_interface::next_event_nonce;
 
 /// Enum that represents the data type of the EventLog.
 /// The enum can either be an NftMint or an NftTransfer.
@@ -13,9 +15,55 @@ use near_sdk::serde::{Deserialize, Serialize};
 pub enum EventLogVariant {
     NftMint(Vec<NftMintLog>),
     NftTransfer(Vec<NftTransferLog>),
+    NftBurn(Vec<NftBurnLog>),
+    NftMetadataUpdate(Vec<NftMetadataUpdateLog>),
     ImoInit(Vec<ImoInitLog>),
     ImoAddZone(Vec<ImoAddZoneLog>),
     ImoChangeZonePrice(Vec<ImoChangeZonePrice>),
+    EmergencyActionQueued(Vec<EmergencyActionLog>),
+    EmergencyActionCancelled(Vec<EmergencyActionLog>),
+    EmergencyActionExecuted(Vec<EmergencyActionLog>),
+    ZoneSoldOut(Vec<ZoneSoldOutLog>),
+    PayoutFailed(Vec<PayoutLog>),
+    PayoutRetried(Vec<PayoutLog>),
+    ParametersUpdate(Vec<ParametersUpdateLog>),
+    MapCommitmentUpdate(Vec<MapCommitmentLog>),
+    RockPriceQuote(Vec<RockPriceQuoteLog>),
+    TokenFlagged(Vec<ModerationLog>),
+    TokenModerated(Vec<ModerationLog>),
+    RockAttributesUpdate(Vec<RockAttributesLog>),
+    HostingFeeUpdate(Vec<HostingLog>),
+    HostingFeePaid(Vec<HostingLog>),
+    HostingOverdue(Vec<HostingLog>),
+    EscrowHeld(Vec<EscrowLog>),
+    EscrowRefunded(Vec<EscrowLog>),
+    EscrowReleased(Vec<EscrowLog>),
+    RockGifted(Vec<RockGiftedLog>),
+    MetadataFrozen(Vec<MetadataFrozenLog>),
+    ContractLocked(Vec<ContractLockedLog>),
+    ZoneExtended(Vec<ZoneExtendedLog>),
+    MetaverseSlugUpdate(Vec<MetaverseSlugLog>),
+    RegionWrapped(Vec<RegionLog>),
+    RegionUnwrapped(Vec<RegionLog>),
+    SlugAuctionStarted(Vec<SlugAuctionLog>),
+    SlugBid(Vec<SlugAuctionLog>),
+    SlugAuctionSettled(Vec<SlugAuctionLog>),
+    SlugAuctionCancelled(Vec<SlugAuctionLog>),
+    AccountsDenied(Vec<DenyListLog>),
+    AccountsAllowed(Vec<DenyListLog>),
+    GasCheckpoint(Vec<GasCheckpointLog>),
+    CollectionChangeProposed(Vec<CollectionChangeLog>),
+    CollectionAddressChanged(Vec<CollectionChangeLog>),
+    CollectionChangeCancelled(Vec<CollectionChangeLog>),
+    CheckerTokenUsed(Vec<CheckerTokenUsedLog>),
+    SponsorshipDeposit(Vec<SponsorshipLog>),
+    SponsorshipWithdraw(Vec<SponsorshipLog>),
+    SponsorshipLowBalance(Vec<SponsorshipLog>),
+    RocksHeld(Vec<RockHoldLog>),
+    RocksReleased(Vec<RockHoldLog>),
+    ZoneVisibilityUpdate(Vec<ZoneVisibilityLog>),
+    MintRefunded(Vec<MintRefundedLog>),
+    DeprecatedCall(Vec<DeprecatedCallLog>),
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -38,6 +86,17 @@ impl fmt::Display for EventLog {
     }
 }
 
+impl EventLog {
+    /// log the event with the next `event_nonce` of this contract, nonces increase by one
+    /// for every emitted event so indexers can detect missed or duplicated receipts
+    pub fn emit(&self) {
+        let event_nonce = next_event_nonce();
+        let mut event_json = serde_json::to_value(self).unwrap();
+        event_json["event_nonce"] = event_nonce.into();
+        env::log_str(&format!("EVENT_JSON:{}", event_json));
+    }
+}
+
 /// An event log to capture token minting
 ///
 /// Arguments
@@ -76,6 +135,16 @@ pub struct NftTransferLog {
     pub memo: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftBurnLog {
+    pub owner_id: String,
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct ImoInitLog {
@@ -115,3 +184,342 @@ pub struct ImoChangeZonePrice {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memo: Option<String>,
 }
+
+// every mutation of a zone price logs ImoChangeZonePrice, memo is the calling method
+pub fn emit_imo_change_zone_price(metaverse_id: String, zone_index: u16, new_price: U128, memo: &str) {
+    let imo_change_zone_price: EventLog = EventLog {
+        standard: "imo_change_zone_price".to_string(),
+        version: "1.1.0".to_string(),
+        event: EventLogVariant::ImoChangeZonePrice(vec![ImoChangeZonePrice {
+            metaverse_id,
+            zone_index,
+            new_price,
+            memo: Some(memo.to_string()),
+        }]),
+    };
+
+    imo_change_zone_price.emit();
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EmergencyActionLog {
+    pub action: String,
+    pub token_id: String,
+    pub admin_id: String,
+    pub executable_at: U64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneSoldOutLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PayoutLog {
+    pub payout_id: u64,
+    pub receiver_id: String,
+    pub amount: U128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorshipLog {
+    pub amount: U128,
+    pub balance: U128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MapCommitmentLog {
+    pub metaverse_id: String,
+    pub commitment: String,
+    pub effective_at: U64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RockPriceQuoteLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub price_usd_cents: u64,
+    pub price: U128,
+    pub from_oracle: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftMetadataUpdateLog {
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ModerationLog {
+    pub token_id: String,
+    // flagger or moderator
+    pub account_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RockAttributesLog {
+    pub token_id: String,
+    pub writer_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HostingLog {
+    pub metaverse_id: String,
+    pub monthly_fee: U128,
+    pub paid_through: U64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ParamChangeLog {
+    pub name: String,
+    // JSON encoded values
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ParametersUpdateLog {
+    pub admin_id: String,
+    pub changes: Vec<ParamChangeLog>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscrowLog {
+    pub token_id: String,
+    pub buyer_id: String,
+    pub amount: U128,
+    pub release_at: U64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CheckerTokenUsedLog {
+    pub metaverse_id: String,
+    pub collection_addr: String,
+    pub token_id: String,
+    pub minter: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RockGiftedLog {
+    pub token_id: String,
+    pub sender_id: String,
+    pub receiver_id: String,
+    pub message: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetadataFrozenLog {
+    pub token_id: String,
+    pub frozen_by: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractLockedLog {
+    pub verified_by: String,
+    pub code_hash: Base58CryptoHash,
+    pub block_height: U64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneExtendedLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    // range of the added rocks
+    pub rock_index_from: u128,
+    pub rock_index_to: u128,
+    pub additional_rocks: u128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetaverseSlugLog {
+    pub metaverse_id: String,
+    // None when the slug was released
+    pub slug: Option<String>,
+    pub previous_slug: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RegionLog {
+    pub region_id: String,
+    pub owner_id: String,
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SlugAuctionLog {
+    pub slug: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bidder_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metaverse_id: Option<String>,
+    // min_bid when started, the bid otherwise
+    pub amount: U128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CollectionChangeLog {
+    pub metaverse_id: String,
+    pub old_addr: String,
+    pub new_addr: String,
+    // operator or metaverse owner that proposed, confirmed or cancelled the change
+    pub account_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DenyListLog {
+    pub account_ids: Vec<String>,
+
+    // reason given by the admin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GasCheckpointLog {
+    pub method: String,
+    pub checkpoint: String,
+    pub used_gas: U64,
+    pub prepaid_gas: U64,
+    pub storage_usage: U64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RockHoldLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub rock_indices: Vec<u128>,
+    // set by RocksHeld, the hold expires by itself at this timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until_timestamp: Option<U64>,
+
+    // reason given by the operator
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintRefundedLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub rock_index: u128,
+    pub minter: String,
+    pub amount: U128,
+
+    // error of the failed holder check
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneVisibilityLog {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub visible: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DeprecatedCallLog {
+    pub method: String,
+    pub replacement: String,
+    // None for view calls, which cannot read the caller
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caller_id: Option<String>,
+}
+
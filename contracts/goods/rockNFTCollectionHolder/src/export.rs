@@ -0,0 +1,194 @@
+use crate::*;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum ExportSection {
+    Owner,
+    Zones,
+    MintedTokens,
+    CheckerUsage,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintedTokenExport {
+    pub token_id: TokenId,
+    pub owner_id: Option<AccountId>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "section", content = "items")]
+#[serde(rename_all = "snake_case")]
+pub enum ExportItems {
+    Owner(Vec<AccountId>),
+    Zones(Vec<Zone>),
+    MintedTokens(Vec<MintedTokenExport>),
+    CheckerUsage(Vec<TokenId>),
+}
+
+// collection token consumed by a holder-gated mint: in the whole metaverse (consume_per_mint)
+// without zone_index, in one zone (one_per_token_per_zone) with it
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CheckerUsage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zone_index: Option<u16>,
+    pub token_id: TokenId,
+}
+
+// one page of export_checker_usage, replayed with import_checker_usage
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CheckerUsageChunk {
+    pub metaverse_id: String,
+    pub from_index: u64,
+    pub total: u64,
+    pub usage: Vec<CheckerUsage>,
+}
+
+// one page of a metaverse section, items are sorted so pages are stable between calls
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetaverseStateChunk {
+    pub metaverse_id: String,
+    pub from_index: u64,
+    pub total: u64,
+    #[serde(flatten)]
+    pub items: ExportItems,
+}
+
+fn paginate<T>(items: Vec<T>, from_index: u64, limit: Option<u64>) -> Vec<T> {
+    let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+    items
+        .into_iter()
+        .skip(from_index as usize)
+        .take(limit)
+        .collect()
+}
+
+#[near_bindgen]
+impl Contract {
+    /// paginated export of a metaverse for off-chain backups and state reconstruction
+    pub fn export_metaverse_state(
+        &self,
+        metaverse_id: String,
+        section: ExportSection,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> MetaverseStateChunk {
+        let metaverse = self.assert_metaverse_exist(&metaverse_id);
+        let from_index = from_index.unwrap_or_default();
+        let (total, items) = match section {
+            ExportSection::Owner => {
+                let owners: Vec<AccountId> = self
+                    .metaverse_owners
+                    .get(&metaverse_id)
+                    .into_iter()
+                    .collect();
+                (
+                    owners.len(),
+                    ExportItems::Owner(paginate(owners, from_index, limit)),
+                )
+            }
+            ExportSection::Zones => {
+                let mut zones: Vec<Zone> = metaverse.zones.into_values().collect();
+                zones.sort_by_key(|zone| zone.zone_index);
+                (
+                    zones.len(),
+                    ExportItems::Zones(paginate(zones, from_index, limit)),
+                )
+            }
+            ExportSection::MintedTokens => {
+                let mut token_ids: Vec<TokenId> = self
+                    .tokens_minted
+                    .get(&metaverse_id)
+                    .unwrap_or_default()
+                    .into_keys()
+                    .collect();
+                token_ids.sort();
+                let total = token_ids.len();
+                let minted_tokens = paginate(token_ids, from_index, limit)
+                    .into_iter()
+                    .map(|token_id| MintedTokenExport {
+                        owner_id: self.tokens.owner_by_id.get(&token_id),
+                        token_id,
+                    })
+                    .collect();
+                (total, ExportItems::MintedTokens(minted_tokens))
+            }
+            ExportSection::CheckerUsage => {
+                let mut token_ids: Vec<TokenId> = self
+                    .nft_checker
+                    .get(&metaverse_id)
+                    .unwrap_or_default()
+                    .into_keys()
+                    .collect();
+                token_ids.sort();
+                (
+                    token_ids.len(),
+                    ExportItems::CheckerUsage(paginate(token_ids, from_index, limit)),
+                )
+            }
+        };
+
+        MetaverseStateChunk {
+            metaverse_id,
+            from_index,
+            total: total as u64,
+            items,
+        }
+    }
+
+    /// paginated export of the collection tokens consumed in a metaverse, metaverse-wide usage
+    /// first then each zone by zone_index. Without it a redeployment would let every
+    /// collection token mint a second rock
+    pub fn export_checker_usage(
+        &self,
+        metaverse_id: String,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> CheckerUsageChunk {
+        let metaverse = self.assert_metaverse_exist(&metaverse_id);
+        let from_index = from_index.unwrap_or_default();
+        let mut usage: Vec<CheckerUsage> = vec![];
+        let mut metaverse_token_ids: Vec<TokenId> = self
+            .nft_checker
+            .get(&metaverse_id)
+            .unwrap_or_default()
+            .into_keys()
+            .collect();
+        metaverse_token_ids.sort();
+        usage.extend(
+            metaverse_token_ids
+                .into_iter()
+                .map(|token_id| CheckerUsage {
+                    zone_index: None,
+                    token_id,
+                }),
+        );
+        let mut zone_indices: Vec<u16> = metaverse.zones.into_keys().collect();
+        zone_indices.sort();
+        for zone_index in zone_indices {
+            let mut zone_token_ids: Vec<TokenId> = self
+                .zone_nft_checker
+                .get(&gen_zone_key(&metaverse_id, zone_index))
+                .unwrap_or_default()
+                .into_keys()
+                .collect();
+            zone_token_ids.sort();
+            usage.extend(zone_token_ids.into_iter().map(|token_id| CheckerUsage {
+                zone_index: Some(zone_index),
+                token_id,
+            }));
+        }
+
+        CheckerUsageChunk {
+            metaverse_id,
+            from_index,
+            total: usage.len() as u64,
+            usage: paginate(usage, from_index, limit),
+        }
+    }
+}
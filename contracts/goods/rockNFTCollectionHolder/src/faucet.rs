@@ -0,0 +1,81 @@
+use crate::*;
+
+// testnet faucet: anyone mints up to max_per_account free rocks of any zone, paying storage only
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FaucetMode {
+    pub max_per_account: u32,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// admin enables (or disables with None) the faucet on a .testnet deployment, for QA and
+    /// partner integrations to mint rocks without funded accounts or operator keys
+    #[payable]
+    pub fn set_faucet_mode(&mut self, max_per_account: Option<u32>) {
+        self.assert_admin_only();
+        self.faucet_mode = max_per_account.map(|max_per_account| {
+            require!(
+                env::current_account_id().as_str().ends_with(".testnet"),
+                RoveError::InvalidArgument.detail("faucet mode is only for testnet deployments")
+            );
+            require!(
+                max_per_account > 0,
+                RoveError::InvalidArgument.detail("max_per_account must be > 0")
+            );
+            FaucetMode { max_per_account }
+        });
+    }
+
+    pub fn get_faucet_mode(&self) -> Option<FaucetMode> {
+        self.faucet_mode.clone()
+    }
+
+    pub fn get_faucet_mints(&self, account_id: AccountId) -> u32 {
+        self.faucet_mints.get(&account_id).unwrap_or_default()
+    }
+
+    /// faucet mode only: mints a rock of any zone to the caller without the zone price or
+    /// policy checks, the caller attaches the storage of the rock and gets back the rest
+    #[payable]
+    pub fn faucet_mint_rock(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        token_metadata: TokenMetadata,
+    ) -> TokenId {
+        let account_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+        self.internal_use_faucet(&account_id);
+        let (_zone, token_id) = self.assert_rock_mintable(&metaverse_id, zone_index, rock_index);
+
+        self._mint(
+            metaverse_id,
+            zone_index,
+            token_id.clone(),
+            account_id.clone(),
+            token_metadata,
+            U128(0),
+            // CoreTeam: free mint consuming no collection token, storage is charged below
+            MintPolicy::CoreTeam,
+            "".to_string(),
+            0,
+        );
+        refund_deposit_to_account(env::storage_usage() - initial_storage_usage, account_id);
+        token_id
+    }
+
+    fn internal_use_faucet(&mut self, account_id: &AccountId) {
+        let faucet_mode = self
+            .faucet_mode
+            .clone()
+            .unwrap_or_else(|| env::panic_str(RoveError::FaucetNotEnabled.as_str()));
+        let faucet_mints = self.faucet_mints.get(account_id).unwrap_or_default();
+        require!(
+            faucet_mints < faucet_mode.max_per_account,
+            RoveError::FaucetLimitReached.as_str()
+        );
+        self.faucet_mints.insert(account_id, &(faucet_mints + 1));
+    }
+}
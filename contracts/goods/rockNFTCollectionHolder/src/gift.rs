@@ -0,0 +1,102 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+pub const MAX_GIFT_MESSAGE_LEN: usize = 256;
+
+// message left by the last gift of a rock, kept until the rock is gifted again
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GiftMessage {
+    pub sender_id: AccountId,
+    pub message: String,
+    pub gifted_at: U64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// owner transfers a rock with a gift message, at least 1 yoctoNEAR plus the storage
+    /// of the message must be attached, the unused deposit is refunded
+    #[payable]
+    pub fn gift_rock(&mut self, token_id: TokenId, receiver_id: AccountId, message: String) {
+        require!(
+            env::attached_deposit() >= 1,
+            RoveError::DepositRequired.as_str()
+        );
+        require!(
+            !message.is_empty(),
+            RoveError::InvalidArgument.detail("message is empty")
+        );
+        require!(
+            message.len() <= MAX_GIFT_MESSAGE_LEN,
+            RoveError::ValueTooLong.detail("message must <= 256 bytes")
+        );
+        let sender_id = env::predecessor_account_id();
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        require!(owner_id == sender_id, RoveError::NotTokenOwner.as_str());
+        require!(owner_id != receiver_id, RoveError::SameOwner.as_str());
+        self.assert_transfer_allowed(&token_id, &receiver_id);
+
+        let initial_storage_usage = env::storage_usage();
+        self.internal_release_escrow(&token_id);
+        if let Some(approved_account_ids) = self
+            .tokens
+            .approvals_by_id
+            .as_mut()
+            .and_then(|by_id| by_id.remove(&token_id))
+        {
+            refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+        }
+        self.internal_transfer_token(&token_id, &owner_id, &receiver_id);
+        let gift_message = GiftMessage {
+            sender_id: sender_id.clone(),
+            message: message.clone(),
+            gifted_at: U64(env::block_timestamp()),
+        };
+        self.gift_messages.insert(&token_id, &gift_message);
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(env::storage_usage() - initial_storage_usage, sender_id);
+        }
+
+        let nft_transfer_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                authorized_id: None,
+                old_owner_id: owner_id.to_string(),
+                new_owner_id: receiver_id.to_string(),
+                token_ids: vec![token_id.clone()],
+                memo: Some(String::from("gift_rock")),
+            }]),
+        };
+        nft_transfer_log.emit();
+        emit_account_activity(vec![AccountActivityLog::new(
+            &owner_id,
+            ActivityAction::Transfer,
+            &token_id,
+            Some(&receiver_id),
+            None,
+        )]);
+
+        let rock_gifted_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::RockGifted(vec![RockGiftedLog {
+                token_id,
+                sender_id: owner_id.to_string(),
+                receiver_id: receiver_id.to_string(),
+                message,
+                memo: None,
+            }]),
+        };
+        rock_gifted_log.emit();
+    }
+
+    pub fn get_gift_message(&self, token_id: TokenId) -> Option<GiftMessage> {
+        self.gift_messages.get(&token_id)
+    }
+}
@@ -0,0 +1,121 @@
+use near_sdk::serde_json;
+
+use crate::*;
+
+// configuration changes applied together by set_parameters, None keeps the current value
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ParamsPatch {
+    pub admin_id: Option<AccountId>,
+    pub operator_id: Option<AccountId>,
+    pub treasury_id: Option<AccountId>,
+    pub init_imo_fee: Option<U128>,
+    pub rock_purchase_fee: Option<u32>,
+    pub fee_dust_policy: Option<DustPolicy>,
+    pub hosting_grace_period: Option<U64>,
+    pub init_imo_nft_holder_size: Option<u32>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// apply a batch of configuration changes in one admin call (e.g. one DAO proposal),
+    /// values equal to the current ones are skipped so the call is idempotent
+    #[payable]
+    pub fn set_parameters(&mut self, patch: ParamsPatch) {
+        self.assert_admin_only();
+        if let Some(rock_purchase_fee) = patch.rock_purchase_fee {
+            require!(
+                rock_purchase_fee <= 10_000,
+                RoveError::InvalidArgument.detail("rock_purchase_fee must <= 10_000")
+            );
+        }
+        let mut changes: Vec<ParamChangeLog> = vec![];
+        if let Some(admin_id) = patch.admin_id {
+            record_param_change(&mut changes, "admin_id", &self.admin_id, &admin_id);
+            self.admin_id = admin_id;
+        }
+        if let Some(operator_id) = patch.operator_id {
+            record_param_change(&mut changes, "operator_id", &self.operator_id, &operator_id);
+            self.tokens.owner_id = operator_id.clone();
+            self.operator_id = operator_id;
+        }
+        if let Some(treasury_id) = patch.treasury_id {
+            record_param_change(&mut changes, "treasury_id", &self.treasury_id, &treasury_id);
+            self.treasury_id = treasury_id;
+        }
+        if let Some(init_imo_fee) = patch.init_imo_fee {
+            record_param_change(
+                &mut changes,
+                "init_imo_fee",
+                &U128(self.init_imo_fee),
+                &init_imo_fee,
+            );
+            self.init_imo_fee = init_imo_fee.0;
+        }
+        if let Some(rock_purchase_fee) = patch.rock_purchase_fee {
+            record_param_change(
+                &mut changes,
+                "rock_purchase_fee",
+                &self.rock_purchase_fee,
+                &rock_purchase_fee,
+            );
+            self.rock_purchase_fee = rock_purchase_fee;
+        }
+        if let Some(fee_dust_policy) = patch.fee_dust_policy {
+            record_param_change(
+                &mut changes,
+                "fee_dust_policy",
+                &self.fee_dust_policy,
+                &fee_dust_policy,
+            );
+            self.fee_dust_policy = fee_dust_policy;
+        }
+        if let Some(hosting_grace_period) = patch.hosting_grace_period {
+            record_param_change(
+                &mut changes,
+                "hosting_grace_period",
+                &U64(self.hosting_grace_period),
+                &hosting_grace_period,
+            );
+            self.hosting_grace_period = hosting_grace_period.0;
+        }
+        if let Some(init_imo_nft_holder_size) = patch.init_imo_nft_holder_size {
+            record_param_change(
+                &mut changes,
+                "init_imo_nft_holder_size",
+                &self.init_imo_nft_holder_size,
+                &init_imo_nft_holder_size,
+            );
+            self.init_imo_nft_holder_size = init_imo_nft_holder_size;
+        }
+        if changes.is_empty() {
+            return;
+        }
+        let parameters_update_log: EventLog = EventLog {
+            standard: "governance".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::ParametersUpdate(vec![ParametersUpdateLog {
+                admin_id: env::predecessor_account_id().to_string(),
+                changes,
+                memo: None,
+            }]),
+        };
+
+        parameters_update_log.emit();
+    }
+}
+
+fn record_param_change<T: Serialize + PartialEq>(
+    changes: &mut Vec<ParamChangeLog>,
+    name: &str,
+    before: &T,
+    after: &T,
+) {
+    if before != after {
+        changes.push(ParamChangeLog {
+            name: name.to_string(),
+            before: serde_json::to_string(before).unwrap(),
+            after: serde_json::to_string(after).unwrap(),
+        });
+    }
+}
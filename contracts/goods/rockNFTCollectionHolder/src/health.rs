@@ -0,0 +1,68 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+// get_health, amounts in yoctoNEAR
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractHealth {
+    pub storage_usage: U64,
+    // balance locked by the storage of the contract
+    pub storage_stake: U128,
+    pub account_balance: U128,
+    // account balance above the storage stake, refunds and payouts fail once it is spent
+    pub available_balance: U128,
+    pub metaverse_count: u64,
+    pub token_count: U64,
+    // payouts that failed and wait for retry_payout
+    pub failed_payout_count: u64,
+    pub failed_payout_amount: U128,
+    // primary sales held during their refund window
+    pub escrowed_sale_count: u64,
+    pub escrowed_amount: U128,
+    // pool paying the storage of free mints in sponsored zones, low below
+    // sponsorship_low_balance
+    pub sponsorship_balance: U128,
+    pub sponsorship_balance_low: bool,
+    // import methods are disabled
+    pub import_finalized: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// storage, balance and pending payouts of the contract in one view, for monitoring to
+    /// alert when the available balance runs low. Reads every failed payout and escrowed sale
+    pub fn get_health(&self) -> ContractHealth {
+        let storage_usage = env::storage_usage();
+        let storage_stake = env::storage_byte_cost() * Balance::from(storage_usage);
+        let account_balance = env::account_balance();
+        let failed_payout_amount: Balance = self
+            .failed_payouts
+            .values()
+            .map(|failed_payout| failed_payout.amount.0)
+            .sum();
+        let escrowed_amount: Balance = self
+            .escrowed_sales
+            .values()
+            .map(|escrowed_sale| {
+                escrowed_sale.treasury_amount.0 + escrowed_sale.metaverse_owner_amount.0
+            })
+            .sum();
+
+        ContractHealth {
+            storage_usage: U64(storage_usage),
+            storage_stake: U128(storage_stake),
+            account_balance: U128(account_balance),
+            available_balance: U128(account_balance.saturating_sub(storage_stake)),
+            metaverse_count: self.metaverses.len(),
+            token_count: U64(self.tokens.owner_by_id.len()),
+            failed_payout_count: self.failed_payouts.len(),
+            failed_payout_amount: U128(failed_payout_amount),
+            escrowed_sale_count: self.escrowed_sales.len(),
+            escrowed_amount: U128(escrowed_amount),
+            sponsorship_balance: U128(self.sponsorship_balance),
+            sponsorship_balance_low: self.sponsorship_balance < self.sponsorship_low_balance,
+            import_finalized: self.import_finalized,
+        }
+    }
+}
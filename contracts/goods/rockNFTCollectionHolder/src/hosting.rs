@@ -0,0 +1,162 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+// one hosting month (30 days)
+pub const HOSTING_PERIOD: u64 = 30 * 24 * 3_600_000_000_000;
+pub const DEFAULT_HOSTING_GRACE_PERIOD: u64 = 7 * 24 * 3_600_000_000_000;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HostingPlan {
+    pub monthly_fee: U128,
+    pub paid_through: U64,
+    // set when the overdue event was emitted, cleared by the next payment
+    pub overdue_reported_at: Option<U64>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator sets (or removes with None) the monthly hosting fee of a metaverse
+    #[payable]
+    pub fn set_hosting_fee(&mut self, metaverse_id: String, monthly_fee: Option<U128>) {
+        self.assert_operator_only();
+        self.assert_metaverse_exist(&metaverse_id);
+
+        match monthly_fee {
+            Some(monthly_fee) => {
+                require!(
+                    monthly_fee.0 > 0,
+                    RoveError::InvalidArgument.detail("monthly_fee must > 0")
+                );
+                // a new plan is due now, the grace period applies before it is overdue
+                let paid_through = self
+                    .hosting_plans
+                    .get(&metaverse_id)
+                    .map(|hosting_plan| hosting_plan.paid_through)
+                    .unwrap_or(U64(env::block_timestamp()));
+                let hosting_plan = HostingPlan {
+                    monthly_fee,
+                    paid_through,
+                    overdue_reported_at: None,
+                };
+                self.hosting_plans.insert(&metaverse_id, &hosting_plan);
+                emit_hosting_log(
+                    EventLogVariant::HostingFeeUpdate,
+                    metaverse_id,
+                    &hosting_plan,
+                );
+            }
+            None => {
+                self.hosting_plans.remove(&metaverse_id);
+            }
+        }
+    }
+
+    #[payable]
+    pub fn set_hosting_grace_period(&mut self, grace_period: U64) {
+        self.assert_operator_only();
+        self.hosting_grace_period = grace_period.0;
+    }
+
+    /// metaverse owner pays whole months of hosting, the rest of the deposit is refunded
+    #[payable]
+    pub fn pay_hosting_fee(&mut self, metaverse_id: String) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let mut hosting_plan = self
+            .hosting_plans
+            .get(&metaverse_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::NoHostingFee.as_str()));
+
+        let monthly_fee = hosting_plan.monthly_fee.0;
+        let attached_deposit = env::attached_deposit();
+        let months = attached_deposit / monthly_fee;
+        require!(
+            months > 0,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR to pay one month of hosting",
+                monthly_fee
+            ))
+        );
+
+        hosting_plan.paid_through =
+            U64(hosting_plan.paid_through.0 + months as u64 * HOSTING_PERIOD);
+        hosting_plan.overdue_reported_at = None;
+        self.hosting_plans.insert(&metaverse_id, &hosting_plan);
+
+        self.internal_pay_treasury(months * monthly_fee);
+        let refund = attached_deposit - months * monthly_fee;
+        if refund > 0 {
+            self.internal_payout(env::predecessor_account_id(), refund);
+        }
+
+        emit_hosting_log(EventLogVariant::HostingFeePaid, metaverse_id, &hosting_plan);
+    }
+
+    /// emit the overdue event of a metaverse past its grace period, callable by anyone
+    pub fn report_overdue_hosting(&mut self, metaverse_id: String) {
+        let mut hosting_plan = self
+            .hosting_plans
+            .get(&metaverse_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::NoHostingFee.as_str()));
+        require!(
+            self.internal_is_hosting_overdue(&hosting_plan),
+            RoveError::HostingNotOverdue.as_str()
+        );
+        require!(
+            hosting_plan.overdue_reported_at.is_none(),
+            RoveError::HostingNotOverdue.detail("hosting overdue is already reported")
+        );
+
+        hosting_plan.overdue_reported_at = Some(U64(env::block_timestamp()));
+        self.hosting_plans.insert(&metaverse_id, &hosting_plan);
+        emit_hosting_log(EventLogVariant::HostingOverdue, metaverse_id, &hosting_plan);
+    }
+
+    pub fn get_hosting_plan(&self, metaverse_id: String) -> Option<HostingPlan> {
+        self.hosting_plans.get(&metaverse_id)
+    }
+
+    pub fn get_hosting_grace_period(&self) -> U64 {
+        U64(self.hosting_grace_period)
+    }
+
+    pub fn get_overdue_metaverses(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<String> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        self.hosting_plans
+            .iter()
+            .filter(|(_, hosting_plan)| self.internal_is_hosting_overdue(hosting_plan))
+            .skip(start_index as usize)
+            .take(limit)
+            .map(|(metaverse_id, _)| metaverse_id)
+            .collect()
+    }
+
+    fn internal_is_hosting_overdue(&self, hosting_plan: &HostingPlan) -> bool {
+        env::block_timestamp() > hosting_plan.paid_through.0 + self.hosting_grace_period
+    }
+}
+
+fn emit_hosting_log(
+    variant: fn(Vec<HostingLog>) -> EventLogVariant,
+    metaverse_id: String,
+    hosting_plan: &HostingPlan,
+) {
+    let hosting_log: EventLog = EventLog {
+        standard: "hosting".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![HostingLog {
+            metaverse_id,
+            monthly_fee: hosting_plan.monthly_fee,
+            paid_through: hosting_plan.paid_through,
+            memo: None,
+        }]),
+    };
+
+    hosting_log.emit();
+}
@@ -0,0 +1,216 @@
+use crate::*;
+
+// a metaverse replayed from export_metaverse_state of a previous deployment
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetaverseImport {
+    pub metaverse_id: String,
+    pub owner_id: AccountId,
+    pub zones: Vec<Zone>,
+    // zone_index => checker policy, ConsumePerMint when missing
+    pub checker_policies: HashMap<u16, CheckerPolicy>,
+    // collection tokens already consumed in the metaverse (export section checker_usage)
+    pub checker_usage: Vec<TokenId>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintedTokenImport {
+    pub token_id: TokenId,
+    pub owner_id: AccountId,
+    pub token_metadata: TokenMetadata,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator replays metaverses of a previous deployment, disabled after finalize_import
+    #[payable]
+    pub fn import_metaverses(&mut self, batch: Vec<MetaverseImport>) {
+        self.assert_operator_only();
+        self.assert_import_open();
+        require!(!batch.is_empty(), RoveError::EmptyBatch.as_str());
+
+        let initial_storage_usage = env::storage_usage();
+        for metaverse_import in batch {
+            require!(
+                self.metaverses
+                    .get(&metaverse_import.metaverse_id)
+                    .is_none(),
+                RoveError::MetaverseAlreadyExists.as_str()
+            );
+            let mut zones = HashMap::new();
+            for zone in metaverse_import.zones {
+                require!(self.check_zone(&zone), RoveError::InvalidZone.as_str());
+                if zone.mint_policy == MintPolicy::HolderGated {
+                    require!(
+                        self.metaverse_nft_collections
+                            .get(&zone.collection_addr)
+                            .is_none(),
+                        RoveError::InvalidCollection
+                            .detail("this collection address is already used")
+                    );
+                    self.metaverse_nft_collections
+                        .insert(&zone.collection_addr, &metaverse_import.metaverse_id);
+                }
+                zones.insert(zone.zone_index, zone);
+            }
+            for (zone_index, checker_policy) in metaverse_import.checker_policies {
+                require!(
+                    zones.get(&zone_index).map(|zone| zone.mint_policy)
+                        == Some(MintPolicy::HolderGated),
+                    RoveError::InvalidZoneType
+                        .detail("checker_policy is only for holder_gated zones")
+                );
+                self.internal_set_checker_policy(
+                    &metaverse_import.metaverse_id,
+                    zone_index,
+                    checker_policy,
+                );
+            }
+            self.metaverses
+                .insert(&metaverse_import.metaverse_id, &Metaverse { zones });
+            self.metaverse_owners
+                .insert(&metaverse_import.metaverse_id, &metaverse_import.owner_id);
+            self.internal_count_owned_metaverse(None, &metaverse_import.owner_id);
+            self.tokens_minted
+                .insert(&metaverse_import.metaverse_id, &HashMap::new());
+            let nft_checker: HashMap<String, bool> = metaverse_import
+                .checker_usage
+                .into_iter()
+                .map(|token_id| (token_id, true))
+                .collect();
+            self.nft_checker
+                .insert(&metaverse_import.metaverse_id, &nft_checker);
+        }
+        self.internal_charge_import_storage(initial_storage_usage);
+    }
+
+    /// operator replays minted rocks of an imported metaverse with their token ids and owners,
+    /// disabled after finalize_import
+    #[payable]
+    pub fn import_minted_tokens(&mut self, metaverse_id: String, batch: Vec<MintedTokenImport>) {
+        self.assert_operator_only();
+        self.assert_import_open();
+        self.assert_metaverse_exist(&metaverse_id);
+        require!(!batch.is_empty(), RoveError::EmptyBatch.as_str());
+
+        let initial_storage_usage = env::storage_usage();
+        let mut tokens_minted = self.tokens_minted.get(&metaverse_id).unwrap_or_default();
+        let mut token_ids_by_owner: HashMap<AccountId, Vec<String>> = HashMap::new();
+        for minted_token in batch {
+            let token_id = minted_token.token_id;
+            let token_id_parts: Vec<&str> = token_id.split(':').collect();
+            require!(
+                token_id_parts.len() == 3 && token_id_parts[0] == metaverse_id,
+                RoveError::InvalidTokenId.as_str()
+            );
+            let zone_index: u16 = token_id_parts[1]
+                .parse()
+                .unwrap_or_else(|_| env::panic_str(RoveError::InvalidTokenId.as_str()));
+            let rock_index: u128 = token_id_parts[2]
+                .parse()
+                .unwrap_or_else(|_| env::panic_str(RoveError::InvalidTokenId.as_str()));
+            self.assert_rock_mintable(&metaverse_id, zone_index, rock_index);
+
+            self.tokens.internal_mint_with_refund(
+                token_id.clone(),
+                minted_token.owner_id.clone(),
+                Some(minted_token.token_metadata),
+                None,
+            );
+            tokens_minted.insert(token_id.clone(), true);
+            // assert_rock_mintable of the next token reads tokens_minted from state
+            self.tokens_minted.insert(&metaverse_id, &tokens_minted);
+            self.internal_increase_zone_minted(&metaverse_id, zone_index);
+            self.internal_update_holding(&token_id, None, Some(&minted_token.owner_id));
+            token_ids_by_owner
+                .entry(minted_token.owner_id)
+                .or_default()
+                .push(token_id);
+        }
+        self.internal_charge_import_storage(initial_storage_usage);
+
+        let nft_mint_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftMint(
+                token_ids_by_owner
+                    .into_iter()
+                    .map(|(owner_id, token_ids)| NftMintLog {
+                        owner_id: owner_id.to_string(),
+                        token_ids,
+                        memo: Some("import".to_string()),
+                    })
+                    .collect(),
+            ),
+        };
+        nft_mint_log.emit();
+    }
+
+    /// operator replays pages of export_checker_usage of a previous deployment into an imported
+    /// metaverse, for usage too large for the checker_usage of import_metaverses. Disabled
+    /// after finalize_import
+    #[payable]
+    pub fn import_checker_usage(&mut self, metaverse_id: String, usage: Vec<CheckerUsage>) {
+        self.assert_operator_only();
+        self.assert_import_open();
+        let metaverse = self.assert_metaverse_exist(&metaverse_id);
+        require!(!usage.is_empty(), RoveError::EmptyBatch.as_str());
+
+        let initial_storage_usage = env::storage_usage();
+        let mut nft_checker = self.nft_checker.get(&metaverse_id).unwrap_or_default();
+        let mut zone_nft_checkers: HashMap<u16, HashMap<String, bool>> = HashMap::new();
+        for checker_usage in usage {
+            match checker_usage.zone_index {
+                None => {
+                    nft_checker.insert(checker_usage.token_id, true);
+                }
+                Some(zone_index) => {
+                    require!(
+                        metaverse.zones.contains_key(&zone_index),
+                        RoveError::ZoneNotFound.as_str()
+                    );
+                    zone_nft_checkers
+                        .entry(zone_index)
+                        .or_insert_with(|| {
+                            self.zone_nft_checker
+                                .get(&gen_zone_key(&metaverse_id, zone_index))
+                                .unwrap_or_default()
+                        })
+                        .insert(checker_usage.token_id, true);
+                }
+            }
+        }
+        self.nft_checker.insert(&metaverse_id, &nft_checker);
+        for (zone_index, zone_nft_checker) in zone_nft_checkers {
+            self.zone_nft_checker
+                .insert(&gen_zone_key(&metaverse_id, zone_index), &zone_nft_checker);
+        }
+        self.internal_charge_import_storage(initial_storage_usage);
+    }
+
+    /// operator closes the import, import methods can not be called anymore
+    #[payable]
+    pub fn finalize_import(&mut self) {
+        self.assert_operator_only();
+        self.assert_import_open();
+        self.import_finalized = true;
+    }
+
+    pub fn is_import_finalized(&self) -> bool {
+        self.import_finalized
+    }
+
+    fn assert_import_open(&self) {
+        require!(!self.import_finalized, RoveError::ImportFinalized.as_str());
+    }
+
+    fn internal_charge_import_storage(&self, initial_storage_usage: u64) {
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+}
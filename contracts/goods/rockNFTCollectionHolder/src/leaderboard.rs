@@ -0,0 +1,110 @@
+use crate::*;
+
+// accounts kept in the top holder list of a metaverse
+pub const MAX_TOP_HOLDERS: usize = 100;
+
+// rocks of a metaverse held by an account, a region counts its rocks
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HolderCount {
+    pub account_id: AccountId,
+    pub count: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// `limit` (default 10, at most MAX_TOP_HOLDERS) accounts holding the most rocks of a
+    /// metaverse, by count. The list is updated on mint, transfer and burn: an account that
+    /// drops out of a full list comes back once its count changes again
+    pub fn get_top_holders(&self, metaverse_id: String, limit: Option<u64>) -> Vec<HolderCount> {
+        let limit = (limit.unwrap_or(10) as usize).min(MAX_TOP_HOLDERS);
+        let mut top_holders = self.top_holders.get(&metaverse_id).unwrap_or_default();
+        top_holders.truncate(limit);
+        top_holders
+    }
+
+    pub fn get_holder_count(&self, metaverse_id: String, account_id: AccountId) -> u64 {
+        self.holder_counts
+            .get(&holder_key(&metaverse_id, &account_id))
+            .unwrap_or_default()
+    }
+
+    // internal_transfer_unguarded of the standard implementation, counted for the leaderboard
+    pub(crate) fn internal_transfer_token(
+        &mut self,
+        token_id: &TokenId,
+        owner_id: &AccountId,
+        receiver_id: &AccountId,
+    ) {
+        self.tokens
+            .internal_transfer_unguarded(token_id, owner_id, receiver_id);
+        self.internal_update_holding(token_id, Some(owner_id), Some(receiver_id));
+    }
+
+    // move the rocks of token_id from previous_owner_id (None on mint) to owner_id (None on
+    // burn). Rocks held by the contract itself (regions, installments) are not counted
+    pub(crate) fn internal_update_holding(
+        &mut self,
+        token_id: &TokenId,
+        previous_owner_id: Option<&AccountId>,
+        owner_id: Option<&AccountId>,
+    ) {
+        let metaverse_id = metaverse_id_of_token(token_id);
+        let rock_count = self
+            .regions
+            .get(token_id)
+            .map(|token_ids| token_ids.len() as u64)
+            .unwrap_or(1);
+        let contract_id = env::current_account_id();
+        if let Some(previous_owner_id) = previous_owner_id.filter(|id| **id != contract_id) {
+            let count = self.get_holder_count(metaverse_id.clone(), previous_owner_id.clone());
+            self.internal_set_holder_count(
+                &metaverse_id,
+                previous_owner_id,
+                count.saturating_sub(rock_count),
+            );
+        }
+        if let Some(owner_id) = owner_id.filter(|id| **id != contract_id) {
+            let count = self.get_holder_count(metaverse_id.clone(), owner_id.clone());
+            self.internal_set_holder_count(&metaverse_id, owner_id, count + rock_count);
+        }
+    }
+
+    fn internal_set_holder_count(
+        &mut self,
+        metaverse_id: &String,
+        account_id: &AccountId,
+        count: u64,
+    ) {
+        let key = holder_key(metaverse_id, account_id);
+        if count > 0 {
+            self.holder_counts.insert(&key, &count);
+        } else {
+            self.holder_counts.remove(&key);
+        }
+
+        let mut top_holders = self.top_holders.get(metaverse_id).unwrap_or_default();
+        top_holders.retain(|holder| holder.account_id != *account_id);
+        let index = top_holders.partition_point(|holder| holder.count >= count);
+        if count > 0 && index < MAX_TOP_HOLDERS {
+            top_holders.insert(
+                index,
+                HolderCount {
+                    account_id: account_id.clone(),
+                    count,
+                },
+            );
+            top_holders.truncate(MAX_TOP_HOLDERS);
+        }
+        if top_holders.is_empty() {
+            self.top_holders.remove(metaverse_id);
+        } else {
+            self.top_holders.insert(metaverse_id, &top_holders);
+        }
+    }
+}
+
+// key of holder_counts
+fn holder_key(metaverse_id: &String, account_id: &AccountId) -> String {
+    format!("{}:{}", metaverse_id, account_id)
+}
@@ -15,40 +15,164 @@ NOTES:
   - To prevent the deployed contract from being modified or deleted, it should not have any access
     keys on its account.
  */
+// near_bindgen generates a wrapper per public method that clippy checks against the argument
+// limit, an allow on the method or impl does not reach it (mint_rock)
+#![allow(clippy::too_many_arguments)]
 use std::collections::HashMap;
 
 use near_contract_standards::non_fungible_token::metadata::{
     NFTContractMetadata, NonFungibleTokenMetadataProvider, TokenMetadata,
 };
-use near_contract_standards::non_fungible_token::{refund_deposit_to_account, NonFungibleToken};
+use near_contract_standards::non_fungible_token::{
+    refund_approved_account_ids, refund_deposit_to_account, NonFungibleToken,
+};
 use near_contract_standards::non_fungible_token::{Token, TokenId};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, UnorderedMap};
+use near_sdk::collections::{
+    LazyOption, LookupMap, LookupSet, UnorderedMap, UnorderedSet, Vector,
+};
 use near_sdk::ext_contract;
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
-    assert_one_yocto, env, near_bindgen, require, AccountId, Balance, BorshStorageKey, Gas,
-    PanicOnDefault, Promise, PromiseOrValue, PromiseResult,
+    assert_one_yocto, env, near_bindgen, require, AccountId, Balance, BorshStorageKey, CryptoHash,
+    Gas, PanicOnDefault, Promise, PromiseOrValue, PromiseResult,
+};
+use rove_contracts_interface::{
+    compute_payout, emit_account_activity, rock_token_id, sha256_hash, split_purchase_fee,
+    verify_merkle_proof, AccountActivityLog, ActivityAction, DustPolicy, Payout,
+    ONE_HUNDRED_PERCENT_IN_BPS,
 };
 
+pub use crate::checker::*;
+pub use crate::collection_change::*;
+pub use crate::eligibility::*;
+pub use crate::allowlist::*;
+pub use crate::api_version::*;
+pub use crate::approval::*;
+pub use crate::attributes::*;
+pub use crate::compatibility::*;
+pub use crate::creation_gate::*;
+pub use crate::deposit_guard::*;
+pub use crate::emergency::*;
+pub use crate::errors::*;
+pub use crate::escrow::*;
 pub use crate::events::*;
+pub use crate::governance::*;
+pub use crate::export::*;
+pub use crate::faucet::*;
+pub use crate::fee_tiers::*;
+pub use crate::gift::*;
+pub use crate::health::*;
+pub use crate::holds::*;
+pub use crate::hosting::*;
+pub use crate::import::*;
+pub use crate::invariants::*;
+pub use crate::leaderboard::*;
+pub use crate::limits::*;
+pub use crate::lock_attestation::*;
 use crate::internal::*;
+pub use crate::map_commitment::*;
+pub use crate::migrate::*;
+pub use crate::mint_listeners::*;
+pub use crate::mint_policy::*;
+pub use crate::mint_requests::*;
+pub use crate::moderation::*;
+pub use crate::oracle::*;
+pub use crate::ownership::*;
+pub use crate::region::*;
+pub use crate::rock_metadata::*;
 pub use crate::royalty::*;
+pub use crate::settlement::*;
+pub use crate::slug_auction::*;
+pub use crate::slugs::*;
+pub use crate::storage_quote::*;
+pub use crate::tags::*;
+pub use crate::themes::*;
+pub use crate::throttle::*;
+pub use crate::token_detail::*;
+pub use crate::treasury_split::*;
 pub use crate::types::*;
-
+pub use crate::zone_receivers::*;
+pub use crate::zone_summary::*;
+pub use crate::zone_capacity::*;
+
+mod checker;
+mod collection_change;
+mod compatibility;
+mod creation_gate;
+#[path = "../../shared/deny_list.rs"]
+mod deny_list;
+mod deposit_guard;
+mod eligibility;
+mod allowlist;
+mod api_version;
+#[path = "../../shared/approval.rs"]
+mod approval;
+mod attributes;
+#[path = "../../shared/emergency.rs"]
+mod emergency;
+#[path = "../../shared/errors.rs"]
+mod errors;
+#[path = "../../shared/escrow.rs"]
+mod escrow;
 mod events;
+mod governance;
+mod export;
+mod faucet;
+mod fee_tiers;
+#[path = "../../shared/gas_profiling.rs"]
+mod gas_profiling;
+mod gift;
+mod health;
+#[path = "../../shared/holds.rs"]
+mod holds;
+mod hosting;
+mod import;
+#[path = "../../shared/internal.rs"]
 mod internal;
+mod invariants;
+mod leaderboard;
+mod limits;
+mod lock_attestation;
+mod map_commitment;
+mod metadata_freeze;
+mod migrate;
+mod mint_listeners;
+mod mint_policy;
+mod mint_requests;
+mod moderation;
+mod non_holder_price;
+mod oracle;
+mod ownership;
+#[path = "../../shared/region.rs"]
+mod region;
+mod rock_metadata;
 mod royalty;
+#[path = "../../shared/settlement.rs"]
+mod settlement;
+mod slug_auction;
+mod slugs;
+mod sponsorship;
+mod storage_quote;
+mod tags;
+mod themes;
+mod throttle;
+mod token_detail;
+mod treasury_split;
 mod types;
+mod visibility;
+#[path = "../../shared/zone_receivers.rs"]
+mod zone_receivers;
+mod zone_summary;
+mod zone_capacity;
 
-const ONE_HUNDRED_PERCENT_IN_BPS: u16 = 10_000;
 pub const NFT_METADATA_SPEC: &str = "1.0.0";
 pub const NFT_STANDARD_NAME: &str = "nep171";
-pub const NOT_FOUND_METAVERSE_ID_ERROR: &str = "Not found metaverse_id";
-pub const NOT_FOUND_ZONE_INDEX_ERROR: &str = "Not found zone_index";
 pub const GAS_FOR_COMMON_OPERATIONS: Gas = Gas(30_000_000_000_000);
 pub const GAS_RESERVED_FOR_CURRENT_CALL: Gas = Gas(20_000_000_000_000);
+// rock indexes returned by one get_unminted_rocks call
+pub const MAX_UNMINTED_ROCKS_LIMIT: u64 = 500;
 
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
@@ -65,6 +189,7 @@ pub struct Contract {
 
     pub init_imo_fee: u128,     // fee in yoctoNEAR 1e-24 NEAR
     pub rock_purchase_fee: u32, // in percent, with 0.01% = 1 = rock_purchase_fee
+    pub fee_dust_policy: DustPolicy, // receiver of the rounding remainder of rock_purchase_fee
     pub init_imo_nft_holder_size: u32,
 
     // Map metaverse_id => MetaverseMetadata
@@ -75,12 +200,202 @@ pub struct Contract {
     // Map metaverse_id => [token_id => true/false]
     pub tokens_minted: UnorderedMap<String, HashMap<String, bool>>,
 
-    // Map metaverse_id => nft collection address
+    // Map nft collection address => metaverse_id
     // 1 metaverse only map with 1 nft collections -> add zone-2 always = this nft collection
     pub metaverse_nft_collections: UnorderedMap<String, String>,
 
     // Map metaverse_id => [token_id => true]
     pub nft_checker: UnorderedMap<String, HashMap<String, bool>>,
+
+    // Map {action}:{token_id} => timestamp from which the emergency action can be executed
+    pub emergency_actions: UnorderedMap<String, u64>,
+
+    // Map metaverse_id => [zone_index => number of minted rocks]
+    pub zones_minted: UnorderedMap<String, HashMap<u16, u128>>,
+
+    // Map payout_id => transfer that failed and can be retried
+    pub failed_payouts: UnorderedMap<u64, FailedPayout>,
+    pub next_payout_id: u64,
+    pub treasury_settlement: TreasurySettlement,
+
+    // Map account_id => rocks bought by this account, each account has its own Vector so a mint
+    // only appends one entry
+    pub purchases: LookupMap<AccountId, Vector<Purchase>>,
+
+    // Map {metaverse_id}:{account_id} => result of the last nft checker call
+    pub holder_checks: UnorderedMap<String, HolderCheck>,
+
+    // Map metaverse_id => [zone_index => checker policy], ConsumePerMint when missing
+    pub checker_policies: UnorderedMap<String, HashMap<u16, CheckerPolicy>>,
+    // Map {metaverse_id}:{zone_index} => [token_id => true], for OnePerTokenPerZone zones
+    pub zone_nft_checker: UnorderedMap<String, HashMap<String, bool>>,
+
+    // pool paying storage of free mints in sponsored zones
+    pub sponsorship_balance: u128,
+    pub sponsorship_low_balance: u128,
+    // Map {metaverse_id}:{zone_index} => true
+    pub sponsored_zones: UnorderedMap<String, bool>,
+
+    // Map metaverse_id => commitment of its off-chain world layout
+    pub map_commitments: UnorderedMap<String, MapCommitment>,
+
+    // Map {metaverse_id}:{zone_index} => Merkle allowlist of the zone
+    pub allowlists: UnorderedMap<String, ZoneAllowlist>,
+
+    // oracle converting USD zone prices to yoctoNEAR
+    pub price_oracle: Option<PriceOracleConfig>,
+    // Map {metaverse_id}:{zone_index} => zone price in USD cents
+    pub zone_usd_prices: UnorderedMap<String, u64>,
+
+    // Map metaverse_id => token metadata fields editable by rock owners
+    pub metadata_schemas: UnorderedMap<String, MetadataSchema>,
+
+    // accounts allowed to review flags and take down token content
+    pub moderators: UnorderedSet<AccountId>,
+    // Map token_id => pending flags of the token
+    pub token_flags: UnorderedMap<TokenId, Vec<TokenFlag>>,
+    // Map account_id => timestamp of its last flag
+    pub last_flag_at: UnorderedMap<AccountId, u64>,
+
+    // Map token_id => gameplay attributes of the rock
+    pub rock_attributes: LookupMap<TokenId, Vec<(String, String)>>,
+    // Map metaverse_id => game contracts allowed to write rock attributes
+    pub attribute_writers: UnorderedMap<String, Vec<AccountId>>,
+
+    // Map metaverse_id => monthly hosting fee and paid-through timestamp
+    pub hosting_plans: UnorderedMap<String, HostingPlan>,
+    pub hosting_grace_period: u64,
+
+    // Map {metaverse_id}:{zone_index} => mint rate limit of the zone
+    pub mint_throttles: UnorderedMap<String, MintThrottle>,
+    // Map {metaverse_id}:{zone_index} => (block_height, mints in that block)
+    pub zone_block_mints: LookupMap<String, (u64, u32)>,
+    // Map {metaverse_id}:{zone_index}:{account_id} => last mint timestamps of the account
+    pub account_mint_rings: LookupMap<String, MintRing>,
+
+    // Map metaverse_id => refund window of its primary sales in seconds
+    pub escrow_windows: UnorderedMap<String, u32>,
+    // Map token_id => primary sale proceeds held during the refund window
+    pub escrowed_sales: UnorderedMap<TokenId, EscrowedSale>,
+
+    // Map token_id => expiry of the holder-gated mint waiting for its nft checker callback
+    pub pending_mints: LookupMap<TokenId, u64>,
+
+    // Map collection_addr => gas of its nft_tokens_for_owner call
+    pub collection_gas: LookupMap<AccountId, u64>,
+
+    // Map metaverse_id => environment themes allowed on its rocks
+    pub metaverse_themes: LookupMap<String, Vec<String>>,
+
+    // set by finalize_import, import_metaverses and import_minted_tokens are disabled after it
+    pub import_finalized: bool,
+
+    // volume-based schedule overriding rock_purchase_fee, ascending by volume_threshold
+    pub fee_tiers: Vec<FeeTier>,
+    // Map metaverse_id => cumulative primary sale volume
+    pub metaverse_volumes: LookupMap<String, Balance>,
+
+    // Map token_id => message of its last gift
+    pub gift_messages: LookupMap<TokenId, GiftMessage>,
+
+    // Map token_id => timestamp its metadata was frozen at
+    pub frozen_metadata: LookupMap<TokenId, u64>,
+
+    // set by assert_no_full_access_keys once the account keys are removed
+    pub lock_attestation: Option<LockAttestation>,
+
+    // Map collection_addr => maximum rocks of the holder zone of its metaverse
+    pub collection_max_zone_sizes: LookupMap<AccountId, u32>,
+    // Map metaverse_id => maximum rocks of all its zones
+    pub metaverse_rock_caps: LookupMap<String, u128>,
+
+    // set by set_creation_gate, init_metaverse checks the creator against it
+    pub creation_gate: Option<CreationGate>,
+
+    // Map metaverse_id => its slug
+    pub metaverse_slugs: LookupMap<String, String>,
+    // Map slug => metaverse_id
+    pub slug_metaverses: LookupMap<String, String>,
+    pub slug_fee: Balance, // fee in yoctoNEAR paid for each claimed slug
+    // Map slug => its auction, the slug is reserved until the auction is settled
+    pub slug_auctions: UnorderedMap<String, SlugAuction>,
+
+    // Map zone key => royalties of its rocks, overrides royalties of the metaverse
+    pub zone_royalties: LookupMap<String, HashMap<AccountId, u16>>,
+
+    // Map region token_id => rocks wrapped in it
+    pub regions: LookupMap<TokenId, Vec<TokenId>>,
+    // Map rock token_id => region it is wrapped in
+    pub wrapped_rocks: LookupMap<TokenId, TokenId>,
+    pub next_region_id: u64,
+
+    // Map metaverse_id => collection change waiting for the other party to confirm
+    pub pending_collection_changes: LookupMap<String, PendingCollectionChange>,
+
+    // Map token_id => expiry of its approvals set with nft_approve_with_expiry
+    pub approval_expiries: LookupMap<TokenId, HashMap<AccountId, ApprovalExpiry>>,
+
+    // accounts the admin denied from minting, transferring and approving rocks
+    pub denied_accounts: UnorderedSet<AccountId>,
+
+    // Map {account_id}:{request_id} => token_id minted by that mint_rock request
+    pub mint_requests: LookupMap<String, TokenId>,
+    // keys of mint_requests in a ring of MAX_MINT_REQUESTS, next_mint_request is the oldest
+    pub mint_request_keys: Vector<String>,
+    pub next_mint_request: u64,
+
+    // Map metaverse_id => contracts notified after each mint
+    pub mint_listeners: LookupMap<String, Vec<AccountId>>,
+
+    // platform wallets sharing treasury payouts, empty pays treasury_id
+    pub treasury_beneficiaries: Vec<TreasuryBeneficiary>,
+
+    // Map {metaverse_id}:{zone_index} => who may receive the rocks of the zone
+    pub zone_receiver_restrictions: LookupMap<String, ZoneReceiverRestriction>,
+    // {metaverse_id}:{zone_index}:{account_id} of the eligible receivers of restricted zones
+    pub zone_receivers: LookupSet<String>,
+
+    // Map token_id => timestamp the operator hold of an unminted rock ends at
+    pub rock_holds: LookupMap<TokenId, u64>,
+
+    // Map zone_key => price of a holder_gated zone rock for accounts without a collection token
+    pub zone_non_holder_prices: LookupMap<String, Balance>,
+
+    // operator caps checked by init_metaverse and add_zone
+    pub metaverse_limits: MetaverseLimits,
+    // Map account_id => metaverses counted against max_metaverses_per_owner
+    pub owned_metaverse_counts: LookupMap<AccountId, u32>,
+
+    // Map metaverse_id => resale royalty of the metaverse owner in bps
+    pub metaverse_owner_royalties: LookupMap<String, u16>,
+
+    // Map metaverse_id => discovery tags set by the metaverse owner
+    pub metaverse_tags: LookupMap<String, Vec<MetaverseTag>>,
+    // Map {kind}:{value} of a tag => sorted metaverse ids with the tag
+    pub metaverse_tag_index: LookupMap<String, Vec<String>>,
+
+    // Map {metaverse_id}:{account_id} => rocks of the metaverse held by the account
+    pub holder_counts: LookupMap<String, u64>,
+    // Map metaverse_id => accounts holding the most rocks of the metaverse, by count
+    pub top_holders: LookupMap<String, Vec<HolderCount>>,
+
+    // mints attaching more than this many times their price plus storage are rejected, 0: off
+    pub max_deposit_multiplier: u32,
+
+    // Map zone:{type_zone} or metaverse:{metaverse_id} => sorted environment nft_type_ids
+    // allowed on the rocks of the class
+    pub environment_compatibility: LookupMap<String, Vec<String>>,
+
+    // testnet faucet minting free rocks, None: off
+    pub faucet_mode: Option<FaucetMode>,
+    // Map account_id => rocks minted with faucet_mint_rock
+    pub faucet_mints: LookupMap<AccountId, u32>,
+
+    // Map {metaverse_id}:{zone_index} => false for hidden zones, zones without an entry are visible
+    pub zone_visibility: LookupMap<String, bool>,
+
+    // operator switch of the gas_checkpoint logs
+    pub gas_profiling: bool,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -89,13 +404,12 @@ pub struct Zone {
     pub zone_index: u16,
     // required, start from 1
     pub price: U128,
-    // required for type=3
+    // required for PublicFixedPrice
     pub core_team_addr: String,
-    // required for type=1
+    // required for CoreTeam
     pub collection_addr: String,
-    // required for type=2
-    pub type_zone: u8,
-    // 1: core_team, 2: nft_holder, 3: public
+    // required for HolderGated
+    pub mint_policy: MintPolicy,
     pub rock_index_from: u128,
     // rock_index start from 1
     pub rock_index_to: u128, // required to >= from
@@ -108,7 +422,7 @@ pub struct Metaverse {
 }
 
 #[ext_contract(collection_contract)]
-trait ExtContract {
+pub trait ExtContract {
     fn nft_tokens_for_owner(
         &self,
         account_id: AccountId,
@@ -126,6 +440,7 @@ pub trait RockNFTContract {
         rock_index: u128,
         receiver_id: AccountId,
         token_metadata: TokenMetadata,
+        use_token_id: Option<TokenId>,
     );
 }
 
@@ -143,6 +458,63 @@ enum StorageKey {
     MetaverseOwner,
     MetaverseNftCollection,
     NftChecker,
+    EmergencyActions,
+    ZonesMinted,
+    FailedPayouts,
+    Purchases,
+    HolderChecks,
+    CheckerPolicies,
+    ZoneNftChecker,
+    SponsoredZones,
+    MapCommitments,
+    Allowlists,
+    ZoneUsdPrices,
+    MetadataSchemas,
+    Moderators,
+    TokenFlags,
+    LastFlagAt,
+    RockAttributes,
+    AttributeWriters,
+    HostingPlans,
+    MintThrottles,
+    ZoneBlockMints,
+    AccountMintRings,
+    EscrowWindows,
+    EscrowedSales,
+    PendingMints,
+    CollectionGas,
+    MetaverseThemes,
+    MetaverseVolumes,
+    GiftMessages,
+    FrozenMetadata,
+    CollectionMaxZoneSizes,
+    MetaverseRockCaps,
+    MetaverseSlugs,
+    SlugMetaverses,
+    SlugAuctions,
+    ZoneRoyalties,
+    Regions,
+    WrappedRocks,
+    PendingCollectionChanges,
+    ApprovalExpiries,
+    DeniedAccounts,
+    MintRequests,
+    MintRequestKeys,
+    MintListeners,
+    ZoneReceiverRestrictions,
+    ZoneReceivers,
+    RockHolds,
+    ZoneNonHolderPrices,
+    OwnedMetaverseCounts,
+    MetaverseOwnerRoyalties,
+    MetaverseTags,
+    MetaverseTagIndex,
+    HolderCounts,
+    TopHolders,
+    EnvironmentCompatibility,
+    FaucetMints,
+    ZoneVisibility,
+    AccountPurchases { account_hash: CryptoHash },
 }
 
 #[near_bindgen]
@@ -157,61 +529,161 @@ impl Contract {
         init_imo_nft_holder_size: u32,
         metadata: NFTContractMetadata,
     ) -> Self {
-        assert!(!env::state_exists(), "Already initialized");
-        assert!(rock_purchase_fee <= 10_000, "rock_purchase_fee must <= 10_000");
+        require!(!env::state_exists(), RoveError::AlreadyInitialized.as_str());
+        require!(
+            rock_purchase_fee <= 10_000,
+            RoveError::InvalidArgument.detail("rock_purchase_fee must <= 10_000")
+        );
         metadata.assert_valid();
         let init_imo_fee_in_128 = u128::from(init_imo_fee);
 
-        Self {
-            admin_id: admin_id.into(),
-            operator_id: operator_id.clone().into(),
-            treasury_id: treasury_id.into(),
+        Self::from_old_state(OldContract {
+            tokens: NonFungibleToken::new(
+                StorageKey::NonFungibleToken,
+                operator_id.clone(),
+                Some(StorageKey::TokenMetadata),
+                Some(StorageKey::Enumeration),
+                Some(StorageKey::Approval),
+            ),
+            metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
+            royalties: UnorderedMap::new(StorageKey::Royalties),
+            tokens_metadata: UnorderedMap::new(StorageKey::TokensMetadata),
+            admin_id,
+            operator_id: operator_id.clone(),
+            treasury_id,
             init_imo_fee: init_imo_fee_in_128,
             rock_purchase_fee,
             init_imo_nft_holder_size,
-
-            royalties: UnorderedMap::new(StorageKey::Royalties),
-            metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
-            tokens_metadata: UnorderedMap::new(StorageKey::TokensMetadata),
-
             metaverses: UnorderedMap::new(StorageKey::Metaverses),
             metaverse_owners: UnorderedMap::new(StorageKey::MetaverseOwner),
             tokens_minted: UnorderedMap::new(StorageKey::TokensMinted),
-            nft_checker: UnorderedMap::new(StorageKey::NftChecker),
             metaverse_nft_collections: UnorderedMap::new(StorageKey::MetaverseNftCollection),
+            nft_checker: UnorderedMap::new(StorageKey::NftChecker),
+        })
+    }
 
-            tokens: NonFungibleToken::new(
-                StorageKey::NonFungibleToken,
-                operator_id.clone().into(),
-                Some(StorageKey::TokenMetadata),
-                Some(StorageKey::Enumeration),
-                Some(StorageKey::Approval),
-            ),
+    // state of a new contract or of a migrated 1.0 one, the fields added since 1.0 start empty
+    // or at their default. New state is added here so both paths initialize it
+    fn from_old_state(old: OldContract) -> Self {
+        Self {
+            tokens: old.tokens,
+            metadata: old.metadata,
+            royalties: old.royalties,
+            tokens_metadata: old.tokens_metadata,
+            admin_id: old.admin_id,
+            operator_id: old.operator_id,
+            treasury_id: old.treasury_id,
+            init_imo_fee: old.init_imo_fee,
+            rock_purchase_fee: old.rock_purchase_fee,
+            init_imo_nft_holder_size: old.init_imo_nft_holder_size,
+            metaverses: old.metaverses,
+            metaverse_owners: old.metaverse_owners,
+            tokens_minted: old.tokens_minted,
+            metaverse_nft_collections: old.metaverse_nft_collections,
+            nft_checker: old.nft_checker,
+
+            fee_dust_policy: DustPolicy::MetaverseOwner,
+            emergency_actions: UnorderedMap::new(StorageKey::EmergencyActions),
+            zones_minted: UnorderedMap::new(StorageKey::ZonesMinted),
+            purchases: LookupMap::new(StorageKey::Purchases),
+            holder_checks: UnorderedMap::new(StorageKey::HolderChecks),
+            checker_policies: UnorderedMap::new(StorageKey::CheckerPolicies),
+            zone_nft_checker: UnorderedMap::new(StorageKey::ZoneNftChecker),
+            sponsorship_balance: 0,
+            sponsorship_low_balance: 0,
+            sponsored_zones: UnorderedMap::new(StorageKey::SponsoredZones),
+            map_commitments: UnorderedMap::new(StorageKey::MapCommitments),
+            allowlists: UnorderedMap::new(StorageKey::Allowlists),
+            price_oracle: None,
+            zone_usd_prices: UnorderedMap::new(StorageKey::ZoneUsdPrices),
+            metadata_schemas: UnorderedMap::new(StorageKey::MetadataSchemas),
+            moderators: UnorderedSet::new(StorageKey::Moderators),
+            token_flags: UnorderedMap::new(StorageKey::TokenFlags),
+            last_flag_at: UnorderedMap::new(StorageKey::LastFlagAt),
+            rock_attributes: LookupMap::new(StorageKey::RockAttributes),
+            attribute_writers: UnorderedMap::new(StorageKey::AttributeWriters),
+            hosting_plans: UnorderedMap::new(StorageKey::HostingPlans),
+            hosting_grace_period: DEFAULT_HOSTING_GRACE_PERIOD,
+            mint_throttles: UnorderedMap::new(StorageKey::MintThrottles),
+            zone_block_mints: LookupMap::new(StorageKey::ZoneBlockMints),
+            account_mint_rings: LookupMap::new(StorageKey::AccountMintRings),
+            escrow_windows: UnorderedMap::new(StorageKey::EscrowWindows),
+            escrowed_sales: UnorderedMap::new(StorageKey::EscrowedSales),
+            pending_mints: LookupMap::new(StorageKey::PendingMints),
+            collection_gas: LookupMap::new(StorageKey::CollectionGas),
+            metaverse_themes: LookupMap::new(StorageKey::MetaverseThemes),
+            import_finalized: false,
+            fee_tiers: Vec::new(),
+            metaverse_volumes: LookupMap::new(StorageKey::MetaverseVolumes),
+            gift_messages: LookupMap::new(StorageKey::GiftMessages),
+            frozen_metadata: LookupMap::new(StorageKey::FrozenMetadata),
+            lock_attestation: None,
+            collection_max_zone_sizes: LookupMap::new(StorageKey::CollectionMaxZoneSizes),
+            metaverse_rock_caps: LookupMap::new(StorageKey::MetaverseRockCaps),
+            creation_gate: None,
+            metaverse_slugs: LookupMap::new(StorageKey::MetaverseSlugs),
+            slug_metaverses: LookupMap::new(StorageKey::SlugMetaverses),
+            slug_fee: DEFAULT_SLUG_FEE,
+            slug_auctions: UnorderedMap::new(StorageKey::SlugAuctions),
+            zone_royalties: LookupMap::new(StorageKey::ZoneRoyalties),
+            regions: LookupMap::new(StorageKey::Regions),
+            wrapped_rocks: LookupMap::new(StorageKey::WrappedRocks),
+            next_region_id: 0,
+            pending_collection_changes: LookupMap::new(StorageKey::PendingCollectionChanges),
+            approval_expiries: LookupMap::new(StorageKey::ApprovalExpiries),
+            denied_accounts: UnorderedSet::new(StorageKey::DeniedAccounts),
+            mint_requests: LookupMap::new(StorageKey::MintRequests),
+            mint_request_keys: Vector::new(StorageKey::MintRequestKeys),
+            next_mint_request: 0,
+            mint_listeners: LookupMap::new(StorageKey::MintListeners),
+            treasury_beneficiaries: Vec::new(),
+            zone_receiver_restrictions: LookupMap::new(StorageKey::ZoneReceiverRestrictions),
+            zone_receivers: LookupSet::new(StorageKey::ZoneReceivers),
+            rock_holds: LookupMap::new(StorageKey::RockHolds),
+            zone_non_holder_prices: LookupMap::new(StorageKey::ZoneNonHolderPrices),
+            metaverse_limits: MetaverseLimits::default(),
+            owned_metaverse_counts: LookupMap::new(StorageKey::OwnedMetaverseCounts),
+            metaverse_owner_royalties: LookupMap::new(StorageKey::MetaverseOwnerRoyalties),
+            metaverse_tags: LookupMap::new(StorageKey::MetaverseTags),
+            metaverse_tag_index: LookupMap::new(StorageKey::MetaverseTagIndex),
+            holder_counts: LookupMap::new(StorageKey::HolderCounts),
+            top_holders: LookupMap::new(StorageKey::TopHolders),
+            max_deposit_multiplier: 0,
+            environment_compatibility: LookupMap::new(StorageKey::EnvironmentCompatibility),
+            faucet_mode: None,
+            faucet_mints: LookupMap::new(StorageKey::FaucetMints),
+            failed_payouts: UnorderedMap::new(StorageKey::FailedPayouts),
+            next_payout_id: 0,
+            treasury_settlement: TreasurySettlement::Native,
+            zone_visibility: LookupMap::new(StorageKey::ZoneVisibility),
+            gas_profiling: false,
         }
     }
 
     fn assert_admin_only(&mut self) {
         // assert that the user attached greater than or equal 1 yoctoNEAR. This is for security and so that user will be redirected to the NEAR wallet
         assert_at_least_one_yocto();
-        assert_eq!(env::predecessor_account_id(), self.admin_id, "Unauthorized");
+        require!(
+            env::predecessor_account_id() == self.admin_id,
+            RoveError::Unauthorized.as_str()
+        );
     }
 
     fn assert_operator_only(&mut self) {
         // assert that the user attached greater than or equal 1 yoctoNEAR. This is for security and so that user will be redirected to the NEAR wallet
         assert_at_least_one_yocto();
-        assert_eq!(
-            env::predecessor_account_id(),
-            self.tokens.owner_id,
-            "Unauthorized"
+        require!(
+            env::predecessor_account_id() == self.tokens.owner_id,
+            RoveError::Unauthorized.as_str()
         );
     }
 
     fn assert_metaverse_exist(&self, metaverse_id: &String) -> Metaverse {
         self.metaverses
-            .get(&metaverse_id)
-            .expect(NOT_FOUND_METAVERSE_ID_ERROR);
+            .get(metaverse_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::MetaverseNotFound.as_str()));
 
-        self.metaverses.get(&metaverse_id).unwrap()
+        self.metaverses.get(metaverse_id).unwrap()
     }
 
     fn assert_zone_exist(&self, metaverse_id: &String, zone_index: u16) -> Zone {
@@ -221,7 +693,7 @@ impl Contract {
             .unwrap()
             .zones
             .get(&zone_index)
-            .expect(NOT_FOUND_ZONE_INDEX_ERROR);
+            .unwrap_or_else(|| env::panic_str(RoveError::ZoneNotFound.as_str()));
 
         let zone = self
             .metaverses
@@ -231,7 +703,7 @@ impl Contract {
             .get(&zone_index)
             .unwrap()
             .clone();
-        return zone;
+        zone
     }
 
     fn assert_metaverse_owner(&self, metaverse_id: &String) {
@@ -241,26 +713,38 @@ impl Contract {
         let metaverse_owner = self
             .metaverse_owners
             .get(metaverse_id)
-            .expect(NOT_FOUND_METAVERSE_ID_ERROR);
-        assert_eq!(
-            env::predecessor_account_id(),
-            metaverse_owner,
-            "Unauthorized"
+            .unwrap_or_else(|| env::panic_str(RoveError::MetaverseNotFound.as_str()));
+        require!(
+            env::predecessor_account_id() == metaverse_owner,
+            RoveError::Unauthorized.as_str()
         );
     }
 
     #[payable]
     pub fn change_rock_purchase_fee(&mut self, rock_purchase_fee: u32) {
         self.assert_operator_only();
-        assert!(rock_purchase_fee <= 10_000, "rock_purchase_fee must <= 10_000");
+        require!(
+            rock_purchase_fee <= 10_000,
+            RoveError::InvalidArgument.detail("rock_purchase_fee must <= 10_000")
+        );
         self.rock_purchase_fee = rock_purchase_fee;
     }
 
+    #[payable]
+    pub fn change_fee_dust_policy(&mut self, fee_dust_policy: DustPolicy) {
+        self.assert_operator_only();
+        self.fee_dust_policy = fee_dust_policy;
+    }
+
+    pub fn get_fee_dust_policy(&self) -> DustPolicy {
+        self.fee_dust_policy.clone()
+    }
+
     /// change contract's admin, only current contract's admin can call this function
     #[payable]
     pub fn change_admin(&mut self, new_admin_id: AccountId) {
         self.assert_admin_only();
-        self.admin_id = new_admin_id.into();
+        self.admin_id = new_admin_id;
     }
 
     #[payable]
@@ -268,13 +752,13 @@ impl Contract {
         self.assert_admin_only();
 
         self.tokens.owner_id = new_operator_id.clone();
-        self.operator_id = new_operator_id.into();
+        self.operator_id = new_operator_id;
     }
 
     #[payable]
     pub fn change_treasury(&mut self, new_treasury_id: AccountId) {
         self.assert_admin_only();
-        self.treasury_id = new_treasury_id.into();
+        self.treasury_id = new_treasury_id;
     }
 
     // Only operator can change init_imo_fee
@@ -294,8 +778,11 @@ impl Contract {
     ) {
         self.assert_metaverse_owner(&metaverse_id);
         let mut zone = self.assert_zone_exist(&metaverse_id, zone_index);
-        assert!(zone.type_zone == 2 || zone.type_zone == 3, "type_zone is invalid");
-        assert!(zone.rock_index_to > 0, "rock_index_to invalid");
+        require!(zone.mint_policy.is_priced(), RoveError::InvalidZoneType.as_str());
+        require!(
+            zone.rock_index_to > 0,
+            RoveError::InvalidRockIndex.detail("rock_index_to invalid")
+        );
         let initial_storage_usage = env::storage_usage();
         let mut metaverse = self.metaverses.get(&metaverse_id).unwrap();
         zone.price = price;
@@ -309,18 +796,48 @@ impl Contract {
             );
         }
 
-        let imo_change_zone_price: EventLog = EventLog {
-            standard: "imo_change_zone_price".to_string(),
-            version: "1.1.0".to_string(),
-            event: EventLogVariant::ImoChangeZonePrice(vec![ImoChangeZonePrice {
-                metaverse_id,
-                zone_index,
-                new_price: price,
-                memo: Some(String::from("change_zone_price")),
-            }]),
-        };
+        emit_imo_change_zone_price(metaverse_id, zone_index, price, "change_zone_price");
+    }
+
+    #[payable]
+    pub fn batch_update_zone_prices(&mut self, metaverse_id: String, updates: Vec<(u16, U128)>) {
+        self.assert_metaverse_owner(&metaverse_id);
+        require!(
+            !updates.is_empty(),
+            RoveError::EmptyBatch.detail("updates is empty")
+        );
+        let initial_storage_usage = env::storage_usage();
+        let mut metaverse = self.metaverses.get(&metaverse_id).unwrap();
+        for (zone_index, price) in updates.iter() {
+            let mut zone = metaverse
+                .zones
+                .get(zone_index)
+                .cloned()
+                .unwrap_or_else(|| env::panic_str(RoveError::ZoneNotFound.as_str()));
+            require!(zone.mint_policy.is_priced(), RoveError::InvalidZoneType.as_str());
+            require!(
+                zone.rock_index_to > 0,
+                RoveError::InvalidRockIndex.detail("rock_index_to invalid")
+            );
+            zone.price = *price;
+            metaverse.zones.insert(*zone_index, zone);
+        }
+        self.metaverses.insert(&metaverse_id, &metaverse);
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::signer_account_id(),
+            );
+        }
 
-        env::log_str(&imo_change_zone_price.to_string());
+        for (zone_index, price) in updates {
+            emit_imo_change_zone_price(
+                metaverse_id.clone(),
+                zone_index,
+                price,
+                "batch_update_zone_prices",
+            );
+        }
     }
 
     #[payable]
@@ -330,16 +847,18 @@ impl Contract {
         new_owner: AccountId,
     ) {
         self.assert_metaverse_exist(&metaverse_id);
-        assert_eq!(self.metaverse_owners.get(&metaverse_id).unwrap(), env::signer_account_id(),
-                   "only current metaverse_owner can call this function");
+        let owner_id = env::predecessor_account_id();
+        require!(
+            self.metaverse_owners.get(&metaverse_id).unwrap() == owner_id,
+            RoveError::NotMetaverseOwner.as_str()
+        );
+        self.assert_owner_metaverse_limit(&new_owner);
         let initial_storage_usage = env::storage_usage();
         self.metaverse_owners.insert(&metaverse_id, &new_owner);
+        self.internal_count_owned_metaverse(Some(&owner_id), &new_owner);
 
         if env::storage_usage() > initial_storage_usage {
-            refund_deposit_to_account(
-                env::storage_usage() - initial_storage_usage,
-                env::signer_account_id(),
-            );
+            refund_deposit_to_account(env::storage_usage() - initial_storage_usage, owner_id);
         }
     }
 
@@ -350,6 +869,14 @@ impl Contract {
         updated_royalties: HashMap<AccountId, u16>,
     ) {
         self.assert_admin_only();
+        require!(
+            updated_royalties
+                .values()
+                .map(|royalty| u32::from(*royalty))
+                .sum::<u32>()
+                <= u32::from(ONE_HUNDRED_PERCENT_IN_BPS),
+            RoveError::InvalidArgument.detail("total royalties must <= 10_000")
+        );
         let initial_storage_usage = env::storage_usage();
         self.royalties.insert(&nft_type_id, &updated_royalties);
         if env::storage_usage() > initial_storage_usage {
@@ -372,81 +899,199 @@ impl Contract {
         self.treasury_id
     }
 
+    pub fn get_metaverse_owner(&self, metaverse_id: String) -> Option<AccountId> {
+        self.metaverse_owners.get(&metaverse_id)
+    }
+
+    // collection of the NFT checker zone (zone 2) of a metaverse
+    pub fn get_collection_for_metaverse(&self, metaverse_id: String) -> Option<String> {
+        self.metaverses
+            .get(&metaverse_id)
+            .and_then(|metaverse| metaverse.zones.get(&2).map(|zone| zone.collection_addr.clone()))
+    }
+
+    pub fn get_metaverse_for_collection(&self, collection_addr: String) -> Option<String> {
+        self.metaverse_nft_collections.get(&collection_addr)
+    }
+
+    pub fn contract_version(&self) -> VersionInfo {
+        VersionInfo {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("BUILD_GIT_COMMIT").to_string(),
+            rustc_version: env!("BUILD_RUSTC_VERSION").to_string(),
+            build_target: env!("BUILD_TARGET").to_string(),
+            build_profile: env!("BUILD_PROFILE").to_string(),
+        }
+    }
+
+    pub fn get_config(&self) -> ContractConfig {
+        ContractConfig {
+            admin_id: self.admin_id.clone(),
+            operator_id: self.tokens.owner_id.clone(),
+            treasury_id: self.treasury_id.clone(),
+            init_imo_fee: U128(self.init_imo_fee),
+            rock_purchase_fee: self.rock_purchase_fee,
+            fee_dust_policy: self.fee_dust_policy.clone(),
+            init_imo_nft_holder_size: self.init_imo_nft_holder_size,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
     fn check_zone(&self, _zone: &Zone) -> bool {
         let zone_price = u128::from(_zone.price);
-        if _zone.type_zone != 2 && _zone.type_zone != 3 {
-            return false;
-        }
 
         if _zone.rock_index_to > 0 {
-            if _zone.type_zone == 2 {
-                if _zone.collection_addr == "".to_string() {
-                    return false;
+            match _zone.mint_policy {
+                MintPolicy::CoreTeam => {
+                    // free rocks minted by core_team_addr
+                    if zone_price != 0 || _zone.core_team_addr.parse::<AccountId>().is_err() {
+                        return false;
+                    }
                 }
-            } else if _zone.type_zone == 3 {
-                if zone_price == 0 {
-                    return false;
+                MintPolicy::HolderGated => {
+                    if _zone.collection_addr.is_empty() {
+                        return false;
+                    }
+                }
+                MintPolicy::PublicFixedPrice => {
+                    if zone_price == 0 {
+                        return false;
+                    }
                 }
             }
             if _zone.rock_index_from > _zone.rock_index_to || _zone.rock_index_from == 0 {
                 return false;
             }
 
-            return true;
+            true
         } else {
-            return false;
+            false
         }
     }
 
     // user init metaverse
     #[payable]
-    pub fn init_metaverse(&mut self, metaverse_id: String, mut _zone2: Zone) {
-        let mut zone2 = _zone2.clone();
-        assert_eq!(zone2.zone_index, 2, "Z2 zone_index must be 2");
-        assert_eq!(zone2.type_zone, 2, "Z2 type_zone must be 2");
-        assert_eq!(zone2.price, U128(0), "Z2 price must be 0");
+    pub fn init_metaverse(
+        &mut self,
+        metaverse_id: String,
+        mut _zone2: Zone,
+        checker_policy: Option<CheckerPolicy>,
+        owner_id: Option<AccountId>,
+    ) -> PromiseOrValue<Option<Zone>> {
+        self.gas_checkpoint("init_metaverse", "start");
+        let zone2 = _zone2.clone();
+        require!(
+            zone2.zone_index == 2,
+            RoveError::InvalidZone.detail("Z2 zone_index must be 2")
+        );
+        require!(
+            zone2.mint_policy == MintPolicy::HolderGated,
+            RoveError::InvalidZone.detail("Z2 mint_policy must be holder_gated")
+        );
+        require!(
+            zone2.price == U128(0),
+            RoveError::InvalidZone.detail("Z2 price must be 0")
+        );
         if zone2.rock_index_from != 2 || !self.check_zone(&zone2) {
-            env::panic_str("Z2_invalid")
+            env::panic_str(&RoveError::InvalidZone.detail("Z2_invalid"))
         }
 
         // Make sure metaverse_id does NOT exist
-        let metaverse_data = self.metaverses.get(&metaverse_id);
-        match metaverse_data {
-            Some(_metaverse) => {
-                env::panic_str("metaverse is already existed");
-            }
-            _ => {}
+        if self.metaverses.get(&metaverse_id).is_some() {
+            env::panic_str(RoveError::MetaverseAlreadyExists.as_str());
         }
-        let nft_collection_address = self.metaverse_nft_collections.get(&_zone2.collection_addr);
-        match nft_collection_address {
-            Some(_address) => {
-                env::panic_str("this collection address is already used");
-            }
-            _ => {}
-        }
-
-        if self.init_imo_nft_holder_size > 0 {
-            zone2.rock_index_to = self.init_imo_nft_holder_size as u128 + 1;
-        } else {
-            zone2.rock_index_to = 501;
+        if self
+            .metaverse_nft_collections
+            .get(&_zone2.collection_addr)
+            .is_some()
+        {
+            env::panic_str(
+                &RoveError::InvalidCollection.detail("this collection address is already used"),
+            );
         }
 
-        let initial_storage_usage = env::storage_usage();
-        let total_rock_size: u128 = zone2.rock_index_to - zone2.rock_index_from + 1;
-        let mut total_init_imo_fee = 0;
-        if self.init_imo_fee > 0 {
-            total_init_imo_fee = self.init_imo_fee * total_rock_size;
-        }
+        let collection_id: AccountId = zone2.collection_addr.parse().unwrap_or_else(|_| {
+            env::panic_str(
+                &RoveError::InvalidCollection.detail("collection_addr is not an account"),
+            )
+        });
+        let total_rock_size: u128 = zone_rock_size(&zone2);
+        let max_zone_size = self.internal_collection_max_zone_size(&collection_id);
+        require!(
+            total_rock_size <= max_zone_size as u128,
+            RoveError::InvalidZone.detail(format!(
+                "Z2 has {} rocks, the maximum of this collection is {}",
+                total_rock_size, max_zone_size
+            ))
+        );
+        self.assert_zone_rock_limit(total_rock_size);
 
+        let total_init_imo_fee = self.init_imo_fee * total_rock_size;
+        let required_deposit = self.internal_init_metaverse_deposit(total_init_imo_fee);
         let attached_deposit = env::attached_deposit();
         require!(
-            total_init_imo_fee <= attached_deposit,
-            format!(
-                "Need {} yoctoNEAR to init metaverse with {} rocks ({} yoctoNEAR per rock)",
-                total_init_imo_fee, total_rock_size, self.init_imo_fee,
-            )
+            required_deposit <= attached_deposit,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR to init metaverse with {} rocks ({} yoctoNEAR per rock, \
+                 storage included)",
+                required_deposit, total_rock_size, self.init_imo_fee
+            ))
         );
-        let refund = attached_deposit - total_init_imo_fee;
+
+        // the world belongs to its creator, only the operator creates worlds for another owner as
+        // they count against that owner's max_metaverses_per_owner
+        let creator_id = env::predecessor_account_id();
+        let owner_id = match owner_id {
+            Some(owner_id) if owner_id != creator_id => {
+                require!(
+                    creator_id == self.tokens.owner_id,
+                    RoveError::Unauthorized
+                        .detail("only the operator can init a metaverse for another owner")
+                );
+                owner_id
+            }
+            _ => creator_id.clone(),
+        };
+        self.assert_owner_metaverse_limit(&owner_id);
+        self.gas_checkpoint("init_metaverse", "validated");
+        // with a creation gate the metaverse is created by resolve_init_metaverse
+        if let Some(promise) = self.internal_check_creation_gate(
+            &metaverse_id,
+            &zone2,
+            &checker_policy,
+            &owner_id,
+            attached_deposit,
+        ) {
+            self.gas_checkpoint("init_metaverse", "creation_gate_call");
+            return PromiseOrValue::Promise(promise);
+        }
+        self.internal_init_metaverse(
+            metaverse_id,
+            zone2.clone(),
+            checker_policy,
+            owner_id,
+            creator_id,
+            attached_deposit,
+        );
+        self.gas_checkpoint("init_metaverse", "end");
+        PromiseOrValue::Value(Some(zone2))
+    }
+
+    // create the metaverse of an already validated zone2, the IMO fee is paid from attached_deposit
+    // and the rest is refunded to creator_id
+    pub(crate) fn internal_init_metaverse(
+        &mut self,
+        metaverse_id: String,
+        zone2: Zone,
+        checker_policy: Option<CheckerPolicy>,
+        owner_id: AccountId,
+        creator_id: AccountId,
+        attached_deposit: Balance,
+    ) {
+        let initial_storage_usage = env::storage_usage();
+        let total_rock_size: u128 = zone_rock_size(&zone2);
+        let total_init_imo_fee = self.init_imo_fee * total_rock_size;
 
         let mut zones: HashMap<u16, Zone> = HashMap::new();
         let collection_address = zone2.clone().collection_addr;
@@ -458,7 +1103,7 @@ impl Contract {
             price: U128(0),
             core_team_addr: self.operator_id.to_string(),
             collection_addr: "".to_string(),
-            type_zone: 1,
+            mint_policy: MintPolicy::CoreTeam,
             rock_index_from: 1,
             rock_index_to: 1,
         };
@@ -467,24 +1112,40 @@ impl Contract {
         let metaverse = Metaverse { zones };
         self.metaverses.insert(&metaverse_id, &metaverse);
 
-        self.metaverse_owners
-            .insert(&metaverse_id, &self.operator_id); // will transfer owner later
+        self.metaverse_owners.insert(&metaverse_id, &owner_id);
+        self.internal_count_owned_metaverse(None, &owner_id);
         self.metaverse_nft_collections
             .insert(&collection_address, &metaverse_id);
 
         self.tokens_minted.insert(&metaverse_id, &HashMap::new());
         self.nft_checker.insert(&metaverse_id, &HashMap::new());
-
-        if refund > 0 {
-            Promise::new(env::predecessor_account_id()).transfer(refund);
+        if let Some(checker_policy) = checker_policy {
+            self.internal_set_checker_policy(&metaverse_id, 2, checker_policy);
         }
 
         let storage_used = env::storage_usage() - initial_storage_usage;
         let storage_cost = env::storage_byte_cost() * Balance::from(storage_used);
+        // storage the IMO fee does not cover is paid by the caller (e.g. init_imo_fee == 0)
+        let storage_deposit = storage_cost.saturating_sub(total_init_imo_fee);
+        require!(
+            total_init_imo_fee + storage_deposit <= attached_deposit,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR to init metaverse ({} yoctoNEAR IMO fee + {} yoctoNEAR storage)",
+                total_init_imo_fee + storage_deposit,
+                total_init_imo_fee,
+                storage_deposit
+            ))
+        );
+        let refund = attached_deposit - total_init_imo_fee - storage_deposit;
+
+        if refund > 0 {
+            self.internal_payout(creator_id, refund);
+        }
+
         if total_init_imo_fee > storage_cost {
             let remain = total_init_imo_fee - storage_cost;
             if remain > 0 {
-                Promise::new(self.treasury_id.clone()).transfer(remain);
+                self.internal_pay_treasury(remain);
             }
         }
         let init_metaverse_log: EventLog = EventLog {
@@ -492,16 +1153,19 @@ impl Contract {
             version: "1.0.0".to_string(),
             event: EventLogVariant::ImoInit(vec![ImoInitLog {
                 metaverse_id,
-                owner_id: env::signer_account_id().to_string(),
+                owner_id: owner_id.to_string(),
                 rock_size: total_rock_size,
                 memo: Some(String::from("mint_rock")),
             }]),
         };
 
-        env::log_str(&init_metaverse_log.to_string());
+        init_metaverse_log.emit();
     }
 
-    // This is callback function (private, CAN NOT CALL DIRECTLY)
+    // callback of the nft checker in mint_rock. The mint and its payouts are settled here, once
+    // the check is known: the payouts run after this receipt commits and a failed one is kept
+    // in failed_payouts, there is no later promise a mint has to be rolled back on
+    #[private]
     #[payable]
     pub fn mint_nft_checker_rock(
         &mut self,
@@ -510,73 +1174,103 @@ impl Contract {
         rock_index: u128,
         receiver_id: AccountId,
         token_metadata: TokenMetadata,
+        use_token_id: Option<TokenId>,
     ) {
-        assert_eq!(env::promise_results_count(), 1, "This is a callback method");
-        match env::promise_result(0) {
-            PromiseResult::NotReady => {
-                env::panic_str("NFT Checker is not ready");
-            }
-            PromiseResult::Failed => {
-                env::panic_str("NFT Checker is not ready is fail");
-            }
-            PromiseResult::Successful(result) => {
-                let tokens = near_sdk::serde_json::from_slice::<Vec<Token>>(&result).unwrap();
-                if tokens.len() == 0 {
-                    env::panic_str("You need to have an NFT to be able to mint this rock")
-                }
+        self.gas_checkpoint("mint_nft_checker_rock", "start");
+        require!(
+            env::promise_results_count() == 1,
+            RoveError::NotCallback.as_str()
+        );
+        let token_id = rock_token_id(&metaverse_id, zone_index, rock_index);
+        // a panic in _mint reverts this unlock, the lock then expires after PENDING_MINT_TTL
+        self.internal_unlock_pending_mint(&token_id);
 
-                let nft_checker = self.nft_checker.get(&metaverse_id).unwrap();
-                let mut mintable = false;
-                let mut use_token_id: TokenId = "".parse().unwrap();
-                for token in tokens {
-                    let _token_id = token.token_id;
-                    let checker = nft_checker.get(&_token_id.to_string());
-
-                    match checker {
-                        Some(_existed) => {} // Skip if that token used
-                        None => {
-                            mintable = true;
-                            use_token_id = _token_id;
-                            break;
-                        }
-                    }
-                }
-                if !mintable {
-                    env::panic_str("You need to have an NFT to mint land in this zone")
+        // the deposit is already held by this contract, a failed check gives it back instead of
+        // panicking (a panic here would keep it on the contract)
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        let non_holder_price = self
+            .internal_non_holder_price(&metaverse_id, zone_index)
+            .filter(|price| *price <= env::attached_deposit());
+        let (price, mint_policy, use_token_id) =
+            match self.internal_checked_token(&metaverse_id, zone_index, use_token_id) {
+                Ok(use_token_id) => (zone.price, zone.mint_policy, use_token_id),
+                // no eligible collection token, the rock is sold like a public one if the zone
+                // has a non-holder price covered by the deposit
+                Err(RoveError::NotCollectionHolder) if non_holder_price.is_some() => (
+                    U128(non_holder_price.unwrap()),
+                    MintPolicy::PublicFixedPrice,
+                    "".to_string(),
+                ),
+                Err(error) => {
+                    self.internal_refund_checker_mint(&metaverse_id, zone_index, rock_index, error);
+                    return;
                 }
-                let zone = self.assert_zone_exist(&metaverse_id, zone_index);
-                let token_id = gen_token_id(&metaverse_id, zone_index, rock_index);
-                self._mint(
-                    metaverse_id.clone(),
-                    token_id.clone(),
-                    receiver_id.clone(),
-                    token_metadata.clone(),
-                    zone.price,
-                    zone.type_zone,
-                    use_token_id.to_string(),
-                );
-            }
-        };
+            };
+        let is_minted = self
+            .tokens_minted
+            .get(&metaverse_id)
+            .map(|tokens_minted| tokens_minted.contains_key(&token_id))
+            .unwrap_or(true);
+        if is_minted {
+            self.internal_refund_checker_mint(
+                &metaverse_id,
+                zone_index,
+                rock_index,
+                RoveError::TokenAlreadyExists,
+            );
+            return;
+        }
+        if let Err(error) = self.internal_check_checker_mint(
+            &metaverse_id,
+            zone_index,
+            &token_id,
+            &receiver_id,
+            price.0,
+            mint_policy,
+        ) {
+            self.internal_refund_checker_mint(&metaverse_id, zone_index, rock_index, error);
+            return;
+        }
+        self.gas_checkpoint("mint_nft_checker_rock", "checked");
+
+        self._mint(
+            metaverse_id.clone(),
+            zone_index,
+            token_id.clone(),
+            receiver_id.clone(),
+            token_metadata.clone(),
+            price,
+            mint_policy,
+            use_token_id,
+            env::attached_deposit(),
+        );
+        self.gas_checkpoint("mint_nft_checker_rock", "end");
     }
 
     fn _mint(
         &mut self,
         metaverse_id: String,
+        zone_index: u16,
         token_id: String,
         receiver_id: AccountId,
         token_metadata: TokenMetadata,
         token_price_str: U128,
-        type_zone: u8,
+        mint_policy: MintPolicy,
         use_token_id: String,
+        attached_deposit: Balance,
     ) {
+        self.assert_not_denied(&receiver_id);
+        self.assert_not_denied(&env::signer_account_id());
+        self.assert_zone_receiver(&metaverse_id, zone_index, &receiver_id);
+        self.rock_holds.remove(&token_id);
         let initial_storage_usage = env::storage_usage();
-        let attached_deposit = env::attached_deposit();
         let token_price = u128::from(token_price_str);
         require!(
             token_price <= attached_deposit,
-            format!("Need {} yoctoNEAR to mint this rock", token_price)
+            RoveError::NotEnoughDeposit
+                .detail(format!("Need {} yoctoNEAR to mint this rock", token_price))
         );
-        let refund = attached_deposit - token_price;
+        let mut refund = attached_deposit - token_price;
 
         let token = self.tokens.internal_mint_with_refund(
             token_id.clone(),
@@ -584,41 +1278,80 @@ impl Contract {
             Some(token_metadata.clone()),
             None,
         );
+        self.internal_update_holding(&token_id, None, Some(&receiver_id));
 
         let mut token_minted = self.tokens_minted.get(&metaverse_id).unwrap();
         token_minted.insert(token.token_id.to_string(), true);
         self.tokens_minted.insert(&metaverse_id, &token_minted);
+        self.internal_increase_zone_minted(&metaverse_id, zone_index);
+        self.internal_throttle_mint(&metaverse_id, zone_index, &receiver_id);
 
-        if type_zone == 2 {
-            let mut nft_checker = self.nft_checker.get(&metaverse_id).unwrap();
-            nft_checker.insert(use_token_id, true);
-            self.nft_checker.insert(&metaverse_id, &nft_checker);
+        // free core team, voucher and holder mints are not purchases
+        if token_price > 0 {
+            self.internal_record_purchase(
+                &receiver_id,
+                Purchase {
+                    token_id: token_id.clone(),
+                    metaverse_id: metaverse_id.clone(),
+                    price: token_price_str,
+                    timestamp: U64(env::block_timestamp()),
+                },
+            );
+        }
+
+        if mint_policy == MintPolicy::HolderGated {
+            self.internal_use_checker_token(&metaverse_id, zone_index, use_token_id);
         }
 
         /*
-            if token_price == 0 => contract account will pay storage cost
+            if token_price == 0 => contract account will pay storage cost (core team),
+            the sponsorship pool (sponsored holder zone) or the minter (holder zone)
          */
         let storage_used = env::storage_usage() - initial_storage_usage;
         let required_storage_cost = env::storage_byte_cost() * Balance::from(storage_used);
-        if token_price > 0 {
-            if token_price > required_storage_cost {
-                let remain = token_price - required_storage_cost;
-                if self.rock_purchase_fee > 0 {
-                    let treasury_amount = remain * self.rock_purchase_fee as u128 / 10_000;
-                    let metaverse_owner_amount = remain - treasury_amount;
-                    if treasury_amount > 0 {
-                        Promise::new(self.treasury_id.clone()).transfer(treasury_amount);
-                    }
-                    if metaverse_owner_amount > 0 {
-                        let metaverse_owner = self.metaverse_owners.get(&metaverse_id).unwrap();
-                        Promise::new(metaverse_owner).transfer(metaverse_owner_amount);
-                    }
-                }
+        if token_price == 0
+            && mint_policy == MintPolicy::HolderGated
+            && !self.internal_use_sponsorship(&metaverse_id, zone_index, required_storage_cost)
+        {
+            require!(
+                required_storage_cost <= refund,
+                RoveError::NotEnoughDeposit.detail(format!(
+                    "Need {} yoctoNEAR to pay storage of this rock",
+                    required_storage_cost
+                ))
+            );
+            refund -= required_storage_cost;
+        }
+        if token_price > 0 && token_price > required_storage_cost {
+            let remain = token_price - required_storage_cost;
+            let rock_purchase_fee = self.internal_effective_fee(&metaverse_id);
+            let (treasury_amount, metaverse_owner_amount) =
+                split_purchase_fee(remain, rock_purchase_fee, &self.fee_dust_policy);
+            self.internal_add_metaverse_volume(&metaverse_id, remain);
+            let is_escrowed = self.internal_escrow_sale(
+                &metaverse_id,
+                &token_id,
+                &receiver_id,
+                treasury_amount,
+                metaverse_owner_amount,
+            );
+            if !is_escrowed && treasury_amount > 0 {
+                self.internal_pay_treasury(treasury_amount);
+            }
+            if !is_escrowed && metaverse_owner_amount > 0 {
+                let metaverse_owner = self.metaverse_owners.get(&metaverse_id).unwrap();
+                self.internal_payout(metaverse_owner, metaverse_owner_amount);
             }
         }
 
+        // the nft checker callback is called by this contract, the original caller paid
+        let payer_id = if env::predecessor_account_id() == env::current_account_id() {
+            env::signer_account_id()
+        } else {
+            env::predecessor_account_id()
+        };
         if refund > 0 {
-            Promise::new(env::predecessor_account_id()).transfer(refund);
+            self.internal_payout(payer_id.clone(), refund);
         }
 
         // Construct the mint log as per the events standard.
@@ -632,15 +1365,36 @@ impl Contract {
             }]),
         };
 
-        env::log_str(&nft_mint_log.to_string());
+        nft_mint_log.emit();
+        emit_account_activity(vec![AccountActivityLog::new(
+            &receiver_id,
+            ActivityAction::Mint,
+            &token_id,
+            Some(&payer_id).filter(|payer_id| **payer_id != receiver_id),
+            Some(token_price).filter(|price| *price > 0),
+        )]);
+        self.internal_notify_mint_listeners(&metaverse_id, zone_index, &token_id, &receiver_id);
     }
 
+    /// token_id a rock gets when minted, `{metaverse_id}:{zone_index}:{rock_index}` in both
+    /// rock contracts. The zone and rock do not have to exist
+    pub fn compute_token_id(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+    ) -> TokenId {
+        rock_token_id(&metaverse_id, zone_index, rock_index)
+    }
+
+    /// deprecated, use get_zones
     pub fn get_zone_info(&self, metaverse_id: String, zone_index: u16) -> String {
+        log_deprecated_call("get_zone_info", None);
         let zone = self.assert_zone_exist(&metaverse_id, zone_index);
         format!(
             "{}, {}, {}, {}, {:?}, {}, {}",
             zone.zone_index,
-            zone.type_zone,
+            zone.mint_policy.type_zone(),
             zone.core_team_addr,
             zone.collection_addr,
             zone.price,
@@ -649,17 +1403,142 @@ impl Contract {
         )
     }
 
+    pub fn get_zone_remaining(&self, metaverse_id: String, zone_index: u16) -> U128 {
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        let minted = self.internal_zone_minted(&metaverse_id, zone_index);
+        U128(zone_rock_size(&zone).saturating_sub(minted))
+    }
+
+    pub fn is_zone_sold_out(&self, metaverse_id: String, zone_index: u16) -> bool {
+        self.get_zone_remaining(metaverse_id, zone_index).0 == 0
+    }
+
+    /// unminted rock indexes of a zone on sale in ascending order, rocks held by the operator
+    /// are left out. from_index is a rock index, pass the last returned index + 1 to read the
+    /// next page. limit defaults to and is capped at 500
+    pub fn get_unminted_rocks(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<u128> {
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        let start_index = from_index
+            .map(u128::from)
+            .unwrap_or_default()
+            .max(zone.rock_index_from);
+        let limit = limit
+            .unwrap_or(MAX_UNMINTED_ROCKS_LIMIT)
+            .min(MAX_UNMINTED_ROCKS_LIMIT) as usize;
+        let tokens_minted = self.tokens_minted.get(&metaverse_id).unwrap_or_default();
+        (start_index..=zone.rock_index_to)
+            .filter(|rock_index| {
+                let token_id = rock_token_id(&metaverse_id, zone_index, *rock_index);
+                !tokens_minted.contains_key(&token_id)
+                    && self.internal_rock_hold(&token_id).is_none()
+            })
+            .take(limit)
+            .collect()
+    }
+
+    fn internal_zone_minted(&self, metaverse_id: &String, zone_index: u16) -> u128 {
+        self.zones_minted
+            .get(metaverse_id)
+            .and_then(|zones_minted| zones_minted.get(&zone_index).cloned())
+            .unwrap_or(0)
+    }
+
+    // count the minted rock and emit ZoneSoldOut when the last rock of the zone is minted
+    fn internal_increase_zone_minted(&mut self, metaverse_id: &String, zone_index: u16) {
+        let zone = self.assert_zone_exist(metaverse_id, zone_index);
+        let mut zones_minted = self.zones_minted.get(metaverse_id).unwrap_or_default();
+        let minted = zones_minted.get(&zone_index).cloned().unwrap_or(0) + 1;
+        zones_minted.insert(zone_index, minted);
+        self.zones_minted.insert(metaverse_id, &zones_minted);
+
+        if minted == zone_rock_size(&zone) {
+            let zone_sold_out_log: EventLog = EventLog {
+                standard: "nft_collection_holder_imo_zone_sold_out".to_string(),
+                version: "1.0.0".to_string(),
+                event: EventLogVariant::ZoneSoldOut(vec![ZoneSoldOutLog {
+                    metaverse_id: metaverse_id.to_string(),
+                    zone_index,
+                    memo: None,
+                }]),
+            };
+
+            zone_sold_out_log.emit();
+        }
+    }
+
+    pub fn get_purchases_for_account(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<Purchase> {
+        let purchases = match self.purchases.get(&account_id) {
+            Some(purchases) => purchases,
+            None => return vec![],
+        };
+        let start_index = u128::from(from_index.unwrap_or(U128(0))).min(purchases.len() as u128);
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        (start_index as u64..purchases.len())
+            .take(limit)
+            .filter_map(|index| purchases.get(index))
+            .collect()
+    }
+
+    pub(crate) fn internal_record_purchase(&mut self, account_id: &AccountId, purchase: Purchase) {
+        let mut purchases = self.purchases.get(account_id).unwrap_or_else(|| {
+            Vector::new(StorageKey::AccountPurchases {
+                account_hash: sha256_hash(account_id.as_bytes()),
+            })
+        });
+        purchases.push(&purchase);
+        self.purchases.insert(account_id, &purchases);
+    }
+
     pub fn get_init_imo_fee(&self) -> U128 {
-        return U128::from(self.init_imo_fee);
+        U128::from(self.init_imo_fee)
     }
 
+    /// deprecated, use change_init_imo_fee
     #[payable]
     pub fn update_init_imo_fee(&mut self, init_imo_fee: U128) {
-        self.assert_operator_only();
-        let init_imo_fee_u128 = u128::from(init_imo_fee);
-        self.init_imo_fee = init_imo_fee_u128;
+        self.change_init_imo_fee(init_imo_fee);
+        log_deprecated_call("update_init_imo_fee", Some(env::predecessor_account_id()));
+    }
+
+    // validate that rock_index belongs to zone and was not minted yet, returns zone and token_id
+    fn assert_rock_mintable(
+        &self,
+        metaverse_id: &String,
+        zone_index: u16,
+        rock_index: u128,
+    ) -> (Zone, String) {
+        let zone = self.assert_zone_exist(metaverse_id, zone_index);
+        self.assert_zone_visible(metaverse_id, &zone);
+        require!(
+            zone.rock_index_from <= rock_index && rock_index <= zone.rock_index_to,
+            RoveError::InvalidRockIndex.as_str()
+        );
+        let token_id = rock_token_id(metaverse_id, zone_index, rock_index);
+        let tokens_minted = self.tokens_minted.get(metaverse_id).unwrap();
+        if tokens_minted.contains_key(&token_id) {
+            env::panic_str(RoveError::TokenAlreadyExists.as_str());
+        }
+        require!(
+            !self.internal_is_mint_pending(&token_id),
+            RoveError::MintPending.as_str()
+        );
+        self.assert_rock_not_held(&token_id);
+        (zone, token_id)
     }
 
+    // request_id: retries with the same request_id return the minted token_id
+    // returns the minted token_id, None when the mint waits for the nft checker or price oracle
     #[payable]
     pub fn mint_rock(
         &mut self,
@@ -668,108 +1547,167 @@ impl Contract {
         rock_index: u128,
         receiver_id: AccountId,
         token_metadata: TokenMetadata,
-    ) {
-        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
-        assert!(
-            zone.rock_index_from <= rock_index && rock_index <= zone.rock_index_to,
-            "rock_index invalid"
-        );
-        let token_id = gen_token_id(&metaverse_id, zone_index, rock_index);
-        let tokens_minted = self.tokens_minted.get(&metaverse_id).unwrap();
-        match tokens_minted.get(&token_id) {
-            Some(_token_minted) => env::panic_str("token is already existed"),
-            _ => {}
+        use_token_id: Option<TokenId>,
+        request_id: Option<String>,
+    ) -> Option<TokenId> {
+        self.gas_checkpoint("mint_rock", "start");
+        if let Some(token_id) = self.internal_replay_mint_request(
+            &request_id,
+            &metaverse_id,
+            zone_index,
+            rock_index,
+            &receiver_id,
+        ) {
+            return Some(token_id);
         }
+        let (zone, token_id) = self.assert_rock_mintable(&metaverse_id, zone_index, rock_index);
+        require!(
+            use_token_id.is_none() || zone.mint_policy == MintPolicy::HolderGated,
+            RoveError::InvalidZoneType.detail("use_token_id is only for holder_gated zones")
+        );
 
         let signer_id = env::signer_account_id();
+        // fail before the nft checker call, _mint checks again in the callback
+        self.assert_not_denied(&receiver_id);
+        self.assert_not_denied(&signer_id);
+        self.internal_record_mint_request(&request_id, &token_id);
         let zone_price = u128::from(zone.price);
-        if zone.type_zone == 1 {
-            assert_eq!(
-                zone.core_team_addr,
-                env::signer_account_id().to_string(),
-                "require core team call this mint"
-            );
-        } else if zone.type_zone == 2 {
-            // NFT checker
-            assert_ne!(
-                zone.collection_addr,
-                "".to_string(),
-                "collection addr is empty"
-            );
-            let collect_contract_account_id: AccountId = zone.collection_addr.parse().unwrap();
-            let call = collection_contract::nft_tokens_for_owner(
-                signer_id,
-                None,
-                None,
-                collect_contract_account_id,
-                0,
-                GAS_FOR_COMMON_OPERATIONS,
-            );
-            let remaining_gas: Gas = env::prepaid_gas()
-                - env::used_gas()
-                - GAS_FOR_COMMON_OPERATIONS
-                - GAS_RESERVED_FOR_CURRENT_CALL;
-            let callback = rock_nft_contract::mint_nft_checker_rock(
-                metaverse_id.clone(),
-                zone_index,
-                rock_index,
-                receiver_id.clone(),
-                token_metadata.clone(),
-                env::current_account_id(),
-                env::attached_deposit(),
-                remaining_gas,
-            );
-            call.then(callback);
-        } else if zone.type_zone == 3 {
-            if zone_price <= 0 {
-                env::panic_str("missing price for public zone");
+        match zone.mint_policy {
+            MintPolicy::CoreTeam => {
+                require!(
+                    zone.core_team_addr == env::signer_account_id().to_string(),
+                    RoveError::NotCoreTeam.as_str()
+                );
+                self.assert_deposit_accepted(0, 1);
+            }
+            MintPolicy::HolderGated => {
+                // NFT checker
+                require!(
+                    !zone.collection_addr.is_empty(),
+                    RoveError::InvalidCollection.detail("collection addr is empty")
+                );
+                // a non-holder pays the non-holder price when the zone has one
+                let max_price = self
+                    .internal_non_holder_price(&metaverse_id, zone_index)
+                    .unwrap_or(zone_price);
+                self.assert_deposit_accepted(max_price, 1);
+                let collect_contract_account_id: AccountId = zone.collection_addr.parse().unwrap();
+                let collection_gas = self.internal_collection_gas(&collect_contract_account_id);
+                let required_gas =
+                    env::used_gas().0 + collection_gas.0 + GAS_RESERVED_FOR_CURRENT_CALL.0;
+                // leave the callback at least the common operations gas
+                require!(
+                    env::prepaid_gas().0 >= required_gas + GAS_FOR_COMMON_OPERATIONS.0,
+                    RoveError::NotEnoughGas.detail(format!(
+                        "not enough prepaid gas, attach at least {} gas",
+                        required_gas + GAS_FOR_COMMON_OPERATIONS.0
+                    ))
+                );
+                // fail fast a concurrent mint of this rock before its checker callback runs
+                self.internal_lock_pending_mint(&token_id);
+                let call = collection_contract::nft_tokens_for_owner(
+                    signer_id,
+                    None,
+                    None,
+                    collect_contract_account_id,
+                    0,
+                    collection_gas,
+                );
+                let remaining_gas = Gas(env::prepaid_gas().0 - required_gas);
+                let callback = rock_nft_contract::mint_nft_checker_rock(
+                    metaverse_id.clone(),
+                    zone_index,
+                    rock_index,
+                    receiver_id.clone(),
+                    token_metadata.clone(),
+                    use_token_id,
+                    env::current_account_id(),
+                    env::attached_deposit(),
+                    remaining_gas,
+                );
+                call.then(callback);
+                self.gas_checkpoint("mint_rock", "checker_call");
+                // minted by mint_nft_checker_rock
+                return None;
+            }
+            MintPolicy::PublicFixedPrice => {
+                if zone_price == 0 {
+                    env::panic_str(RoveError::MissingZonePrice.as_str());
+                }
+                // a USD zone is guarded against its NEAR price before the oracle call
+                self.assert_deposit_accepted(zone_price, 1);
+                if self.internal_mint_rock_in_usd(
+                    &metaverse_id,
+                    zone_index,
+                    rock_index,
+                    &receiver_id,
+                    &token_metadata,
+                ) {
+                    self.gas_checkpoint("mint_rock", "oracle_call");
+                    return None;
+                }
             }
-        } else {
-            env::panic_str("does not support zone");
-        }
-        let mut price = zone.price;
-        if zone.type_zone == 1 {
-            price = U128::from(0);
         }
+        let price = if zone.mint_policy.is_priced() {
+            zone.price
+        } else {
+            U128::from(0)
+        };
 
-        if zone.type_zone != 2 {
-            self._mint(
-                metaverse_id.clone(),
-                token_id.clone(),
-                receiver_id.clone(),
-                token_metadata.clone(),
-                price,
-                zone.type_zone,
-                "".to_string(),
-            );
-        }
+        self._mint(
+            metaverse_id.clone(),
+            zone_index,
+            token_id.clone(),
+            receiver_id.clone(),
+            token_metadata.clone(),
+            price,
+            zone.mint_policy,
+            "".to_string(),
+            env::attached_deposit(),
+        );
+        self.gas_checkpoint("mint_rock", "end");
+        Some(token_id)
     }
 
     #[payable]
-    pub fn add_zone(&mut self, metaverse_id: String, _zone: Zone) {
+    pub fn add_zone(
+        &mut self,
+        metaverse_id: String,
+        _zone: Zone,
+        checker_policy: Option<CheckerPolicy>,
+    ) {
         let metaverse = self.assert_metaverse_exist(&metaverse_id);
-        let zone_checker = metaverse.zones.get(&_zone.zone_index);
-        match zone_checker {
-            Some(_zone) => {
-                env::panic_str("zone_index is already existed");
-            }
-            _ => {}
+        if metaverse.zones.contains_key(&_zone.zone_index) {
+            env::panic_str(RoveError::ZoneAlreadyExists.as_str());
         }
 
-        assert_eq!(self.metaverse_owners.get(&metaverse_id).unwrap(), env::signer_account_id(), "only metaverse owner can call this function");
+        require!(
+            self.metaverse_owners.get(&metaverse_id).unwrap() == env::predecessor_account_id(),
+            RoveError::NotMetaverseOwner.as_str()
+        );
 
         if !self.check_zone(&_zone) {
-            env::panic_str("zone is invalid");
+            env::panic_str(RoveError::InvalidZone.as_str());
         }
+        self.assert_zone_rock_limit(zone_rock_size(&_zone));
+        self.assert_zone_count_limit(metaverse.zones.len() + 1);
 
-        assert!(_zone.type_zone == 2 || _zone.type_zone == 3, "type_zone must be 2 or 3");
         let mut zones = metaverse.zones;
-        if _zone.type_zone == 2 {
+        if _zone.mint_policy == MintPolicy::HolderGated {
             if let Some(_zone_index_2) = zones.get(&2u16) {
-                assert_eq!(_zone_index_2.type_zone, 2, "zone_index 2 doest not have type_zone = 2");
-                assert_eq!(_zone_index_2.collection_addr, _zone.collection_addr, "collection_address is invalid");
+                require!(
+                    _zone_index_2.mint_policy == MintPolicy::HolderGated,
+                    RoveError::InvalidZone.detail("zone_index 2 is not holder_gated")
+                );
+                require!(
+                    _zone_index_2.collection_addr == _zone.collection_addr,
+                    RoveError::InvalidCollection.as_str()
+                );
             } else {
-                env::panic_str("this metaverse_id does not still have zone_index 2");
+                env::panic_str(
+                    &RoveError::InvalidZone
+                        .detail("this metaverse_id does not still have zone_index 2"),
+                );
             }
         }
         let total_rock_size: u128 = _zone.rock_index_to - _zone.rock_index_from + 1;
@@ -781,41 +1719,55 @@ impl Contract {
         let attached_deposit = env::attached_deposit();
         require!(
             total_add_zone_fee <= attached_deposit,
-            format!(
+            RoveError::NotEnoughDeposit.detail(format!(
                 "Need {} yoctoNEAR to add zone with {} rocks ({} yoctoNEAR per rock)",
-                total_add_zone_fee, total_rock_size, self.init_imo_fee,
-            )
+                total_add_zone_fee, total_rock_size, self.init_imo_fee
+            ))
         );
 
-        let refund = attached_deposit - total_add_zone_fee;
         let initial_storage_usage = env::storage_usage();
         zones.insert(_zone.zone_index, _zone.clone());
         let metaverse = Metaverse { zones };
         self.metaverses.insert(&metaverse_id, &metaverse);
-
-        if refund > 0 {
-            Promise::new(env::predecessor_account_id()).transfer(refund);
+        if let Some(checker_policy) = checker_policy {
+            require!(
+                _zone.mint_policy == MintPolicy::HolderGated,
+                RoveError::InvalidZoneType.detail("checker_policy is only for holder_gated zones")
+            );
+            self.internal_set_checker_policy(&metaverse_id, _zone.zone_index, checker_policy);
         }
 
+        // storage of the zone is charged to the caller on top of the IMO fee
         let storage_used = env::storage_usage() - initial_storage_usage;
         let storage_cost = env::storage_byte_cost() * Balance::from(storage_used);
-        if total_add_zone_fee > storage_cost {
-            let remain = total_add_zone_fee - storage_cost;
-            if remain > 0 {
-                Promise::new(self.treasury_id.clone()).transfer(remain);
-            }
+        require!(
+            total_add_zone_fee + storage_cost <= attached_deposit,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR to add zone ({} yoctoNEAR IMO fee + {} yoctoNEAR storage)",
+                total_add_zone_fee + storage_cost,
+                total_add_zone_fee,
+                storage_cost
+            ))
+        );
+        if total_add_zone_fee > 0 {
+            self.internal_pay_treasury(total_add_zone_fee);
+        }
+        let refund = attached_deposit - total_add_zone_fee - storage_cost;
+        if refund > 0 {
+            self.internal_payout(env::predecessor_account_id(), refund);
         }
+
         let add_zone_log: EventLog = EventLog {
             standard: "nft_collection_holder_imo_add_zone".to_string(),
             version: "1.0.0".to_string(),
             event: EventLogVariant::ImoAddZone(vec![ImoAddZoneLog {
                 metaverse_id,
-                owner_id: env::signer_account_id().to_string(),
+                owner_id: env::predecessor_account_id().to_string(),
                 zone_index: _zone.zone_index,
                 price: _zone.price,
                 core_team_addr: _zone.core_team_addr,
                 collection_addr: _zone.collection_addr,
-                type_zone: _zone.type_zone,
+                type_zone: _zone.mint_policy.type_zone(),
                 rock_index_from: _zone.rock_index_from,
                 rock_index_to: _zone.rock_index_to,
                 rock_size: total_rock_size,
@@ -823,7 +1775,7 @@ impl Contract {
             }]),
         };
 
-        env::log_str(&add_zone_log.to_string());
+        add_zone_log.emit();
     }
 
     #[payable]
@@ -833,8 +1785,6 @@ impl Contract {
     }
 }
 
-near_contract_standards::impl_non_fungible_token_core!(Contract, tokens);
-near_contract_standards::impl_non_fungible_token_approval!(Contract, tokens);
 near_contract_standards::impl_non_fungible_token_enumeration!(Contract, tokens);
 
 #[near_bindgen]
@@ -843,3 +1793,332 @@ impl NonFungibleTokenMetadataProvider for Contract {
         self.metadata.get().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use near_contract_standards::non_fungible_token::metadata::NFT_METADATA_SPEC;
+    use near_sdk::mock::VmAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts, VMContextBuilder};
+    use near_sdk::{testing_env, PromiseResult, RuntimeFeesConfig, VMConfig, ONE_NEAR};
+
+    use super::*;
+
+    pub(crate) const METAVERSE_ID: &str = "m1";
+
+    pub(crate) fn context(
+        predecessor_id: AccountId,
+        attached_deposit: Balance,
+    ) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .predecessor_account_id(predecessor_id)
+            .attached_deposit(attached_deposit);
+        builder
+    }
+
+    // admin accounts(0), operator accounts(1), treasury accounts(2), metaverse owner accounts(3),
+    // metaverse "m1" with a holder-gated zone 2 priced 1 NEAR
+    pub(crate) fn setup() -> Contract {
+        testing_env!(context(accounts(0), 0).build());
+        let mut contract = Contract::new(
+            accounts(0),
+            accounts(1),
+            accounts(2),
+            U128(0),
+            250,
+            100,
+            NFTContractMetadata {
+                spec: NFT_METADATA_SPEC.to_string(),
+                name: "Rocks".to_string(),
+                symbol: "ROCK".to_string(),
+                icon: None,
+                base_uri: None,
+                reference: None,
+                reference_hash: None,
+            },
+        );
+        let zone = Zone {
+            zone_index: 2,
+            price: U128(ONE_NEAR),
+            core_team_addr: String::new(),
+            collection_addr: "collection.near".to_string(),
+            mint_policy: MintPolicy::HolderGated,
+            rock_index_from: 1,
+            rock_index_to: 10,
+        };
+        contract.metaverses.insert(
+            &METAVERSE_ID.to_string(),
+            &Metaverse {
+                zones: HashMap::from([(2, zone)]),
+            },
+        );
+        contract
+            .metaverse_owners
+            .insert(&METAVERSE_ID.to_string(), &accounts(3));
+        contract
+            .tokens_minted
+            .insert(&METAVERSE_ID.to_string(), &HashMap::new());
+        contract
+    }
+
+    pub(crate) fn rock_metadata() -> TokenMetadata {
+        TokenMetadata {
+            title: Some("rock".to_string()),
+            description: None,
+            media: None,
+            media_hash: None,
+            copies: None,
+            issued_at: None,
+            expires_at: None,
+            starts_at: None,
+            updated_at: None,
+            extra: None,
+            reference: None,
+            reference_hash: None,
+        }
+    }
+
+    // the nft checker callback of accounts(4) buying rock 1 of zone 2 with attached_deposit
+    pub(crate) fn checker_callback(attached_deposit: Balance, promise_result: PromiseResult) {
+        let contract_id = accounts(5);
+        testing_env!(
+            context(contract_id.clone(), attached_deposit)
+                .current_account_id(contract_id)
+                .signer_account_id(accounts(4))
+                .build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![promise_result]
+        );
+    }
+
+    // amounts transferred to account_id by the receipts of the last call
+    pub(crate) fn transfers_to(account_id: &AccountId) -> Vec<Balance> {
+        get_created_receipts()
+            .into_iter()
+            .filter(|receipt| receipt.receiver_id == *account_id)
+            .flat_map(|receipt| receipt.actions)
+            .filter_map(|action| match action {
+                VmAction::Transfer { deposit } => Some(deposit),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn mint_checked_rock(contract: &mut Contract) {
+        contract.mint_nft_checker_rock(
+            METAVERSE_ID.to_string(),
+            2,
+            1,
+            accounts(4),
+            rock_metadata(),
+            None,
+        );
+    }
+
+    #[test]
+    fn failed_checker_refunds_the_deposit() {
+        let mut contract = setup();
+        checker_callback(ONE_NEAR, PromiseResult::Failed);
+        mint_checked_rock(&mut contract);
+
+        assert!(contract
+            .tokens
+            .owner_by_id
+            .get(&rock_token_id(METAVERSE_ID, 2, 1))
+            .is_none());
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR]);
+    }
+
+    #[test]
+    fn checker_without_collection_tokens_refunds_the_deposit() {
+        let mut contract = setup();
+        checker_callback(ONE_NEAR, PromiseResult::Successful(b"[]".to_vec()));
+        mint_checked_rock(&mut contract);
+
+        assert!(contract
+            .tokens
+            .owner_by_id
+            .get(&rock_token_id(METAVERSE_ID, 2, 1))
+            .is_none());
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR]);
+    }
+
+    // nft_tokens_for_owner result of accounts(4) holding collection token "1"
+    fn holder_tokens() -> PromiseResult {
+        PromiseResult::Successful(
+            format!(r#"[{{"token_id":"1","owner_id":"{}"}}]"#, accounts(4)).into_bytes(),
+        )
+    }
+
+    #[test]
+    fn checker_mint_for_denied_receiver_refunds_the_deposit() {
+        let mut contract = setup();
+        contract.denied_accounts.insert(&accounts(4));
+        checker_callback(ONE_NEAR, holder_tokens());
+        mint_checked_rock(&mut contract);
+
+        assert!(contract
+            .tokens
+            .owner_by_id
+            .get(&rock_token_id(METAVERSE_ID, 2, 1))
+            .is_none());
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR]);
+    }
+
+    #[test]
+    fn checker_mint_below_price_refunds_the_deposit() {
+        let mut contract = setup();
+        checker_callback(ONE_NEAR / 2, holder_tokens());
+        mint_checked_rock(&mut contract);
+
+        assert!(contract
+            .tokens
+            .owner_by_id
+            .get(&rock_token_id(METAVERSE_ID, 2, 1))
+            .is_none());
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR / 2]);
+    }
+
+    #[test]
+    fn checker_mint_of_holder_mints_the_rock() {
+        let mut contract = setup();
+        checker_callback(ONE_NEAR, holder_tokens());
+        mint_checked_rock(&mut contract);
+
+        assert_eq!(
+            contract
+                .tokens
+                .owner_by_id
+                .get(&rock_token_id(METAVERSE_ID, 2, 1)),
+            Some(accounts(4))
+        );
+    }
+
+    #[test]
+    fn checker_mint_of_rock_held_meanwhile_refunds_the_deposit() {
+        let mut contract = setup();
+        let token_id = rock_token_id(METAVERSE_ID, 2, 1);
+        contract.rock_holds.insert(&token_id, &1_000);
+        checker_callback(ONE_NEAR, holder_tokens());
+        mint_checked_rock(&mut contract);
+
+        assert!(contract.tokens.owner_by_id.get(&token_id).is_none());
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR]);
+    }
+
+    #[test]
+    fn checker_mint_over_the_throttle_refunds_the_deposit() {
+        let mut contract = setup();
+        let zone_key = gen_zone_key(&METAVERSE_ID.to_string(), 2);
+        contract.mint_throttles.insert(
+            &zone_key,
+            &MintThrottle {
+                max_per_account: 0,
+                window_sec: 0,
+                max_per_block: 1,
+            },
+        );
+        checker_callback(ONE_NEAR, holder_tokens());
+        contract
+            .zone_block_mints
+            .insert(&zone_key, &(env::block_height(), 1));
+        mint_checked_rock(&mut contract);
+
+        assert!(contract
+            .tokens
+            .owner_by_id
+            .get(&rock_token_id(METAVERSE_ID, 2, 1))
+            .is_none());
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR]);
+    }
+
+    #[test]
+    fn usd_mint_for_denied_receiver_refunds_the_deposit() {
+        let mut contract = setup();
+        contract.denied_accounts.insert(&accounts(4));
+        checker_callback(ONE_NEAR, PromiseResult::Failed);
+        contract.resolve_usd_mint(
+            METAVERSE_ID.to_string(),
+            2,
+            1,
+            accounts(4),
+            rock_metadata(),
+            accounts(4),
+        );
+
+        assert!(contract
+            .tokens
+            .owner_by_id
+            .get(&rock_token_id(METAVERSE_ID, 2, 1))
+            .is_none());
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR]);
+    }
+
+    // zone 2 of a new metaverse gated by other.near
+    fn init_zone2() -> Zone {
+        Zone {
+            zone_index: 2,
+            price: U128(0),
+            core_team_addr: String::new(),
+            collection_addr: "other.near".to_string(),
+            mint_policy: MintPolicy::HolderGated,
+            rock_index_from: 2,
+            rock_index_to: 10,
+        }
+    }
+
+    #[test]
+    fn operator_inits_metaverse_for_another_owner() {
+        let mut contract = setup();
+        testing_env!(context(accounts(1), ONE_NEAR).build());
+        contract.init_metaverse("m2".to_string(), init_zone2(), None, Some(accounts(3)));
+
+        assert_eq!(
+            contract.metaverse_owners.get(&"m2".to_string()),
+            Some(accounts(3))
+        );
+        assert_eq!(contract.get_owned_metaverse_count(accounts(3)), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR1000")]
+    fn init_metaverse_for_another_owner_panics_for_non_operator() {
+        let mut contract = setup();
+        testing_env!(context(accounts(4), ONE_NEAR).build());
+        contract.init_metaverse("m2".to_string(), init_zone2(), None, Some(accounts(3)));
+    }
+
+    #[test]
+    fn metaverse_owner_is_the_predecessor() {
+        let mut contract = setup();
+        testing_env!(context(accounts(4), ONE_NEAR)
+            .signer_account_id(accounts(3))
+            .build());
+        contract.init_metaverse("m2".to_string(), init_zone2(), None, None);
+        assert_eq!(
+            contract.metaverse_owners.get(&"m2".to_string()),
+            Some(accounts(4))
+        );
+
+        testing_env!(context(accounts(4), 1)
+            .signer_account_id(accounts(5))
+            .build());
+        contract.change_metaverse_owner("m2".to_string(), accounts(3));
+        assert_eq!(
+            contract.metaverse_owners.get(&"m2".to_string()),
+            Some(accounts(3))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR1001")]
+    fn change_metaverse_owner_by_signer_of_another_contract_panics() {
+        let mut contract = setup();
+        testing_env!(context(accounts(5), 1)
+            .signer_account_id(accounts(3))
+            .build());
+        contract.change_metaverse_owner(METAVERSE_ID.to_string(), accounts(5));
+    }
+}
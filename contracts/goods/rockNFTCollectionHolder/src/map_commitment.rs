@@ -0,0 +1,102 @@
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::CryptoHash;
+
+use crate::*;
+
+// delay before an updated map commitment replaces the current one: 72h in nanoseconds
+pub const MAP_COMMITMENT_TIMELOCK: u64 = 72 * 60 * 60 * 1_000_000_000;
+
+// sha256 Merkle root of the off-chain world layout of a metaverse
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MapCommitment {
+    pub commitment: Option<Base58CryptoHash>,
+    pub pending_commitment: Option<Base58CryptoHash>,
+    pub pending_effective_at: U64,
+}
+
+impl MapCommitment {
+    // commitment in force at timestamp, a pending commitment applies once its timelock passed
+    fn resolve(&self, timestamp: u64) -> MapCommitment {
+        match self.pending_commitment {
+            Some(pending_commitment) if timestamp >= self.pending_effective_at.0 => MapCommitment {
+                commitment: Some(pending_commitment),
+                pending_commitment: None,
+                pending_effective_at: U64(0),
+            },
+            _ => self.clone(),
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner commits the world layout; the first commitment applies immediately,
+    /// later updates apply after MAP_COMMITMENT_TIMELOCK
+    #[payable]
+    pub fn update_map_commitment(&mut self, metaverse_id: String, commitment: Base58CryptoHash) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let initial_storage_usage = env::storage_usage();
+        let map_commitment = match self.map_commitments.get(&metaverse_id) {
+            None => MapCommitment {
+                commitment: Some(commitment),
+                pending_commitment: None,
+                pending_effective_at: U64(0),
+            },
+            Some(map_commitment) => MapCommitment {
+                pending_commitment: Some(commitment),
+                pending_effective_at: U64(env::block_timestamp() + MAP_COMMITMENT_TIMELOCK),
+                ..map_commitment.resolve(env::block_timestamp())
+            },
+        };
+        self.map_commitments.insert(&metaverse_id, &map_commitment);
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+
+        let effective_at = if map_commitment.pending_commitment.is_some() {
+            map_commitment.pending_effective_at
+        } else {
+            U64(env::block_timestamp())
+        };
+        let map_commitment_log: EventLog = EventLog {
+            standard: "map_commitment".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::MapCommitmentUpdate(vec![MapCommitmentLog {
+                metaverse_id,
+                commitment: String::from(&commitment),
+                effective_at,
+                memo: None,
+            }]),
+        };
+
+        map_commitment_log.emit();
+    }
+
+    pub fn get_map_commitment(&self, metaverse_id: String) -> Option<MapCommitment> {
+        self.map_commitments
+            .get(&metaverse_id)
+            .map(|map_commitment| map_commitment.resolve(env::block_timestamp()))
+    }
+
+    /// verify that leaf sha256("{rock_index}:{position}") belongs to the committed map,
+    /// proof is the list of sibling hashes from the leaf to the root (sorted pairs)
+    pub fn verify_rock_in_map(
+        &self,
+        metaverse_id: String,
+        rock_index: u128,
+        position: String,
+        proof: Vec<Base58CryptoHash>,
+    ) -> bool {
+        let commitment = self
+            .get_map_commitment(metaverse_id)
+            .and_then(|map_commitment| map_commitment.commitment)
+            .unwrap_or_else(|| env::panic_str(RoveError::MapCommitmentNotSet.as_str()));
+        let leaf = sha256_hash(format!("{}:{}", rock_index, position).as_bytes());
+        let proof: Vec<CryptoHash> = proof.into_iter().map(CryptoHash::from).collect();
+        verify_merkle_proof(leaf, &proof, &CryptoHash::from(commitment))
+    }
+}
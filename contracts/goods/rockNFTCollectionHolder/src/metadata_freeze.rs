@@ -0,0 +1,58 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// operator or metaverse owner permanently freezes the metadata of a rock,
+    /// update_rock_metadata is rejected afterwards, a moderation takedown still applies
+    #[payable]
+    pub fn freeze_token_metadata(&mut self, token_id: TokenId) {
+        assert_at_least_one_yocto();
+        require!(
+            self.tokens.owner_by_id.get(&token_id).is_some(),
+            RoveError::TokenNotFound.as_str()
+        );
+        let caller_id = env::predecessor_account_id();
+        let metaverse_id = metaverse_id_of_token(&token_id);
+        require!(
+            caller_id == self.tokens.owner_id
+                || self.metaverse_owners.get(&metaverse_id) == Some(caller_id.clone()),
+            RoveError::Unauthorized.as_str()
+        );
+        self.assert_metadata_not_frozen(&token_id);
+
+        let initial_storage_usage = env::storage_usage();
+        self.frozen_metadata
+            .insert(&token_id, &env::block_timestamp());
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                caller_id.clone(),
+            );
+        }
+
+        let metadata_frozen_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::MetadataFrozen(vec![MetadataFrozenLog {
+                token_id,
+                frozen_by: caller_id.to_string(),
+                memo: None,
+            }]),
+        };
+        metadata_frozen_log.emit();
+    }
+
+    /// timestamp the metadata of a rock was frozen at, None if it is still editable
+    pub fn get_metadata_frozen_at(&self, token_id: TokenId) -> Option<U64> {
+        self.frozen_metadata.get(&token_id).map(U64)
+    }
+
+    pub(crate) fn assert_metadata_not_frozen(&self, token_id: &TokenId) {
+        require!(
+            self.frozen_metadata.get(token_id).is_none(),
+            RoveError::MetadataFrozen.as_str()
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use crate::*;
+
+// Contract state of the 1.0 release, read once by migrate. Keep it as deployed
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldContract {
+    pub tokens: NonFungibleToken,
+    pub metadata: LazyOption<NFTContractMetadata>,
+    pub royalties: UnorderedMap<String, HashMap<AccountId, u16>>,
+    pub tokens_metadata: UnorderedMap<String, TokenMetadata>,
+    pub admin_id: AccountId,
+    pub operator_id: AccountId,
+    pub treasury_id: AccountId,
+    pub init_imo_fee: u128,
+    pub rock_purchase_fee: u32,
+    pub init_imo_nft_holder_size: u32,
+    pub metaverses: UnorderedMap<String, Metaverse>,
+    pub metaverse_owners: UnorderedMap<String, AccountId>,
+    pub tokens_minted: UnorderedMap<String, HashMap<String, bool>>,
+    pub metaverse_nft_collections: UnorderedMap<String, String>,
+    pub nft_checker: UnorderedMap<String, HashMap<String, bool>>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// upgrade the state of a 1.0 deployment in place, called by the upgrade script in the
+    /// deploy transaction. Fails on an already migrated state, which does not read as 1.0
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old_state: OldContract =
+            env::state_read().unwrap_or_else(|| env::panic_str(RoveError::NotInitialized.as_str()));
+        Self::from_old_state(old_state)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::*;
+
+// request ids remembered for retried mints, the oldest is forgotten once this many are recorded
+// so their storage (paid by the contract) stays bounded
+pub const MAX_MINT_REQUESTS: u64 = 1000;
+pub const MAX_MINT_REQUEST_ID_LEN: usize = 64;
+
+#[near_bindgen]
+impl Contract {
+    /// token minted by an earlier mint_rock of `account_id` with `request_id`, while it is
+    /// among the last MAX_MINT_REQUESTS request ids
+    pub fn get_mint_request(&self, account_id: AccountId, request_id: String) -> Option<TokenId> {
+        self.mint_requests
+            .get(&gen_mint_request_key(&account_id, &request_id))
+    }
+
+    // a retried mint_rock returns the rock minted for its request_id and refunds the deposit,
+    // None when the request is unknown or its earlier attempt did not mint. The request is
+    // recorded before an async mint, so the rock must also be owned by the receiver of the
+    // request: a refunded attempt followed by another account's mint is not a replay
+    pub(crate) fn internal_replay_mint_request(
+        &mut self,
+        request_id: &Option<String>,
+        metaverse_id: &str,
+        zone_index: u16,
+        rock_index: u128,
+        receiver_id: &AccountId,
+    ) -> Option<TokenId> {
+        let account_id = env::predecessor_account_id();
+        let token_id = self
+            .mint_requests
+            .get(&gen_mint_request_key(&account_id, request_id.as_ref()?))?;
+        if self.tokens.owner_by_id.get(&token_id)? != *receiver_id {
+            return None;
+        }
+        require!(
+            token_id == rock_token_id(metaverse_id, zone_index, rock_index),
+            RoveError::InvalidArgument.detail(format!("request_id was used to mint {}", token_id))
+        );
+        let attached_deposit = env::attached_deposit();
+        if attached_deposit > 0 {
+            self.internal_payout(account_id, attached_deposit);
+        }
+        Some(token_id)
+    }
+
+    pub(crate) fn internal_record_mint_request(
+        &mut self,
+        request_id: &Option<String>,
+        token_id: &TokenId,
+    ) {
+        let request_id = match request_id {
+            Some(request_id) => request_id,
+            None => return,
+        };
+        require!(
+            request_id.len() <= MAX_MINT_REQUEST_ID_LEN,
+            RoveError::ValueTooLong.detail("request_id must <= 64 bytes")
+        );
+        let key = gen_mint_request_key(&env::predecessor_account_id(), request_id);
+        if self.mint_requests.insert(&key, token_id).is_some() {
+            return;
+        }
+        if self.mint_request_keys.len() < MAX_MINT_REQUESTS {
+            self.mint_request_keys.push(&key);
+        } else {
+            let forgotten_key = self.mint_request_keys.replace(self.next_mint_request, &key);
+            self.mint_requests.remove(&forgotten_key);
+            self.next_mint_request = (self.next_mint_request + 1) % MAX_MINT_REQUESTS;
+        }
+    }
+}
+
+fn gen_mint_request_key(account_id: &AccountId, request_id: &String) -> String {
+    format!("{}:{}", account_id, request_id)
+}
@@ -0,0 +1,179 @@
+use near_sdk::json_types::U64;
+use near_sdk::ONE_NEAR;
+
+use crate::*;
+
+// anti-spam deposit of flag_token, kept by the contract to cover the flag storage
+pub const FLAG_DEPOSIT: Balance = ONE_NEAR / 100;
+// minimum time between two flags of the same account (1 hour)
+pub const FLAG_COOLDOWN: u64 = 3_600_000_000_000;
+pub const MAX_FLAG_REASON_LEN: usize = 256;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenFlag {
+    pub flagger_id: AccountId,
+    pub reason: String,
+    pub flagged_at: U64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingFlags {
+    pub token_id: TokenId,
+    pub flags: Vec<TokenFlag>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ModerationAction {
+    // close the flags and keep the token as is
+    Dismiss,
+    // blank media and reference of the token, ownership is kept
+    Takedown,
+}
+
+#[near_bindgen]
+impl Contract {
+    #[payable]
+    pub fn add_moderator(&mut self, account_id: AccountId) {
+        self.assert_admin_only();
+        self.moderators.insert(&account_id);
+    }
+
+    #[payable]
+    pub fn remove_moderator(&mut self, account_id: AccountId) {
+        self.assert_admin_only();
+        self.moderators.remove(&account_id);
+    }
+
+    pub fn get_moderators(&self) -> Vec<AccountId> {
+        self.moderators.to_vec()
+    }
+
+    /// report a token for review by the moderators, requires FLAG_DEPOSIT
+    #[payable]
+    pub fn flag_token(&mut self, token_id: TokenId, reason: String) {
+        let attached_deposit = env::attached_deposit();
+        require!(
+            attached_deposit >= FLAG_DEPOSIT,
+            RoveError::NotEnoughDeposit
+                .detail(format!("Need {} yoctoNEAR to flag a token", FLAG_DEPOSIT))
+        );
+        require!(
+            self.tokens.owner_by_id.get(&token_id).is_some(),
+            RoveError::TokenNotFound.as_str()
+        );
+        require!(
+            reason.len() <= MAX_FLAG_REASON_LEN,
+            RoveError::ValueTooLong.detail("reason is too long")
+        );
+
+        let flagger_id = env::predecessor_account_id();
+        let now = env::block_timestamp();
+        if let Some(last_flag_at) = self.last_flag_at.get(&flagger_id) {
+            require!(
+                now >= last_flag_at + FLAG_COOLDOWN,
+                RoveError::FlagRateLimited.as_str()
+            );
+        }
+        let mut flags = self.token_flags.get(&token_id).unwrap_or_default();
+        require!(
+            flags.iter().all(|flag| flag.flagger_id != flagger_id),
+            RoveError::AlreadyFlagged.as_str()
+        );
+
+        flags.push(TokenFlag {
+            flagger_id: flagger_id.clone(),
+            reason: reason.clone(),
+            flagged_at: U64(now),
+        });
+        self.token_flags.insert(&token_id, &flags);
+        self.last_flag_at.insert(&flagger_id, &now);
+
+        if attached_deposit > FLAG_DEPOSIT {
+            Promise::new(flagger_id.clone()).transfer(attached_deposit - FLAG_DEPOSIT);
+        }
+
+        emit_moderation_log(
+            EventLogVariant::TokenFlagged,
+            token_id,
+            flagger_id,
+            Some(reason),
+        );
+    }
+
+    /// moderator closes the flags of a token, Takedown blanks its display metadata
+    #[payable]
+    pub fn moderate_token(&mut self, token_id: TokenId, action: ModerationAction) {
+        assert_at_least_one_yocto();
+        let moderator_id = env::predecessor_account_id();
+        require!(
+            self.moderators.contains(&moderator_id),
+            RoveError::Unauthorized.as_str()
+        );
+
+        if action == ModerationAction::Takedown {
+            let token_metadata_by_id = self.tokens.token_metadata_by_id.as_mut().unwrap();
+            let mut token_metadata = token_metadata_by_id.get(&token_id).unwrap_or_else(|| {
+                env::panic_str(&RoveError::TokenNotFound.detail("token metadata not exist"))
+            });
+            token_metadata.media = None;
+            token_metadata.media_hash = None;
+            token_metadata.reference = None;
+            token_metadata.reference_hash = None;
+            token_metadata.updated_at = Some((env::block_timestamp() / 1_000_000).to_string());
+            token_metadata_by_id.insert(&token_id, &token_metadata);
+        }
+        self.token_flags.remove(&token_id);
+
+        let memo = match action {
+            ModerationAction::Dismiss => "dismiss",
+            ModerationAction::Takedown => "takedown",
+        };
+        emit_moderation_log(
+            EventLogVariant::TokenModerated,
+            token_id,
+            moderator_id,
+            Some(memo.to_string()),
+        );
+    }
+
+    pub fn get_token_flags(&self, token_id: TokenId) -> Vec<TokenFlag> {
+        self.token_flags.get(&token_id).unwrap_or_default()
+    }
+
+    pub fn get_pending_flags(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<PendingFlags> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        self.token_flags
+            .iter()
+            .skip(start_index as usize)
+            .take(limit)
+            .map(|(token_id, flags)| PendingFlags { token_id, flags })
+            .collect()
+    }
+}
+
+fn emit_moderation_log(
+    variant: fn(Vec<ModerationLog>) -> EventLogVariant,
+    token_id: TokenId,
+    account_id: AccountId,
+    memo: Option<String>,
+) {
+    let moderation_log: EventLog = EventLog {
+        standard: "moderation".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![ModerationLog {
+            token_id,
+            account_id: account_id.to_string(),
+            memo,
+        }]),
+    };
+
+    moderation_log.emit();
+}
@@ -0,0 +1,55 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner lets accounts without an eligible collection token mint rocks of a
+    /// holder_gated zone at `price` (or stops it with None). The price must be above the holder
+    /// price, a non-holder mint attaching less is refunded
+    #[payable]
+    pub fn set_zone_non_holder_price(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        price: Option<U128>,
+    ) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(
+            zone.mint_policy == MintPolicy::HolderGated,
+            RoveError::InvalidZoneType.as_str()
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        match price {
+            Some(price) => {
+                require!(
+                    price.0 > zone.price.0,
+                    RoveError::InvalidArgument.detail("price must > zone price")
+                );
+                self.zone_non_holder_prices.insert(&zone_key, &price.0)
+            }
+            None => self.zone_non_holder_prices.remove(&zone_key),
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_zone_non_holder_price(&self, metaverse_id: String, zone_index: u16) -> Option<U128> {
+        self.internal_non_holder_price(&metaverse_id, zone_index)
+            .map(U128)
+    }
+
+    pub(crate) fn internal_non_holder_price(
+        &self,
+        metaverse_id: &String,
+        zone_index: u16,
+    ) -> Option<Balance> {
+        self.zone_non_holder_prices
+            .get(&gen_zone_key(metaverse_id, zone_index))
+    }
+}
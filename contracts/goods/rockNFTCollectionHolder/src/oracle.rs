@@ -0,0 +1,283 @@
+use near_sdk::json_types::U64;
+use near_sdk::{ext_contract, Gas, PromiseResult};
+
+use crate::*;
+
+pub const GAS_FOR_GET_PRICE_DATA: Gas = Gas(10_000_000_000_000);
+pub const GAS_FOR_RESOLVE_USD_MINT: Gas = Gas(50_000_000_000_000);
+
+// oracle used to convert USD zone prices into yoctoNEAR at mint time
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PriceOracleConfig {
+    pub oracle_id: AccountId,
+    // asset of the NEAR price, e.g. "wrap.near"
+    pub asset_id: String,
+    // older oracle prices fall back to the zone NEAR price
+    pub max_staleness_sec: u32,
+}
+
+// USD price of the smallest unit of an asset = multiplier / 10^decimals (priceoracle.near interface)
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OraclePrice {
+    pub multiplier: U128,
+    pub decimals: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AssetOptionalPrice {
+    pub asset_id: String,
+    pub price: Option<OraclePrice>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PriceData {
+    pub timestamp: U64,
+    pub recency_duration_sec: u32,
+    pub prices: Vec<AssetOptionalPrice>,
+}
+
+#[ext_contract(ext_price_oracle)]
+pub trait PriceOracle {
+    fn get_price_data(&self, asset_ids: Option<Vec<String>>) -> PriceData;
+}
+
+#[ext_contract(ext_usd_mint_resolver)]
+pub trait UsdMintResolver {
+    fn resolve_usd_mint(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        receiver_id: AccountId,
+        token_metadata: TokenMetadata,
+        minter_id: AccountId,
+    );
+}
+
+#[near_bindgen]
+impl Contract {
+    #[payable]
+    pub fn set_price_oracle(&mut self, price_oracle: Option<PriceOracleConfig>) {
+        self.assert_admin_only();
+        self.price_oracle = price_oracle;
+    }
+
+    pub fn get_price_oracle(&self) -> Option<PriceOracleConfig> {
+        self.price_oracle.clone()
+    }
+
+    /// metaverse owner prices a public zone in USD cents, None goes back to the zone NEAR price
+    #[payable]
+    pub fn set_zone_price_usd(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        price_usd_cents: Option<u64>,
+    ) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(
+            zone.mint_policy == MintPolicy::PublicFixedPrice,
+            RoveError::InvalidZoneType.as_str()
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        match price_usd_cents {
+            Some(price_usd_cents) => {
+                require!(
+                    price_usd_cents > 0,
+                    RoveError::InvalidArgument.detail("price_usd_cents must > 0")
+                );
+                self.zone_usd_prices.insert(&zone_key, &price_usd_cents)
+            }
+            None => self.zone_usd_prices.remove(&zone_key),
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_zone_price_usd(&self, metaverse_id: String, zone_index: u16) -> Option<u64> {
+        self.zone_usd_prices
+            .get(&gen_zone_key(&metaverse_id, zone_index))
+    }
+
+    // This is callback function of mint_rock for USD priced zones (private, CAN NOT CALL DIRECTLY)
+    #[private]
+    #[payable]
+    pub fn resolve_usd_mint(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        receiver_id: AccountId,
+        token_metadata: TokenMetadata,
+        minter_id: AccountId,
+    ) {
+        require!(
+            env::promise_results_count() == 1,
+            RoveError::NotCallback.as_str()
+        );
+        let attached_deposit = env::attached_deposit();
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        let price_usd_cents = self
+            .zone_usd_prices
+            .get(&gen_zone_key(&metaverse_id, zone_index))
+            .unwrap_or_default();
+        let oracle_price = match env::promise_result(0) {
+            PromiseResult::Successful(result) => {
+                self.internal_oracle_usd_price(price_usd_cents, &result)
+            }
+            _ => None,
+        };
+        // oracle failed or its price is stale => the zone NEAR price applies
+        let price = oracle_price.map(U128).unwrap_or(zone.price);
+
+        // the deposit is already held by this contract, give it back instead of panicking
+        let token_id = rock_token_id(&metaverse_id, zone_index, rock_index);
+        let is_minted = self
+            .tokens_minted
+            .get(&metaverse_id)
+            .map(|tokens_minted| tokens_minted.contains_key(&token_id))
+            .unwrap_or(true);
+        let mint_check = if is_minted {
+            Err(RoveError::TokenAlreadyExists)
+        } else {
+            self.internal_check_checker_mint(
+                &metaverse_id,
+                zone_index,
+                &token_id,
+                &receiver_id,
+                price.0,
+                zone.mint_policy,
+            )
+        };
+        emit_rock_price_quote_log(
+            &metaverse_id,
+            zone_index,
+            price_usd_cents,
+            price,
+            oracle_price.is_some(),
+            mint_check
+                .err()
+                .map(|error| format!("refunded: {}", error.as_str())),
+        );
+        if mint_check.is_err() {
+            self.internal_payout(minter_id, attached_deposit);
+            return;
+        }
+
+        // _mint refunds the rest of the deposit to the signer
+        self._mint(
+            metaverse_id,
+            zone_index,
+            token_id,
+            receiver_id,
+            token_metadata,
+            price,
+            zone.mint_policy,
+            "".to_string(),
+            env::attached_deposit(),
+        );
+    }
+
+    // quote the NEAR price of a USD priced zone, the rock is minted in resolve_usd_mint
+    pub(crate) fn internal_mint_rock_in_usd(
+        &mut self,
+        metaverse_id: &String,
+        zone_index: u16,
+        rock_index: u128,
+        receiver_id: &AccountId,
+        token_metadata: &TokenMetadata,
+    ) -> bool {
+        let price_oracle = match self.price_oracle.clone() {
+            Some(price_oracle) => price_oracle,
+            None => return false,
+        };
+        if self
+            .zone_usd_prices
+            .get(&gen_zone_key(metaverse_id, zone_index))
+            .is_none()
+        {
+            return false;
+        }
+
+        ext_price_oracle::get_price_data(
+            Some(vec![price_oracle.asset_id]),
+            price_oracle.oracle_id,
+            0,
+            GAS_FOR_GET_PRICE_DATA,
+        )
+        .then(ext_usd_mint_resolver::resolve_usd_mint(
+            metaverse_id.clone(),
+            zone_index,
+            rock_index,
+            receiver_id.clone(),
+            token_metadata.clone(),
+            env::predecessor_account_id(),
+            env::current_account_id(),
+            env::attached_deposit(),
+            GAS_FOR_RESOLVE_USD_MINT,
+        ));
+        true
+    }
+
+    fn internal_oracle_usd_price(&self, price_usd_cents: u64, result: &[u8]) -> Option<Balance> {
+        let price_oracle = self.price_oracle.as_ref()?;
+        let price_data = near_sdk::serde_json::from_slice::<PriceData>(result).ok()?;
+        let max_staleness = price_oracle.max_staleness_sec as u64 * 1_000_000_000;
+        if env::block_timestamp().saturating_sub(price_data.timestamp.0) > max_staleness {
+            return None;
+        }
+        let price = price_data
+            .prices
+            .into_iter()
+            .find(|asset_price| asset_price.asset_id == price_oracle.asset_id)?
+            .price?;
+        usd_cents_to_yocto(price_usd_cents, &price)
+    }
+}
+
+// yoctoNEAR worth price_usd_cents at the oracle price, rounded up
+fn usd_cents_to_yocto(price_usd_cents: u64, price: &OraclePrice) -> Option<Balance> {
+    let denominator = price.multiplier.0.checked_mul(100)?;
+    if price_usd_cents == 0 || denominator == 0 {
+        return None;
+    }
+    let numerator = 10u128
+        .checked_pow(price.decimals as u32)?
+        .checked_mul(price_usd_cents as u128)?;
+    Some(numerator / denominator + (numerator % denominator > 0) as u128)
+}
+
+fn emit_rock_price_quote_log(
+    metaverse_id: &str,
+    zone_index: u16,
+    price_usd_cents: u64,
+    price: U128,
+    from_oracle: bool,
+    memo: Option<String>,
+) {
+    let price_quote_log: EventLog = EventLog {
+        standard: "price_oracle".to_string(),
+        version: "1.0.0".to_string(),
+        event: EventLogVariant::RockPriceQuote(vec![RockPriceQuoteLog {
+            metaverse_id: metaverse_id.to_string(),
+            zone_index,
+            price_usd_cents,
+            price,
+            from_oracle,
+            memo,
+        }]),
+    };
+
+    price_quote_log.emit();
+}
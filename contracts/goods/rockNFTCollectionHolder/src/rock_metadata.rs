@@ -0,0 +1,161 @@
+use near_sdk::serde_json::{self, Map, Value};
+
+use crate::*;
+
+// token metadata fields a rock owner may personalize, set per metaverse by its owner
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetadataSchema {
+    // None => the field is not editable
+    pub title_max_len: Option<u32>,
+    pub description_max_len: Option<u32>,
+    // keys of the extra JSON object, e.g. "build_uri"
+    pub extra_keys: Vec<String>,
+    pub extra_value_max_len: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RockMetadataPatch {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    // an empty value removes the key
+    pub extra: Option<HashMap<String, String>>,
+}
+
+impl MetadataSchema {
+    fn assert_valid_patch(&self, patch: &RockMetadataPatch) {
+        if let Some(title) = &patch.title {
+            let title_max_len = self.title_max_len.unwrap_or_else(|| {
+                env::panic_str(&RoveError::FieldNotEditable.detail("title is not editable"))
+            });
+            require!(
+                title.len() <= title_max_len as usize,
+                RoveError::ValueTooLong.detail("title is too long")
+            );
+        }
+        if let Some(description) = &patch.description {
+            let description_max_len = self.description_max_len.unwrap_or_else(|| {
+                env::panic_str(&RoveError::FieldNotEditable.detail("description is not editable"))
+            });
+            require!(
+                description.len() <= description_max_len as usize,
+                RoveError::ValueTooLong.detail("description is too long")
+            );
+        }
+        if let Some(extra) = &patch.extra {
+            for (key, value) in extra {
+                require!(
+                    self.extra_keys.contains(key),
+                    RoveError::FieldNotEditable.detail(format!("extra.{} is not editable", key))
+                );
+                require!(
+                    value.len() <= self.extra_value_max_len as usize,
+                    RoveError::ValueTooLong.detail(format!("extra.{} is too long", key))
+                );
+            }
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner sets (or removes with None) the fields its rock owners may update
+    #[payable]
+    pub fn set_metadata_schema(&mut self, metaverse_id: String, schema: Option<MetadataSchema>) {
+        self.assert_metaverse_owner(&metaverse_id);
+
+        let initial_storage_usage = env::storage_usage();
+        match schema {
+            Some(schema) => self.metadata_schemas.insert(&metaverse_id, &schema),
+            None => self.metadata_schemas.remove(&metaverse_id),
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_metadata_schema(&self, metaverse_id: String) -> Option<MetadataSchema> {
+        self.metadata_schemas.get(&metaverse_id)
+    }
+
+    /// rock owner patches the metadata of its rock within the metaverse schema,
+    /// the operator may patch any rock without the schema to take down abusive content
+    #[payable]
+    pub fn update_rock_metadata(&mut self, token_id: TokenId, patch: RockMetadataPatch) {
+        assert_at_least_one_yocto();
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        self.assert_metadata_not_frozen(&token_id);
+        let caller_id = env::predecessor_account_id();
+        let is_operator_override = caller_id != owner_id && caller_id == self.tokens.owner_id;
+        if !is_operator_override {
+            require!(caller_id == owner_id, RoveError::Unauthorized.as_str());
+            let metaverse_id = metaverse_id_of_token(&token_id);
+            self.metadata_schemas
+                .get(&metaverse_id)
+                .unwrap_or_else(|| env::panic_str(RoveError::MetadataNotEditable.as_str()))
+                .assert_valid_patch(&patch);
+        }
+
+        let initial_storage_usage = env::storage_usage();
+        let token_metadata_by_id = self.tokens.token_metadata_by_id.as_mut().unwrap();
+        let mut token_metadata = token_metadata_by_id.get(&token_id).unwrap_or_else(|| {
+            env::panic_str(&RoveError::TokenNotFound.detail("token metadata not exist"))
+        });
+        if let Some(title) = patch.title {
+            token_metadata.title = Some(title).filter(|title| !title.is_empty());
+        }
+        if let Some(description) = patch.description {
+            token_metadata.description =
+                Some(description).filter(|description| !description.is_empty());
+        }
+        if let Some(extra_patch) = patch.extra {
+            // extra that is not a JSON object is replaced
+            let mut extra: Map<String, Value> = token_metadata
+                .extra
+                .as_ref()
+                .and_then(|extra| serde_json::from_str(extra).ok())
+                .unwrap_or_default();
+            for (key, value) in extra_patch {
+                if value.is_empty() {
+                    extra.remove(&key);
+                } else {
+                    extra.insert(key, Value::String(value));
+                }
+            }
+            token_metadata.extra = if extra.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_string(&extra).unwrap())
+            };
+        }
+        token_metadata.updated_at = Some((env::block_timestamp() / 1_000_000).to_string());
+        token_metadata_by_id.insert(&token_id, &token_metadata);
+
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(env::storage_usage() - initial_storage_usage, caller_id);
+        }
+
+        let nft_metadata_update_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: "1.1.0".to_string(),
+            event: EventLogVariant::NftMetadataUpdate(vec![NftMetadataUpdateLog {
+                token_ids: vec![token_id],
+                memo: if is_operator_override {
+                    Some(String::from("operator_override"))
+                } else {
+                    None
+                },
+            }]),
+        };
+
+        nft_metadata_update_log.emit();
+    }
+}
@@ -0,0 +1,228 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+// longest auction the operator can list (30 days)
+pub const MAX_SLUG_AUCTION_DURATION_SEC: u32 = 30 * 24 * 3600;
+// a new bid must beat the highest bid by 5%
+pub const MIN_BID_INCREMENT_BPS: u128 = 500;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SlugBid {
+    pub bidder_id: AccountId,
+    // metaverse of the bidder the slug is bound to when the bid wins
+    pub metaverse_id: String,
+    pub amount: U128,
+}
+
+// auction of a reserved slug, the highest bid is held by the contract until it is outbid
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SlugAuction {
+    pub min_bid: U128,
+    pub ends_at: U64,
+    pub highest_bid: Option<SlugBid>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator reserves a premium slug and opens its auction for `duration_sec`,
+    /// set_metaverse_slug can not claim it until the auction is settled or cancelled
+    #[payable]
+    pub fn start_slug_auction(&mut self, slug: String, min_bid: U128, duration_sec: u32) {
+        self.assert_operator_only();
+        assert_valid_slug(&slug);
+        require!(
+            self.internal_is_slug_available(&slug),
+            RoveError::SlugTaken.as_str()
+        );
+        require!(
+            0 < duration_sec && duration_sec <= MAX_SLUG_AUCTION_DURATION_SEC,
+            RoveError::InvalidArgument.detail(format!(
+                "duration_sec must be in (0, {}]",
+                MAX_SLUG_AUCTION_DURATION_SEC
+            ))
+        );
+
+        let ends_at = env::block_timestamp() + u64::from(duration_sec) * 1_000_000_000;
+        let initial_storage_usage = env::storage_usage();
+        self.slug_auctions.insert(
+            &slug,
+            &SlugAuction {
+                min_bid,
+                ends_at: U64(ends_at),
+                highest_bid: None,
+            },
+        );
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+
+        emit_slug_auction_log(
+            EventLogVariant::SlugAuctionStarted,
+            slug,
+            None,
+            None,
+            min_bid,
+        );
+    }
+
+    /// metaverse owner bids the attached deposit for `slug` on behalf of `metaverse_id`,
+    /// the previous highest bid is refunded
+    #[payable]
+    pub fn bid_slug(&mut self, slug: String, metaverse_id: String) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let mut auction = self
+            .slug_auctions
+            .get(&slug)
+            .unwrap_or_else(|| env::panic_str(RoveError::SlugAuctionNotFound.as_str()));
+        require!(
+            env::block_timestamp() < auction.ends_at.0,
+            RoveError::SlugAuctionEnded.as_str()
+        );
+        let amount = env::attached_deposit();
+        let min_amount = match &auction.highest_bid {
+            Some(highest_bid) => {
+                highest_bid.amount.0
+                    + std::cmp::max(highest_bid.amount.0 * MIN_BID_INCREMENT_BPS / 10_000, 1)
+            }
+            None => auction.min_bid.0,
+        };
+        require!(
+            amount >= min_amount,
+            RoveError::NotEnoughDeposit
+                .detail(format!("Need a bid of at least {} yoctoNEAR", min_amount))
+        );
+
+        let bidder_id = env::predecessor_account_id();
+        if let Some(outbid) = auction.highest_bid.replace(SlugBid {
+            bidder_id: bidder_id.clone(),
+            metaverse_id: metaverse_id.clone(),
+            amount: U128(amount),
+        }) {
+            self.internal_payout(outbid.bidder_id, outbid.amount.0);
+        }
+        self.slug_auctions.insert(&slug, &auction);
+
+        emit_slug_auction_log(
+            EventLogVariant::SlugBid,
+            slug,
+            Some(bidder_id),
+            Some(metaverse_id),
+            U128(amount),
+        );
+    }
+
+    /// anyone settles an ended auction: the slug is bound to the metaverse of the highest bid
+    /// and the bid goes to the treasury, without bids the slug is released
+    pub fn settle_slug_auction(&mut self, slug: String) {
+        let auction = self
+            .slug_auctions
+            .get(&slug)
+            .unwrap_or_else(|| env::panic_str(RoveError::SlugAuctionNotFound.as_str()));
+        require!(
+            env::block_timestamp() >= auction.ends_at.0,
+            RoveError::SlugAuctionNotEnded.as_str()
+        );
+        self.slug_auctions.remove(&slug);
+
+        let (bidder_id, metaverse_id, amount) = match auction.highest_bid {
+            Some(highest_bid) => {
+                // the slug entries are paid from the winning bid
+                let initial_storage_usage = env::storage_usage();
+                self.internal_bind_slug(&highest_bid.metaverse_id, &slug);
+                let storage_cost = env::storage_byte_cost()
+                    * Balance::from(env::storage_usage().saturating_sub(initial_storage_usage));
+                if highest_bid.amount.0 > storage_cost {
+                    self.internal_pay_treasury(highest_bid.amount.0 - storage_cost);
+                }
+                (
+                    Some(highest_bid.bidder_id),
+                    Some(highest_bid.metaverse_id),
+                    highest_bid.amount,
+                )
+            }
+            None => (None, None, U128(0)),
+        };
+
+        emit_slug_auction_log(
+            EventLogVariant::SlugAuctionSettled,
+            slug,
+            bidder_id,
+            metaverse_id,
+            amount,
+        );
+    }
+
+    /// operator cancels an auction, the highest bid is refunded and the slug released
+    #[payable]
+    pub fn cancel_slug_auction(&mut self, slug: String) {
+        self.assert_operator_only();
+        let auction = self
+            .slug_auctions
+            .remove(&slug)
+            .unwrap_or_else(|| env::panic_str(RoveError::SlugAuctionNotFound.as_str()));
+        let (bidder_id, metaverse_id, amount) = match auction.highest_bid {
+            Some(highest_bid) => {
+                self.internal_payout(highest_bid.bidder_id.clone(), highest_bid.amount.0);
+                (
+                    Some(highest_bid.bidder_id),
+                    Some(highest_bid.metaverse_id),
+                    highest_bid.amount,
+                )
+            }
+            None => (None, None, U128(0)),
+        };
+
+        emit_slug_auction_log(
+            EventLogVariant::SlugAuctionCancelled,
+            slug,
+            bidder_id,
+            metaverse_id,
+            amount,
+        );
+    }
+
+    pub fn get_slug_auction(&self, slug: String) -> Option<SlugAuction> {
+        self.slug_auctions.get(&slug)
+    }
+
+    pub fn get_slug_auctions(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<(String, SlugAuction)> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        self.slug_auctions
+            .iter()
+            .skip(start_index as usize)
+            .take(limit)
+            .collect()
+    }
+}
+
+fn emit_slug_auction_log(
+    variant: fn(Vec<SlugAuctionLog>) -> EventLogVariant,
+    slug: String,
+    bidder_id: Option<AccountId>,
+    metaverse_id: Option<String>,
+    amount: U128,
+) {
+    let slug_auction_log: EventLog = EventLog {
+        standard: "metaverse_slug".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![SlugAuctionLog {
+            slug,
+            bidder_id: bidder_id.map(|bidder_id| bidder_id.to_string()),
+            metaverse_id,
+            amount,
+            memo: None,
+        }]),
+    };
+    slug_auction_log.emit();
+}
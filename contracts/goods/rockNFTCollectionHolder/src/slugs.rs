@@ -0,0 +1,132 @@
+use near_sdk::ONE_NEAR;
+
+use crate::*;
+
+pub const MIN_SLUG_LEN: usize = 3;
+pub const MAX_SLUG_LEN: usize = 64;
+// paid to the treasury for every slug claimed, so names can not be squatted for free
+pub const DEFAULT_SLUG_FEE: Balance = ONE_NEAR / 10;
+
+#[near_bindgen]
+impl Contract {
+    /// operator sets the fee paid to the treasury for each slug claimed with set_metaverse_slug
+    #[payable]
+    pub fn set_slug_fee(&mut self, slug_fee: U128) {
+        self.assert_operator_only();
+        self.slug_fee = slug_fee.0;
+    }
+
+    pub fn get_slug_fee(&self) -> U128 {
+        U128(self.slug_fee)
+    }
+
+    /// metaverse owner claims `slug` (lowercase letters, digits and dashes) for its metaverse,
+    /// paying slug_fee plus storage, the previous slug is released. None releases the slug
+    #[payable]
+    pub fn set_metaverse_slug(&mut self, metaverse_id: String, slug: Option<String>) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let previous_slug = self.metaverse_slugs.get(&metaverse_id);
+        require!(
+            previous_slug != slug,
+            RoveError::InvalidArgument.detail("slug is unchanged")
+        );
+        if let Some(slug) = &slug {
+            assert_valid_slug(slug);
+            require!(
+                self.internal_is_slug_available(slug),
+                RoveError::SlugTaken.as_str()
+            );
+        }
+
+        let attached_deposit = env::attached_deposit();
+        let initial_storage_usage = env::storage_usage();
+        let slug_fee = match &slug {
+            Some(slug) => {
+                self.internal_bind_slug(&metaverse_id, slug);
+                self.slug_fee
+            }
+            None => {
+                if let Some(previous_slug) = &previous_slug {
+                    self.slug_metaverses.remove(previous_slug);
+                }
+                self.metaverse_slugs.remove(&metaverse_id);
+                0
+            }
+        };
+
+        let storage_cost = env::storage_byte_cost()
+            * Balance::from(env::storage_usage().saturating_sub(initial_storage_usage));
+        require!(
+            slug_fee + storage_cost <= attached_deposit,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR to set slug ({} yoctoNEAR slug fee + {} yoctoNEAR storage)",
+                slug_fee + storage_cost,
+                slug_fee,
+                storage_cost
+            ))
+        );
+        if slug_fee > 0 {
+            self.internal_pay_treasury(slug_fee);
+        }
+        let refund = attached_deposit - slug_fee - storage_cost;
+        if refund > 0 {
+            self.internal_payout(env::predecessor_account_id(), refund);
+        }
+
+        let metaverse_slug_log: EventLog = EventLog {
+            standard: "metaverse_slug".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::MetaverseSlugUpdate(vec![MetaverseSlugLog {
+                metaverse_id,
+                slug,
+                previous_slug,
+                memo: None,
+            }]),
+        };
+        metaverse_slug_log.emit();
+    }
+
+    /// metaverse_id of a slug, e.g. for `rove.to/worlds/{slug}`
+    pub fn resolve_slug(&self, slug: String) -> Option<String> {
+        self.slug_metaverses.get(&slug)
+    }
+
+    pub fn get_metaverse_slug(&self, metaverse_id: String) -> Option<String> {
+        self.metaverse_slugs.get(&metaverse_id)
+    }
+
+    // a slug is taken while a metaverse holds it or it is reserved for an auction
+    pub(crate) fn internal_is_slug_available(&self, slug: &String) -> bool {
+        self.slug_metaverses.get(slug).is_none() && self.slug_auctions.get(slug).is_none()
+    }
+
+    // bind slug to metaverse_id, releasing the previous slug of the metaverse
+    pub(crate) fn internal_bind_slug(
+        &mut self,
+        metaverse_id: &String,
+        slug: &String,
+    ) -> Option<String> {
+        let previous_slug = self.metaverse_slugs.insert(metaverse_id, slug);
+        if let Some(previous_slug) = &previous_slug {
+            self.slug_metaverses.remove(previous_slug);
+        }
+        self.slug_metaverses.insert(slug, metaverse_id);
+        previous_slug
+    }
+}
+
+pub(crate) fn assert_valid_slug(slug: &str) {
+    require!(
+        slug.len() >= MIN_SLUG_LEN && slug.len() <= MAX_SLUG_LEN,
+        RoveError::InvalidArgument.detail("slug must be 3 to 64 bytes")
+    );
+    require!(
+        slug.bytes()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-')
+            && !slug.starts_with('-')
+            && !slug.ends_with('-'),
+        RoveError::InvalidArgument.detail(
+            "slug must be lowercase letters, digits and dashes, not starting or ending with a dash"
+        )
+    );
+}
@@ -0,0 +1,114 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// operator tops up the pool paying storage of free mints in sponsored zones
+    #[payable]
+    pub fn deposit_sponsorship(&mut self) {
+        self.assert_operator_only();
+        self.sponsorship_balance += env::attached_deposit();
+        emit_sponsorship_log(
+            EventLogVariant::SponsorshipDeposit,
+            env::attached_deposit(),
+            self.sponsorship_balance,
+        );
+    }
+
+    #[payable]
+    pub fn withdraw_sponsorship(&mut self, amount: U128) {
+        self.assert_operator_only();
+        require!(
+            amount.0 <= self.sponsorship_balance,
+            RoveError::NotEnoughSponsorship.as_str()
+        );
+        self.sponsorship_balance -= amount.0;
+        self.internal_payout(env::predecessor_account_id(), amount.0);
+        emit_sponsorship_log(
+            EventLogVariant::SponsorshipWithdraw,
+            amount.0,
+            self.sponsorship_balance,
+        );
+    }
+
+    // a SponsorshipLowBalance event is emitted when a mint leaves less than this in the pool
+    #[payable]
+    pub fn set_sponsorship_low_balance(&mut self, low_balance: U128) {
+        self.assert_operator_only();
+        self.sponsorship_low_balance = low_balance.0;
+    }
+
+    #[payable]
+    pub fn set_zone_sponsored(&mut self, metaverse_id: String, zone_index: u16, sponsored: bool) {
+        self.assert_operator_only();
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(
+            zone.mint_policy == MintPolicy::HolderGated,
+            RoveError::InvalidZoneType.as_str()
+        );
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        if sponsored {
+            self.sponsored_zones.insert(&zone_key, &true);
+        } else {
+            self.sponsored_zones.remove(&zone_key);
+        }
+    }
+
+    pub fn is_zone_sponsored(&self, metaverse_id: String, zone_index: u16) -> bool {
+        self.sponsored_zones
+            .get(&gen_zone_key(&metaverse_id, zone_index))
+            .is_some()
+    }
+
+    pub fn get_sponsorship_balance(&self) -> U128 {
+        U128(self.sponsorship_balance)
+    }
+
+    pub fn get_sponsorship_low_balance(&self) -> U128 {
+        U128(self.sponsorship_low_balance)
+    }
+
+    // pay storage_cost from the pool if the zone is sponsored, return false if it can not
+    pub(crate) fn internal_use_sponsorship(
+        &mut self,
+        metaverse_id: &String,
+        zone_index: u16,
+        storage_cost: Balance,
+    ) -> bool {
+        if self
+            .sponsored_zones
+            .get(&gen_zone_key(metaverse_id, zone_index))
+            .is_none()
+            || self.sponsorship_balance < storage_cost
+        {
+            return false;
+        }
+
+        self.sponsorship_balance -= storage_cost;
+        if self.sponsorship_balance < self.sponsorship_low_balance {
+            emit_sponsorship_log(
+                EventLogVariant::SponsorshipLowBalance,
+                storage_cost,
+                self.sponsorship_balance,
+            );
+        }
+        true
+    }
+}
+
+fn emit_sponsorship_log(
+    variant: fn(Vec<SponsorshipLog>) -> EventLogVariant,
+    amount: Balance,
+    balance: Balance,
+) {
+    let sponsorship_log: EventLog = EventLog {
+        standard: "nft_collection_holder_sponsorship".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![SponsorshipLog {
+            amount: U128(amount),
+            balance: U128(balance),
+            memo: None,
+        }]),
+    };
+
+    sponsorship_log.emit();
+}
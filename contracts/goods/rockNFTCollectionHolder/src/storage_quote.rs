@@ -0,0 +1,29 @@
+use crate::*;
+
+// storage of a new metaverse outside its zones (metaverse, owner and minted token records),
+// rounded up for 64 byte ids
+pub const INIT_METAVERSE_STORAGE_BYTES: u64 = 800;
+// storage of one zone of a metaverse, rounded up for 64 byte addresses
+pub const ZONE_STORAGE_BYTES: u64 = 250;
+// init_metaverse creates the operator zone and the zone given by its caller
+pub const INIT_METAVERSE_ZONE_COUNT: u16 = 2;
+
+#[near_bindgen]
+impl Contract {
+    /// deposit init_metaverse needs for the storage of a metaverse of `zone_count` zones (2 for
+    /// init_metaverse). The IMO fee pays the storage first, only the part it does not cover (all
+    /// of it when init_imo_fee is 0) is asked on top. The unused part is refunded
+    pub fn get_init_storage_quote(&self, zone_count: u16) -> U128 {
+        U128(storage_quote(zone_count))
+    }
+
+    // deposit init_metaverse requires with an IMO fee of total_init_imo_fee
+    pub(crate) fn internal_init_metaverse_deposit(&self, total_init_imo_fee: Balance) -> Balance {
+        total_init_imo_fee.max(storage_quote(INIT_METAVERSE_ZONE_COUNT))
+    }
+}
+
+fn storage_quote(zone_count: u16) -> Balance {
+    env::storage_byte_cost()
+        * Balance::from(INIT_METAVERSE_STORAGE_BYTES + ZONE_STORAGE_BYTES * zone_count as u64)
+}
@@ -0,0 +1,131 @@
+use crate::*;
+
+pub const MAX_METAVERSE_TAGS: usize = 8;
+pub const MAX_TAG_LEN: usize = 32;
+
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum MetaverseTagKind {
+    Region,
+    Genre,
+    Language,
+}
+
+impl MetaverseTagKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetaverseTagKind::Region => "region",
+            MetaverseTagKind::Genre => "genre",
+            MetaverseTagKind::Language => "language",
+        }
+    }
+}
+
+// discovery tag of a metaverse, e.g. { kind: language, value: "en" }. Values are lowercased
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetaverseTag {
+    pub kind: MetaverseTagKind,
+    pub value: String,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner replaces the discovery tags of its metaverse, an empty list removes it
+    /// from the world directory
+    #[payable]
+    pub fn set_metaverse_tags(&mut self, metaverse_id: String, tags: Vec<MetaverseTag>) {
+        self.assert_metaverse_owner(&metaverse_id);
+        require!(
+            tags.len() <= MAX_METAVERSE_TAGS,
+            RoveError::TooManyItems.detail("too many tags")
+        );
+        require!(
+            tags.iter()
+                .all(|tag| !tag.value.is_empty() && tag.value.len() <= MAX_TAG_LEN),
+            RoveError::InvalidArgument.detail("tag value must not be empty and <= 32 bytes")
+        );
+        let mut tags: Vec<MetaverseTag> = tags
+            .into_iter()
+            .map(|tag| MetaverseTag {
+                kind: tag.kind,
+                value: tag.value.to_lowercase(),
+            })
+            .collect();
+        tags.sort_by_key(tag_key);
+        tags.dedup();
+
+        let initial_storage_usage = env::storage_usage();
+        for tag in self.get_metaverse_tags(metaverse_id.clone()) {
+            self.internal_unindex_metaverse_tag(&metaverse_id, &tag);
+        }
+        for tag in tags.iter() {
+            self.internal_index_metaverse_tag(&metaverse_id, tag);
+        }
+        if tags.is_empty() {
+            self.metaverse_tags.remove(&metaverse_id);
+        } else {
+            self.metaverse_tags.insert(&metaverse_id, &tags);
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_metaverse_tags(&self, metaverse_id: String) -> Vec<MetaverseTag> {
+        self.metaverse_tags.get(&metaverse_id).unwrap_or_default()
+    }
+
+    /// metaverse ids with the tag, sorted, `limit` (default 50) from `from_index`
+    pub fn find_metaverses_by_tag(
+        &self,
+        tag: MetaverseTag,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<String> {
+        let tag = MetaverseTag {
+            kind: tag.kind,
+            value: tag.value.to_lowercase(),
+        };
+        self.metaverse_tag_index
+            .get(&tag_key(&tag))
+            .unwrap_or_default()
+            .into_iter()
+            .skip(from_index.unwrap_or_default() as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .collect()
+    }
+
+    fn internal_index_metaverse_tag(&mut self, metaverse_id: &String, tag: &MetaverseTag) {
+        let key = tag_key(tag);
+        let mut metaverse_ids = self.metaverse_tag_index.get(&key).unwrap_or_default();
+        if let Err(index) = metaverse_ids.binary_search(metaverse_id) {
+            metaverse_ids.insert(index, metaverse_id.clone());
+            self.metaverse_tag_index.insert(&key, &metaverse_ids);
+        }
+    }
+
+    fn internal_unindex_metaverse_tag(&mut self, metaverse_id: &String, tag: &MetaverseTag) {
+        let key = tag_key(tag);
+        let mut metaverse_ids = self.metaverse_tag_index.get(&key).unwrap_or_default();
+        if let Ok(index) = metaverse_ids.binary_search(metaverse_id) {
+            metaverse_ids.remove(index);
+            if metaverse_ids.is_empty() {
+                self.metaverse_tag_index.remove(&key);
+            } else {
+                self.metaverse_tag_index.insert(&key, &metaverse_ids);
+            }
+        }
+    }
+}
+
+// key of the metaverse_tag_index, e.g. "language:en"
+fn tag_key(tag: &MetaverseTag) -> String {
+    format!("{}:{}", tag.kind.as_str(), tag.value)
+}
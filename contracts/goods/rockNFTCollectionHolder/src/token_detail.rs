@@ -0,0 +1,62 @@
+use near_contract_standards::non_fungible_token::core::NonFungibleTokenCore;
+use near_contract_standards::non_fungible_token::enumeration::NonFungibleTokenEnumeration;
+
+use crate::*;
+
+// everything a parcel card shows about a rock, joined in one view
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenDetail {
+    pub token: Token,
+    // royalties applied by nft_payout, zone override first then metaverse
+    pub royalties: HashMap<AccountId, u16>,
+    // primary sale of the rock, while its minter still owns it
+    pub mint_receipt: Option<Purchase>,
+    // set while the primary sale can still be refunded
+    pub escrowed_sale: Option<EscrowedSale>,
+    // set while the rock is wrapped in a region
+    pub region_id: Option<TokenId>,
+    pub metadata_frozen_at: Option<U64>,
+    pub attributes: Vec<(String, String)>,
+    pub gift_message: Option<GiftMessage>,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn nft_token_detail(&self, token_id: TokenId) -> Option<TokenDetail> {
+        self.internal_token_detail(token_id)
+    }
+
+    /// nft_token_detail of the tokens of an owner, paginated like nft_tokens_for_owner
+    pub fn nft_tokens_detail_for_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<TokenDetail> {
+        self.tokens
+            .nft_tokens_for_owner(account_id, from_index, limit)
+            .into_iter()
+            .filter_map(|token| self.internal_token_detail(token.token_id))
+            .collect()
+    }
+
+    fn internal_token_detail(&self, token_id: TokenId) -> Option<TokenDetail> {
+        let token = self.nft_token(token_id.clone())?;
+        let mint_receipt = self.purchases.get(&token.owner_id).and_then(|purchases| {
+            purchases
+                .iter()
+                .find(|purchase| purchase.token_id == token_id)
+        });
+        Some(TokenDetail {
+            royalties: self.internal_token_royalties(&token_id),
+            mint_receipt,
+            escrowed_sale: self.escrowed_sales.get(&token_id),
+            region_id: self.wrapped_rocks.get(&token_id),
+            metadata_frozen_at: self.frozen_metadata.get(&token_id).map(U64),
+            attributes: self.rock_attributes.get(&token_id).unwrap_or_default(),
+            gift_message: self.gift_messages.get(&token_id),
+            token,
+        })
+    }
+}
@@ -0,0 +1,83 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner hides a zone to prepare and price it privately (e.g. for a surprise drop)
+    /// or makes it visible again. Rocks of a hidden zone are only minted by the metaverse owner
+    /// and get_zones and get_zone_summaries leave the zone out
+    #[payable]
+    pub fn set_zone_visibility(&mut self, metaverse_id: String, zone_index: u16, visible: bool) {
+        self.assert_metaverse_owner(&metaverse_id);
+        self.assert_zone_exist(&metaverse_id, zone_index);
+        if self.internal_is_zone_visible(&metaverse_id, zone_index) == visible {
+            return;
+        }
+        // only hidden zones are stored, a zone without an entry is visible
+        let initial_storage_usage = env::storage_usage();
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        if visible {
+            self.zone_visibility.remove(&zone_key);
+        } else {
+            self.zone_visibility.insert(&zone_key, &false);
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+
+        let zone_visibility_log: EventLog = EventLog {
+            standard: "imo_zone_visibility".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::ZoneVisibilityUpdate(vec![ZoneVisibilityLog {
+                metaverse_id,
+                zone_index,
+                visible,
+                memo: None,
+            }]),
+        };
+        zone_visibility_log.emit();
+    }
+
+    /// zones of a metaverse by zone_index, hidden zones are only listed with include_hidden
+    pub fn get_zones(&self, metaverse_id: String, include_hidden: Option<bool>) -> Vec<Zone> {
+        let include_hidden = include_hidden.unwrap_or(false);
+        let mut zones: Vec<Zone> = self
+            .assert_metaverse_exist(&metaverse_id)
+            .zones
+            .into_values()
+            .filter(|zone| {
+                include_hidden || self.internal_is_zone_visible(&metaverse_id, zone.zone_index)
+            })
+            .collect();
+        zones.sort_by_key(|zone| zone.zone_index);
+        zones
+    }
+
+    pub fn is_zone_visible(&self, metaverse_id: String, zone_index: u16) -> bool {
+        self.assert_zone_exist(&metaverse_id, zone_index);
+        self.internal_is_zone_visible(&metaverse_id, zone_index)
+    }
+
+    pub(crate) fn internal_is_zone_visible(&self, metaverse_id: &String, zone_index: u16) -> bool {
+        self.zone_visibility
+            .get(&gen_zone_key(metaverse_id, zone_index))
+            .unwrap_or(true)
+    }
+
+    // rocks of a hidden zone are minted by the metaverse owner only. The operator (import) and
+    // this contract (callbacks of a checked call) are let through
+    pub(crate) fn assert_zone_visible(&self, metaverse_id: &String, zone: &Zone) {
+        if self.internal_is_zone_visible(metaverse_id, zone.zone_index) {
+            return;
+        }
+        let caller_id = env::predecessor_account_id();
+        require!(
+            self.metaverse_owners.get(metaverse_id) == Some(caller_id.clone())
+                || caller_id == self.tokens.owner_id
+                || caller_id == env::current_account_id(),
+            RoveError::ZoneHidden.as_str()
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::*;
+
+// rocks of all zones of a metaverse when the operator did not set its cap
+pub const DEFAULT_METAVERSE_ROCK_CAP: u128 = 10_000;
+
+#[near_bindgen]
+impl Contract {
+    /// operator sets (or resets to the default with None) the maximum rocks of all zones
+    /// of a metaverse, it bounds extend_zone
+    #[payable]
+    pub fn set_metaverse_rock_cap(&mut self, metaverse_id: String, rock_cap: Option<U128>) {
+        self.assert_operator_only();
+        self.assert_metaverse_exist(&metaverse_id);
+        match rock_cap {
+            Some(rock_cap) => {
+                self.metaverse_rock_caps.insert(&metaverse_id, &rock_cap.0);
+            }
+            None => {
+                self.metaverse_rock_caps.remove(&metaverse_id);
+            }
+        }
+    }
+
+    pub fn get_metaverse_rock_cap(&self, metaverse_id: String) -> U128 {
+        U128(self.internal_metaverse_rock_cap(&metaverse_id))
+    }
+
+    pub(crate) fn internal_metaverse_rock_cap(&self, metaverse_id: &String) -> u128 {
+        self.metaverse_rock_caps
+            .get(metaverse_id)
+            .unwrap_or(DEFAULT_METAVERSE_ROCK_CAP)
+    }
+
+    /// metaverse owner adds `additional_rocks` after the last rock of a sold out holder zone,
+    /// paying init_imo_fee per rock plus storage, returns the extended zone
+    #[payable]
+    pub fn extend_zone(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        additional_rocks: U128,
+    ) -> Zone {
+        self.assert_metaverse_owner(&metaverse_id);
+        let metaverse = self.assert_metaverse_exist(&metaverse_id);
+        let mut zone = metaverse
+            .zones
+            .get(&zone_index)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str(RoveError::ZoneNotFound.as_str()));
+        require!(
+            zone.mint_policy == MintPolicy::HolderGated,
+            RoveError::InvalidZoneType.detail("only holder_gated zones can be extended")
+        );
+        require!(
+            additional_rocks.0 > 0,
+            RoveError::InvalidArgument.detail("additional_rocks must be > 0")
+        );
+        require!(
+            self.internal_zone_minted(&metaverse_id, zone_index) >= zone_rock_size(&zone),
+            RoveError::ZoneNotSoldOut.as_str()
+        );
+
+        let rock_index_from = zone.rock_index_to + 1;
+        let rock_index_to = zone
+            .rock_index_to
+            .checked_add(additional_rocks.0)
+            .unwrap_or_else(|| env::panic_str(RoveError::InvalidRockIndex.as_str()));
+        require!(
+            metaverse.zones.values().all(|other_zone| {
+                other_zone.zone_index == zone_index
+                    || rock_index_to < other_zone.rock_index_from
+                    || rock_index_from > other_zone.rock_index_to
+            }),
+            RoveError::InvalidZone.detail("extended rocks overlap another zone")
+        );
+        self.assert_zone_rock_limit(zone_rock_size(&zone) + additional_rocks.0);
+        let metaverse_rock_size: u128 = metaverse.zones.values().map(zone_rock_size).sum();
+        let rock_cap = self.internal_metaverse_rock_cap(&metaverse_id);
+        require!(
+            metaverse_rock_size + additional_rocks.0 <= rock_cap,
+            RoveError::RockCapReached.detail(format!(
+                "metaverse has {} rocks, the cap is {}",
+                metaverse_rock_size, rock_cap
+            ))
+        );
+
+        let total_extend_fee = self.init_imo_fee * additional_rocks.0;
+        let attached_deposit = env::attached_deposit();
+        let initial_storage_usage = env::storage_usage();
+        zone.rock_index_to = rock_index_to;
+        let mut zones = metaverse.zones;
+        zones.insert(zone_index, zone.clone());
+        self.metaverses.insert(&metaverse_id, &Metaverse { zones });
+
+        // storage of the zone is charged to the caller on top of the IMO fee
+        let storage_cost = env::storage_byte_cost()
+            * Balance::from(env::storage_usage().saturating_sub(initial_storage_usage));
+        require!(
+            total_extend_fee + storage_cost <= attached_deposit,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR to extend zone ({} yoctoNEAR IMO fee + {} yoctoNEAR storage)",
+                total_extend_fee + storage_cost,
+                total_extend_fee,
+                storage_cost
+            ))
+        );
+        if total_extend_fee > 0 {
+            self.internal_pay_treasury(total_extend_fee);
+        }
+        let refund = attached_deposit - total_extend_fee - storage_cost;
+        if refund > 0 {
+            self.internal_payout(env::predecessor_account_id(), refund);
+        }
+
+        let zone_extended_log: EventLog = EventLog {
+            standard: "nft_collection_holder_imo_extend_zone".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::ZoneExtended(vec![ZoneExtendedLog {
+                metaverse_id,
+                zone_index,
+                rock_index_from,
+                rock_index_to,
+                additional_rocks: additional_rocks.0,
+                memo: None,
+            }]),
+        };
+        zone_extended_log.emit();
+
+        zone
+    }
+}
@@ -0,0 +1,74 @@
+use crate::*;
+
+// what currently restricts minting the rocks of a zone
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneSalePhase {
+    // every rock of the zone is minted
+    SoldOut,
+    // rocks are claimed with a proof of the zone airdrop (rocks contract only)
+    Airdrop,
+    // a raffle is open, nothing is minted until it is drawn (rocks contract only)
+    Raffle,
+    // allowlisted accounts also mint with mint_rock_with_proof at the allowlist price
+    Allowlist,
+    // rocks are minted as set by the zone mint_policy
+    Open,
+}
+
+// one zone of get_zone_summaries
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneSummary {
+    pub zone_index: u16,
+    pub mint_policy: MintPolicy,
+    pub capacity: U128,
+    pub minted: U128,
+    pub remaining: U128,
+    pub price: U128,
+    // set when the zone is priced in USD through the price oracle
+    pub price_usd_cents: Option<u64>,
+    pub phase: ZoneSalePhase,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// every visible zone of a metaverse by zone_index with its minted count, price and sale
+    /// phase, e.g. for the legend of the world map
+    pub fn get_zone_summaries(&self, metaverse_id: String) -> Vec<ZoneSummary> {
+        let metaverse = self.assert_metaverse_exist(&metaverse_id);
+        let mut zones: Vec<Zone> = metaverse
+            .zones
+            .into_values()
+            .filter(|zone| self.internal_is_zone_visible(&metaverse_id, zone.zone_index))
+            .collect();
+        zones.sort_by_key(|zone| zone.zone_index);
+        zones
+            .into_iter()
+            .map(|zone| {
+                let zone_key = gen_zone_key(&metaverse_id, zone.zone_index);
+                let capacity = zone_rock_size(&zone);
+                let minted = self.internal_zone_minted(&metaverse_id, zone.zone_index);
+                let remaining = capacity.saturating_sub(minted);
+                let phase = if remaining == 0 {
+                    ZoneSalePhase::SoldOut
+                } else if self.allowlists.get(&zone_key).is_some() {
+                    ZoneSalePhase::Allowlist
+                } else {
+                    ZoneSalePhase::Open
+                };
+                ZoneSummary {
+                    zone_index: zone.zone_index,
+                    mint_policy: zone.mint_policy,
+                    capacity: U128(capacity),
+                    minted: U128(minted),
+                    remaining: U128(remaining),
+                    price: zone.price,
+                    price_usd_cents: self.zone_usd_prices.get(&zone_key),
+                    phase,
+                }
+            })
+            .collect()
+    }
+}
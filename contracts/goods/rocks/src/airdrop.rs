@@ -0,0 +1,211 @@
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::CryptoHash;
+
+use crate::*;
+
+// Merkle airdrop of a public zone, leaves are sha256("{account_id}:{rock_index}")
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneAirdrop {
+    pub airdrop_root: Base58CryptoHash,
+    pub claimed: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner allocates rocks of a public zone to accounts off-chain and sets (or removes
+    /// with None) the Merkle root of the allocation. While it is set the zone can only be minted
+    /// through claim_airdrop
+    #[payable]
+    pub fn set_airdrop_root(
+        &mut self,
+        metaverse_id: String,
+        root: Option<Base58CryptoHash>,
+        zone_index: u16,
+    ) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(
+            zone.mint_policy == MintPolicy::PublicFixedPrice,
+            RoveError::InvalidZoneType.detail("only public_fixed_price zones can be airdropped")
+        );
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        require!(
+            self.raffles.get(&zone_key).is_none(),
+            RoveError::RaffleActive.as_str()
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        match root {
+            Some(airdrop_root) => {
+                let claimed = self
+                    .airdrops
+                    .get(&zone_key)
+                    .map(|airdrop| airdrop.claimed)
+                    .unwrap_or(0);
+                self.airdrops.insert(
+                    &zone_key,
+                    &ZoneAirdrop {
+                        airdrop_root,
+                        claimed,
+                    },
+                );
+            }
+            None => {
+                self.airdrops
+                    .remove(&zone_key)
+                    .unwrap_or_else(|| env::panic_str(RoveError::AirdropNotFound.as_str()));
+            }
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+
+        emit_airdrop_log(
+            EventLogVariant::AirdropRootSet,
+            &metaverse_id,
+            zone_index,
+            &env::predecessor_account_id(),
+            root.map(|root| String::from(&root)),
+            None,
+        );
+    }
+
+    pub fn get_zone_airdrop(&self, metaverse_id: String, zone_index: u16) -> Option<ZoneAirdrop> {
+        self.airdrops.get(&gen_zone_key(&metaverse_id, zone_index))
+    }
+
+    pub fn is_airdrop_claimed(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        account_id: AccountId,
+    ) -> bool {
+        match self.get_zone_airdrop(metaverse_id, zone_index) {
+            Some(airdrop) => self.airdrop_claims.contains(&gen_airdrop_claim_key(
+                &airdrop.airdrop_root,
+                &account_id,
+                rock_index,
+            )),
+            None => false,
+        }
+    }
+
+    /// recipient mints the rock allocated to them, proof is the list of sibling hashes from the
+    /// leaf to the root (sorted pairs). No deposit is needed, the contract pays the storage
+    pub fn claim_airdrop(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        proof: Vec<Base58CryptoHash>,
+        token_metadata: TokenMetadata,
+    ) -> TokenId {
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        let mut airdrop = self
+            .airdrops
+            .get(&zone_key)
+            .unwrap_or_else(|| env::panic_str(RoveError::AirdropNotFound.as_str()));
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        self.assert_zone_visible(&metaverse_id, &zone);
+        require!(
+            zone.rock_index_from > 0 && zone.rock_index_to > 0,
+            RoveError::InvalidRockIndex.as_str()
+        );
+        require!(
+            zone.rock_index_from <= rock_index && rock_index <= zone.rock_index_to,
+            RoveError::InvalidRockIndex.as_str()
+        );
+        let account_id = env::predecessor_account_id();
+        let leaf = sha256_hash(format!("{}:{}", account_id, rock_index).as_bytes());
+        let proof: Vec<CryptoHash> = proof.into_iter().map(CryptoHash::from).collect();
+        require!(
+            verify_merkle_proof(leaf, &proof, &CryptoHash::from(airdrop.airdrop_root)),
+            RoveError::NotInAirdrop.as_str()
+        );
+        let claim_key = gen_airdrop_claim_key(&airdrop.airdrop_root, &account_id, rock_index);
+        require!(
+            self.airdrop_claims.insert(&claim_key),
+            RoveError::AirdropAlreadyClaimed.as_str()
+        );
+        let token_id = rock_token_id(&metaverse_id, zone_index, rock_index);
+        require!(
+            !self
+                .tokens_minted
+                .get(&metaverse_id)
+                .unwrap()
+                .contains_key(&token_id),
+            RoveError::TokenAlreadyExists.as_str()
+        );
+        airdrop.claimed += 1;
+        self.airdrops.insert(&zone_key, &airdrop);
+
+        self._mint(
+            metaverse_id.clone(),
+            zone_index,
+            token_id.clone(),
+            account_id.clone(),
+            token_metadata,
+            U128(0),
+            0,
+        );
+
+        emit_airdrop_log(
+            EventLogVariant::AirdropClaimed,
+            &metaverse_id,
+            zone_index,
+            &account_id,
+            None,
+            Some(rock_index),
+        );
+
+        token_id
+    }
+
+    // an airdropped zone can only be minted through claim_airdrop until its root is removed
+    pub(crate) fn assert_zone_not_airdropped(&self, metaverse_id: &String, zone_index: u16) {
+        require!(
+            self.airdrops
+                .get(&gen_zone_key(metaverse_id, zone_index))
+                .is_none(),
+            RoveError::AirdropActive.as_str()
+        );
+    }
+}
+
+// claims are kept per root so a rock burnt after its claim is not claimable again with it
+fn gen_airdrop_claim_key(
+    airdrop_root: &Base58CryptoHash,
+    account_id: &AccountId,
+    rock_index: u128,
+) -> CryptoHash {
+    let leaf = sha256_hash(format!("{}:{}", account_id, rock_index).as_bytes());
+    sha256_hash(&[CryptoHash::from(*airdrop_root), leaf].concat())
+}
+
+fn emit_airdrop_log(
+    variant: fn(Vec<AirdropLog>) -> EventLogVariant,
+    metaverse_id: &str,
+    zone_index: u16,
+    account_id: &AccountId,
+    airdrop_root: Option<String>,
+    rock_index: Option<u128>,
+) {
+    let airdrop_log: EventLog = EventLog {
+        standard: "airdrop".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![AirdropLog {
+            metaverse_id: metaverse_id.to_string(),
+            zone_index,
+            account_id: account_id.to_string(),
+            airdrop_root,
+            rock_index,
+            memo: None,
+        }]),
+    };
+    airdrop_log.emit();
+}
@@ -0,0 +1,68 @@
+use crate::*;
+
+// version of the public method set, bumped when methods are deprecated or removed
+pub const API_VERSION: u32 = 2;
+
+// deprecated methods still served as shims with their replacement, every call logs
+// DeprecatedCall until they are removed in a later upgrade
+pub const DEPRECATED_METHODS: [(&str, &str); 2] = [
+    ("get_zone_info", "get_zones"),
+    ("update_init_imo_fee", "change_init_imo_fee"),
+];
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DeprecatedMethod {
+    pub method: String,
+    pub replacement: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ApiVersion {
+    pub version: u32,
+    pub deprecated_methods: Vec<DeprecatedMethod>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// api version for clients to negotiate with and the deprecated methods they should move
+    /// away from
+    pub fn api_version(&self) -> ApiVersion {
+        ApiVersion {
+            version: API_VERSION,
+            deprecated_methods: DEPRECATED_METHODS
+                .iter()
+                .map(|(method, replacement)| DeprecatedMethod {
+                    method: method.to_string(),
+                    replacement: replacement.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+// log DeprecatedCall for a shim of DEPRECATED_METHODS. View calls can neither read the caller
+// nor write the event nonce, a deprecated view logs without both
+pub(crate) fn log_deprecated_call(method: &str, caller_id: Option<AccountId>) {
+    let replacement = DEPRECATED_METHODS
+        .iter()
+        .find(|(deprecated, _)| *deprecated == method)
+        .map(|(_, replacement)| replacement.to_string())
+        .unwrap();
+    let is_view = caller_id.is_none();
+    let deprecated_call_log: EventLog = EventLog {
+        standard: "deprecated_call".to_string(),
+        version: "1.0.0".to_string(),
+        event: EventLogVariant::DeprecatedCall(vec![DeprecatedCallLog {
+            method: method.to_string(),
+            replacement,
+            caller_id: caller_id.map(|caller_id| caller_id.to_string()),
+        }]),
+    };
+    if is_view {
+        env::log_str(&deprecated_call_log.to_string());
+    } else {
+        deprecated_call_log.emit();
+    }
+}
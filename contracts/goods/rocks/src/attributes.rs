@@ -0,0 +1,122 @@
+use crate::*;
+
+pub const MAX_ROCK_ATTRIBUTES: usize = 32;
+pub const MAX_ATTRIBUTE_KEY_LEN: usize = 64;
+pub const MAX_ATTRIBUTE_VALUE_LEN: usize = 256;
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner registers a game contract allowed to write rock attributes of the metaverse
+    #[payable]
+    pub fn add_attribute_writer(&mut self, metaverse_id: String, writer_id: AccountId) {
+        self.assert_metaverse_owner(&metaverse_id);
+
+        let initial_storage_usage = env::storage_usage();
+        let mut writers = self
+            .attribute_writers
+            .get(&metaverse_id)
+            .unwrap_or_default();
+        if !writers.contains(&writer_id) {
+            writers.push(writer_id);
+            self.attribute_writers.insert(&metaverse_id, &writers);
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    #[payable]
+    pub fn remove_attribute_writer(&mut self, metaverse_id: String, writer_id: AccountId) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let mut writers = self
+            .attribute_writers
+            .get(&metaverse_id)
+            .unwrap_or_default();
+        writers.retain(|writer| *writer != writer_id);
+        self.attribute_writers.insert(&metaverse_id, &writers);
+    }
+
+    pub fn get_attribute_writers(&self, metaverse_id: String) -> Vec<AccountId> {
+        self.attribute_writers
+            .get(&metaverse_id)
+            .unwrap_or_default()
+    }
+
+    /// set attributes of a rock, an empty value removes the attribute,
+    /// callable by the metaverse owner or a registered attribute writer
+    #[payable]
+    pub fn set_rock_attributes(&mut self, token_id: TokenId, attributes: Vec<(String, String)>) {
+        assert_at_least_one_yocto();
+        require!(
+            self.tokens.owner_by_id.get(&token_id).is_some(),
+            RoveError::TokenNotFound.as_str()
+        );
+        let metaverse_id = metaverse_id_of_token(&token_id);
+        let writer_id = env::predecessor_account_id();
+        let is_metaverse_owner =
+            self.metaverse_owners.get(&metaverse_id) == Some(writer_id.clone());
+        require!(
+            is_metaverse_owner
+                || self
+                    .attribute_writers
+                    .get(&metaverse_id)
+                    .unwrap_or_default()
+                    .contains(&writer_id),
+            RoveError::Unauthorized.as_str()
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let mut rock_attributes = self.rock_attributes.get(&token_id).unwrap_or_default();
+        for (key, value) in attributes {
+            require!(
+                !key.is_empty() && key.len() <= MAX_ATTRIBUTE_KEY_LEN,
+                RoveError::InvalidArgument.detail("attribute key is invalid")
+            );
+            require!(
+                value.len() <= MAX_ATTRIBUTE_VALUE_LEN,
+                RoveError::ValueTooLong.detail(format!("attribute {} is too long", key))
+            );
+            rock_attributes.retain(|(attribute_key, _)| *attribute_key != key);
+            if !value.is_empty() {
+                rock_attributes.push((key, value));
+            }
+        }
+        require!(
+            rock_attributes.len() <= MAX_ROCK_ATTRIBUTES,
+            RoveError::TooManyItems.detail(format!(
+                "rock can not have more than {} attributes",
+                MAX_ROCK_ATTRIBUTES
+            ))
+        );
+        if rock_attributes.is_empty() {
+            self.rock_attributes.remove(&token_id);
+        } else {
+            self.rock_attributes.insert(&token_id, &rock_attributes);
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                writer_id.clone(),
+            );
+        }
+
+        let rock_attributes_log: EventLog = EventLog {
+            standard: "rock_attributes".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::RockAttributesUpdate(vec![RockAttributesLog {
+                token_id,
+                writer_id: writer_id.to_string(),
+                memo: None,
+            }]),
+        };
+
+        rock_attributes_log.emit();
+    }
+
+    pub fn get_rock_attributes(&self, token_id: TokenId) -> Vec<(String, String)> {
+        self.rock_attributes.get(&token_id).unwrap_or_default()
+    }
+}
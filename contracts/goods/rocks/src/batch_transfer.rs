@@ -0,0 +1,79 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// transfer many rocks in one call, every transfer must be allowed or none is applied
+    #[payable]
+    pub fn nft_batch_transfer(
+        &mut self,
+        transfers: Vec<(TokenId, AccountId)>,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+        require!(
+            !transfers.is_empty(),
+            RoveError::EmptyBatch.detail("transfers is empty")
+        );
+        let sender_id = env::predecessor_account_id();
+
+        let mut nft_transfer_logs: Vec<NftTransferLog> = vec![];
+        let mut activities: Vec<AccountActivityLog> = vec![];
+        for (token_id, receiver_id) in transfers {
+            let owner_id = self
+                .tokens
+                .owner_by_id
+                .get(&token_id)
+                .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+            let approved_account_ids = self
+                .tokens
+                .approvals_by_id
+                .as_mut()
+                .and_then(|by_id| by_id.remove(&token_id));
+            let authorized_id = if sender_id != owner_id {
+                require!(
+                    approved_account_ids
+                        .as_ref()
+                        .map(|approved| approved.contains_key(&sender_id))
+                        .unwrap_or(false),
+                    RoveError::NotApproved.detail(format!("Sender not approved for {}", token_id))
+                );
+                self.assert_approval_not_expired(&token_id, &sender_id);
+                Some(sender_id.to_string())
+            } else {
+                None
+            };
+            require!(owner_id != receiver_id, RoveError::SameOwner.as_str());
+            self.assert_transfer_allowed(&token_id, &receiver_id);
+
+            self.internal_release_escrow(&token_id);
+            self.internal_transfer_token(&token_id, &owner_id, &receiver_id);
+            if let Some(approved_account_ids) = approved_account_ids {
+                refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+            }
+
+            activities.push(AccountActivityLog::new(
+                &owner_id,
+                ActivityAction::Transfer,
+                &token_id,
+                Some(&receiver_id),
+                None,
+            ));
+            nft_transfer_logs.push(NftTransferLog {
+                authorized_id,
+                old_owner_id: owner_id.to_string(),
+                new_owner_id: receiver_id.to_string(),
+                token_ids: vec![token_id],
+                memo: memo.clone(),
+            });
+        }
+
+        let nft_transfer_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftTransfer(nft_transfer_logs),
+        };
+
+        nft_transfer_log.emit();
+        emit_account_activity(activities);
+    }
+}
@@ -0,0 +1,337 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+// "first parcel free" campaign of a public zone, the operator budget pays the zone price of
+// the rocks new accounts claim
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Campaign {
+    pub budget: U128,
+    pub spent: U128,
+    // rocks one account may claim
+    pub per_account: u32,
+    pub claimed: u64,
+}
+
+// get_campaign
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CampaignStats {
+    pub budget: U128,
+    pub spent: U128,
+    pub remaining: U128,
+    pub per_account: u32,
+    pub claimed: u64,
+    // rocks the remaining budget pays at the current zone price
+    pub claimable: u64,
+}
+
+// anti-sybil checks of campaign claims. The chain does not expose when an account was
+// created, its age counts from register_for_campaigns
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CampaignConfig {
+    // implicit (64 hex) accounts cost nothing to create
+    pub reject_implicit_accounts: bool,
+    // 0: claims do not need a registration
+    pub min_account_age_sec: u32,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator funds a campaign of a public zone with `budget` (attached with the storage of
+    /// the campaign, the rest is refunded). Accounts without rocks mint `per_account` (default
+    /// 1) rocks of the zone for free with claim_campaign_rock until the budget is spent
+    #[payable]
+    pub fn create_campaign(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        budget: U128,
+        per_account: Option<u32>,
+    ) {
+        self.assert_operator_only();
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(
+            zone.mint_policy == MintPolicy::PublicFixedPrice,
+            RoveError::InvalidZoneType.detail("only public_fixed_price zones have campaigns")
+        );
+        require!(zone.price.0 > 0, RoveError::MissingZonePrice.as_str());
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        require!(
+            self.zone_usd_prices.get(&zone_key).is_none(),
+            RoveError::InvalidZoneType.detail("zones priced in USD can not have campaigns")
+        );
+        require!(
+            self.campaigns.get(&zone_key).is_none(),
+            RoveError::InvalidArgument.detail("zone already has a campaign")
+        );
+        let per_account = per_account.unwrap_or(1);
+        require!(
+            budget.0 > 0 && per_account > 0,
+            RoveError::InvalidArgument.detail("budget and per_account must be > 0")
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        self.campaigns.insert(
+            &zone_key,
+            &Campaign {
+                budget,
+                spent: U128(0),
+                per_account,
+                claimed: 0,
+            },
+        );
+        let storage_cost =
+            env::storage_byte_cost() * Balance::from(env::storage_usage() - initial_storage_usage);
+        let attached_deposit = env::attached_deposit();
+        require!(
+            budget.0 + storage_cost <= attached_deposit,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR ({} yoctoNEAR budget + {} yoctoNEAR storage)",
+                budget.0 + storage_cost,
+                budget.0,
+                storage_cost
+            ))
+        );
+        let refund = attached_deposit - budget.0 - storage_cost;
+        if refund > 0 {
+            self.internal_payout(env::predecessor_account_id(), refund);
+        }
+
+        emit_campaign_log(
+            EventLogVariant::CampaignCreated,
+            &metaverse_id,
+            zone_index,
+            &env::predecessor_account_id(),
+            budget.0,
+            None,
+        );
+    }
+
+    /// operator ends a campaign, the unspent budget is paid back to the operator
+    #[payable]
+    pub fn close_campaign(&mut self, metaverse_id: String, zone_index: u16) {
+        self.assert_operator_only();
+        let campaign = self
+            .campaigns
+            .remove(&gen_zone_key(&metaverse_id, zone_index))
+            .unwrap_or_else(|| env::panic_str(RoveError::CampaignNotFound.as_str()));
+        let unspent = campaign.budget.0 - campaign.spent.0;
+        if unspent > 0 {
+            self.internal_payout(env::predecessor_account_id(), unspent);
+        }
+
+        emit_campaign_log(
+            EventLogVariant::CampaignClosed,
+            &metaverse_id,
+            zone_index,
+            &env::predecessor_account_id(),
+            unspent,
+            None,
+        );
+    }
+
+    #[payable]
+    pub fn set_campaign_config(&mut self, config: CampaignConfig) {
+        self.assert_operator_only();
+        self.campaign_config = config;
+    }
+
+    pub fn get_campaign_config(&self) -> CampaignConfig {
+        self.campaign_config.clone()
+    }
+
+    /// account starts the min_account_age_sec wait of campaign claims, the storage of the
+    /// registration must be attached and the unused deposit is refunded
+    #[payable]
+    pub fn register_for_campaigns(&mut self) -> U64 {
+        let account_id = env::predecessor_account_id();
+        if let Some(registered_at) = self.campaign_registrations.get(&account_id) {
+            return U64(registered_at);
+        }
+        let initial_storage_usage = env::storage_usage();
+        self.campaign_registrations
+            .insert(&account_id, &env::block_timestamp());
+        refund_deposit_to_account(env::storage_usage() - initial_storage_usage, account_id);
+        U64(env::block_timestamp())
+    }
+
+    pub fn get_campaign_registration(&self, account_id: AccountId) -> Option<U64> {
+        self.campaign_registrations.get(&account_id).map(U64)
+    }
+
+    pub fn get_campaign(&self, metaverse_id: String, zone_index: u16) -> Option<CampaignStats> {
+        let campaign = self
+            .campaigns
+            .get(&gen_zone_key(&metaverse_id, zone_index))?;
+        let remaining = campaign.budget.0 - campaign.spent.0;
+        let zone_price = self
+            .assert_zone_exist(&metaverse_id, zone_index)
+            .price
+            .0
+            .max(1);
+        Some(CampaignStats {
+            budget: campaign.budget,
+            spent: campaign.spent,
+            remaining: U128(remaining),
+            per_account: campaign.per_account,
+            claimed: campaign.claimed,
+            claimable: (remaining / zone_price) as u64,
+        })
+    }
+
+    pub fn get_campaign_claims(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        account_id: AccountId,
+    ) -> u32 {
+        self.campaign_claims
+            .get(&gen_campaign_claim_key(
+                &metaverse_id,
+                zone_index,
+                &account_id,
+            ))
+            .unwrap_or_default()
+    }
+
+    /// new account mints a rock of a campaign zone, the campaign budget pays the zone price
+    pub fn claim_campaign_rock(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        token_metadata: TokenMetadata,
+    ) -> TokenId {
+        let account_id = env::predecessor_account_id();
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        let mut campaign = self
+            .campaigns
+            .get(&zone_key)
+            .unwrap_or_else(|| env::panic_str(RoveError::CampaignNotFound.as_str()));
+        let claim_key = gen_campaign_claim_key(&metaverse_id, zone_index, &account_id);
+        let claims = self.campaign_claims.get(&claim_key).unwrap_or_default();
+        require!(
+            claims < campaign.per_account,
+            RoveError::NotCampaignEligible.detail("campaign rocks of the account are claimed")
+        );
+        self.assert_campaign_eligible(&account_id, claims);
+        // a refund would pay the campaign budget to the account
+        require!(
+            self.escrow_windows.get(&metaverse_id).is_none(),
+            RoveError::InvalidArgument.detail("metaverse has a refund window")
+        );
+        let (zone, token_id) = self.assert_rock_mintable(&metaverse_id, zone_index, rock_index);
+        require!(
+            campaign.spent.0 + zone.price.0 <= campaign.budget.0,
+            RoveError::CampaignExhausted.as_str()
+        );
+
+        campaign.spent = U128(campaign.spent.0 + zone.price.0);
+        campaign.claimed += 1;
+        self.campaigns.insert(&zone_key, &campaign);
+        self.campaign_claims.insert(&claim_key, &(claims + 1));
+
+        self._mint(
+            metaverse_id.clone(),
+            zone_index,
+            token_id.clone(),
+            account_id.clone(),
+            token_metadata,
+            zone.price,
+            zone.price.0,
+        );
+
+        emit_campaign_log(
+            EventLogVariant::CampaignClaimed,
+            &metaverse_id,
+            zone_index,
+            &account_id,
+            zone.price.0,
+            Some(rock_index),
+        );
+
+        token_id
+    }
+
+    // brand-new accounts only: no rock bought, minted or received outside the `claims` rocks of
+    // the campaign, then the campaign_config checks
+    fn assert_campaign_eligible(&self, account_id: &AccountId, claims: u32) {
+        let owned_rocks = self
+            .tokens
+            .tokens_per_owner
+            .as_ref()
+            .and_then(|tokens_per_owner| tokens_per_owner.get(account_id))
+            .map(|token_ids| token_ids.len())
+            .unwrap_or(0);
+        let purchases = self
+            .purchases
+            .get(account_id)
+            .map(|purchases| purchases.len())
+            .unwrap_or(0);
+        require!(
+            owned_rocks <= claims as u64 && purchases <= claims as u64,
+            RoveError::NotCampaignEligible.detail("account already has rocks")
+        );
+
+        let is_implicit_account = account_id.as_str().len() == 64
+            && account_id.as_str().chars().all(|c| c.is_ascii_hexdigit());
+        require!(
+            !(self.campaign_config.reject_implicit_accounts && is_implicit_account),
+            RoveError::NotCampaignEligible.detail("implicit accounts can not claim")
+        );
+
+        let min_account_age_sec = self.campaign_config.min_account_age_sec;
+        if min_account_age_sec > 0 {
+            let registered_at = self
+                .campaign_registrations
+                .get(account_id)
+                .unwrap_or_else(|| {
+                    env::panic_str(
+                        &RoveError::NotCampaignEligible.detail("call register_for_campaigns"),
+                    )
+                });
+            require!(
+                env::block_timestamp() - registered_at
+                    >= min_account_age_sec as u64 * 1_000_000_000,
+                RoveError::NotCampaignEligible.detail(format!(
+                    "claims open {} seconds after register_for_campaigns",
+                    min_account_age_sec
+                ))
+            );
+        }
+    }
+}
+
+fn gen_campaign_claim_key(
+    metaverse_id: &String,
+    zone_index: u16,
+    account_id: &AccountId,
+) -> String {
+    format!("{}:{}", gen_zone_key(metaverse_id, zone_index), account_id)
+}
+
+fn emit_campaign_log(
+    variant: fn(Vec<CampaignLog>) -> EventLogVariant,
+    metaverse_id: &str,
+    zone_index: u16,
+    account_id: &AccountId,
+    amount: Balance,
+    rock_index: Option<u128>,
+) {
+    let campaign_log: EventLog = EventLog {
+        standard: "campaign".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![CampaignLog {
+            metaverse_id: metaverse_id.to_string(),
+            zone_index,
+            account_id: account_id.to_string(),
+            amount: U128(amount),
+            rock_index,
+            memo: None,
+        }]),
+    };
+    campaign_log.emit();
+}
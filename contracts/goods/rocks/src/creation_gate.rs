@@ -0,0 +1,138 @@
+use near_sdk::{ext_contract, Gas, PromiseResult};
+
+use crate::*;
+
+pub const GAS_FOR_CREATION_GATE_CHECK: Gas = Gas(10_000_000_000_000);
+pub const GAS_FOR_RESOLVE_INIT_METAVERSE: Gas = Gas(50_000_000_000_000);
+
+// what the creator of a metaverse must hold when the creation gate is set
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum CreationGate {
+    // at least one token of a NEP-171 collection (nft_supply_for_owner)
+    Nft {
+        contract_id: AccountId,
+    },
+    // at least min_balance of a NEP-141 token (ft_balance_of)
+    Ft {
+        contract_id: AccountId,
+        min_balance: U128,
+    },
+}
+
+#[ext_contract(ext_gate_nft)]
+pub trait GateNft {
+    fn nft_supply_for_owner(&self, account_id: AccountId) -> U128;
+}
+
+#[ext_contract(ext_gate_ft)]
+pub trait GateFt {
+    fn ft_balance_of(&self, account_id: AccountId) -> U128;
+}
+
+#[ext_contract(ext_init_metaverse_resolver)]
+pub trait InitMetaverseResolver {
+    fn resolve_init_metaverse(
+        &mut self,
+        metaverse_id: String,
+        zone3: Zone,
+        creator_id: AccountId,
+        attached_deposit: U128,
+    ) -> Option<Zone>;
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator requires (or stops requiring with None) metaverse creators to hold a pass
+    /// nft or a minimum ft balance
+    #[payable]
+    pub fn set_creation_gate(&mut self, config: Option<CreationGate>) {
+        self.assert_operator_only();
+        self.creation_gate = config;
+    }
+
+    pub fn get_creation_gate(&self) -> Option<CreationGate> {
+        self.creation_gate.clone()
+    }
+
+    // This is callback function of init_metaverse with a creation gate (private, CAN NOT CALL DIRECTLY)
+    #[private]
+    pub fn resolve_init_metaverse(
+        &mut self,
+        metaverse_id: String,
+        zone3: Zone,
+        creator_id: AccountId,
+        attached_deposit: U128,
+    ) -> Option<Zone> {
+        require!(
+            env::promise_results_count() == 1,
+            RoveError::NotCallback.as_str()
+        );
+        let attached_deposit = attached_deposit.0;
+        let is_holder = match env::promise_result(0) {
+            PromiseResult::Successful(result) => self.internal_is_creation_gate_holder(&result),
+            _ => false,
+        };
+        // the deposit is already held by this contract, give it back instead of panicking
+        let total_init_imo_fee = self.init_imo_fee * zone_rock_size(&zone3);
+        if !is_holder
+            || self.metaverses.get(&metaverse_id).is_some()
+            || self.internal_init_metaverse_deposit(total_init_imo_fee) > attached_deposit
+        {
+            self.internal_payout(creator_id, attached_deposit);
+            return None;
+        }
+
+        self.internal_init_metaverse(metaverse_id, zone3.clone(), creator_id, attached_deposit);
+        Some(zone3)
+    }
+
+    // check the creator against the creation gate, None when no gate is set
+    pub(crate) fn internal_check_creation_gate(
+        &self,
+        metaverse_id: &str,
+        zone3: &Zone,
+        attached_deposit: Balance,
+    ) -> Option<Promise> {
+        let creator_id = env::predecessor_account_id();
+        let check = match self.creation_gate.as_ref()? {
+            CreationGate::Nft { contract_id } => ext_gate_nft::nft_supply_for_owner(
+                creator_id.clone(),
+                contract_id.clone(),
+                0,
+                GAS_FOR_CREATION_GATE_CHECK,
+            ),
+            CreationGate::Ft { contract_id, .. } => ext_gate_ft::ft_balance_of(
+                creator_id.clone(),
+                contract_id.clone(),
+                0,
+                GAS_FOR_CREATION_GATE_CHECK,
+            ),
+        };
+        Some(
+            check.then(ext_init_metaverse_resolver::resolve_init_metaverse(
+                metaverse_id.to_string(),
+                zone3.clone(),
+                creator_id,
+                U128(attached_deposit),
+                env::current_account_id(),
+                0,
+                GAS_FOR_RESOLVE_INIT_METAVERSE,
+            )),
+        )
+    }
+
+    fn internal_is_creation_gate_holder(&self, result: &[u8]) -> bool {
+        let balance = match near_sdk::serde_json::from_slice::<U128>(result) {
+            Ok(balance) => balance.0,
+            Err(_) => return false,
+        };
+        match &self.creation_gate {
+            Some(CreationGate::Nft { .. }) => balance > 0,
+            Some(CreationGate::Ft { min_balance, .. }) => balance >= min_balance.0,
+            // the gate was removed while the check was running
+            None => true,
+        }
+    }
+}
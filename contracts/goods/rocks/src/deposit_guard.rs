@@ -0,0 +1,86 @@
+use crate::*;
+
+// storage a mint may attach per token on top of its price, a token with its metadata
+pub const MINT_STORAGE_ALLOWANCE_BYTES: u64 = 2_000;
+
+#[near_bindgen]
+impl Contract {
+    /// operator rejects mints attaching more than `multiplier` times their price plus the
+    /// storage allowance, instead of refunding the excess (a refund to a deleted account is
+    /// lost). 0 disables the guard
+    #[payable]
+    pub fn set_max_deposit_multiplier(&mut self, multiplier: u32) {
+        self.assert_operator_only();
+        self.max_deposit_multiplier = multiplier;
+    }
+
+    pub fn get_max_deposit_multiplier(&self) -> u32 {
+        self.max_deposit_multiplier
+    }
+
+    /// largest deposit a mint of `token_count` tokens for `price` accepts, None without a guard
+    pub fn get_max_accepted_deposit(&self, price: U128, token_count: u32) -> Option<U128> {
+        self.internal_max_accepted_deposit(price.0, token_count)
+            .map(U128)
+    }
+
+    // panics when the attached deposit is above the max accepted deposit of the mint
+    pub(crate) fn assert_deposit_accepted(&self, price: Balance, token_count: u32) {
+        if let Some(max_accepted_deposit) = self.internal_max_accepted_deposit(price, token_count) {
+            require!(
+                env::attached_deposit() <= max_accepted_deposit,
+                RoveError::DepositTooLarge
+                    .detail(format!("attach at most {} yoctoNEAR", max_accepted_deposit))
+            );
+        }
+    }
+
+    fn internal_max_accepted_deposit(&self, price: Balance, token_count: u32) -> Option<Balance> {
+        if self.max_deposit_multiplier == 0 {
+            return None;
+        }
+        let storage_allowance = env::storage_byte_cost()
+            * Balance::from(MINT_STORAGE_ALLOWANCE_BYTES)
+            * Balance::from(token_count);
+        Some(
+            price
+                .saturating_add(storage_allowance)
+                .saturating_mul(Balance::from(self.max_deposit_multiplier)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::test_utils::accounts;
+    use near_sdk::{testing_env, ONE_NEAR};
+
+    use crate::tests::{context, setup};
+    use crate::*;
+
+    // 5 x (1 NEAR price + storage allowance of one token)
+    fn max_accepted_deposit() -> Balance {
+        5 * (ONE_NEAR + env::storage_byte_cost() * Balance::from(MINT_STORAGE_ALLOWANCE_BYTES))
+    }
+
+    #[test]
+    fn deposit_of_exactly_max_is_accepted() {
+        let mut contract = setup(0);
+        contract.max_deposit_multiplier = 5;
+        testing_env!(context(accounts(3), max_accepted_deposit()).build());
+        assert_eq!(
+            contract.get_max_accepted_deposit(U128(ONE_NEAR), 1),
+            Some(U128(max_accepted_deposit()))
+        );
+        contract.assert_deposit_accepted(ONE_NEAR, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR2007")]
+    fn deposit_one_yocto_above_max_is_rejected() {
+        let mut contract = setup(0);
+        contract.max_deposit_multiplier = 5;
+        testing_env!(context(accounts(3), max_accepted_deposit() + 1).build());
+        contract.assert_deposit_accepted(ONE_NEAR, 1);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::*;
+
+pub const MAX_FEE_TIERS: usize = 10;
+
+// rock_purchase_fee applied once the metaverse sale volume reaches volume_threshold
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeTier {
+    pub volume_threshold: U128,
+    pub rock_purchase_fee: u32, // 1 = 0.01% = 0.0001
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator sets the volume-based fee schedule, thresholds must be strictly ascending,
+    /// below the first threshold (or with an empty schedule) rock_purchase_fee applies
+    #[payable]
+    pub fn set_fee_tiers(&mut self, fee_tiers: Vec<FeeTier>) {
+        self.assert_operator_only();
+        require!(
+            fee_tiers.len() <= MAX_FEE_TIERS,
+            RoveError::TooManyItems.detail("too many fee tiers")
+        );
+        require!(
+            fee_tiers
+                .iter()
+                .all(|fee_tier| fee_tier.rock_purchase_fee <= 10_000),
+            RoveError::InvalidArgument.detail("rock_purchase_fee must <= 10_000")
+        );
+        require!(
+            fee_tiers
+                .windows(2)
+                .all(|pair| pair[0].volume_threshold.0 < pair[1].volume_threshold.0),
+            RoveError::InvalidArgument.detail("volume_threshold must be ascending")
+        );
+        self.fee_tiers = fee_tiers;
+    }
+
+    pub fn get_fee_tiers(&self) -> Vec<FeeTier> {
+        self.fee_tiers.clone()
+    }
+
+    /// cumulative primary sale volume of a metaverse, net of storage cost and refunds
+    pub fn get_metaverse_volume(&self, metaverse_id: String) -> U128 {
+        U128(self.metaverse_volumes.get(&metaverse_id).unwrap_or(0))
+    }
+
+    /// rock_purchase_fee applied to the next primary sale of a metaverse
+    pub fn get_effective_fee(&self, metaverse_id: String) -> u32 {
+        self.internal_effective_fee(&metaverse_id)
+    }
+
+    pub(crate) fn internal_effective_fee(&self, metaverse_id: &String) -> u32 {
+        let volume = self.metaverse_volumes.get(metaverse_id).unwrap_or(0);
+        self.fee_tiers
+            .iter()
+            .rev()
+            .find(|fee_tier| volume >= fee_tier.volume_threshold.0)
+            .map(|fee_tier| fee_tier.rock_purchase_fee)
+            .unwrap_or(self.rock_purchase_fee)
+    }
+
+    pub(crate) fn internal_add_metaverse_volume(&mut self, metaverse_id: &String, amount: Balance) {
+        let volume = self.metaverse_volumes.get(metaverse_id).unwrap_or(0);
+        self.metaverse_volumes
+            .insert(metaverse_id, &volume.saturating_add(amount));
+    }
+
+    pub(crate) fn internal_sub_metaverse_volume(&mut self, metaverse_id: &String, amount: Balance) {
+        let volume = self.metaverse_volumes.get(metaverse_id).unwrap_or(0);
+        self.metaverse_volumes
+            .insert(metaverse_id, &volume.saturating_sub(amount));
+    }
+}
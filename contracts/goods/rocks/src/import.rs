@@ -0,0 +1,139 @@
+use crate::*;
+
+// a metaverse replayed from export_metaverse_state of a previous deployment
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetaverseImport {
+    pub metaverse_id: String,
+    pub owner_id: AccountId,
+    pub zones: Vec<Zone>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintedTokenImport {
+    pub token_id: TokenId,
+    pub owner_id: AccountId,
+    pub token_metadata: TokenMetadata,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator replays metaverses of a previous deployment, disabled after finalize_import
+    #[payable]
+    pub fn import_metaverses(&mut self, batch: Vec<MetaverseImport>) {
+        self.assert_operator_only();
+        self.assert_import_open();
+        require!(!batch.is_empty(), RoveError::EmptyBatch.as_str());
+
+        let initial_storage_usage = env::storage_usage();
+        for metaverse_import in batch {
+            require!(
+                self.metaverses
+                    .get(&metaverse_import.metaverse_id)
+                    .is_none(),
+                RoveError::MetaverseAlreadyExists.as_str()
+            );
+            let mut zones = HashMap::new();
+            for zone in metaverse_import.zones {
+                require!(self.check_zone(&zone), RoveError::InvalidZone.as_str());
+                zones.insert(zone.zone_index, zone);
+            }
+            self.metaverses
+                .insert(&metaverse_import.metaverse_id, &Metaverse { zones });
+            self.metaverse_owners
+                .insert(&metaverse_import.metaverse_id, &metaverse_import.owner_id);
+            self.internal_count_owned_metaverse(None, &metaverse_import.owner_id);
+            self.tokens_minted
+                .insert(&metaverse_import.metaverse_id, &HashMap::new());
+        }
+        self.internal_charge_import_storage(initial_storage_usage);
+    }
+
+    /// operator replays minted rocks of an imported metaverse with their token ids and owners,
+    /// disabled after finalize_import
+    #[payable]
+    pub fn import_minted_tokens(&mut self, metaverse_id: String, batch: Vec<MintedTokenImport>) {
+        self.assert_operator_only();
+        self.assert_import_open();
+        self.assert_metaverse_exist(&metaverse_id);
+        require!(!batch.is_empty(), RoveError::EmptyBatch.as_str());
+
+        let initial_storage_usage = env::storage_usage();
+        let mut tokens_minted = self.tokens_minted.get(&metaverse_id).unwrap_or_default();
+        let mut token_ids_by_owner: HashMap<AccountId, Vec<String>> = HashMap::new();
+        for minted_token in batch {
+            let token_id = minted_token.token_id;
+            let token_id_parts: Vec<&str> = token_id.split(':').collect();
+            require!(
+                token_id_parts.len() == 3 && token_id_parts[0] == metaverse_id,
+                RoveError::InvalidTokenId.as_str()
+            );
+            let zone_index: u16 = token_id_parts[1]
+                .parse()
+                .unwrap_or_else(|_| env::panic_str(RoveError::InvalidTokenId.as_str()));
+            let rock_index: u128 = token_id_parts[2]
+                .parse()
+                .unwrap_or_else(|_| env::panic_str(RoveError::InvalidTokenId.as_str()));
+            self.assert_rock_mintable(&metaverse_id, zone_index, rock_index);
+
+            self.tokens.internal_mint_with_refund(
+                token_id.clone(),
+                minted_token.owner_id.clone(),
+                Some(minted_token.token_metadata),
+                None,
+            );
+            tokens_minted.insert(token_id.clone(), true);
+            // assert_rock_mintable of the next token reads tokens_minted from state
+            self.tokens_minted.insert(&metaverse_id, &tokens_minted);
+            self.internal_increase_zone_minted(&metaverse_id, zone_index);
+            self.internal_update_holding(&token_id, None, Some(&minted_token.owner_id));
+            token_ids_by_owner
+                .entry(minted_token.owner_id)
+                .or_default()
+                .push(token_id);
+        }
+        self.internal_charge_import_storage(initial_storage_usage);
+
+        let nft_mint_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftMint(
+                token_ids_by_owner
+                    .into_iter()
+                    .map(|(owner_id, token_ids)| NftMintLog {
+                        owner_id: owner_id.to_string(),
+                        token_ids,
+                        memo: Some("import".to_string()),
+                    })
+                    .collect(),
+            ),
+        };
+        nft_mint_log.emit();
+    }
+
+    /// operator closes the import, import methods can not be called anymore
+    #[payable]
+    pub fn finalize_import(&mut self) {
+        self.assert_operator_only();
+        self.assert_import_open();
+        self.import_finalized = true;
+    }
+
+    pub fn is_import_finalized(&self) -> bool {
+        self.import_finalized
+    }
+
+    fn assert_import_open(&self) {
+        require!(!self.import_finalized, RoveError::ImportFinalized.as_str());
+    }
+
+    fn internal_charge_import_storage(&self, initial_storage_usage: u64) {
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+}
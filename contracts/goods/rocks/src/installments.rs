@@ -0,0 +1,439 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+// longest time a buyer can take to pay a rock in installments (180 days)
+pub const MAX_INSTALLMENT_DURATION_SEC: u32 = 180 * 24 * 3600;
+
+// installment terms a metaverse offers on its public zones
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InstallmentPlan {
+    // only rocks priced at least min_price can be bought in installments
+    pub min_price: U128,
+    // share of the price paid by start_installment, in bps
+    pub deposit_bps: u16,
+    // time to pay the full price after start_installment
+    pub duration_sec: u32,
+    // share of the payments kept when the buyer defaults, in bps
+    pub forfeit_bps: u16,
+}
+
+// a rock held by the contract until its buyer pays the full price
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Installment {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub buyer_id: AccountId,
+    pub price: U128,
+    pub paid: U128,
+    // storage of the held rock, taken from the price like a direct mint
+    pub storage_cost: U128,
+    pub forfeit_bps: u16,
+    pub due_at: U64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner enables (or disables with None) installment purchases on its public zones
+    #[payable]
+    pub fn set_installment_plan(&mut self, metaverse_id: String, plan: Option<InstallmentPlan>) {
+        self.assert_metaverse_owner(&metaverse_id);
+
+        let initial_storage_usage = env::storage_usage();
+        match plan {
+            Some(plan) => {
+                require!(
+                    0 < plan.deposit_bps && plan.deposit_bps < ONE_HUNDRED_PERCENT_IN_BPS,
+                    RoveError::InvalidArgument.detail("deposit_bps must be in (0, 10_000)")
+                );
+                require!(
+                    plan.forfeit_bps <= ONE_HUNDRED_PERCENT_IN_BPS,
+                    RoveError::InvalidArgument.detail("forfeit_bps must <= 10_000")
+                );
+                require!(
+                    0 < plan.duration_sec && plan.duration_sec <= MAX_INSTALLMENT_DURATION_SEC,
+                    RoveError::InvalidArgument.detail(format!(
+                        "duration_sec must be in (0, {}]",
+                        MAX_INSTALLMENT_DURATION_SEC
+                    ))
+                );
+                self.installment_plans.insert(&metaverse_id, &plan)
+            }
+            None => self.installment_plans.remove(&metaverse_id),
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_installment_plan(&self, metaverse_id: String) -> Option<InstallmentPlan> {
+        self.installment_plans.get(&metaverse_id)
+    }
+
+    pub fn get_installment(&self, token_id: TokenId) -> Option<Installment> {
+        self.installments.get(&token_id)
+    }
+
+    /// buyer reserves a rock of a public zone by paying the plan deposit, the rock is minted
+    /// to the contract and transferred to the buyer once pay_installment completes the price
+    #[payable]
+    pub fn start_installment(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        token_metadata: TokenMetadata,
+    ) -> Installment {
+        let plan = self
+            .installment_plans
+            .get(&metaverse_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::InstallmentsNotEnabled.as_str()));
+        let (zone, token_id) = self.assert_rock_mintable(&metaverse_id, zone_index, rock_index);
+        require!(
+            zone.mint_policy == MintPolicy::PublicFixedPrice,
+            RoveError::InvalidZoneType
+                .detail("only public_fixed_price rocks can be bought in installments")
+        );
+        let price = zone.price.0;
+        require!(
+            price >= plan.min_price.0,
+            RoveError::InvalidArgument.detail(format!(
+                "rocks priced below {} yoctoNEAR can not be bought in installments",
+                plan.min_price.0
+            ))
+        );
+
+        let buyer_id = env::predecessor_account_id();
+        self.assert_not_denied(&buyer_id);
+        self.assert_zone_receiver(&metaverse_id, zone_index, &buyer_id);
+        let initial_storage_usage = env::storage_usage();
+        self.tokens.internal_mint_with_refund(
+            token_id.clone(),
+            env::current_account_id(),
+            Some(token_metadata),
+            None,
+        );
+        self.internal_update_holding(&token_id, None, Some(&env::current_account_id()));
+        let mut tokens_minted = self.tokens_minted.get(&metaverse_id).unwrap();
+        tokens_minted.insert(token_id.clone(), true);
+        self.tokens_minted.insert(&metaverse_id, &tokens_minted);
+        self.internal_increase_zone_minted(&metaverse_id, zone_index);
+        self.internal_throttle_mint(&metaverse_id, zone_index, &buyer_id);
+        let mut installment = Installment {
+            metaverse_id: metaverse_id.clone(),
+            zone_index,
+            buyer_id: buyer_id.clone(),
+            price: zone.price,
+            paid: U128(0),
+            storage_cost: U128(0),
+            forfeit_bps: plan.forfeit_bps,
+            due_at: U64(env::block_timestamp() + u64::from(plan.duration_sec) * 1_000_000_000),
+        };
+        self.installments.insert(&token_id, &installment);
+        let storage_cost =
+            env::storage_byte_cost() * Balance::from(env::storage_usage() - initial_storage_usage);
+
+        let deposit = std::cmp::max(
+            price * u128::from(plan.deposit_bps) / u128::from(ONE_HUNDRED_PERCENT_IN_BPS),
+            storage_cost,
+        );
+        let attached_deposit = env::attached_deposit();
+        require!(
+            deposit <= attached_deposit,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR to start the installment plan",
+                deposit
+            ))
+        );
+        let paid = std::cmp::min(attached_deposit, price);
+        if attached_deposit > paid {
+            self.internal_payout(buyer_id.clone(), attached_deposit - paid);
+        }
+        installment.paid = U128(paid);
+        installment.storage_cost = U128(storage_cost);
+
+        let nft_mint_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftMint(vec![NftMintLog {
+                owner_id: env::current_account_id().to_string(),
+                token_ids: vec![token_id.clone()],
+                memo: Some(String::from("start_installment")),
+            }]),
+        };
+        nft_mint_log.emit();
+        // the contract holds the rock until the buyer pays the rest
+        emit_account_activity(vec![AccountActivityLog::new(
+            &env::current_account_id(),
+            ActivityAction::Mint,
+            &token_id,
+            Some(&buyer_id),
+            Some(paid),
+        )]);
+        emit_installment_log(
+            EventLogVariant::InstallmentStarted,
+            token_id.clone(),
+            &installment,
+        );
+
+        if paid == price {
+            self.internal_complete_installment(token_id, installment.clone());
+        } else {
+            self.installments.insert(&token_id, &installment);
+        }
+        installment
+    }
+
+    /// buyer pays the attached deposit towards the price, the rock is transferred to the buyer
+    /// when the price is paid in full, any overpayment is refunded
+    #[payable]
+    pub fn pay_installment(&mut self, token_id: TokenId) -> Installment {
+        assert_at_least_one_yocto();
+        let mut installment = self
+            .installments
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::InstallmentNotFound.as_str()));
+        require!(
+            env::predecessor_account_id() == installment.buyer_id,
+            RoveError::Unauthorized.as_str()
+        );
+        self.assert_not_denied(&installment.buyer_id);
+        require!(
+            env::block_timestamp() < installment.due_at.0,
+            RoveError::InstallmentOverdue.as_str()
+        );
+
+        let attached_deposit = env::attached_deposit();
+        let paid = std::cmp::min(installment.paid.0 + attached_deposit, installment.price.0);
+        let refund = installment.paid.0 + attached_deposit - paid;
+        if refund > 0 {
+            self.internal_payout(installment.buyer_id.clone(), refund);
+        }
+        installment.paid = U128(paid);
+        emit_installment_log(
+            EventLogVariant::InstallmentPaid,
+            token_id.clone(),
+            &installment,
+        );
+
+        if paid == installment.price.0 {
+            self.internal_complete_installment(token_id, installment.clone());
+        } else {
+            self.installments.insert(&token_id, &installment);
+        }
+        installment
+    }
+
+    /// anyone closes an overdue installment plan: the rock goes back to the zone, the plan
+    /// forfeit_bps of the payments is kept as a sale and the rest is refunded to the buyer
+    pub fn default_installment(&mut self, token_id: TokenId) {
+        let installment = self
+            .installments
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::InstallmentNotFound.as_str()));
+        require!(
+            env::block_timestamp() >= installment.due_at.0,
+            RoveError::InstallmentNotOverdue.as_str()
+        );
+        self.installments.remove(&token_id);
+
+        let contract_id = env::current_account_id();
+        self.internal_burn(
+            &installment.metaverse_id,
+            installment.zone_index,
+            &token_id,
+            &contract_id,
+            &installment.buyer_id,
+        );
+
+        let forfeited = installment.paid.0 * u128::from(installment.forfeit_bps)
+            / u128::from(ONE_HUNDRED_PERCENT_IN_BPS);
+        if forfeited > 0 {
+            let (treasury_amount, metaverse_owner_amount) = split_purchase_fee(
+                forfeited,
+                self.internal_effective_fee(&installment.metaverse_id),
+                &self.fee_dust_policy,
+            );
+            if treasury_amount > 0 {
+                self.internal_pay_treasury(treasury_amount);
+            }
+            if metaverse_owner_amount > 0 {
+                let metaverse_owner = self
+                    .metaverse_owners
+                    .get(&installment.metaverse_id)
+                    .unwrap();
+                self.internal_payout(metaverse_owner, metaverse_owner_amount);
+            }
+        }
+        if installment.paid.0 > forfeited {
+            self.internal_payout(installment.buyer_id.clone(), installment.paid.0 - forfeited);
+        }
+
+        let nft_burn_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                owner_id: contract_id.to_string(),
+                token_ids: vec![token_id.clone()],
+                memo: Some(String::from("default_installment")),
+            }]),
+        };
+        nft_burn_log.emit();
+        emit_installment_log(
+            EventLogVariant::InstallmentDefaulted,
+            token_id,
+            &installment,
+        );
+    }
+
+    // transfer the held rock to its buyer and settle the price like a direct mint
+    fn internal_complete_installment(&mut self, token_id: TokenId, installment: Installment) {
+        self.installments.remove(&token_id);
+        let contract_id = env::current_account_id();
+        self.internal_transfer_token(&token_id, &contract_id, &installment.buyer_id);
+
+        self.internal_record_purchase(
+            &installment.buyer_id,
+            Purchase {
+                token_id: token_id.clone(),
+                metaverse_id: installment.metaverse_id.clone(),
+                price: installment.price,
+                timestamp: U64(env::block_timestamp()),
+            },
+        );
+
+        if installment.price.0 > installment.storage_cost.0 {
+            self.internal_distribute_sale_proceeds(
+                &installment.metaverse_id,
+                &token_id,
+                &installment.buyer_id,
+                installment.price.0 - installment.storage_cost.0,
+            );
+        }
+
+        let nft_transfer_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                authorized_id: None,
+                old_owner_id: contract_id.to_string(),
+                new_owner_id: installment.buyer_id.to_string(),
+                token_ids: vec![token_id.clone()],
+                memo: Some(String::from("pay_installment")),
+            }]),
+        };
+        nft_transfer_log.emit();
+        emit_account_activity(vec![AccountActivityLog::new(
+            &contract_id,
+            ActivityAction::Transfer,
+            &token_id,
+            Some(&installment.buyer_id),
+            None,
+        )]);
+        emit_installment_log(
+            EventLogVariant::InstallmentCompleted,
+            token_id,
+            &installment,
+        );
+    }
+}
+
+fn emit_installment_log(
+    variant: fn(Vec<InstallmentLog>) -> EventLogVariant,
+    token_id: TokenId,
+    installment: &Installment,
+) {
+    let installment_log: EventLog = EventLog {
+        standard: "installment".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![InstallmentLog {
+            token_id,
+            buyer_id: installment.buyer_id.to_string(),
+            price: installment.price,
+            paid: installment.paid,
+            due_at: installment.due_at,
+            memo: None,
+        }]),
+    };
+    installment_log.emit();
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::test_utils::accounts;
+    use near_sdk::{testing_env, ONE_NEAR};
+
+    use crate::tests::{context, public_zone, rock_metadata, setup, METAVERSE_ID};
+    use crate::*;
+
+    #[test]
+    fn default_installment_reverses_the_mint() {
+        let mut contract = setup(0);
+        testing_env!(context(accounts(3), ONE_NEAR).build());
+        contract.add_zone(METAVERSE_ID.to_string(), public_zone(2, 1, 10));
+        contract
+            .tokens_minted
+            .insert(&METAVERSE_ID.to_string(), &HashMap::new());
+        contract.set_installment_plan(
+            METAVERSE_ID.to_string(),
+            Some(InstallmentPlan {
+                min_price: U128(ONE_NEAR),
+                deposit_bps: 2_000,
+                duration_sec: 3600,
+                forfeit_bps: 1_000,
+            }),
+        );
+        contract.set_mint_throttle(
+            METAVERSE_ID.to_string(),
+            2,
+            Some(MintThrottle {
+                max_per_account: 1,
+                window_sec: 3600,
+                max_per_block: 0,
+            }),
+        );
+
+        testing_env!(context(accounts(4), ONE_NEAR / 2).build());
+        let installment =
+            contract.start_installment(METAVERSE_ID.to_string(), 2, 1, rock_metadata());
+        let token_id = rock_token_id(METAVERSE_ID, 2, 1);
+        contract
+            .rock_attributes
+            .insert(&token_id, &vec![("level".to_string(), "1".to_string())]);
+        assert_eq!(
+            contract.get_zone_remaining(METAVERSE_ID.to_string(), 2),
+            U128(9)
+        );
+
+        testing_env!(context(accounts(5), 0)
+            .block_timestamp(installment.due_at.0)
+            .build());
+        contract.default_installment(token_id.clone());
+        assert!(contract.tokens.owner_by_id.get(&token_id).is_none());
+        assert!(contract.get_rock_attributes(token_id.clone()).is_empty());
+        assert!(!contract
+            .tokens_minted
+            .get(&METAVERSE_ID.to_string())
+            .unwrap()
+            .contains_key(&token_id));
+        assert_eq!(
+            contract.get_zone_remaining(METAVERSE_ID.to_string(), 2),
+            U128(10)
+        );
+
+        // the defaulted mint no longer counts towards the buyer throttle
+        testing_env!(context(accounts(4), ONE_NEAR / 2)
+            .block_timestamp(installment.due_at.0)
+            .build());
+        contract.start_installment(METAVERSE_ID.to_string(), 2, 1, rock_metadata());
+        assert_eq!(
+            contract.tokens.owner_by_id.get(&token_id),
+            Some(env::current_account_id())
+        );
+    }
+}
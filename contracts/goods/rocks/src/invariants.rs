@@ -0,0 +1,148 @@
+use crate::*;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum InvariantViolation {
+    // tokens_minted entry is not {metaverse_id}:{zone_index}:{rock_index} of its metaverse
+    MalformedTokenId,
+    // tokens_minted entry of a zone the metaverse does not have
+    ZoneNotFound,
+    // tokens_minted entry outside the rock range of its zone
+    RockOutOfRange,
+    // tokens_minted entry without an nft owner record
+    MissingOwner,
+    // zones_minted count differs from the tokens_minted entries of the zone
+    MintedCountMismatch,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InvariantDiscrepancy {
+    pub metaverse_id: String,
+    pub zone_index: Option<u16>,
+    pub token_id: Option<TokenId>,
+    pub violation: InvariantViolation,
+    pub detail: Option<String>,
+}
+
+// one page of verify_invariants, pass from_index + limit to check the next metaverses
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InvariantReport {
+    pub from_index: u64,
+    pub total: u64,
+    pub discrepancies: Vec<InvariantDiscrepancy>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// maintenance check of `limit` metaverses from `from_index` (default 10): every
+    /// tokens_minted entry must be a rock of an existing zone range with an nft owner, and the
+    /// zones_minted counts must match. Run after upgrades, an empty report means no corruption
+    pub fn verify_invariants(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> InvariantReport {
+        let from_index = from_index.unwrap_or_default();
+        let limit = limit.unwrap_or(10);
+        let metaverse_ids = self.metaverses.keys_as_vector();
+        let mut discrepancies = vec![];
+        for index in from_index..metaverse_ids.len().min(from_index.saturating_add(limit)) {
+            let metaverse_id = metaverse_ids.get(index).unwrap();
+            self.internal_verify_metaverse(&metaverse_id, &mut discrepancies);
+        }
+
+        InvariantReport {
+            from_index,
+            total: metaverse_ids.len(),
+            discrepancies,
+        }
+    }
+
+    fn internal_verify_metaverse(
+        &self,
+        metaverse_id: &String,
+        discrepancies: &mut Vec<InvariantDiscrepancy>,
+    ) {
+        let metaverse = self.metaverses.get(metaverse_id).unwrap();
+        let mut token_ids: Vec<TokenId> = self
+            .tokens_minted
+            .get(metaverse_id)
+            .unwrap_or_default()
+            .into_keys()
+            .collect();
+        token_ids.sort();
+
+        let mut zone_counts: HashMap<u16, u128> = HashMap::new();
+        for token_id in token_ids {
+            let mut discrepancy = |zone_index: Option<u16>, violation: InvariantViolation| {
+                discrepancies.push(InvariantDiscrepancy {
+                    metaverse_id: metaverse_id.clone(),
+                    zone_index,
+                    token_id: Some(token_id.clone()),
+                    violation,
+                    detail: None,
+                })
+            };
+            let (zone_index, rock_index) = match parse_rock_token_id(metaverse_id, &token_id) {
+                Some(indices) => indices,
+                None => {
+                    discrepancy(None, InvariantViolation::MalformedTokenId);
+                    continue;
+                }
+            };
+            *zone_counts.entry(zone_index).or_default() += 1;
+            match metaverse.zones.get(&zone_index) {
+                None => discrepancy(Some(zone_index), InvariantViolation::ZoneNotFound),
+                Some(zone)
+                    if rock_index < zone.rock_index_from || zone.rock_index_to < rock_index =>
+                {
+                    discrepancy(Some(zone_index), InvariantViolation::RockOutOfRange)
+                }
+                Some(_) => {}
+            }
+            if self.tokens.owner_by_id.get(&token_id).is_none() {
+                discrepancy(Some(zone_index), InvariantViolation::MissingOwner);
+            }
+        }
+
+        let zones_minted = self.zones_minted.get(metaverse_id).unwrap_or_default();
+        let mut zone_indices: Vec<u16> = zones_minted
+            .keys()
+            .chain(zone_counts.keys())
+            .cloned()
+            .collect();
+        zone_indices.sort();
+        zone_indices.dedup();
+        for zone_index in zone_indices {
+            let counted = zones_minted.get(&zone_index).cloned().unwrap_or(0);
+            let minted = zone_counts.get(&zone_index).cloned().unwrap_or(0);
+            if counted != minted {
+                discrepancies.push(InvariantDiscrepancy {
+                    metaverse_id: metaverse_id.clone(),
+                    zone_index: Some(zone_index),
+                    token_id: None,
+                    violation: InvariantViolation::MintedCountMismatch,
+                    detail: Some(format!(
+                        "zones_minted {}, tokens_minted {}",
+                        counted, minted
+                    )),
+                });
+            }
+        }
+    }
+}
+
+// (zone_index, rock_index) of a rock token id of metaverse_id, None when it is not one
+fn parse_rock_token_id(metaverse_id: &String, token_id: &TokenId) -> Option<(u16, u128)> {
+    let token_id_parts: Vec<&str> = token_id.split(':').collect();
+    if token_id_parts.len() != 3 || token_id_parts[0] != metaverse_id {
+        return None;
+    }
+    Some((
+        token_id_parts[1].parse().ok()?,
+        token_id_parts[2].parse().ok()?,
+    ))
+}
@@ -1,7 +1,7 @@
 /*!
 Non-Fungible Token implementation with JSON serialization.
 NOTES:
-  - This is NFT contract for Public Zone Rocks (type_zone = 3) only Metaverse
+  - This is NFT contract for Public Zone Rocks (mint_policy PublicFixedPrice) only Metaverse
   - The maximum balance value is limited by U128 (2**128 - 1).
   - JSON calls should pass U128 as a base-10 string. E.g. "100".
   - The contract optimizes the inner trie structure by hashing account IDs. It will prevent some
@@ -22,32 +22,156 @@ use near_contract_standards::non_fungible_token::metadata::{
     NFTContractMetadata, NonFungibleTokenMetadataProvider, TokenMetadata,
 };
 use near_contract_standards::non_fungible_token::{
-    refund_deposit_to_account, NonFungibleToken, Token, TokenId,
+    refund_approved_account_ids, refund_deposit_to_account, NonFungibleToken, Token, TokenId,
 };
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, UnorderedMap};
-use near_sdk::json_types::U128;
+use near_sdk::collections::{
+    LazyOption, LookupMap, LookupSet, UnorderedMap, UnorderedSet, Vector,
+};
+use near_sdk::json_types::{U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
-    assert_one_yocto, env, near_bindgen, require, AccountId, Balance, BorshStorageKey,
-    PanicOnDefault, Promise, PromiseOrValue,
+    assert_one_yocto, env, near_bindgen, require, AccountId, Balance, BorshStorageKey, CryptoHash,
+    PanicOnDefault, Promise, PromiseOrValue, PublicKey,
+};
+use rove_contracts_interface::{
+    compute_payout, emit_account_activity, rock_token_id, sha256_hash, split_purchase_fee,
+    verify_merkle_proof, AccountActivityLog, ActivityAction, DustPolicy, Payout,
+    ONE_HUNDRED_PERCENT_IN_BPS,
 };
 
+pub use crate::airdrop::*;
+pub use crate::allowlist::*;
+pub use crate::api_version::*;
+pub use crate::approval::*;
+pub use crate::attributes::*;
+pub use crate::campaign::*;
+pub use crate::compatibility::*;
+pub use crate::creation_gate::*;
+pub use crate::deposit_guard::*;
+pub use crate::emergency::*;
+pub use crate::errors::*;
+pub use crate::escrow::*;
 pub use crate::events::*;
+pub use crate::governance::*;
+pub use crate::export::*;
+pub use crate::faucet::*;
+pub use crate::fee_tiers::*;
+pub use crate::gift::*;
+pub use crate::health::*;
+pub use crate::holds::*;
+pub use crate::hosting::*;
+pub use crate::import::*;
+pub use crate::installments::*;
+pub use crate::invariants::*;
+pub use crate::leaderboard::*;
+pub use crate::limits::*;
+pub use crate::lock_attestation::*;
 use crate::internal::*;
+pub use crate::map_commitment::*;
+pub use crate::migrate::*;
+pub use crate::mint_listeners::*;
+pub use crate::mint_policy::*;
+pub use crate::mint_requests::*;
+pub use crate::moderation::*;
+pub use crate::oracle::*;
+pub use crate::ownership::*;
+pub use crate::quote::*;
+pub use crate::raffle::*;
+pub use crate::meta_tx::*;
+pub use crate::region::*;
+pub use crate::rock_metadata::*;
 pub use crate::royalty::*;
+pub use crate::settlement::*;
+pub use crate::slug_auction::*;
+pub use crate::slugs::*;
+pub use crate::storage_quote::*;
+pub use crate::tags::*;
+pub use crate::themes::*;
+pub use crate::throttle::*;
+pub use crate::token_detail::*;
+pub use crate::treasury_split::*;
 pub use crate::types::*;
-
+pub use crate::zone_receivers::*;
+pub use crate::zone_summary::*;
+pub use crate::voucher::*;
+
+mod airdrop;
+mod allowlist;
+mod api_version;
+#[path = "../../shared/approval.rs"]
+mod approval;
+mod attributes;
+mod batch_transfer;
+mod campaign;
+mod compatibility;
+mod creation_gate;
+#[path = "../../shared/deny_list.rs"]
+mod deny_list;
+mod deposit_guard;
+#[path = "../../shared/emergency.rs"]
+mod emergency;
+#[path = "../../shared/errors.rs"]
+mod errors;
+#[path = "../../shared/escrow.rs"]
+mod escrow;
 mod events;
+mod governance;
+mod export;
+mod faucet;
+mod fee_tiers;
+#[path = "../../shared/gas_profiling.rs"]
+mod gas_profiling;
+mod gift;
+mod health;
+#[path = "../../shared/holds.rs"]
+mod holds;
+mod hosting;
+mod import;
+mod installments;
+#[path = "../../shared/internal.rs"]
 mod internal;
+mod invariants;
+mod leaderboard;
+mod limits;
+mod lock_attestation;
+mod map_commitment;
+mod metadata_freeze;
+mod migrate;
+mod mint_listeners;
+mod mint_policy;
+mod mint_requests;
+mod moderation;
+mod oracle;
+mod ownership;
+mod quote;
+mod raffle;
+mod meta_tx;
+#[path = "../../shared/region.rs"]
+mod region;
+mod rock_metadata;
 mod royalty;
+#[path = "../../shared/settlement.rs"]
+mod settlement;
+mod slug_auction;
+mod slugs;
+mod storage_quote;
+mod tags;
+mod themes;
+mod throttle;
+mod token_detail;
+mod treasury_split;
 mod types;
+mod visibility;
+#[path = "../../shared/zone_receivers.rs"]
+mod zone_receivers;
+mod zone_summary;
+mod voucher;
 
-const ONE_HUNDRED_PERCENT_IN_BPS: u16 = 10_000;
 pub const NFT_METADATA_SPEC: &str = "1.0.0";
 pub const NFT_STANDARD_NAME: &str = "nep171";
-pub const NOT_FOUND_METAVERSE_ID_ERROR: &str = "Not found metaverse_id";
-pub const NOT_FOUND_ZONE_INDEX_ERROR: &str = "Not found zone_index";
+// rock indexes returned by one get_unminted_rocks call
+pub const MAX_UNMINTED_ROCKS_LIMIT: u64 = 500;
 
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
@@ -64,6 +188,7 @@ pub struct Contract {
 
     pub init_imo_fee: u128,     // fee in yoctoNEAR 1e-24 NEAR
     pub rock_purchase_fee: u32, // in percent, with 0.01% = 1 = rock_purchase_fee
+    pub fee_dust_policy: DustPolicy, // receiver of the rounding remainder of rock_purchase_fee
 
     // Map metaverse_id => Metaverse
     pub metaverses: UnorderedMap<String, Metaverse>,
@@ -72,6 +197,202 @@ pub struct Contract {
 
     // Map metaverse_id => [token_id => true/false]
     pub tokens_minted: UnorderedMap<String, HashMap<String, bool>>,
+
+    // Map metaverse_id => [zone_index => voucher nft collection]
+    pub voucher_collections: UnorderedMap<String, HashMap<u16, AccountId>>,
+
+    // Map {action}:{token_id} => timestamp from which the emergency action can be executed
+    pub emergency_actions: UnorderedMap<String, u64>,
+
+    // Map metaverse_id => [zone_index => number of minted rocks]
+    pub zones_minted: UnorderedMap<String, HashMap<u16, u128>>,
+
+    // Map payout_id => transfer that failed and can be retried
+    pub failed_payouts: UnorderedMap<u64, FailedPayout>,
+    pub next_payout_id: u64,
+    pub treasury_settlement: TreasurySettlement,
+
+    // Map account_id => rocks bought by this account, each account has its own Vector so a mint
+    // only appends one entry
+    pub purchases: LookupMap<AccountId, Vector<Purchase>>,
+
+    // Map account_id => next nonce of its signed mint payloads
+    pub mint_nonces: UnorderedMap<AccountId, u64>,
+
+    // NEP-366 relayer allowed to submit relayed_mint and the fund paying those mints
+    pub relayer_id: Option<AccountId>,
+    pub relay_fund: u128,
+    pub relayed_mint_daily_limit: u32,
+    // Map account_id => relayed mints of the current day
+    pub relayed_mints: UnorderedMap<AccountId, RelayedMintCounter>,
+
+    // Map metaverse_id => commitment of its off-chain world layout
+    pub map_commitments: UnorderedMap<String, MapCommitment>,
+
+    // Map {metaverse_id}:{zone_index} => Merkle allowlist of the zone
+    pub allowlists: UnorderedMap<String, ZoneAllowlist>,
+
+    // oracle converting USD zone prices to yoctoNEAR
+    pub price_oracle: Option<PriceOracleConfig>,
+    // Map {metaverse_id}:{zone_index} => zone price in USD cents
+    pub zone_usd_prices: UnorderedMap<String, u64>,
+
+    // Map metaverse_id => token metadata fields editable by rock owners
+    pub metadata_schemas: UnorderedMap<String, MetadataSchema>,
+
+    // accounts allowed to review flags and take down token content
+    pub moderators: UnorderedSet<AccountId>,
+    // Map token_id => pending flags of the token
+    pub token_flags: UnorderedMap<TokenId, Vec<TokenFlag>>,
+    // Map account_id => timestamp of its last flag
+    pub last_flag_at: UnorderedMap<AccountId, u64>,
+
+    // Map token_id => gameplay attributes of the rock
+    pub rock_attributes: LookupMap<TokenId, Vec<(String, String)>>,
+    // Map metaverse_id => game contracts allowed to write rock attributes
+    pub attribute_writers: UnorderedMap<String, Vec<AccountId>>,
+
+    // Map metaverse_id => monthly hosting fee and paid-through timestamp
+    pub hosting_plans: UnorderedMap<String, HostingPlan>,
+    pub hosting_grace_period: u64,
+
+    // Map {metaverse_id}:{zone_index} => mint rate limit of the zone
+    pub mint_throttles: UnorderedMap<String, MintThrottle>,
+    // Map {metaverse_id}:{zone_index} => (block_height, mints in that block)
+    pub zone_block_mints: LookupMap<String, (u64, u32)>,
+    // Map {metaverse_id}:{zone_index}:{account_id} => last mint timestamps of the account
+    pub account_mint_rings: LookupMap<String, MintRing>,
+
+    // Map metaverse_id => refund window of its primary sales in seconds
+    pub escrow_windows: UnorderedMap<String, u32>,
+    // Map token_id => primary sale proceeds held during the refund window
+    pub escrowed_sales: UnorderedMap<TokenId, EscrowedSale>,
+
+    // Map metaverse_id => environment themes allowed on its rocks
+    pub metaverse_themes: LookupMap<String, Vec<String>>,
+
+    // set by finalize_import, import_metaverses and import_minted_tokens are disabled after it
+    pub import_finalized: bool,
+
+    // volume-based schedule overriding rock_purchase_fee, ascending by volume_threshold
+    pub fee_tiers: Vec<FeeTier>,
+    // Map metaverse_id => cumulative primary sale volume
+    pub metaverse_volumes: LookupMap<String, Balance>,
+
+    // Map token_id => message of its last gift
+    pub gift_messages: LookupMap<TokenId, GiftMessage>,
+
+    // Map token_id => timestamp its metadata was frozen at
+    pub frozen_metadata: LookupMap<TokenId, u64>,
+
+    // set by assert_no_full_access_keys once the account keys are removed
+    pub lock_attestation: Option<LockAttestation>,
+
+    // set by set_creation_gate, init_metaverse checks the creator against it
+    pub creation_gate: Option<CreationGate>,
+
+    // Map metaverse_id => its slug
+    pub metaverse_slugs: LookupMap<String, String>,
+    // Map slug => metaverse_id
+    pub slug_metaverses: LookupMap<String, String>,
+    pub slug_fee: Balance, // fee in yoctoNEAR paid for each claimed slug
+    // Map slug => its auction, the slug is reserved until the auction is settled
+    pub slug_auctions: UnorderedMap<String, SlugAuction>,
+
+    // Map zone key => royalties of its rocks, overrides royalties of the metaverse
+    pub zone_royalties: LookupMap<String, HashMap<AccountId, u16>>,
+
+    // Map metaverse_id => installment terms of its public zones
+    pub installment_plans: LookupMap<String, InstallmentPlan>,
+    // Map token_id => installment plan of a rock held by the contract
+    pub installments: LookupMap<TokenId, Installment>,
+
+    // Map zone key => raffle of the zone, removed once drawn and claimed
+    pub raffles: LookupMap<String, Raffle>,
+
+    // Map region token_id => rocks wrapped in it
+    pub regions: LookupMap<TokenId, Vec<TokenId>>,
+    // Map rock token_id => region it is wrapped in
+    pub wrapped_rocks: LookupMap<TokenId, TokenId>,
+    pub next_region_id: u64,
+
+    // Map token_id => expiry of its approvals set with nft_approve_with_expiry
+    pub approval_expiries: LookupMap<TokenId, HashMap<AccountId, ApprovalExpiry>>,
+
+    // accounts the admin denied from minting, transferring and approving rocks
+    pub denied_accounts: UnorderedSet<AccountId>,
+
+    // Map {account_id}:{request_id} => token_id minted by that mint_rock request
+    pub mint_requests: LookupMap<String, TokenId>,
+    // keys of mint_requests in a ring of MAX_MINT_REQUESTS, next_mint_request is the oldest
+    pub mint_request_keys: Vector<String>,
+    pub next_mint_request: u64,
+
+    // Map metaverse_id => contracts notified after each mint
+    pub mint_listeners: LookupMap<String, Vec<AccountId>>,
+
+    // Map {metaverse_id}:{zone_index} => Merkle airdrop of the zone
+    pub airdrops: LookupMap<String, ZoneAirdrop>,
+    // sha256(root + leaf) of each claimed airdrop leaf
+    pub airdrop_claims: LookupSet<CryptoHash>,
+
+    // platform wallets sharing treasury payouts, empty pays treasury_id
+    pub treasury_beneficiaries: Vec<TreasuryBeneficiary>,
+
+    // Map {metaverse_id}:{zone_index} => who may receive the rocks of the zone
+    pub zone_receiver_restrictions: LookupMap<String, ZoneReceiverRestriction>,
+    // {metaverse_id}:{zone_index}:{account_id} of the eligible receivers of restricted zones
+    pub zone_receivers: LookupSet<String>,
+
+    // Map token_id => timestamp the operator hold of an unminted rock ends at
+    pub rock_holds: LookupMap<TokenId, u64>,
+
+    // operator caps checked by init_metaverse and add_zone
+    pub metaverse_limits: MetaverseLimits,
+    // Map account_id => metaverses counted against max_metaverses_per_owner
+    pub owned_metaverse_counts: LookupMap<AccountId, u32>,
+
+    // Map metaverse_id => resale royalty of the metaverse owner in bps
+    pub metaverse_owner_royalties: LookupMap<String, u16>,
+
+    // Map metaverse_id => discovery tags set by the metaverse owner
+    pub metaverse_tags: LookupMap<String, Vec<MetaverseTag>>,
+    // Map {kind}:{value} of a tag => sorted metaverse ids with the tag
+    pub metaverse_tag_index: LookupMap<String, Vec<String>>,
+
+    // Map {metaverse_id}:{account_id} => rocks of the metaverse held by the account
+    pub holder_counts: LookupMap<String, u64>,
+    // Map metaverse_id => accounts holding the most rocks of the metaverse, by count
+    pub top_holders: LookupMap<String, Vec<HolderCount>>,
+
+    // mints attaching more than this many times their price plus storage are rejected, 0: off
+    pub max_deposit_multiplier: u32,
+
+    // Map {metaverse_id}:{zone_index} => "first parcel free" campaign of the zone
+    pub campaigns: LookupMap<String, Campaign>,
+    // Map {metaverse_id}:{zone_index}:{account_id} => campaign rocks claimed by the account
+    pub campaign_claims: LookupMap<String, u32>,
+    pub campaign_config: CampaignConfig,
+    // Map account_id => register_for_campaigns timestamp
+    pub campaign_registrations: LookupMap<AccountId, u64>,
+
+    // Map zone:{type_zone} or metaverse:{metaverse_id} => sorted environment nft_type_ids
+    // allowed on the rocks of the class
+    pub environment_compatibility: LookupMap<String, Vec<String>>,
+
+    // testnet faucet minting free rocks, None: off
+    pub faucet_mode: Option<FaucetMode>,
+    // Map account_id => rocks minted with faucet_mint_rock
+    pub faucet_mints: LookupMap<AccountId, u32>,
+
+    // Map {metaverse_id}:{zone_index} => false for hidden zones, zones without an entry are visible
+    pub zone_visibility: LookupMap<String, bool>,
+
+    // ed25519 key signing the price quotes of mint_rock_with_quote
+    pub quote_public_key: Option<PublicKey>,
+
+    // operator switch of the gas_checkpoint logs
+    pub gas_profiling: bool,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -79,9 +400,9 @@ pub struct Contract {
 pub struct Zone {
     pub zone_index: u16,         // required, start from 1
     pub price: U128,             // required
-    pub core_team_addr: String,  // required for type=1
-    pub collection_addr: String, // required for type=2
-    pub type_zone: u8,           // 1: core_team, 2: nft_holder, 3: public
+    pub core_team_addr: String,  // required for CoreTeam
+    pub collection_addr: String, // unused, HolderGated is rockNFTCollectionHolder only
+    pub mint_policy: MintPolicy, // CoreTeam or PublicFixedPrice
     pub rock_index_from: u128,   // rock_index start from 1
     pub rock_index_to: u128,     // required to >= from
 }
@@ -104,6 +425,64 @@ enum StorageKey {
     Royalties,
     Metaverses,
     MetaverseOwner,
+    VoucherCollections,
+    EmergencyActions,
+    ZonesMinted,
+    FailedPayouts,
+    Purchases,
+    MintNonces,
+    RelayedMints,
+    MapCommitments,
+    Allowlists,
+    ZoneUsdPrices,
+    MetadataSchemas,
+    Moderators,
+    TokenFlags,
+    LastFlagAt,
+    RockAttributes,
+    AttributeWriters,
+    HostingPlans,
+    MintThrottles,
+    ZoneBlockMints,
+    AccountMintRings,
+    EscrowWindows,
+    EscrowedSales,
+    MetaverseThemes,
+    MetaverseVolumes,
+    GiftMessages,
+    FrozenMetadata,
+    MetaverseSlugs,
+    SlugMetaverses,
+    SlugAuctions,
+    ZoneRoyalties,
+    InstallmentPlans,
+    Installments,
+    Raffles,
+    Regions,
+    WrappedRocks,
+    ApprovalExpiries,
+    DeniedAccounts,
+    MintRequests,
+    MintRequestKeys,
+    MintListeners,
+    Airdrops,
+    AirdropClaims,
+    ZoneReceiverRestrictions,
+    ZoneReceivers,
+    RockHolds,
+    OwnedMetaverseCounts,
+    MetaverseOwnerRoyalties,
+    MetaverseTags,
+    MetaverseTagIndex,
+    HolderCounts,
+    TopHolders,
+    Campaigns,
+    CampaignClaims,
+    CampaignRegistrations,
+    EnvironmentCompatibility,
+    FaucetMints,
+    ZoneVisibility,
+    AccountPurchases { account_hash: CryptoHash },
 }
 
 #[near_bindgen]
@@ -117,58 +496,157 @@ impl Contract {
         rock_purchase_fee: u32, // 1 = 0.01% = 0.0001
         metadata: NFTContractMetadata,
     ) -> Self {
-        assert!(!env::state_exists(), "Already initialized");
-        assert!(rock_purchase_fee <= 10_000, "rock_purchase_fee must <= 10_000");
+        require!(!env::state_exists(), RoveError::AlreadyInitialized.as_str());
+        require!(
+            rock_purchase_fee <= 10_000,
+            RoveError::InvalidArgument.detail("rock_purchase_fee must <= 10_000")
+        );
         metadata.assert_valid();
         let init_imo_fee_in_128 = u128::from(init_imo_fee);
 
-        Self {
-            admin_id: admin_id.into(),
-            operator_id: operator_id.clone().into(),
-            treasury_id: treasury_id.into(),
-            init_imo_fee: init_imo_fee_in_128,
-            rock_purchase_fee,
-
-            royalties: UnorderedMap::new(StorageKey::Royalties),
-            metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
-            tokens_metadata: UnorderedMap::new(StorageKey::TokensMetadata),
-
-            metaverses: UnorderedMap::new(StorageKey::Metaverses),
-            metaverse_owners: UnorderedMap::new(StorageKey::MetaverseOwner),
-            tokens_minted: UnorderedMap::new(StorageKey::TokensMinted),
-
+        Self::from_old_state(OldContract {
             tokens: NonFungibleToken::new(
                 StorageKey::NonFungibleToken,
-                operator_id.clone().into(),
+                operator_id.clone(),
                 Some(StorageKey::TokenMetadata),
                 Some(StorageKey::Enumeration),
                 Some(StorageKey::Approval),
             ),
+            metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
+            royalties: UnorderedMap::new(StorageKey::Royalties),
+            tokens_metadata: UnorderedMap::new(StorageKey::TokensMetadata),
+            admin_id,
+            operator_id,
+            treasury_id,
+            init_imo_fee: init_imo_fee_in_128,
+            rock_purchase_fee,
+            metaverses: UnorderedMap::new(StorageKey::Metaverses),
+            metaverse_owners: UnorderedMap::new(StorageKey::MetaverseOwner),
+            tokens_minted: UnorderedMap::new(StorageKey::TokensMinted),
+        })
+    }
+
+    // state of a new contract or of a migrated 1.0 one, the fields added since 1.0 start empty
+    // or at their default. New state is added here so both paths initialize it
+    fn from_old_state(old: OldContract) -> Self {
+        Self {
+            tokens: old.tokens,
+            metadata: old.metadata,
+            royalties: old.royalties,
+            tokens_metadata: old.tokens_metadata,
+            admin_id: old.admin_id,
+            operator_id: old.operator_id,
+            treasury_id: old.treasury_id,
+            init_imo_fee: old.init_imo_fee,
+            rock_purchase_fee: old.rock_purchase_fee,
+            metaverses: old.metaverses,
+            metaverse_owners: old.metaverse_owners,
+            tokens_minted: old.tokens_minted,
+
+            fee_dust_policy: DustPolicy::MetaverseOwner,
+            voucher_collections: UnorderedMap::new(StorageKey::VoucherCollections),
+            emergency_actions: UnorderedMap::new(StorageKey::EmergencyActions),
+            zones_minted: UnorderedMap::new(StorageKey::ZonesMinted),
+            purchases: LookupMap::new(StorageKey::Purchases),
+            mint_nonces: UnorderedMap::new(StorageKey::MintNonces),
+            relayer_id: None,
+            relay_fund: 0,
+            relayed_mint_daily_limit: 0,
+            relayed_mints: UnorderedMap::new(StorageKey::RelayedMints),
+            map_commitments: UnorderedMap::new(StorageKey::MapCommitments),
+            allowlists: UnorderedMap::new(StorageKey::Allowlists),
+            price_oracle: None,
+            zone_usd_prices: UnorderedMap::new(StorageKey::ZoneUsdPrices),
+            metadata_schemas: UnorderedMap::new(StorageKey::MetadataSchemas),
+            moderators: UnorderedSet::new(StorageKey::Moderators),
+            token_flags: UnorderedMap::new(StorageKey::TokenFlags),
+            last_flag_at: UnorderedMap::new(StorageKey::LastFlagAt),
+            rock_attributes: LookupMap::new(StorageKey::RockAttributes),
+            attribute_writers: UnorderedMap::new(StorageKey::AttributeWriters),
+            hosting_plans: UnorderedMap::new(StorageKey::HostingPlans),
+            hosting_grace_period: DEFAULT_HOSTING_GRACE_PERIOD,
+            mint_throttles: UnorderedMap::new(StorageKey::MintThrottles),
+            zone_block_mints: LookupMap::new(StorageKey::ZoneBlockMints),
+            account_mint_rings: LookupMap::new(StorageKey::AccountMintRings),
+            escrow_windows: UnorderedMap::new(StorageKey::EscrowWindows),
+            escrowed_sales: UnorderedMap::new(StorageKey::EscrowedSales),
+            metaverse_themes: LookupMap::new(StorageKey::MetaverseThemes),
+            import_finalized: false,
+            fee_tiers: Vec::new(),
+            metaverse_volumes: LookupMap::new(StorageKey::MetaverseVolumes),
+            gift_messages: LookupMap::new(StorageKey::GiftMessages),
+            frozen_metadata: LookupMap::new(StorageKey::FrozenMetadata),
+            lock_attestation: None,
+            creation_gate: None,
+            metaverse_slugs: LookupMap::new(StorageKey::MetaverseSlugs),
+            slug_metaverses: LookupMap::new(StorageKey::SlugMetaverses),
+            slug_fee: DEFAULT_SLUG_FEE,
+            slug_auctions: UnorderedMap::new(StorageKey::SlugAuctions),
+            zone_royalties: LookupMap::new(StorageKey::ZoneRoyalties),
+            installment_plans: LookupMap::new(StorageKey::InstallmentPlans),
+            installments: LookupMap::new(StorageKey::Installments),
+            raffles: LookupMap::new(StorageKey::Raffles),
+            regions: LookupMap::new(StorageKey::Regions),
+            wrapped_rocks: LookupMap::new(StorageKey::WrappedRocks),
+            next_region_id: 0,
+            approval_expiries: LookupMap::new(StorageKey::ApprovalExpiries),
+            denied_accounts: UnorderedSet::new(StorageKey::DeniedAccounts),
+            mint_requests: LookupMap::new(StorageKey::MintRequests),
+            mint_request_keys: Vector::new(StorageKey::MintRequestKeys),
+            next_mint_request: 0,
+            mint_listeners: LookupMap::new(StorageKey::MintListeners),
+            airdrops: LookupMap::new(StorageKey::Airdrops),
+            airdrop_claims: LookupSet::new(StorageKey::AirdropClaims),
+            treasury_beneficiaries: Vec::new(),
+            zone_receiver_restrictions: LookupMap::new(StorageKey::ZoneReceiverRestrictions),
+            zone_receivers: LookupSet::new(StorageKey::ZoneReceivers),
+            rock_holds: LookupMap::new(StorageKey::RockHolds),
+            metaverse_limits: MetaverseLimits::default(),
+            owned_metaverse_counts: LookupMap::new(StorageKey::OwnedMetaverseCounts),
+            metaverse_owner_royalties: LookupMap::new(StorageKey::MetaverseOwnerRoyalties),
+            metaverse_tags: LookupMap::new(StorageKey::MetaverseTags),
+            metaverse_tag_index: LookupMap::new(StorageKey::MetaverseTagIndex),
+            holder_counts: LookupMap::new(StorageKey::HolderCounts),
+            top_holders: LookupMap::new(StorageKey::TopHolders),
+            max_deposit_multiplier: 0,
+            campaigns: LookupMap::new(StorageKey::Campaigns),
+            campaign_claims: LookupMap::new(StorageKey::CampaignClaims),
+            campaign_config: CampaignConfig::default(),
+            campaign_registrations: LookupMap::new(StorageKey::CampaignRegistrations),
+            environment_compatibility: LookupMap::new(StorageKey::EnvironmentCompatibility),
+            faucet_mode: None,
+            faucet_mints: LookupMap::new(StorageKey::FaucetMints),
+            quote_public_key: None,
+            failed_payouts: UnorderedMap::new(StorageKey::FailedPayouts),
+            next_payout_id: 0,
+            treasury_settlement: TreasurySettlement::Native,
+            zone_visibility: LookupMap::new(StorageKey::ZoneVisibility),
+            gas_profiling: false,
         }
     }
 
     fn assert_admin_only(&mut self) {
         // assert that the user attached greater than or equal 1 yoctoNEAR. This is for security and so that user will be redirected to the NEAR wallet
         assert_at_least_one_yocto();
-        assert_eq!(env::predecessor_account_id(), self.admin_id, "Unauthorized");
+        require!(
+            env::predecessor_account_id() == self.admin_id,
+            RoveError::Unauthorized.as_str()
+        );
     }
 
     fn assert_operator_only(&mut self) {
         // assert that the user attached greater than or equal 1 yoctoNEAR. This is for security and so that user will be redirected to the NEAR wallet
         assert_at_least_one_yocto();
-        assert_eq!(
-            env::predecessor_account_id(),
-            self.tokens.owner_id,
-            "Unauthorized"
+        require!(
+            env::predecessor_account_id() == self.tokens.owner_id,
+            RoveError::Unauthorized.as_str()
         );
     }
 
     fn assert_metaverse_exist(&self, metaverse_id: &String) -> Metaverse {
         self.metaverses
-            .get(&metaverse_id)
-            .expect(NOT_FOUND_METAVERSE_ID_ERROR);
-
-        self.metaverses.get(&metaverse_id).unwrap()
+            .get(metaverse_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::MetaverseNotFound.as_str()))
     }
 
     fn assert_zone_exist(&self, metaverse_id: &String, zone_index: u16) -> Zone {
@@ -178,7 +656,7 @@ impl Contract {
             .unwrap()
             .zones
             .get(&zone_index)
-            .expect(NOT_FOUND_ZONE_INDEX_ERROR);
+            .unwrap_or_else(|| env::panic_str(RoveError::ZoneNotFound.as_str()));
 
         let zone = self
             .metaverses
@@ -188,7 +666,7 @@ impl Contract {
             .get(&zone_index)
             .unwrap()
             .clone();
-        return zone;
+        zone
     }
 
     fn assert_metaverse_owner(&self, metaverse_id: &String) {
@@ -198,26 +676,38 @@ impl Contract {
         let metaverse_owner = self
             .metaverse_owners
             .get(metaverse_id)
-            .expect(NOT_FOUND_METAVERSE_ID_ERROR);
-        assert_eq!(
-            env::predecessor_account_id(),
-            metaverse_owner,
-            "Unauthorized"
+            .unwrap_or_else(|| env::panic_str(RoveError::MetaverseNotFound.as_str()));
+        require!(
+            env::predecessor_account_id() == metaverse_owner,
+            RoveError::Unauthorized.as_str()
         );
     }
 
     #[payable]
     pub fn change_rock_purchase_fee(&mut self, rock_purchase_fee: u32) {
         self.assert_operator_only();
-        assert!(rock_purchase_fee <= 10_000, "rock_purchase_fee must <= 10_000");
+        require!(
+            rock_purchase_fee <= 10_000,
+            RoveError::InvalidArgument.detail("rock_purchase_fee must <= 10_000")
+        );
         self.rock_purchase_fee = rock_purchase_fee;
     }
 
+    #[payable]
+    pub fn change_fee_dust_policy(&mut self, fee_dust_policy: DustPolicy) {
+        self.assert_operator_only();
+        self.fee_dust_policy = fee_dust_policy;
+    }
+
+    pub fn get_fee_dust_policy(&self) -> DustPolicy {
+        self.fee_dust_policy.clone()
+    }
+
     /// change contract's admin, only current contract's admin can call this function
     #[payable]
     pub fn change_admin(&mut self, new_admin_id: AccountId) {
         self.assert_admin_only();
-        self.admin_id = new_admin_id.into();
+        self.admin_id = new_admin_id;
     }
 
     #[payable]
@@ -225,13 +715,13 @@ impl Contract {
         self.assert_admin_only();
 
         self.tokens.owner_id = new_operator_id.clone();
-        self.operator_id = new_operator_id.into();
+        self.operator_id = new_operator_id;
     }
 
     #[payable]
     pub fn change_treasury(&mut self, new_treasury_id: AccountId) {
         self.assert_admin_only();
-        self.treasury_id = new_treasury_id.into();
+        self.treasury_id = new_treasury_id;
     }
 
     // Only operator can change init_imo_fee
@@ -249,6 +739,14 @@ impl Contract {
         updated_royalties: HashMap<AccountId, u16>,
     ) {
         self.assert_admin_only();
+        require!(
+            updated_royalties
+                .values()
+                .map(|royalty| u32::from(*royalty))
+                .sum::<u32>()
+                <= u32::from(ONE_HUNDRED_PERCENT_IN_BPS),
+            RoveError::InvalidArgument.detail("total royalties must <= 10_000")
+        );
         let initial_storage_usage = env::storage_usage();
         self.royalties.insert(&nft_type_id, &updated_royalties);
         if env::storage_usage() > initial_storage_usage {
@@ -271,17 +769,48 @@ impl Contract {
         self.treasury_id
     }
 
+    pub fn contract_version(&self) -> VersionInfo {
+        VersionInfo {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("BUILD_GIT_COMMIT").to_string(),
+            rustc_version: env!("BUILD_RUSTC_VERSION").to_string(),
+            build_target: env!("BUILD_TARGET").to_string(),
+            build_profile: env!("BUILD_PROFILE").to_string(),
+        }
+    }
+
+    pub fn get_config(&self) -> ContractConfig {
+        ContractConfig {
+            admin_id: self.admin_id.clone(),
+            operator_id: self.tokens.owner_id.clone(),
+            treasury_id: self.treasury_id.clone(),
+            init_imo_fee: U128(self.init_imo_fee),
+            rock_purchase_fee: self.rock_purchase_fee,
+            fee_dust_policy: self.fee_dust_policy.clone(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
     fn check_zone(&self, _zone: &Zone) -> bool {
         let zone_price = u128::from(_zone.price);
-        if _zone.type_zone != 3 {
-            return false;
-        }
         if _zone.rock_index_to == 0 {
             return false;
         }
 
-        if zone_price == 0 {
-            return false;
+        match _zone.mint_policy {
+            MintPolicy::CoreTeam => {
+                // free rocks minted by core_team_addr
+                if zone_price != 0 || _zone.core_team_addr.parse::<AccountId>().is_err() {
+                    return false;
+                }
+            }
+            MintPolicy::HolderGated => return false,
+            MintPolicy::PublicFixedPrice => {
+                if zone_price == 0 {
+                    return false;
+                }
+            }
         }
 
         if _zone.rock_index_from > _zone.rock_index_to {
@@ -294,38 +823,84 @@ impl Contract {
     // user init metaverse
     // user pay storage fee
     #[payable]
-    pub fn init_metaverse(&mut self, metaverse_id: String, zone3: Zone) {
+    pub fn init_metaverse(
+        &mut self,
+        metaverse_id: String,
+        zone3: Zone,
+    ) -> PromiseOrValue<Option<Zone>> {
+        self.gas_checkpoint("init_metaverse", "start");
         // Make sure metaverse_id does NOT exist
-        let metaverse_data = self.metaverses.get(&metaverse_id);
-        match metaverse_data {
-            Some(_metaverse) => {
-                env::panic_str("metaverse is already existed");
-            }
-            _ => {}
+        if self.metaverses.get(&metaverse_id).is_some() {
+            env::panic_str(RoveError::MetaverseAlreadyExists.as_str());
         }
-        require!(zone3.zone_index == 3, "zone_index must == 3");
-        require!(zone3.type_zone == 3, "must be public zone");
+        require!(
+            zone3.zone_index == 3,
+            RoveError::InvalidZone.detail("zone_index must == 3")
+        );
+        require!(
+            zone3.mint_policy == MintPolicy::PublicFixedPrice,
+            RoveError::InvalidZoneType.detail("must be public zone")
+        );
         // rock index = 1 for rove team
-        require!(zone3.rock_index_from == 2, "rock_index_from must = 2");
+        require!(
+            zone3.rock_index_from == 2,
+            RoveError::InvalidRockIndex.detail("rock_index_from must = 2")
+        );
 
         if zone3.rock_index_to < 2 || !self.check_zone(&zone3) {
-            env::panic_str("Z3_invalid")
+            env::panic_str(&RoveError::InvalidZone.detail("Z3_invalid"))
         }
+        self.assert_zone_rock_limit(zone_rock_size(&zone3));
+        self.assert_owner_metaverse_limit(&env::signer_account_id());
 
-        let initial_storage_usage = env::storage_usage();
         let total_rock_size: u128 = zone3.rock_index_to - zone3.rock_index_from + 1;
-        require!(total_rock_size > 0, "total_rock_size is invalid");
+        require!(
+            total_rock_size > 0,
+            RoveError::InvalidArgument.detail("total_rock_size is invalid")
+        );
 
         let total_init_imo_fee = self.init_imo_fee * total_rock_size;
+        let required_deposit = self.internal_init_metaverse_deposit(total_init_imo_fee);
         let attached_deposit = env::attached_deposit();
         require!(
-            total_init_imo_fee <= attached_deposit,
-            format!(
-                "Need {} yoctoNEAR to init metaverse with {} rocks ({} yoctoNEAR per rock)",
-                total_init_imo_fee, total_rock_size, self.init_imo_fee,
-            )
+            required_deposit <= attached_deposit,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR to init metaverse with {} rocks ({} yoctoNEAR per rock, \
+                 storage included)",
+                required_deposit, total_rock_size, self.init_imo_fee
+            ))
+        );
+
+        self.gas_checkpoint("init_metaverse", "validated");
+        // with a creation gate the metaverse is created by resolve_init_metaverse
+        if let Some(promise) =
+            self.internal_check_creation_gate(&metaverse_id, &zone3, attached_deposit)
+        {
+            self.gas_checkpoint("init_metaverse", "creation_gate_call");
+            return PromiseOrValue::Promise(promise);
+        }
+        self.internal_init_metaverse(
+            metaverse_id,
+            zone3.clone(),
+            env::predecessor_account_id(),
+            attached_deposit,
         );
-        let refund = attached_deposit - total_init_imo_fee;
+        self.gas_checkpoint("init_metaverse", "end");
+        PromiseOrValue::Value(Some(zone3))
+    }
+
+    // create the metaverse of an already validated zone3, the IMO fee is paid from attached_deposit
+    // and the rest is refunded to creator_id
+    pub(crate) fn internal_init_metaverse(
+        &mut self,
+        metaverse_id: String,
+        zone3: Zone,
+        creator_id: AccountId,
+        attached_deposit: Balance,
+    ) {
+        let initial_storage_usage = env::storage_usage();
+        let total_rock_size: u128 = zone_rock_size(&zone3);
+        let total_init_imo_fee = self.init_imo_fee * total_rock_size;
 
         let mut zones = HashMap::new();
         zones.insert(zone3.zone_index, zone3);
@@ -336,7 +911,7 @@ impl Contract {
             price: U128(0),
             core_team_addr: self.operator_id.to_string(),
             collection_addr: "".to_string(),
-            type_zone: 1,
+            mint_policy: MintPolicy::CoreTeam,
             rock_index_from: 1,
             rock_index_to: 1,
         };
@@ -346,19 +921,32 @@ impl Contract {
         self.metaverses.insert(&metaverse_id, &metaverse);
         self.metaverse_owners
             .insert(&metaverse_id, &env::signer_account_id());
+        self.internal_count_owned_metaverse(None, &env::signer_account_id());
         self.tokens_minted.insert(&metaverse_id, &HashMap::new());
 
         let storage_used = env::storage_usage() - initial_storage_usage;
         let storage_cost = env::storage_byte_cost() * Balance::from(storage_used);
+        // storage the IMO fee does not cover is paid by the caller (e.g. init_imo_fee == 0)
+        let storage_deposit = storage_cost.saturating_sub(total_init_imo_fee);
+        require!(
+            total_init_imo_fee + storage_deposit <= attached_deposit,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR to init metaverse ({} yoctoNEAR IMO fee + {} yoctoNEAR storage)",
+                total_init_imo_fee + storage_deposit,
+                total_init_imo_fee,
+                storage_deposit
+            ))
+        );
+        let refund = attached_deposit - total_init_imo_fee - storage_deposit;
 
         if refund > 0 {
-            Promise::new(env::predecessor_account_id()).transfer(refund);
+            self.internal_payout(creator_id, refund);
         }
 
         if total_init_imo_fee > storage_cost {
             let remain = total_init_imo_fee - storage_cost;
             if remain > 0 {
-                Promise::new(self.treasury_id.clone()).transfer(remain);
+                self.internal_pay_treasury(remain);
             }
         }
 
@@ -373,23 +961,30 @@ impl Contract {
             }]),
         };
 
-        env::log_str(&init_metaverse_log.to_string());
+        init_metaverse_log.emit();
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn _mint(
         &mut self,
         metaverse_id: String,
+        zone_index: u16,
         token_id: String,
         receiver_id: AccountId,
         token_metadata: TokenMetadata,
         token_price_str: U128,
+        attached_deposit: Balance,
     ) {
+        self.assert_not_denied(&receiver_id);
+        self.assert_not_denied(&env::signer_account_id());
+        self.assert_zone_receiver(&metaverse_id, zone_index, &receiver_id);
+        self.rock_holds.remove(&token_id);
         let initial_storage_usage = env::storage_usage();
         let token_price = u128::from(token_price_str);
-        let attached_deposit = env::attached_deposit();
         require!(
             token_price <= attached_deposit,
-            format!("Need {} yoctoNEAR to mint this rock", token_price)
+            RoveError::NotEnoughDeposit
+                .detail(format!("Need {} yoctoNEAR to mint this rock", token_price))
         );
         let refund = attached_deposit - token_price;
 
@@ -399,10 +994,26 @@ impl Contract {
             Some(token_metadata.clone()),
             None,
         );
+        self.internal_update_holding(&token_id, None, Some(&receiver_id));
 
         let mut token_minted = self.tokens_minted.get(&metaverse_id).unwrap();
         token_minted.insert(token.token_id.to_string(), true);
         self.tokens_minted.insert(&metaverse_id, &token_minted);
+        self.internal_increase_zone_minted(&metaverse_id, zone_index);
+        self.internal_throttle_mint(&metaverse_id, zone_index, &receiver_id);
+
+        // free core team, voucher and holder mints are not purchases
+        if token_price > 0 {
+            self.internal_record_purchase(
+                &receiver_id,
+                Purchase {
+                    token_id: token_id.clone(),
+                    metaverse_id: metaverse_id.clone(),
+                    price: token_price_str,
+                    timestamp: U64(env::block_timestamp()),
+                },
+            );
+        }
 
         /*
         if token_price == 0 (Rove team) => contract's account will pay storage cost
@@ -412,22 +1023,17 @@ impl Contract {
             let required_storage_cost = env::storage_byte_cost() * Balance::from(storage_used);
             if token_price > required_storage_cost {
                 let remain = token_price - required_storage_cost;
-                if self.rock_purchase_fee > 0 {
-                    let treasury_amount = remain * self.rock_purchase_fee as u128 / 10_000;
-                    let metaverse_owner_amount = remain - treasury_amount;
-                    if treasury_amount > 0 {
-                        Promise::new(self.treasury_id.clone()).transfer(treasury_amount);
-                    }
-                    if metaverse_owner_amount > 0 {
-                        let metaverse_owner = self.metaverse_owners.get(&metaverse_id).unwrap();
-                        Promise::new(metaverse_owner).transfer(metaverse_owner_amount);
-                    }
-                }
+                self.internal_distribute_sale_proceeds(
+                    &metaverse_id,
+                    &token_id,
+                    &receiver_id,
+                    remain,
+                );
             }
         }
 
         if refund > 0 {
-            Promise::new(env::predecessor_account_id()).transfer(refund);
+            self.internal_payout(env::predecessor_account_id(), refund);
         }
 
         // Construct the mint log as per the events standard.
@@ -441,15 +1047,66 @@ impl Contract {
             }]),
         };
 
-        env::log_str(&nft_mint_log.to_string());
+        nft_mint_log.emit();
+        let payer_id = env::predecessor_account_id();
+        emit_account_activity(vec![AccountActivityLog::new(
+            &receiver_id,
+            ActivityAction::Mint,
+            &token_id,
+            Some(&payer_id).filter(|payer_id| **payer_id != receiver_id),
+            Some(token_price).filter(|price| *price > 0),
+        )]);
+        self.internal_notify_mint_listeners(&metaverse_id, zone_index, &token_id, &receiver_id);
+    }
+
+    // pay the proceeds of a primary sale (price net of storage) to the treasury and the
+    // metaverse owner, or hold them in escrow when the metaverse has a refund window
+    pub(crate) fn internal_distribute_sale_proceeds(
+        &mut self,
+        metaverse_id: &String,
+        token_id: &TokenId,
+        buyer_id: &AccountId,
+        remain: Balance,
+    ) {
+        let rock_purchase_fee = self.internal_effective_fee(metaverse_id);
+        let (treasury_amount, metaverse_owner_amount) =
+            split_purchase_fee(remain, rock_purchase_fee, &self.fee_dust_policy);
+        self.internal_add_metaverse_volume(metaverse_id, remain);
+        let is_escrowed = self.internal_escrow_sale(
+            metaverse_id,
+            token_id,
+            buyer_id,
+            treasury_amount,
+            metaverse_owner_amount,
+        );
+        if !is_escrowed && treasury_amount > 0 {
+            self.internal_pay_treasury(treasury_amount);
+        }
+        if !is_escrowed && metaverse_owner_amount > 0 {
+            let metaverse_owner = self.metaverse_owners.get(metaverse_id).unwrap();
+            self.internal_payout(metaverse_owner, metaverse_owner_amount);
+        }
+    }
+
+    /// token_id a rock gets when minted, `{metaverse_id}:{zone_index}:{rock_index}` in both
+    /// rock contracts. The zone and rock do not have to exist
+    pub fn compute_token_id(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+    ) -> TokenId {
+        rock_token_id(&metaverse_id, zone_index, rock_index)
     }
 
+    /// deprecated, use get_zones
     pub fn get_zone_info(&self, metaverse_id: String, zone_index: u16) -> String {
+        log_deprecated_call("get_zone_info", None);
         let zone = self.assert_zone_exist(&metaverse_id, zone_index);
         format!(
             "{}, {}, {}, {}, {:?}, {}, {}",
             zone.zone_index,
-            zone.type_zone,
+            zone.mint_policy.type_zone(),
             zone.core_team_addr,
             zone.collection_addr,
             zone.price,
@@ -458,15 +1115,187 @@ impl Contract {
         )
     }
 
+    pub fn get_zone_remaining(&self, metaverse_id: String, zone_index: u16) -> U128 {
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        let minted = self.internal_zone_minted(&metaverse_id, zone_index);
+        U128(zone_rock_size(&zone).saturating_sub(minted))
+    }
+
+    pub fn is_zone_sold_out(&self, metaverse_id: String, zone_index: u16) -> bool {
+        self.get_zone_remaining(metaverse_id, zone_index).0 == 0
+    }
+
+    /// unminted rock indexes of a zone on sale in ascending order, rocks held by the operator
+    /// are left out. from_index is a rock index, pass the last returned index + 1 to read the
+    /// next page. limit defaults to and is capped at 500
+    pub fn get_unminted_rocks(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<u128> {
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        let start_index = from_index
+            .map(u128::from)
+            .unwrap_or_default()
+            .max(zone.rock_index_from);
+        let limit = limit
+            .unwrap_or(MAX_UNMINTED_ROCKS_LIMIT)
+            .min(MAX_UNMINTED_ROCKS_LIMIT) as usize;
+        let tokens_minted = self.tokens_minted.get(&metaverse_id).unwrap_or_default();
+        (start_index..=zone.rock_index_to)
+            .filter(|rock_index| {
+                let token_id = rock_token_id(&metaverse_id, zone_index, *rock_index);
+                !tokens_minted.contains_key(&token_id)
+                    && self.internal_rock_hold(&token_id).is_none()
+            })
+            .take(limit)
+            .collect()
+    }
+
+    fn internal_zone_minted(&self, metaverse_id: &String, zone_index: u16) -> u128 {
+        self.zones_minted
+            .get(metaverse_id)
+            .and_then(|zones_minted| zones_minted.get(&zone_index).cloned())
+            .unwrap_or(0)
+    }
+
+    // count the minted rock and emit ZoneSoldOut when the last rock of the zone is minted
+    fn internal_increase_zone_minted(&mut self, metaverse_id: &String, zone_index: u16) {
+        let zone = self.assert_zone_exist(metaverse_id, zone_index);
+        let mut zones_minted = self.zones_minted.get(metaverse_id).unwrap_or_default();
+        let minted = zones_minted.get(&zone_index).cloned().unwrap_or(0) + 1;
+        zones_minted.insert(zone_index, minted);
+        self.zones_minted.insert(metaverse_id, &zones_minted);
+
+        if minted == zone_rock_size(&zone) {
+            let zone_sold_out_log: EventLog = EventLog {
+                standard: "public_imo_zone_sold_out".to_string(),
+                version: "1.0.0".to_string(),
+                event: EventLogVariant::ZoneSoldOut(vec![ZoneSoldOutLog {
+                    metaverse_id: metaverse_id.to_string(),
+                    zone_index,
+                    memo: None,
+                }]),
+            };
+
+            zone_sold_out_log.emit();
+        }
+    }
+
+    // reverse _mint: remove a rock of owner_id with its approvals, attributes and purchase and
+    // give it back to its zone. minter_id is the account the mint was counted for, the buyer
+    // of a rock the contract holds for an installment plan
+    pub(crate) fn internal_burn(
+        &mut self,
+        metaverse_id: &String,
+        zone_index: u16,
+        token_id: &TokenId,
+        owner_id: &AccountId,
+        minter_id: &AccountId,
+    ) {
+        let token_owner_id = self
+            .tokens
+            .owner_by_id
+            .get(token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        require!(
+            &token_owner_id == owner_id,
+            RoveError::NotTokenOwner.as_str()
+        );
+
+        self.internal_update_holding(token_id, Some(owner_id), None);
+        self.tokens.owner_by_id.remove(token_id);
+        if let Some(token_metadata_by_id) = &mut self.tokens.token_metadata_by_id {
+            token_metadata_by_id.remove(token_id);
+        }
+        if let Some(tokens_per_owner) = &mut self.tokens.tokens_per_owner {
+            let mut owner_tokens = tokens_per_owner
+                .get(owner_id)
+                .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+            owner_tokens.remove(token_id);
+            if owner_tokens.is_empty() {
+                tokens_per_owner.remove(owner_id);
+            } else {
+                tokens_per_owner.insert(owner_id, &owner_tokens);
+            }
+        }
+        if let Some(approvals_by_id) = &mut self.tokens.approvals_by_id {
+            approvals_by_id.remove(token_id);
+        }
+        if let Some(next_approval_id_by_id) = &mut self.tokens.next_approval_id_by_id {
+            next_approval_id_by_id.remove(token_id);
+        }
+        self.rock_attributes.remove(token_id);
+        self.frozen_metadata.remove(token_id);
+        self.gift_messages.remove(token_id);
+
+        if let Some(mut purchases) = self.purchases.get(minter_id) {
+            // keep the history in order, the burnt rock is usually the last purchase
+            if let Some(index) = purchases
+                .iter()
+                .position(|purchase| purchase.token_id == *token_id)
+            {
+                for next_index in index as u64 + 1..purchases.len() {
+                    purchases.replace(next_index - 1, &purchases.get(next_index).unwrap());
+                }
+                purchases.pop();
+            }
+            if purchases.is_empty() {
+                self.purchases.remove(minter_id);
+            } else {
+                self.purchases.insert(minter_id, &purchases);
+            }
+        }
+        let mut tokens_minted = self.tokens_minted.get(metaverse_id).unwrap();
+        tokens_minted.remove(token_id);
+        self.tokens_minted.insert(metaverse_id, &tokens_minted);
+        let mut zones_minted = self.zones_minted.get(metaverse_id).unwrap_or_default();
+        if let Some(minted) = zones_minted.get_mut(&zone_index) {
+            *minted = minted.saturating_sub(1);
+        }
+        self.zones_minted.insert(metaverse_id, &zones_minted);
+        self.internal_unthrottle_mint(metaverse_id, zone_index, minter_id);
+    }
+
+    pub fn get_purchases_for_account(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<Purchase> {
+        let purchases = match self.purchases.get(&account_id) {
+            Some(purchases) => purchases,
+            None => return vec![],
+        };
+        let start_index = u128::from(from_index.unwrap_or(U128(0))).min(purchases.len() as u128);
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        (start_index as u64..purchases.len())
+            .take(limit)
+            .filter_map(|index| purchases.get(index))
+            .collect()
+    }
+
+    pub(crate) fn internal_record_purchase(&mut self, account_id: &AccountId, purchase: Purchase) {
+        let mut purchases = self.purchases.get(account_id).unwrap_or_else(|| {
+            Vector::new(StorageKey::AccountPurchases {
+                account_hash: sha256_hash(account_id.as_bytes()),
+            })
+        });
+        purchases.push(&purchase);
+        self.purchases.insert(account_id, &purchases);
+    }
+
     pub fn get_init_imo_fee(&self) -> U128 {
-        return U128::from(self.init_imo_fee);
+        U128::from(self.init_imo_fee)
     }
 
+    /// deprecated, use change_init_imo_fee
     #[payable]
     pub fn update_init_imo_fee(&mut self, init_imo_fee: U128) {
-        self.assert_operator_only();
-        let init_imo_fee_u128 = u128::from(init_imo_fee);
-        self.init_imo_fee = init_imo_fee_u128;
+        self.change_init_imo_fee(init_imo_fee);
+        log_deprecated_call("update_init_imo_fee", Some(env::predecessor_account_id()));
     }
 
     #[payable]
@@ -478,8 +1307,11 @@ impl Contract {
     ) {
         self.assert_metaverse_owner(&metaverse_id);
         let mut zone = self.assert_zone_exist(&metaverse_id, zone_index);
-        assert_eq!(zone.type_zone, 3, "type_zone is invalid");
-        assert!(zone.rock_index_to > 0, "rock_index_to invalid");
+        require!(zone.mint_policy.is_priced(), RoveError::InvalidZoneType.as_str());
+        require!(
+            zone.rock_index_to > 0,
+            RoveError::InvalidRockIndex.detail("rock_index_to invalid")
+        );
         let initial_storage_usage = env::storage_usage();
         let mut metaverse = self.metaverses.get(&metaverse_id).unwrap();
         zone.price = price;
@@ -493,36 +1325,64 @@ impl Contract {
             );
         }
 
-        let imo_change_zone_price: EventLog = EventLog {
-            standard: "imo_change_zone_price".to_string(),
-            version: "1.1.0".to_string(),
-            event: EventLogVariant::ImoChangeZonePrice(vec![ImoChangeZonePrice {
-                metaverse_id,
-                zone_index,
-                new_price: price,
-                memo: Some(String::from("change_zone_price")),
-            }]),
-        };
+        emit_imo_change_zone_price(metaverse_id, zone_index, price, "change_zone_price");
+    }
+
+    #[payable]
+    pub fn batch_update_zone_prices(&mut self, metaverse_id: String, updates: Vec<(u16, U128)>) {
+        self.assert_metaverse_owner(&metaverse_id);
+        require!(
+            !updates.is_empty(),
+            RoveError::EmptyBatch.detail("updates is empty")
+        );
+        let initial_storage_usage = env::storage_usage();
+        let mut metaverse = self.metaverses.get(&metaverse_id).unwrap();
+        for (zone_index, price) in updates.iter() {
+            let mut zone = metaverse
+                .zones
+                .get(zone_index)
+                .cloned()
+                .unwrap_or_else(|| env::panic_str(RoveError::ZoneNotFound.as_str()));
+            require!(zone.mint_policy.is_priced(), RoveError::InvalidZoneType.as_str());
+            require!(
+                zone.rock_index_to > 0,
+                RoveError::InvalidRockIndex.detail("rock_index_to invalid")
+            );
+            zone.price = *price;
+            metaverse.zones.insert(*zone_index, zone);
+        }
+        self.metaverses.insert(&metaverse_id, &metaverse);
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::signer_account_id(),
+            );
+        }
 
-        env::log_str(&imo_change_zone_price.to_string());
+        for (zone_index, price) in updates {
+            emit_imo_change_zone_price(
+                metaverse_id.clone(),
+                zone_index,
+                price,
+                "batch_update_zone_prices",
+            );
+        }
     }
 
     #[payable]
     pub fn add_zone(&mut self, metaverse_id: String, _zone: Zone) {
         let metaverse = self.assert_metaverse_exist(&metaverse_id);
-        let zone_checker = metaverse.zones.get(&_zone.zone_index);
-        match zone_checker {
-            Some(_zone) => {
-                env::panic_str("zone_index is already existed");
-            }
-            _ => {}
+        if metaverse.zones.contains_key(&_zone.zone_index) {
+            env::panic_str(RoveError::ZoneAlreadyExists.as_str());
         }
 
         self.assert_metaverse_owner(&metaverse_id);
 
         if !self.check_zone(&_zone) {
-            env::panic_str("zone is invalid");
+            env::panic_str(RoveError::InvalidZone.as_str());
         }
+        self.assert_zone_rock_limit(zone_rock_size(&_zone));
+        self.assert_zone_count_limit(metaverse.zones.len() + 1);
 
         let mut zones = metaverse.zones;
         let total_rock_size: u128 = _zone.rock_index_to - _zone.rock_index_from + 1;
@@ -534,29 +1394,35 @@ impl Contract {
         let attached_deposit = env::attached_deposit();
         require!(
             total_add_zone_fee <= attached_deposit,
-            format!(
+            RoveError::NotEnoughDeposit.detail(format!(
                 "Need {} yoctoNEAR to add zone with {} rocks ({} yoctoNEAR per rock)",
-                total_add_zone_fee, total_rock_size, self.init_imo_fee,
-            )
+                total_add_zone_fee, total_rock_size, self.init_imo_fee
+            ))
         );
 
-        let refund = attached_deposit - total_add_zone_fee;
         let initial_storage_usage = env::storage_usage();
         zones.insert(_zone.zone_index, _zone.clone());
         let metaverse = Metaverse { zones };
         self.metaverses.insert(&metaverse_id, &metaverse);
 
-        if refund > 0 {
-            Promise::new(env::predecessor_account_id()).transfer(refund);
-        }
-
+        // storage of the zone is charged to the caller on top of the IMO fee
         let storage_used = env::storage_usage() - initial_storage_usage;
         let storage_cost = env::storage_byte_cost() * Balance::from(storage_used);
-        if total_add_zone_fee > storage_cost {
-            let remain = total_add_zone_fee - storage_cost;
-            if remain > 0 {
-                Promise::new(self.treasury_id.clone()).transfer(remain);
-            }
+        require!(
+            total_add_zone_fee + storage_cost <= attached_deposit,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR to add zone ({} yoctoNEAR IMO fee + {} yoctoNEAR storage)",
+                total_add_zone_fee + storage_cost,
+                total_add_zone_fee,
+                storage_cost
+            ))
+        );
+        if total_add_zone_fee > 0 {
+            self.internal_pay_treasury(total_add_zone_fee);
+        }
+        let refund = attached_deposit - total_add_zone_fee - storage_cost;
+        if refund > 0 {
+            self.internal_payout(env::predecessor_account_id(), refund);
         }
 
         let add_zone_log: EventLog = EventLog {
@@ -569,7 +1435,7 @@ impl Contract {
                 price: _zone.price,
                 core_team_addr: _zone.core_team_addr,
                 collection_addr: _zone.collection_addr,
-                type_zone: _zone.type_zone,
+                type_zone: _zone.mint_policy.type_zone(),
                 rock_index_from: _zone.rock_index_from,
                 rock_index_to: _zone.rock_index_to,
                 rock_size: total_rock_size,
@@ -577,58 +1443,105 @@ impl Contract {
             }]),
         };
 
-        env::log_str(&add_zone_log.to_string());
+        add_zone_log.emit();
     }
 
-    #[payable]
-    pub fn mint_rock(
-        &mut self,
-        metaverse_id: String,
+    // validate that rock_index belongs to zone and was not minted yet, returns zone and token_id
+    fn assert_rock_mintable(
+        &self,
+        metaverse_id: &String,
         zone_index: u16,
         rock_index: u128,
-        receiver_id: AccountId,
-        token_metadata: TokenMetadata,
-    ) {
-        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
-        assert!(
+    ) -> (Zone, String) {
+        let zone = self.assert_zone_exist(metaverse_id, zone_index);
+        self.assert_zone_visible(metaverse_id, &zone);
+        require!(
             zone.rock_index_from > 0 && zone.rock_index_to > 0,
-            "zone rock index invalid"
+            RoveError::InvalidRockIndex.as_str()
         );
-        assert!(
+        require!(
             zone.rock_index_from <= rock_index && rock_index <= zone.rock_index_to,
-            "rock_index invalid"
+            RoveError::InvalidRockIndex.as_str()
         );
 
-        let token_id = gen_token_id(&metaverse_id, zone_index, rock_index);
-        let tokens_minted = self.tokens_minted.get(&metaverse_id).unwrap();
-        let tokens_minted_checker = tokens_minted.get(&token_id);
-        match tokens_minted_checker {
-            Some(_token_minted) => env::panic_str("token_id is existed"),
-            _ => {}
+        self.assert_rock_not_raffled(metaverse_id, zone_index, rock_index);
+        self.assert_zone_not_airdropped(metaverse_id, zone_index);
+
+        let token_id = rock_token_id(metaverse_id, zone_index, rock_index);
+        let tokens_minted = self.tokens_minted.get(metaverse_id).unwrap();
+        if tokens_minted.contains_key(&token_id) {
+            env::panic_str(RoveError::TokenAlreadyExists.as_str());
         }
+        self.assert_rock_not_held(&token_id);
+        (zone, token_id)
+    }
 
-        if zone.type_zone == 1 {
-            assert_eq!(
-                zone.core_team_addr,
-                env::predecessor_account_id().to_string(),
-                "require core team call this mint"
-            );
-        } else if zone.type_zone == 3 {
-            let zone_price = u128::from(zone.price);
-            if zone_price <= 0 {
-                env::panic_str("missing price for public zone");
+    // request_id: retries with the same request_id return the minted token_id
+    // returns the minted token_id, None when the mint waits for the price oracle
+    #[payable]
+    pub fn mint_rock(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        receiver_id: AccountId,
+        token_metadata: TokenMetadata,
+        request_id: Option<String>,
+    ) -> Option<TokenId> {
+        self.gas_checkpoint("mint_rock", "start");
+        if let Some(token_id) = self.internal_replay_mint_request(
+            &request_id,
+            &metaverse_id,
+            zone_index,
+            rock_index,
+            &receiver_id,
+        ) {
+            return Some(token_id);
+        }
+        let (zone, token_id) = self.assert_rock_mintable(&metaverse_id, zone_index, rock_index);
+        self.internal_record_mint_request(&request_id, &token_id);
+
+        match zone.mint_policy {
+            MintPolicy::CoreTeam => {
+                require!(
+                    zone.core_team_addr == env::predecessor_account_id().to_string(),
+                    RoveError::NotCoreTeam.as_str()
+                );
+                self.assert_deposit_accepted(0, 1);
+            }
+            MintPolicy::HolderGated => env::panic_str(RoveError::InvalidZoneType.as_str()),
+            MintPolicy::PublicFixedPrice => {
+                let zone_price = u128::from(zone.price);
+                if zone_price == 0 {
+                    env::panic_str(RoveError::MissingZonePrice.as_str());
+                }
+                // a USD zone is guarded against its NEAR price before the oracle call
+                self.assert_deposit_accepted(zone_price, 1);
+                if self.internal_mint_rock_in_usd(
+                    &metaverse_id,
+                    zone_index,
+                    rock_index,
+                    &receiver_id,
+                    &token_metadata,
+                ) {
+                    self.gas_checkpoint("mint_rock", "oracle_call");
+                    return None;
+                }
             }
-        } else {
-            env::panic_str("does not support zone");
         }
 
+        self.gas_checkpoint("mint_rock", "validated");
         self._mint(
             metaverse_id.clone(),
+            zone_index,
             token_id.clone(),
             receiver_id.clone(),
             token_metadata.clone(),
             zone.price,
+            env::attached_deposit(),
         );
+        self.gas_checkpoint("mint_rock", "end");
+        Some(token_id)
     }
 
     #[payable]
@@ -638,8 +1551,6 @@ impl Contract {
     }
 }
 
-near_contract_standards::impl_non_fungible_token_core!(Contract, tokens);
-near_contract_standards::impl_non_fungible_token_approval!(Contract, tokens);
 near_contract_standards::impl_non_fungible_token_enumeration!(Contract, tokens);
 
 #[near_bindgen]
@@ -648,3 +1559,215 @@ impl NonFungibleTokenMetadataProvider for Contract {
         self.metadata.get().unwrap()
     }
 }
+
+#[cfg(test)]
+mod settlement_tests;
+
+#[cfg(test)]
+mod tests {
+    use near_contract_standards::non_fungible_token::metadata::NFT_METADATA_SPEC;
+    use near_sdk::mock::VmAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts, VMContextBuilder};
+    use near_sdk::{testing_env, ONE_NEAR};
+
+    use super::*;
+
+    pub(crate) const METAVERSE_ID: &str = "m1";
+
+    pub(crate) fn context(
+        predecessor_id: AccountId,
+        attached_deposit: Balance,
+    ) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .predecessor_account_id(predecessor_id)
+            .attached_deposit(attached_deposit);
+        builder
+    }
+
+    // admin accounts(0), operator accounts(1), treasury accounts(2), metaverse owner accounts(3)
+    pub(crate) fn setup(init_imo_fee: Balance) -> Contract {
+        testing_env!(context(accounts(0), 0).build());
+        let mut contract = Contract::new(
+            accounts(0),
+            accounts(1),
+            accounts(2),
+            U128(init_imo_fee),
+            250,
+            NFTContractMetadata {
+                spec: NFT_METADATA_SPEC.to_string(),
+                name: "Rocks".to_string(),
+                symbol: "ROCK".to_string(),
+                icon: None,
+                base_uri: None,
+                reference: None,
+                reference_hash: None,
+            },
+        );
+        contract.metaverses.insert(
+            &METAVERSE_ID.to_string(),
+            &Metaverse {
+                zones: HashMap::new(),
+            },
+        );
+        contract
+            .metaverse_owners
+            .insert(&METAVERSE_ID.to_string(), &accounts(3));
+        contract
+    }
+
+    pub(crate) fn public_zone(zone_index: u16, rock_index_from: u128, rock_index_to: u128) -> Zone {
+        Zone {
+            zone_index,
+            price: U128(ONE_NEAR),
+            core_team_addr: String::new(),
+            collection_addr: String::new(),
+            mint_policy: MintPolicy::PublicFixedPrice,
+            rock_index_from,
+            rock_index_to,
+        }
+    }
+
+    pub(crate) fn rock_metadata() -> TokenMetadata {
+        TokenMetadata {
+            title: Some("rock".to_string()),
+            description: None,
+            media: None,
+            media_hash: None,
+            copies: None,
+            issued_at: None,
+            expires_at: None,
+            starts_at: None,
+            updated_at: None,
+            extra: None,
+            reference: None,
+            reference_hash: None,
+        }
+    }
+
+    // amounts transferred to account_id by the receipts of the last call
+    pub(crate) fn transfers_to(account_id: &AccountId) -> Vec<Balance> {
+        get_created_receipts()
+            .into_iter()
+            .filter(|receipt| receipt.receiver_id == *account_id)
+            .flat_map(|receipt| receipt.actions)
+            .filter_map(|action| match action {
+                VmAction::Transfer { deposit } => Some(deposit),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn add_zone_without_imo_fee_charges_storage_and_refunds() {
+        let mut contract = setup(0);
+        testing_env!(context(accounts(3), ONE_NEAR).build());
+        contract.add_zone(METAVERSE_ID.to_string(), public_zone(2, 1, 10));
+
+        assert!(transfers_to(&accounts(2)).is_empty());
+        let refunds = transfers_to(&accounts(3));
+        assert_eq!(refunds.len(), 1);
+        let storage_cost = ONE_NEAR - refunds[0];
+        assert!(storage_cost > 0);
+        assert_eq!(storage_cost % env::storage_byte_cost(), 0);
+    }
+
+    #[test]
+    fn get_unminted_rocks_limit_is_capped() {
+        let mut contract = setup(0);
+        testing_env!(context(accounts(3), ONE_NEAR).build());
+        contract.add_zone(METAVERSE_ID.to_string(), public_zone(2, 1, 600));
+
+        let page = contract.get_unminted_rocks(METAVERSE_ID.to_string(), 2, None, None);
+        assert_eq!(page.len(), 500);
+        assert_eq!(page[499], 500);
+        let page = contract.get_unminted_rocks(METAVERSE_ID.to_string(), 2, None, Some(10_000));
+        assert_eq!(page.len(), 500);
+        let page = contract.get_unminted_rocks(METAVERSE_ID.to_string(), 2, Some(U128(501)), None);
+        assert_eq!(page, (501..=600).collect::<Vec<u128>>());
+    }
+
+    #[test]
+    fn purchases_record_paid_mints_in_order() {
+        let mut contract = setup(0);
+        testing_env!(context(accounts(3), ONE_NEAR).build());
+        contract.add_zone(METAVERSE_ID.to_string(), public_zone(2, 1, 10));
+        contract
+            .tokens_minted
+            .insert(&METAVERSE_ID.to_string(), &HashMap::new());
+
+        testing_env!(context(accounts(4), ONE_NEAR).build());
+        for rock_index in 1..=4 {
+            // rock 2 is a free core team mint
+            let price = if rock_index == 2 { 0 } else { ONE_NEAR };
+            contract._mint(
+                METAVERSE_ID.to_string(),
+                2,
+                rock_token_id(METAVERSE_ID, 2, rock_index),
+                accounts(4),
+                rock_metadata(),
+                U128(price),
+                price,
+            );
+        }
+
+        let token_ids = |purchases: Vec<Purchase>| -> Vec<TokenId> {
+            purchases
+                .into_iter()
+                .map(|purchase| purchase.token_id)
+                .collect()
+        };
+        assert_eq!(
+            token_ids(contract.get_purchases_for_account(accounts(4), None, None)),
+            vec![
+                rock_token_id(METAVERSE_ID, 2, 1),
+                rock_token_id(METAVERSE_ID, 2, 3),
+                rock_token_id(METAVERSE_ID, 2, 4),
+            ]
+        );
+        assert_eq!(
+            token_ids(contract.get_purchases_for_account(accounts(4), Some(U128(1)), Some(1))),
+            vec![rock_token_id(METAVERSE_ID, 2, 3)]
+        );
+        assert!(contract
+            .get_purchases_for_account(accounts(4), Some(U128(10)), None)
+            .is_empty());
+        assert!(contract
+            .get_purchases_for_account(accounts(5), None, None)
+            .is_empty());
+
+        contract.internal_burn(
+            &METAVERSE_ID.to_string(),
+            2,
+            &rock_token_id(METAVERSE_ID, 2, 3),
+            &accounts(4),
+            &accounts(4),
+        );
+        assert_eq!(
+            token_ids(contract.get_purchases_for_account(accounts(4), None, None)),
+            vec![
+                rock_token_id(METAVERSE_ID, 2, 1),
+                rock_token_id(METAVERSE_ID, 2, 4),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR2001")]
+    fn add_zone_without_imo_fee_requires_storage_deposit() {
+        let mut contract = setup(0);
+        testing_env!(context(accounts(3), 1).build());
+        contract.add_zone(METAVERSE_ID.to_string(), public_zone(2, 1, 10));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR5000")]
+    fn update_royalties_above_100_percent_panics() {
+        let mut contract = setup(0);
+        testing_env!(context(accounts(0), 1).build());
+        contract.update_royalties(
+            "rock".to_string(),
+            HashMap::from([(accounts(2), 6_000), (accounts(3), 5_000)]),
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::*;
+
+// operator caps bounding metaverse storage and the size of the zones map read by every mint,
+// 0 disables a cap
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetaverseLimits {
+    // zones of a metaverse, the operator zone included
+    pub max_zones_per_metaverse: u16,
+    // rocks of one zone range
+    pub max_rocks_per_zone: U128,
+    // metaverses owned by one account
+    pub max_metaverses_per_owner: u32,
+}
+
+impl Default for MetaverseLimits {
+    fn default() -> Self {
+        Self {
+            max_zones_per_metaverse: 0,
+            max_rocks_per_zone: U128(0),
+            max_metaverses_per_owner: 0,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator sets the caps checked by init_metaverse and add_zone. Metaverses already above
+    /// a new cap keep their zones and rocks
+    #[payable]
+    pub fn set_metaverse_limits(&mut self, limits: MetaverseLimits) {
+        self.assert_operator_only();
+        require!(
+            limits.max_zones_per_metaverse == 0
+                || limits.max_zones_per_metaverse >= INIT_METAVERSE_ZONE_COUNT,
+            RoveError::InvalidArgument.detail(format!(
+                "max_zones_per_metaverse must be 0 or >= {}",
+                INIT_METAVERSE_ZONE_COUNT
+            ))
+        );
+        self.metaverse_limits = limits;
+    }
+
+    pub fn get_metaverse_limits(&self) -> MetaverseLimits {
+        self.metaverse_limits.clone()
+    }
+
+    // metaverses counted against max_metaverses_per_owner, the ones created, imported or
+    // received since the caps exist
+    pub fn get_owned_metaverse_count(&self, account_id: AccountId) -> u32 {
+        self.owned_metaverse_counts
+            .get(&account_id)
+            .unwrap_or_default()
+    }
+
+    // panics when a metaverse of zone_count zones would be above max_zones_per_metaverse
+    pub(crate) fn assert_zone_count_limit(&self, zone_count: usize) {
+        let max_zones = self.metaverse_limits.max_zones_per_metaverse;
+        require!(
+            max_zones == 0 || zone_count <= max_zones as usize,
+            RoveError::MetaverseLimitReached
+                .detail(format!("a metaverse has at most {} zones", max_zones))
+        );
+    }
+
+    // panics when a zone of rock_size rocks would be above max_rocks_per_zone
+    pub(crate) fn assert_zone_rock_limit(&self, rock_size: u128) {
+        let max_rocks = self.metaverse_limits.max_rocks_per_zone.0;
+        require!(
+            max_rocks == 0 || rock_size <= max_rocks,
+            RoveError::MetaverseLimitReached.detail(format!(
+                "zone has {} rocks, a zone has at most {}",
+                rock_size, max_rocks
+            ))
+        );
+    }
+
+    // panics when owner_id can not own one more metaverse
+    pub(crate) fn assert_owner_metaverse_limit(&self, owner_id: &AccountId) {
+        let max_metaverses = self.metaverse_limits.max_metaverses_per_owner;
+        require!(
+            max_metaverses == 0
+                || self.get_owned_metaverse_count(owner_id.clone()) < max_metaverses,
+            RoveError::MetaverseLimitReached.detail(format!(
+                "{} already owns {} metaverses",
+                owner_id, max_metaverses
+            ))
+        );
+    }
+
+    // move one metaverse of the owned counts from previous_owner_id (None for a new metaverse)
+    // to owner_id
+    pub(crate) fn internal_count_owned_metaverse(
+        &mut self,
+        previous_owner_id: Option<&AccountId>,
+        owner_id: &AccountId,
+    ) {
+        if let Some(previous_owner_id) = previous_owner_id {
+            match self.owned_metaverse_counts.get(previous_owner_id) {
+                Some(count) if count > 1 => {
+                    self.owned_metaverse_counts
+                        .insert(previous_owner_id, &(count - 1));
+                }
+                Some(_) => {
+                    self.owned_metaverse_counts.remove(previous_owner_id);
+                }
+                None => {}
+            }
+        }
+        let count = self.get_owned_metaverse_count(owner_id.clone());
+        self.owned_metaverse_counts.insert(owner_id, &(count + 1));
+    }
+}
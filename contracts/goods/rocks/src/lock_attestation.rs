@@ -0,0 +1,53 @@
+use near_sdk::json_types::{Base58CryptoHash, U64};
+
+use crate::*;
+
+// record that the contract account was verified to have no access keys left
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct LockAttestation {
+    pub verified_by: AccountId,
+    pub verified_at: U64,
+    pub block_height: U64,
+    // code_hash of the contract account (view_account) at verification time
+    pub code_hash: Base58CryptoHash,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// admin attests that the contract account has no full access keys, so the code with
+    /// code_hash can not be redeployed. A contract can not list its own keys, the admin checks
+    /// them with view_access_key_list, a call signed by the contract account itself is rejected
+    #[payable]
+    pub fn assert_no_full_access_keys(&mut self, code_hash: Base58CryptoHash) {
+        self.assert_admin_only();
+        require!(
+            env::signer_account_id() != env::current_account_id(),
+            RoveError::ContractNotLocked.as_str()
+        );
+
+        let lock_attestation = LockAttestation {
+            verified_by: env::predecessor_account_id(),
+            verified_at: U64(env::block_timestamp()),
+            block_height: U64(env::block_height()),
+            code_hash,
+        };
+        self.lock_attestation = Some(lock_attestation.clone());
+
+        let contract_locked_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::ContractLocked(vec![ContractLockedLog {
+                verified_by: lock_attestation.verified_by.to_string(),
+                code_hash: lock_attestation.code_hash,
+                block_height: lock_attestation.block_height,
+                memo: None,
+            }]),
+        };
+        contract_locked_log.emit();
+    }
+
+    pub fn get_lock_attestation(&self) -> Option<LockAttestation> {
+        self.lock_attestation.clone()
+    }
+}
@@ -0,0 +1,139 @@
+use crate::*;
+
+pub const ONE_DAY_IN_NANOSECONDS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+// relayed mints of an account during one day
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RelayedMintCounter {
+    pub day: u64,
+    pub count: u32,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator sets the relayer account allowed to submit NEP-366 delegate actions
+    #[payable]
+    pub fn set_relayer(&mut self, relayer_id: Option<AccountId>, daily_limit: u32) {
+        self.assert_operator_only();
+        self.relayer_id = relayer_id;
+        self.relayed_mint_daily_limit = daily_limit;
+    }
+
+    /// top up the fund paying price and storage of relayed mints
+    #[payable]
+    pub fn deposit_relay_fund(&mut self) {
+        assert_at_least_one_yocto();
+        let caller_id = env::predecessor_account_id();
+        require!(
+            caller_id == self.tokens.owner_id || Some(caller_id) == self.relayer_id,
+            RoveError::Unauthorized.as_str()
+        );
+        self.relay_fund += env::attached_deposit();
+    }
+
+    #[payable]
+    pub fn withdraw_relay_fund(&mut self, amount: U128) {
+        self.assert_operator_only();
+        require!(
+            amount.0 <= self.relay_fund,
+            RoveError::NotEnoughRelayFund.as_str()
+        );
+        self.relay_fund -= amount.0;
+        self.internal_payout(env::predecessor_account_id(), amount.0);
+    }
+
+    /// Mint a rock for an account without NEAR: the account signs a NEP-366 delegate action
+    /// calling this method, the relayer submits it and the relay fund pays the rock.
+    /// `nonce` must be get_nonce(account) so a payload can not be replayed.
+    pub fn relayed_mint(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+        token_metadata: TokenMetadata,
+        nonce: u64,
+    ) {
+        let account_id = env::predecessor_account_id();
+        let relayer_id = self
+            .relayer_id
+            .clone()
+            .unwrap_or_else(|| env::panic_str(RoveError::RelayerNotSet.as_str()));
+        require!(
+            env::signer_account_id() == relayer_id && account_id != relayer_id,
+            RoveError::NotRelayer.as_str()
+        );
+        self.internal_use_nonce(&account_id, nonce);
+        self.internal_count_relayed_mint(&account_id);
+
+        let (zone, token_id) = self.assert_rock_mintable(&metaverse_id, zone_index, rock_index);
+        require!(
+            zone.mint_policy == MintPolicy::PublicFixedPrice,
+            RoveError::InvalidZoneType.as_str()
+        );
+        let price = u128::from(zone.price);
+        require!(
+            price <= self.relay_fund,
+            RoveError::NotEnoughRelayFund.as_str()
+        );
+        self.relay_fund -= price;
+
+        self._mint(
+            metaverse_id,
+            zone_index,
+            token_id,
+            account_id,
+            token_metadata,
+            zone.price,
+            price,
+        );
+    }
+
+    pub fn get_relayer(&self) -> Option<AccountId> {
+        self.relayer_id.clone()
+    }
+
+    pub fn get_relay_fund(&self) -> U128 {
+        U128(self.relay_fund)
+    }
+
+    // relayed mints left today for account_id
+    pub fn get_relayed_mints_remaining(&self, account_id: AccountId) -> u32 {
+        let today = env::block_timestamp() / ONE_DAY_IN_NANOSECONDS;
+        match self.relayed_mints.get(&account_id) {
+            Some(counter) if counter.day == today => {
+                self.relayed_mint_daily_limit.saturating_sub(counter.count)
+            }
+            _ => self.relayed_mint_daily_limit,
+        }
+    }
+
+    // next nonce a signed mint payload of account_id must carry
+    pub fn get_nonce(&self, account_id: AccountId) -> u64 {
+        self.mint_nonces.get(&account_id).unwrap_or(0)
+    }
+
+    // consume nonce of account_id, a payload can only be used once and in order
+    pub(crate) fn internal_use_nonce(&mut self, account_id: &AccountId, nonce: u64) {
+        let expected_nonce = self.mint_nonces.get(account_id).unwrap_or(0);
+        require!(
+            nonce == expected_nonce,
+            RoveError::InvalidNonce.detail(format!("Invalid nonce, expected {}", expected_nonce))
+        );
+        self.mint_nonces.insert(account_id, &(expected_nonce + 1));
+    }
+
+    fn internal_count_relayed_mint(&mut self, account_id: &AccountId) {
+        require!(
+            self.get_relayed_mints_remaining(account_id.clone()) > 0,
+            RoveError::RelayLimitReached.as_str()
+        );
+        let today = env::block_timestamp() / ONE_DAY_IN_NANOSECONDS;
+        let count = match self.relayed_mints.get(account_id) {
+            Some(counter) if counter.day == today => counter.count + 1,
+            _ => 1,
+        };
+        self.relayed_mints
+            .insert(account_id, &RelayedMintCounter { day: today, count });
+    }
+}
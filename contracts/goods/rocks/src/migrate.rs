@@ -0,0 +1,78 @@
+use crate::*;
+
+// Contract state of the 1.0 release, read once by migrate. Keep it as deployed
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldContract {
+    pub tokens: NonFungibleToken,
+    pub metadata: LazyOption<NFTContractMetadata>,
+    pub royalties: UnorderedMap<String, HashMap<AccountId, u16>>,
+    pub tokens_metadata: UnorderedMap<String, TokenMetadata>,
+    pub admin_id: AccountId,
+    pub operator_id: AccountId,
+    pub treasury_id: AccountId,
+    pub init_imo_fee: u128,
+    pub rock_purchase_fee: u32,
+    pub metaverses: UnorderedMap<String, Metaverse>,
+    pub metaverse_owners: UnorderedMap<String, AccountId>,
+    pub tokens_minted: UnorderedMap<String, HashMap<String, bool>>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// upgrade the state of a 1.0 deployment in place, called by the upgrade script in the
+    /// deploy transaction. Fails on an already migrated state, which does not read as 1.0
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old_state: OldContract =
+            env::state_read().unwrap_or_else(|| env::panic_str(RoveError::NotInitialized.as_str()));
+        Self::from_old_state(old_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn old_state() -> OldContract {
+        OldContract {
+            tokens: NonFungibleToken::new(
+                StorageKey::NonFungibleToken,
+                accounts(1),
+                Some(StorageKey::TokenMetadata),
+                Some(StorageKey::Enumeration),
+                Some(StorageKey::Approval),
+            ),
+            metadata: LazyOption::new(StorageKey::Metadata, None),
+            royalties: UnorderedMap::new(StorageKey::Royalties),
+            tokens_metadata: UnorderedMap::new(StorageKey::TokensMetadata),
+            admin_id: accounts(0),
+            operator_id: accounts(1),
+            treasury_id: accounts(2),
+            init_imo_fee: 7,
+            rock_purchase_fee: 250,
+            metaverses: UnorderedMap::new(StorageKey::Metaverses),
+            metaverse_owners: UnorderedMap::new(StorageKey::MetaverseOwner),
+            tokens_minted: UnorderedMap::new(StorageKey::TokensMinted),
+        }
+    }
+
+    #[test]
+    fn migrate_keeps_old_state() {
+        testing_env!(VMContextBuilder::new().build());
+        env::state_write(&old_state());
+
+        let contract = Contract::migrate();
+        assert_eq!(contract.get_init_imo_fee(), U128(7));
+        assert_eq!(contract.get_admin(), accounts(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR1016")]
+    fn migrate_without_state_fails() {
+        testing_env!(VMContextBuilder::new().build());
+        Contract::migrate();
+    }
+}
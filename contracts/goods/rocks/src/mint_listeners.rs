@@ -0,0 +1,106 @@
+use near_sdk::{ext_contract, Gas};
+
+use crate::*;
+
+pub const MAX_MINT_LISTENERS: usize = 5;
+// gas attached to each on_rock_minted call, listeners needing more should record and process later
+pub const GAS_FOR_ON_ROCK_MINTED: Gas = Gas(5_000_000_000_000);
+// gas kept for the rest of the mint once the notifications are scheduled
+pub const GAS_RESERVED_AFTER_MINT_LISTENERS: Gas = Gas(5_000_000_000_000);
+
+#[ext_contract(ext_mint_listener)]
+pub trait MintListener {
+    fn on_rock_minted(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: U128,
+        owner_id: AccountId,
+    );
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner registers a contract notified with on_rock_minted after each rock minted
+    /// in the metaverse, at most MAX_MINT_LISTENERS. At least 1 yoctoNEAR plus storage must be
+    /// attached
+    #[payable]
+    pub fn add_mint_listener(&mut self, metaverse_id: String, listener_id: AccountId) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let mut listeners = self.mint_listeners.get(&metaverse_id).unwrap_or_default();
+        require!(
+            !listeners.contains(&listener_id),
+            RoveError::InvalidArgument.detail("listener is already registered")
+        );
+        require!(
+            listeners.len() < MAX_MINT_LISTENERS,
+            RoveError::TooManyItems.detail("too many mint listeners")
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        listeners.push(listener_id);
+        self.mint_listeners.insert(&metaverse_id, &listeners);
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    /// metaverse owner stops notifying a listener contract
+    #[payable]
+    pub fn remove_mint_listener(&mut self, metaverse_id: String, listener_id: AccountId) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let mut listeners = self.mint_listeners.get(&metaverse_id).unwrap_or_default();
+        let len = listeners.len();
+        listeners.retain(|account_id| account_id != &listener_id);
+        require!(
+            listeners.len() < len,
+            RoveError::InvalidArgument.detail("listener is not registered")
+        );
+        if listeners.is_empty() {
+            self.mint_listeners.remove(&metaverse_id);
+        } else {
+            self.mint_listeners.insert(&metaverse_id, &listeners);
+        }
+    }
+
+    pub fn get_mint_listeners(&self, metaverse_id: String) -> Vec<AccountId> {
+        self.mint_listeners.get(&metaverse_id).unwrap_or_default()
+    }
+
+    // best effort: the calls are detached so a failing listener does not revert the mint, and
+    // listeners are skipped once the remaining gas can not cover them
+    pub(crate) fn internal_notify_mint_listeners(
+        &self,
+        metaverse_id: &String,
+        zone_index: u16,
+        token_id: &TokenId,
+        owner_id: &AccountId,
+    ) {
+        let listeners = match self.mint_listeners.get(metaverse_id) {
+            Some(listeners) => listeners,
+            None => return,
+        };
+        let rock_index: u128 = match token_id.rsplit(':').next().and_then(|v| v.parse().ok()) {
+            Some(rock_index) => rock_index,
+            None => return,
+        };
+        for listener_id in listeners {
+            let remaining_gas = env::prepaid_gas() - env::used_gas();
+            if remaining_gas < GAS_FOR_ON_ROCK_MINTED + GAS_RESERVED_AFTER_MINT_LISTENERS {
+                break;
+            }
+            ext_mint_listener::on_rock_minted(
+                metaverse_id.clone(),
+                zone_index,
+                U128(rock_index),
+                owner_id.clone(),
+                listener_id,
+                0,
+                GAS_FOR_ON_ROCK_MINTED,
+            );
+        }
+    }
+}
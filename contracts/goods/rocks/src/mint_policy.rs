@@ -0,0 +1,85 @@
+use std::io;
+
+use crate::*;
+
+// how the rocks of a zone are sold. A new sale mechanic (e.g. auction, allowlist) is a new
+// variant: the exhaustive matches in check_zone and mint_rock point at what it must handle.
+// Stored as its type_zone u8, the value zones were saved with before the enum
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum MintPolicy {
+    // free rocks minted by core_team_addr
+    CoreTeam,
+    // rocks minted by holders of a collection_addr NFT, rockNFTCollectionHolder only
+    HolderGated,
+    // rocks sold to anyone at the zone price
+    PublicFixedPrice,
+}
+
+impl MintPolicy {
+    // type_zone number of the policy (1: core_team, 2: nft_holder, 3: public), still carried by
+    // the zone events and get_zone_info
+    pub fn type_zone(&self) -> u8 {
+        match self {
+            MintPolicy::CoreTeam => 1,
+            MintPolicy::HolderGated => 2,
+            MintPolicy::PublicFixedPrice => 3,
+        }
+    }
+
+    pub fn from_type_zone(type_zone: u8) -> Option<Self> {
+        match type_zone {
+            1 => Some(MintPolicy::CoreTeam),
+            2 => Some(MintPolicy::HolderGated),
+            3 => Some(MintPolicy::PublicFixedPrice),
+            _ => None,
+        }
+    }
+
+    // whether the metaverse owner sets the price of the zone rocks
+    pub fn is_priced(&self) -> bool {
+        match self {
+            MintPolicy::CoreTeam => false,
+            MintPolicy::HolderGated | MintPolicy::PublicFixedPrice => true,
+        }
+    }
+}
+
+impl BorshSerialize for MintPolicy {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        BorshSerialize::serialize(&self.type_zone(), writer)
+    }
+}
+
+impl BorshDeserialize for MintPolicy {
+    fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
+        let type_zone: u8 = BorshDeserialize::deserialize(buf)?;
+        MintPolicy::from_type_zone(type_zone).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                RoveError::InvalidZoneType.as_str(),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn mint_policy_is_stored_as_type_zone() {
+        for mint_policy in [
+            MintPolicy::CoreTeam,
+            MintPolicy::HolderGated,
+            MintPolicy::PublicFixedPrice,
+        ] {
+            let bytes = mint_policy.try_to_vec().unwrap();
+            assert_eq!(bytes, vec![mint_policy.type_zone()]);
+            assert_eq!(MintPolicy::try_from_slice(&bytes).unwrap(), mint_policy);
+        }
+        assert!(MintPolicy::try_from_slice(&[0]).is_err());
+        assert!(MintPolicy::try_from_slice(&[4]).is_err());
+    }
+}
@@ -0,0 +1,448 @@
+use near_sdk::json_types::U64;
+use near_sdk::{ext_contract, Gas, PromiseResult};
+
+use crate::*;
+
+pub const GAS_FOR_GET_PRICE_DATA: Gas = Gas(10_000_000_000_000);
+pub const GAS_FOR_RESOLVE_USD_MINT: Gas = Gas(50_000_000_000_000);
+
+// oracle used to convert USD zone prices into yoctoNEAR at mint time
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PriceOracleConfig {
+    pub oracle_id: AccountId,
+    // asset of the NEAR price, e.g. "wrap.near"
+    pub asset_id: String,
+    // older oracle prices fall back to the zone NEAR price
+    pub max_staleness_sec: u32,
+}
+
+// USD price of the smallest unit of an asset = multiplier / 10^decimals (priceoracle.near interface)
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OraclePrice {
+    pub multiplier: U128,
+    pub decimals: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AssetOptionalPrice {
+    pub asset_id: String,
+    pub price: Option<OraclePrice>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PriceData {
+    pub timestamp: U64,
+    pub recency_duration_sec: u32,
+    pub prices: Vec<AssetOptionalPrice>,
+}
+
+// rock of a USD priced zone waiting for the oracle price, minted by resolve_usd_mint
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UsdMintRequest {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub rock_index: u128,
+    pub receiver_id: AccountId,
+    pub token_metadata: TokenMetadata,
+    pub minter_id: AccountId,
+    pub attached_deposit: U128,
+}
+
+#[ext_contract(ext_price_oracle)]
+pub trait PriceOracle {
+    fn get_price_data(&self, asset_ids: Option<Vec<String>>) -> PriceData;
+}
+
+#[ext_contract(ext_usd_mint_resolver)]
+pub trait UsdMintResolver {
+    fn resolve_usd_mint(&mut self, request: UsdMintRequest);
+}
+
+#[near_bindgen]
+impl Contract {
+    #[payable]
+    pub fn set_price_oracle(&mut self, price_oracle: Option<PriceOracleConfig>) {
+        self.assert_admin_only();
+        self.price_oracle = price_oracle;
+    }
+
+    pub fn get_price_oracle(&self) -> Option<PriceOracleConfig> {
+        self.price_oracle.clone()
+    }
+
+    /// metaverse owner prices a public zone in USD cents, None goes back to the zone NEAR price
+    #[payable]
+    pub fn set_zone_price_usd(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        price_usd_cents: Option<u64>,
+    ) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(
+            zone.mint_policy == MintPolicy::PublicFixedPrice,
+            RoveError::InvalidZoneType.as_str()
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        match price_usd_cents {
+            Some(price_usd_cents) => {
+                require!(
+                    price_usd_cents > 0,
+                    RoveError::InvalidArgument.detail("price_usd_cents must > 0")
+                );
+                self.zone_usd_prices.insert(&zone_key, &price_usd_cents)
+            }
+            None => self.zone_usd_prices.remove(&zone_key),
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_zone_price_usd(&self, metaverse_id: String, zone_index: u16) -> Option<u64> {
+        self.zone_usd_prices
+            .get(&gen_zone_key(&metaverse_id, zone_index))
+    }
+
+    // This is callback function of mint_rock for USD priced zones (private, CAN NOT CALL DIRECTLY)
+    #[private]
+    pub fn resolve_usd_mint(&mut self, request: UsdMintRequest) {
+        require!(
+            env::promise_results_count() == 1,
+            RoveError::NotCallback.as_str()
+        );
+        let zone = self.assert_zone_exist(&request.metaverse_id, request.zone_index);
+        let price_usd_cents = self
+            .zone_usd_prices
+            .get(&gen_zone_key(&request.metaverse_id, request.zone_index))
+            .unwrap_or_default();
+        let oracle_price = match env::promise_result(0) {
+            PromiseResult::Successful(result) => {
+                self.internal_oracle_usd_price(price_usd_cents, &result)
+            }
+            _ => None,
+        };
+        // oracle failed or its price is stale => the zone NEAR price applies
+        let price = oracle_price.map(U128).unwrap_or(zone.price);
+
+        // the deposit is already held by this contract, give it back instead of panicking
+        let mint_check = self.internal_check_usd_mint(&request, price.0);
+        emit_rock_price_quote_log(
+            &request.metaverse_id,
+            request.zone_index,
+            price_usd_cents,
+            price,
+            oracle_price.is_some(),
+            mint_check
+                .err()
+                .map(|error| format!("refunded: {}", error.as_str())),
+        );
+        let UsdMintRequest {
+            metaverse_id,
+            zone_index,
+            rock_index,
+            receiver_id,
+            token_metadata,
+            minter_id,
+            attached_deposit,
+        } = request;
+        let attached_deposit = attached_deposit.0;
+        if mint_check.is_err() {
+            self.internal_payout(minter_id, attached_deposit);
+            return;
+        }
+
+        self._mint(
+            metaverse_id.clone(),
+            zone_index,
+            rock_token_id(&metaverse_id, zone_index, rock_index),
+            receiver_id,
+            token_metadata,
+            price,
+            price.0,
+        );
+        if attached_deposit > price.0 {
+            self.internal_payout(minter_id, attached_deposit - price.0);
+        }
+    }
+
+    // checks of _mint that can fail in resolve_usd_mint, run before it so the deposit is
+    // refunded instead of kept by a panic. A rock put on hold while the oracle was queried
+    // is not minted either
+    fn internal_check_usd_mint(
+        &self,
+        request: &UsdMintRequest,
+        price: Balance,
+    ) -> Result<(), RoveError> {
+        let token_id = rock_token_id(
+            &request.metaverse_id,
+            request.zone_index,
+            request.rock_index,
+        );
+        let is_minted = self
+            .tokens_minted
+            .get(&request.metaverse_id)
+            .map(|tokens_minted| tokens_minted.contains_key(&token_id))
+            .unwrap_or(true);
+        if is_minted {
+            return Err(RoveError::TokenAlreadyExists);
+        }
+        if self.denied_accounts.contains(&request.receiver_id)
+            || self.denied_accounts.contains(&env::signer_account_id())
+        {
+            return Err(RoveError::AccountDenied);
+        }
+        if !self.is_zone_receiver(
+            request.metaverse_id.clone(),
+            request.zone_index,
+            request.receiver_id.clone(),
+        ) {
+            return Err(RoveError::NotZoneReceiver);
+        }
+        if self.internal_rock_hold(&token_id).is_some() && request.minter_id != self.tokens.owner_id
+        {
+            return Err(RoveError::RockOnHold);
+        }
+        self.internal_check_mint_throttle(
+            &request.metaverse_id,
+            request.zone_index,
+            &request.receiver_id,
+        )?;
+        if price > request.attached_deposit.0 {
+            return Err(RoveError::NotEnoughDeposit);
+        }
+        Ok(())
+    }
+
+    // quote the NEAR price of a USD priced zone, the rock is minted in resolve_usd_mint
+    pub(crate) fn internal_mint_rock_in_usd(
+        &mut self,
+        metaverse_id: &String,
+        zone_index: u16,
+        rock_index: u128,
+        receiver_id: &AccountId,
+        token_metadata: &TokenMetadata,
+    ) -> bool {
+        let price_oracle = match self.price_oracle.clone() {
+            Some(price_oracle) => price_oracle,
+            None => return false,
+        };
+        if self
+            .zone_usd_prices
+            .get(&gen_zone_key(metaverse_id, zone_index))
+            .is_none()
+        {
+            return false;
+        }
+
+        ext_price_oracle::get_price_data(
+            Some(vec![price_oracle.asset_id]),
+            price_oracle.oracle_id,
+            0,
+            GAS_FOR_GET_PRICE_DATA,
+        )
+        .then(ext_usd_mint_resolver::resolve_usd_mint(
+            UsdMintRequest {
+                metaverse_id: metaverse_id.clone(),
+                zone_index,
+                rock_index,
+                receiver_id: receiver_id.clone(),
+                token_metadata: token_metadata.clone(),
+                minter_id: env::predecessor_account_id(),
+                attached_deposit: U128(env::attached_deposit()),
+            },
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_USD_MINT,
+        ));
+        true
+    }
+
+    fn internal_oracle_usd_price(&self, price_usd_cents: u64, result: &[u8]) -> Option<Balance> {
+        let price_oracle = self.price_oracle.as_ref()?;
+        let price_data = near_sdk::serde_json::from_slice::<PriceData>(result).ok()?;
+        let max_staleness = price_oracle.max_staleness_sec as u64 * 1_000_000_000;
+        if env::block_timestamp().saturating_sub(price_data.timestamp.0) > max_staleness {
+            return None;
+        }
+        let price = price_data
+            .prices
+            .into_iter()
+            .find(|asset_price| asset_price.asset_id == price_oracle.asset_id)?
+            .price?;
+        usd_cents_to_yocto(price_usd_cents, &price)
+    }
+}
+
+// yoctoNEAR worth price_usd_cents at the oracle price, rounded up
+fn usd_cents_to_yocto(price_usd_cents: u64, price: &OraclePrice) -> Option<Balance> {
+    let denominator = price.multiplier.0.checked_mul(100)?;
+    if price_usd_cents == 0 || denominator == 0 {
+        return None;
+    }
+    let numerator = 10u128
+        .checked_pow(price.decimals as u32)?
+        .checked_mul(price_usd_cents as u128)?;
+    Some(numerator / denominator + (numerator % denominator > 0) as u128)
+}
+
+fn emit_rock_price_quote_log(
+    metaverse_id: &str,
+    zone_index: u16,
+    price_usd_cents: u64,
+    price: U128,
+    from_oracle: bool,
+    memo: Option<String>,
+) {
+    let price_quote_log: EventLog = EventLog {
+        standard: "price_oracle".to_string(),
+        version: "1.0.0".to_string(),
+        event: EventLogVariant::RockPriceQuote(vec![RockPriceQuoteLog {
+            metaverse_id: metaverse_id.to_string(),
+            zone_index,
+            price_usd_cents,
+            price,
+            from_oracle,
+            memo,
+        }]),
+    };
+
+    price_quote_log.emit();
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::test_utils::accounts;
+    use near_sdk::{testing_env, PromiseResult, RuntimeFeesConfig, VMConfig, ONE_NEAR};
+
+    use crate::tests::{context, public_zone, rock_metadata, setup, transfers_to, METAVERSE_ID};
+    use crate::*;
+
+    fn usd_mint_request(attached_deposit: Balance) -> UsdMintRequest {
+        UsdMintRequest {
+            metaverse_id: METAVERSE_ID.to_string(),
+            zone_index: 2,
+            rock_index: 1,
+            receiver_id: accounts(4),
+            token_metadata: rock_metadata(),
+            minter_id: accounts(4),
+            attached_deposit: U128(attached_deposit),
+        }
+    }
+
+    // zone 2 sells rocks 1..=10 for 1 NEAR, the oracle callback runs on accounts(5)
+    fn setup_usd_zone() -> Contract {
+        let mut contract = setup(0);
+        testing_env!(context(accounts(3), ONE_NEAR).build());
+        contract.add_zone(METAVERSE_ID.to_string(), public_zone(2, 1, 10));
+        contract
+            .tokens_minted
+            .insert(&METAVERSE_ID.to_string(), &HashMap::new());
+        contract
+    }
+
+    fn usd_mint_callback(promise_result: PromiseResult) {
+        let contract_id = accounts(5);
+        testing_env!(
+            context(contract_id.clone(), 0)
+                .current_account_id(contract_id)
+                .signer_account_id(accounts(4))
+                .build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![promise_result]
+        );
+    }
+
+    #[test]
+    fn failed_oracle_mints_at_the_zone_price() {
+        let mut contract = setup_usd_zone();
+        usd_mint_callback(PromiseResult::Failed);
+        contract.resolve_usd_mint(usd_mint_request(2 * ONE_NEAR));
+
+        let token_id = rock_token_id(METAVERSE_ID, 2, 1);
+        assert_eq!(
+            contract.tokens.owner_by_id.get(&token_id),
+            Some(accounts(4))
+        );
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR]);
+    }
+
+    #[test]
+    fn failed_oracle_below_the_zone_price_refunds_the_deposit() {
+        let mut contract = setup_usd_zone();
+        usd_mint_callback(PromiseResult::Failed);
+        contract.resolve_usd_mint(usd_mint_request(ONE_NEAR / 2));
+
+        assert!(contract
+            .tokens
+            .owner_by_id
+            .get(&rock_token_id(METAVERSE_ID, 2, 1))
+            .is_none());
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR / 2]);
+    }
+
+    #[test]
+    fn usd_mint_for_denied_receiver_refunds_the_deposit() {
+        let mut contract = setup_usd_zone();
+        contract.denied_accounts.insert(&accounts(4));
+        usd_mint_callback(PromiseResult::Failed);
+        contract.resolve_usd_mint(usd_mint_request(ONE_NEAR));
+
+        assert!(contract
+            .tokens
+            .owner_by_id
+            .get(&rock_token_id(METAVERSE_ID, 2, 1))
+            .is_none());
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR]);
+    }
+
+    #[test]
+    fn usd_mint_of_rock_held_meanwhile_refunds_the_deposit() {
+        let mut contract = setup_usd_zone();
+        let token_id = rock_token_id(METAVERSE_ID, 2, 1);
+        contract.rock_holds.insert(&token_id, &1_000);
+        usd_mint_callback(PromiseResult::Failed);
+        contract.resolve_usd_mint(usd_mint_request(ONE_NEAR));
+
+        assert!(contract.tokens.owner_by_id.get(&token_id).is_none());
+        assert_eq!(contract.internal_rock_hold(&token_id), Some(1_000));
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR2007")]
+    fn usd_mint_above_max_deposit_is_rejected_before_the_oracle_call() {
+        let mut contract = setup_usd_zone();
+        contract.price_oracle = Some(PriceOracleConfig {
+            oracle_id: accounts(5),
+            asset_id: "wrap.near".to_string(),
+            max_staleness_sec: 60,
+        });
+        contract
+            .zone_usd_prices
+            .insert(&gen_zone_key(&METAVERSE_ID.to_string(), 2), &500);
+        contract.max_deposit_multiplier = 5;
+        testing_env!(context(accounts(4), 100 * ONE_NEAR).build());
+        contract.mint_rock(
+            METAVERSE_ID.to_string(),
+            2,
+            1,
+            accounts(4),
+            rock_metadata(),
+            None,
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use crate::*;
+
+// rocks read by one get_owners_for_range call
+pub const MAX_OWNERS_RANGE: u128 = 500;
+
+#[near_bindgen]
+impl Contract {
+    /// owner of each rock from_rock..=to_rock of a zone (at most 500 rocks) in rock order, None
+    /// for unminted rocks. A rock wrapped in a region reports the owner of the region. Lets the
+    /// map renderer read the parcels of a viewport in one call
+    pub fn get_owners_for_range(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        from_rock: U128,
+        to_rock: U128,
+    ) -> Vec<Option<AccountId>> {
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        let (from_rock, to_rock) = (from_rock.0, to_rock.0);
+        require!(
+            zone.rock_index_from <= from_rock
+                && from_rock <= to_rock
+                && to_rock <= zone.rock_index_to,
+            RoveError::InvalidRockIndex.detail("range must be inside the zone")
+        );
+        require!(
+            to_rock - from_rock < MAX_OWNERS_RANGE,
+            RoveError::TooManyItems.detail(format!("at most {} rocks", MAX_OWNERS_RANGE))
+        );
+
+        (from_rock..=to_rock)
+            .map(|rock_index| {
+                let token_id = rock_token_id(&metaverse_id, zone_index, rock_index);
+                let owner_id = self.tokens.owner_by_id.get(&token_id)?;
+                match self.wrapped_rocks.get(&token_id) {
+                    Some(region_id) => self.tokens.owner_by_id.get(&region_id),
+                    None => Some(owner_id),
+                }
+            })
+            .collect()
+    }
+}
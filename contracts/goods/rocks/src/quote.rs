@@ -0,0 +1,121 @@
+use ed25519_dalek::Verifier;
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::CurveType;
+
+use crate::*;
+
+// rock minted by mint_rock_with_quote
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct QuoteMintArgs {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub rock_index: u128,
+    pub receiver_id: AccountId,
+    pub token_metadata: TokenMetadata,
+}
+
+// price computed off-chain for one rock and receiver, signed by the quote key
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SignedQuote {
+    pub price: U128,
+    // timestamp in nanoseconds
+    pub expires_at: U64,
+    // ed25519 signature of quote_message
+    pub signature: Base64VecU8,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// operator registers (or removes with None) the ed25519 key signing the dynamic price
+    /// quotes of mint_rock_with_quote
+    #[payable]
+    pub fn set_quote_key(&mut self, public_key: Option<PublicKey>) {
+        self.assert_operator_only();
+        if let Some(public_key) = &public_key {
+            require!(
+                public_key.curve_type() == CurveType::ED25519,
+                RoveError::InvalidArgument.detail("quote key must be ed25519")
+            );
+        }
+        self.quote_public_key = public_key;
+    }
+
+    pub fn get_quote_key(&self) -> Option<PublicKey> {
+        self.quote_public_key.clone()
+    }
+
+    /// mint a rock of a public zone at the price of an operator-signed quote instead of the
+    /// zone price, e.g. demand-based pricing computed off-chain. The quote signs the borsh
+    /// serialization of (contract account, metaverse_id, zone_index, rock_index, receiver_id,
+    /// price, expires_at). Attach the quoted price, the rest is refunded. Signature checking
+    /// runs in the contract, attach at least 100 Tgas
+    #[payable]
+    pub fn mint_rock_with_quote(&mut self, args: QuoteMintArgs, quote: SignedQuote) -> TokenId {
+        let public_key = self
+            .quote_public_key
+            .clone()
+            .unwrap_or_else(|| env::panic_str(RoveError::QuoteKeyNotSet.as_str()));
+        require!(
+            env::block_timestamp() < quote.expires_at.0,
+            RoveError::QuoteExpired.as_str()
+        );
+        require!(
+            verify_ed25519(
+                &public_key,
+                &quote_message(&args, quote.price, quote.expires_at),
+                &quote.signature.0
+            ),
+            RoveError::InvalidQuoteSignature.as_str()
+        );
+
+        let (zone, token_id) =
+            self.assert_rock_mintable(&args.metaverse_id, args.zone_index, args.rock_index);
+        require!(
+            zone.mint_policy == MintPolicy::PublicFixedPrice,
+            RoveError::InvalidZoneType.as_str()
+        );
+        require!(quote.price.0 > 0, RoveError::MissingZonePrice.as_str());
+        self.assert_deposit_accepted(quote.price.0, 1);
+
+        self._mint(
+            args.metaverse_id,
+            args.zone_index,
+            token_id.clone(),
+            args.receiver_id,
+            args.token_metadata,
+            quote.price,
+            env::attached_deposit(),
+        );
+        token_id
+    }
+}
+
+// signed bytes of a quote, bound to this contract and to one rock and receiver
+fn quote_message(args: &QuoteMintArgs, price: U128, expires_at: U64) -> Vec<u8> {
+    (
+        env::current_account_id(),
+        &args.metaverse_id,
+        args.zone_index,
+        args.rock_index,
+        &args.receiver_id,
+        price.0,
+        expires_at.0,
+    )
+        .try_to_vec()
+        .unwrap()
+}
+
+fn verify_ed25519(public_key: &PublicKey, message: &[u8], signature: &[u8]) -> bool {
+    // the first byte of a near PublicKey is its curve type
+    let public_key = match ed25519_dalek::PublicKey::from_bytes(&public_key.as_bytes()[1..]) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    let signature = match ed25519_dalek::Signature::try_from(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    public_key.verify(message, &signature).is_ok()
+}
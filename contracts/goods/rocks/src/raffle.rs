@@ -0,0 +1,304 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+// longest entry window of a raffle (30 days)
+pub const MAX_RAFFLE_DURATION_SEC: u32 = 30 * 24 * 3600;
+// draw_raffle refunds every losing entrant in one call, this bounds its gas
+pub const MAX_RAFFLE_ENTRANTS: usize = 100;
+
+// raffle of the unminted rocks of a public zone, entry prices are held until the draw
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Raffle {
+    pub entry_price: U128,
+    pub closes_at: U64,
+    pub entrants: Vec<AccountId>,
+    // set by draw_raffle, rock index of each winner until it is claimed
+    pub winners: Option<HashMap<AccountId, u128>>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner opens a raffle of a public zone for `duration_sec`, the zone can not be
+    /// minted until the raffle is drawn and its winners claimed their rocks
+    #[payable]
+    pub fn start_raffle(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        entry_price: U128,
+        duration_sec: u32,
+    ) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(
+            zone.mint_policy == MintPolicy::PublicFixedPrice,
+            RoveError::InvalidZoneType.detail("only public_fixed_price zones can be raffled")
+        );
+        self.assert_zone_not_airdropped(&metaverse_id, zone_index);
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        require!(
+            self.raffles.get(&zone_key).is_none(),
+            RoveError::RaffleActive.as_str()
+        );
+        require!(
+            entry_price.0 > 0,
+            RoveError::InvalidArgument.detail("entry_price must be > 0")
+        );
+        require!(
+            0 < duration_sec && duration_sec <= MAX_RAFFLE_DURATION_SEC,
+            RoveError::InvalidArgument.detail(format!(
+                "duration_sec must be in (0, {}]",
+                MAX_RAFFLE_DURATION_SEC
+            ))
+        );
+
+        let closes_at = env::block_timestamp() + u64::from(duration_sec) * 1_000_000_000;
+        let initial_storage_usage = env::storage_usage();
+        self.raffles.insert(
+            &zone_key,
+            &Raffle {
+                entry_price,
+                closes_at: U64(closes_at),
+                entrants: vec![],
+                winners: None,
+            },
+        );
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+
+        emit_raffle_log(
+            EventLogVariant::RaffleStarted,
+            &metaverse_id,
+            zone_index,
+            vec![(env::predecessor_account_id(), None)],
+        );
+    }
+
+    /// caller enters the raffle of a zone once, attaching entry_price plus storage
+    #[payable]
+    pub fn enter_raffle(&mut self, metaverse_id: String, zone_index: u16) {
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        let mut raffle = self
+            .raffles
+            .get(&zone_key)
+            .unwrap_or_else(|| env::panic_str(RoveError::RaffleNotFound.as_str()));
+        require!(
+            env::block_timestamp() < raffle.closes_at.0,
+            RoveError::RaffleClosed.as_str()
+        );
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        self.assert_zone_visible(&metaverse_id, &zone);
+        let account_id = env::predecessor_account_id();
+        require!(
+            !raffle.entrants.contains(&account_id),
+            RoveError::RaffleAlreadyEntered.as_str()
+        );
+        require!(
+            raffle.entrants.len() < MAX_RAFFLE_ENTRANTS,
+            RoveError::TooManyItems.detail("raffle is full")
+        );
+
+        let attached_deposit = env::attached_deposit();
+        let initial_storage_usage = env::storage_usage();
+        raffle.entrants.push(account_id.clone());
+        self.raffles.insert(&zone_key, &raffle);
+        let storage_cost = env::storage_byte_cost()
+            * Balance::from(env::storage_usage().saturating_sub(initial_storage_usage));
+        let entry_price = raffle.entry_price.0;
+        require!(
+            entry_price + storage_cost <= attached_deposit,
+            RoveError::NotEnoughDeposit.detail(format!(
+                "Need {} yoctoNEAR to enter the raffle ({} yoctoNEAR entry price + {} yoctoNEAR storage)",
+                entry_price + storage_cost,
+                entry_price,
+                storage_cost
+            ))
+        );
+        let refund = attached_deposit - entry_price - storage_cost;
+        if refund > 0 {
+            self.internal_payout(account_id.clone(), refund);
+        }
+
+        emit_raffle_log(
+            EventLogVariant::RaffleEntered,
+            &metaverse_id,
+            zone_index,
+            vec![(account_id, None)],
+        );
+    }
+
+    /// anyone draws a closed raffle: entrants are shuffled with env::random_seed and assigned
+    /// the unminted rocks of the zone, entrants left without a rock are refunded
+    pub fn draw_raffle(&mut self, metaverse_id: String, zone_index: u16) {
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        let mut raffle = self
+            .raffles
+            .get(&zone_key)
+            .unwrap_or_else(|| env::panic_str(RoveError::RaffleNotFound.as_str()));
+        require!(
+            env::block_timestamp() >= raffle.closes_at.0,
+            RoveError::RaffleNotClosed.as_str()
+        );
+        require!(
+            raffle.winners.is_none(),
+            RoveError::RaffleAlreadyDrawn.as_str()
+        );
+
+        let mut entrants = raffle.entrants.clone();
+        shuffle_with_seed(&mut entrants, &env::random_seed());
+        let rock_indexes = self.get_unminted_rocks(
+            metaverse_id.clone(),
+            zone_index,
+            None,
+            Some(entrants.len() as u64),
+        );
+        let winners: HashMap<AccountId, u128> = entrants
+            .iter()
+            .cloned()
+            .zip(rock_indexes)
+            .collect();
+        let losers: Vec<AccountId> = entrants.into_iter().skip(winners.len()).collect();
+        for loser_id in losers.iter() {
+            self.internal_payout(loser_id.clone(), raffle.entry_price.0);
+        }
+
+        if !winners.is_empty() {
+            emit_raffle_log(
+                EventLogVariant::RaffleWon,
+                &metaverse_id,
+                zone_index,
+                winners
+                    .iter()
+                    .map(|(winner_id, rock_index)| (winner_id.clone(), Some(*rock_index)))
+                    .collect(),
+            );
+        }
+        if !losers.is_empty() {
+            emit_raffle_log(
+                EventLogVariant::RaffleRefunded,
+                &metaverse_id,
+                zone_index,
+                losers
+                    .into_iter()
+                    .map(|loser_id| (loser_id, None))
+                    .collect(),
+            );
+        }
+
+        if winners.is_empty() {
+            self.raffles.remove(&zone_key);
+        } else {
+            raffle.winners = Some(winners);
+            self.raffles.insert(&zone_key, &raffle);
+        }
+    }
+
+    /// raffle winner mints the rock assigned by draw_raffle, the entry price pays for it
+    pub fn claim_raffle_rock(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        token_metadata: TokenMetadata,
+    ) {
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        let mut raffle = self
+            .raffles
+            .get(&zone_key)
+            .unwrap_or_else(|| env::panic_str(RoveError::RaffleNotFound.as_str()));
+        let account_id = env::predecessor_account_id();
+        let mut winners = raffle
+            .winners
+            .unwrap_or_else(|| env::panic_str(RoveError::RaffleNotClosed.as_str()));
+        let rock_index = winners.remove(&account_id).unwrap_or_else(|| {
+            env::panic_str(&RoveError::Unauthorized.detail("caller has no rock to claim"))
+        });
+        if winners.is_empty() {
+            self.raffles.remove(&zone_key);
+        } else {
+            raffle.winners = Some(winners);
+            self.raffles.insert(&zone_key, &raffle);
+        }
+
+        let token_id = rock_token_id(&metaverse_id, zone_index, rock_index);
+        self._mint(
+            metaverse_id.clone(),
+            zone_index,
+            token_id,
+            account_id.clone(),
+            token_metadata,
+            raffle.entry_price,
+            raffle.entry_price.0,
+        );
+
+        emit_raffle_log(
+            EventLogVariant::RaffleClaimed,
+            &metaverse_id,
+            zone_index,
+            vec![(account_id, Some(rock_index))],
+        );
+    }
+
+    pub fn get_raffle(&self, metaverse_id: String, zone_index: u16) -> Option<Raffle> {
+        self.raffles.get(&gen_zone_key(&metaverse_id, zone_index))
+    }
+
+    // a raffled zone can only be minted through claim_raffle_rock until its winners claimed
+    pub(crate) fn assert_rock_not_raffled(
+        &self,
+        metaverse_id: &String,
+        zone_index: u16,
+        rock_index: u128,
+    ) {
+        if let Some(raffle) = self.raffles.get(&gen_zone_key(metaverse_id, zone_index)) {
+            let is_reserved = match &raffle.winners {
+                Some(winners) => winners.values().any(|index| *index == rock_index),
+                None => true,
+            };
+            require!(!is_reserved, RoveError::RaffleActive.as_str());
+        }
+    }
+}
+
+// Fisher-Yates shuffle, each swap index is read from sha256(seed ++ position)
+fn shuffle_with_seed(items: &mut [AccountId], seed: &[u8]) {
+    for i in (1..items.len()).rev() {
+        let mut value = seed.to_vec();
+        value.extend_from_slice(&(i as u64).to_le_bytes());
+        let hash = sha256_hash(&value);
+        let mut random_bytes = [0u8; 8];
+        random_bytes.copy_from_slice(&hash[..8]);
+        let j = (u64::from_le_bytes(random_bytes) % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+fn emit_raffle_log(
+    variant: fn(Vec<RaffleLog>) -> EventLogVariant,
+    metaverse_id: &str,
+    zone_index: u16,
+    entries: Vec<(AccountId, Option<u128>)>,
+) {
+    let raffle_log: EventLog = EventLog {
+        standard: "raffle".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(
+            entries
+                .into_iter()
+                .map(|(account_id, rock_index)| RaffleLog {
+                    metaverse_id: metaverse_id.to_string(),
+                    zone_index,
+                    account_id: account_id.to_string(),
+                    rock_index,
+                    memo: None,
+                })
+                .collect(),
+        ),
+    };
+    raffle_log.emit();
+}
@@ -3,6 +3,9 @@ use near_sdk::json_types::U128;
 
 use crate::*;
 
+// largest share of a resale a metaverse owner may take, on top of the creator royalties
+pub const MAX_METAVERSE_OWNER_ROYALTY_BPS: u16 = 1_000;
+
 pub trait NonFungibleTokenRoyalty {
     //calculates the payout for a token given the passed in balance. This is a view method
     fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: u32) -> Payout;
@@ -23,47 +26,16 @@ pub trait NonFungibleTokenRoyalty {
 impl NonFungibleTokenRoyalty for Contract {
     //calculates the payout for a token given the passed in balance. This is a view method
     fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: u32) -> Payout {
-        // token id has format {nft_type_id}:{token_count}
-        let token_id_parts: Vec<&str> = token_id.split(':').collect();
-
-        require!(token_id_parts.len() == 2, "token_id has wrong format");
-
-        let nft_type_id_str = token_id_parts.get(0).expect("token_id has wrong format");
-        let nft_type_id = format!("{}", nft_type_id_str);
-
-        let token_owner_id = self.tokens.owner_by_id.get(&token_id).expect("token not exist");
-        //keep track of the total perpetual royalties
-        let mut total_perpetual: u16 = 0;
-        //get the u128 version of the passed in balance (which was U128 before)
-        let balance_u128 = u128::from(balance);
-        //keep track of the payout object to send back
-        let mut payout_object = Payout {
-            payout: HashMap::new()
-        };
-
-        let royalties = self.royalties.get(&nft_type_id).expect(NOT_FOUND_METAVERSE_ID_ERROR);
-
-        //get the royalty object from token
-        //make sure we're not paying out to too many people (GAS limits this)
-        assert!(royalties.len() as u32 <= max_len_payout, "Market cannot payout to that many receivers");
-
-        //go through each key and value in the royalty object
-        for (k, v) in royalties.iter() {
-            //get the key
-            let key = k.clone();
-            //only insert into the payout if the key isn't the token owner (we add their payout at the end)
-            if key != token_owner_id {
-                //
-                payout_object.payout.insert(key, royalty_to_payout(*v, balance_u128));
-                total_perpetual += v;
-            }
-        }
-
-        // payout to previous owner who gets 100% - total perpetual royalties
-        payout_object.payout.insert(token_owner_id, royalty_to_payout(ONE_HUNDRED_PERCENT_IN_BPS - total_perpetual, balance_u128));
-
-        //return the payout object
-        payout_object
+        // a metaverse without royalties pays its owner 100%
+        let token_owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        let royalties = self.internal_token_royalties(&token_id);
+
+        compute_payout(&royalties, token_owner_id, u128::from(balance), max_len_payout)
+            .unwrap_or_else(|| env::panic_str(RoveError::TooManyPayoutReceivers.as_str()))
     }
 
     //transfers the token to the receiver ID and returns the payout object that should be payed given the passed in balance.
@@ -79,11 +51,135 @@ impl NonFungibleTokenRoyalty for Contract {
     ) -> Payout {
         //assert that the user attached 1 yocto NEAR for security reasons
         assert_one_yocto();
+        self.assert_transfer_allowed(&token_id, &receiver_id);
+        self.assert_approval_not_expired(&token_id, &env::predecessor_account_id());
+        // a resale after the refund window settles the primary sale
+        self.internal_release_escrow(&token_id);
 
         let payout = self.nft_payout(token_id.clone(), balance, max_len_payout);
-
-        self.tokens.nft_transfer(receiver_id.try_into().unwrap(), token_id, Some(approval_id), memo);
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+
+        self.tokens.nft_transfer(receiver_id.clone(), token_id.clone(), Some(approval_id), memo);
+        self.internal_update_holding(&token_id, Some(&owner_id), Some(&receiver_id));
+        emit_account_activity(vec![AccountActivityLog::new(
+            &owner_id,
+            ActivityAction::Transfer,
+            &token_id,
+            Some(&receiver_id),
+            Some(balance.0),
+        )]);
 
         payout
     }
 }
+
+#[near_bindgen]
+impl Contract {
+    /// admin overrides the metaverse royalties for the rocks of one zone (e.g. core team parcels),
+    /// None removes the override
+    #[payable]
+    pub fn set_zone_royalties(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        royalties: Option<HashMap<AccountId, u16>>,
+    ) {
+        self.assert_admin_only();
+        self.assert_zone_exist(&metaverse_id, zone_index);
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+
+        let initial_storage_usage = env::storage_usage();
+        match royalties {
+            Some(royalties) => {
+                require!(
+                    royalties
+                        .values()
+                        .map(|royalty| u32::from(*royalty))
+                        .sum::<u32>()
+                        <= u32::from(ONE_HUNDRED_PERCENT_IN_BPS),
+                    RoveError::InvalidArgument.detail("total royalties must <= 10_000")
+                );
+                self.zone_royalties.insert(&zone_key, &royalties)
+            }
+            None => self.zone_royalties.remove(&zone_key),
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    /// metaverse owner takes `royalty` bps (at most MAX_METAVERSE_OWNER_ROYALTY_BPS, 0 stops it)
+    /// of every rock resale of the metaverse for the world maintenance. It is paid to the owner
+    /// of the metaverse at the time of the sale, within what the creator royalties leave
+    #[payable]
+    pub fn set_metaverse_owner_royalty(&mut self, metaverse_id: String, royalty: u16) {
+        self.assert_metaverse_owner(&metaverse_id);
+        require!(
+            royalty <= MAX_METAVERSE_OWNER_ROYALTY_BPS,
+            RoveError::InvalidArgument.detail(format!(
+                "royalty must <= {}",
+                MAX_METAVERSE_OWNER_ROYALTY_BPS
+            ))
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        if royalty > 0 {
+            self.metaverse_owner_royalties
+                .insert(&metaverse_id, &royalty);
+        } else {
+            self.metaverse_owner_royalties.remove(&metaverse_id);
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_metaverse_owner_royalty(&self, metaverse_id: String) -> u16 {
+        self.metaverse_owner_royalties
+            .get(&metaverse_id)
+            .unwrap_or_default()
+    }
+
+    // royalties of a rock are set per metaverse and may be overridden per zone, the metaverse
+    // owner royalty comes on top of them
+    pub(crate) fn internal_token_royalties(&self, token_id: &TokenId) -> HashMap<AccountId, u16> {
+        let metaverse_id = metaverse_id_of_token(token_id);
+        // a region pays the royalties of its metaverse
+        let zone_royalties = match self.regions.get(token_id) {
+            Some(_) => None,
+            None => self.zone_royalties.get(&zone_key_of_token(token_id)),
+        };
+        let mut royalties = zone_royalties
+            .or_else(|| self.royalties.get(&metaverse_id))
+            .unwrap_or_default();
+
+        let owner_royalty = self.get_metaverse_owner_royalty(metaverse_id.clone());
+        let total_royalties: u32 = royalties.values().map(|royalty| u32::from(*royalty)).sum();
+        let owner_royalty = u32::from(owner_royalty)
+            .min(u32::from(ONE_HUNDRED_PERCENT_IN_BPS).saturating_sub(total_royalties));
+        if owner_royalty > 0 {
+            if let Some(metaverse_owner_id) = self.metaverse_owners.get(&metaverse_id) {
+                *royalties.entry(metaverse_owner_id).or_default() += owner_royalty as u16;
+            }
+        }
+        royalties
+    }
+
+    pub fn get_zone_royalties(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+    ) -> Option<HashMap<AccountId, u16>> {
+        self.zone_royalties.get(&gen_zone_key(&metaverse_id, zone_index))
+    }
+}
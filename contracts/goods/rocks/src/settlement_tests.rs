@@ -0,0 +1,172 @@
+// payout callbacks and retry_payout of shared/settlement.rs, run against the rocks contract
+
+use near_sdk::mock::VmAction;
+use near_sdk::test_utils::{accounts, get_created_receipts};
+use near_sdk::{testing_env, PromiseResult, RuntimeFeesConfig, VMConfig, ONE_NEAR};
+
+use crate::tests::{context, setup, transfers_to};
+use crate::*;
+
+fn wnear_id() -> AccountId {
+    "wrap.testnet".parse().unwrap()
+}
+
+// the payout callbacks run on accounts(5) with promise_result as their only result
+fn payout_callback(promise_result: PromiseResult) {
+    let contract_id = accounts(5);
+    testing_env!(
+        context(contract_id.clone(), 0)
+            .current_account_id(contract_id)
+            .build(),
+        VMConfig::test(),
+        RuntimeFeesConfig::test(),
+        Default::default(),
+        vec![promise_result]
+    );
+}
+
+fn calls_to(account_id: &AccountId) -> Vec<(String, Balance)> {
+    get_created_receipts()
+        .into_iter()
+        .filter(|receipt| receipt.receiver_id == *account_id)
+        .flat_map(|receipt| receipt.actions)
+        .filter_map(|action| match action {
+            VmAction::FunctionCall {
+                function_name,
+                deposit,
+                ..
+            } => Some((function_name, deposit)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn failed_payouts(contract: &Contract) -> Vec<(AccountId, Balance, Option<AccountId>)> {
+    contract
+        .get_failed_payouts(None, None)
+        .into_iter()
+        .map(|payout| (payout.receiver_id, payout.amount.0, payout.wnear_id))
+        .collect()
+}
+
+#[test]
+fn failed_transfer_is_kept_for_its_receiver() {
+    let mut contract = setup(0);
+    payout_callback(PromiseResult::Successful(vec![]));
+    contract.resolve_payout(accounts(4), U128(ONE_NEAR));
+    assert!(failed_payouts(&contract).is_empty());
+
+    payout_callback(PromiseResult::Failed);
+    contract.resolve_payout(accounts(4), U128(ONE_NEAR));
+    assert_eq!(
+        failed_payouts(&contract),
+        vec![(accounts(4), ONE_NEAR, None)]
+    );
+}
+
+#[test]
+fn wrapped_payout_is_sent_as_wnear() {
+    let mut contract = setup(0);
+    payout_callback(PromiseResult::Successful(vec![]));
+    contract.resolve_wrap_payout(accounts(2), U128(ONE_NEAR), wnear_id());
+
+    assert!(failed_payouts(&contract).is_empty());
+    assert_eq!(calls_to(&wnear_id()), vec![("ft_transfer".to_string(), 1)]);
+}
+
+#[test]
+fn failed_wrap_is_kept_as_native_near() {
+    let mut contract = setup(0);
+    payout_callback(PromiseResult::Failed);
+    contract.resolve_wrap_payout(accounts(2), U128(ONE_NEAR), wnear_id());
+
+    assert!(calls_to(&wnear_id()).is_empty());
+    assert_eq!(
+        failed_payouts(&contract),
+        vec![(accounts(2), ONE_NEAR, None)]
+    );
+}
+
+#[test]
+fn failed_ft_transfer_unwraps_the_wnear() {
+    let mut contract = setup(0);
+    payout_callback(PromiseResult::Successful(vec![]));
+    contract.resolve_ft_payout(accounts(2), U128(ONE_NEAR), wnear_id());
+    assert!(calls_to(&wnear_id()).is_empty());
+
+    payout_callback(PromiseResult::Failed);
+    contract.resolve_ft_payout(accounts(2), U128(ONE_NEAR), wnear_id());
+
+    // nothing is owed yet, resolve_near_withdraw records the payout
+    assert!(failed_payouts(&contract).is_empty());
+    assert_eq!(
+        calls_to(&wnear_id()),
+        vec![("near_withdraw".to_string(), 1)]
+    );
+}
+
+#[test]
+fn unwrapped_payout_is_kept_as_native_near() {
+    let mut contract = setup(0);
+    payout_callback(PromiseResult::Successful(vec![]));
+    contract.resolve_near_withdraw(accounts(2), U128(ONE_NEAR), wnear_id());
+
+    assert_eq!(
+        failed_payouts(&contract),
+        vec![(accounts(2), ONE_NEAR, None)]
+    );
+}
+
+#[test]
+fn failed_unwrap_is_kept_as_wnear() {
+    let mut contract = setup(0);
+    payout_callback(PromiseResult::Failed);
+    contract.resolve_near_withdraw(accounts(2), U128(ONE_NEAR), wnear_id());
+
+    assert_eq!(
+        failed_payouts(&contract),
+        vec![(accounts(2), ONE_NEAR, Some(wnear_id()))]
+    );
+}
+
+#[test]
+fn retry_payout_sends_native_near_again() {
+    let mut contract = setup(0);
+    payout_callback(PromiseResult::Failed);
+    contract.resolve_payout(accounts(4), U128(ONE_NEAR));
+    let payout_id = contract.get_failed_payouts(None, None)[0].payout_id;
+
+    testing_env!(context(accounts(4), 1).build());
+    contract.retry_payout(payout_id);
+
+    assert!(failed_payouts(&contract).is_empty());
+    assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR]);
+}
+
+#[test]
+fn retry_payout_sends_wnear_again() {
+    let mut contract = setup(0);
+    payout_callback(PromiseResult::Failed);
+    contract.resolve_near_withdraw(accounts(2), U128(ONE_NEAR), wnear_id());
+    let payout_id = contract.get_failed_payouts(None, None)[0].payout_id;
+
+    // the operator retries on behalf of the treasury
+    testing_env!(context(accounts(1), 1).build());
+    contract.retry_payout(payout_id);
+
+    assert!(failed_payouts(&contract).is_empty());
+    assert!(transfers_to(&accounts(2)).is_empty());
+    assert_eq!(calls_to(&wnear_id()), vec![("ft_transfer".to_string(), 1)]);
+}
+
+#[test]
+#[should_panic(expected = "ERR1000")]
+fn retry_payout_by_another_account_panics() {
+    let mut contract = setup(0);
+    payout_callback(PromiseResult::Failed);
+    contract.resolve_payout(accounts(4), U128(ONE_NEAR));
+    let payout_id = contract.get_failed_payouts(None, None)[0].payout_id;
+
+    testing_env!(context(accounts(3), 1).build());
+    contract.retry_payout(payout_id);
+}
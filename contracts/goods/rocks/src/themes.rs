@@ -0,0 +1,41 @@
+use crate::*;
+
+pub const MAX_METAVERSE_THEMES: usize = 16;
+pub const MAX_THEME_LEN: usize = 64;
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner sets the environment themes (metadata `extra.theme`) allowed on its rocks,
+    /// an empty list allows every theme
+    #[payable]
+    pub fn set_metaverse_themes(&mut self, metaverse_id: String, themes: Vec<String>) {
+        self.assert_metaverse_owner(&metaverse_id);
+        require!(
+            themes.len() <= MAX_METAVERSE_THEMES,
+            RoveError::TooManyItems.detail("too many themes")
+        );
+        require!(
+            themes
+                .iter()
+                .all(|theme| !theme.is_empty() && theme.len() <= MAX_THEME_LEN),
+            RoveError::InvalidArgument.detail("theme must not be empty and <= 64 bytes")
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        if themes.is_empty() {
+            self.metaverse_themes.remove(&metaverse_id);
+        } else {
+            self.metaverse_themes.insert(&metaverse_id, &themes);
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_metaverse_themes(&self, metaverse_id: String) -> Vec<String> {
+        self.metaverse_themes.get(&metaverse_id).unwrap_or_default()
+    }
+}
@@ -0,0 +1,194 @@
+use crate::*;
+
+// largest ring buffer kept per account and zone
+pub const MAX_THROTTLE_MINTS_PER_ACCOUNT: u32 = 100;
+
+// mint rate limit of a zone, 0 disables a limit
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintThrottle {
+    // mints an account may receive within window_sec
+    pub max_per_account: u32,
+    pub window_sec: u32,
+    // mints of the zone within one block, all accounts together
+    pub max_per_block: u32,
+}
+
+// timestamps of the last mints of an account, oldest at head once full
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct MintRing {
+    pub timestamps: Vec<u64>,
+    pub head: u32,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner sets (or removes with None) the mint rate limit of a zone
+    #[payable]
+    pub fn set_mint_throttle(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        throttle: Option<MintThrottle>,
+    ) {
+        self.assert_metaverse_owner(&metaverse_id);
+        self.assert_zone_exist(&metaverse_id, zone_index);
+
+        let initial_storage_usage = env::storage_usage();
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        match throttle {
+            Some(throttle) => {
+                require!(
+                    throttle.max_per_account <= MAX_THROTTLE_MINTS_PER_ACCOUNT,
+                    RoveError::InvalidArgument.detail(format!(
+                        "max_per_account must <= {}",
+                        MAX_THROTTLE_MINTS_PER_ACCOUNT
+                    ))
+                );
+                require!(
+                    throttle.max_per_account == 0 || throttle.window_sec > 0,
+                    RoveError::InvalidArgument.detail("window_sec must > 0")
+                );
+                self.mint_throttles.insert(&zone_key, &throttle)
+            }
+            None => self.mint_throttles.remove(&zone_key),
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_mint_throttle(&self, metaverse_id: String, zone_index: u16) -> Option<MintThrottle> {
+        self.mint_throttles
+            .get(&gen_zone_key(&metaverse_id, zone_index))
+    }
+
+    // count a mint of the zone for account_id, panics when the zone throttle is exceeded
+    pub(crate) fn internal_throttle_mint(
+        &mut self,
+        metaverse_id: &String,
+        zone_index: u16,
+        account_id: &AccountId,
+    ) {
+        let zone_key = gen_zone_key(metaverse_id, zone_index);
+        let throttle = match self.mint_throttles.get(&zone_key) {
+            Some(throttle) => throttle,
+            None => return,
+        };
+
+        if throttle.max_per_block > 0 {
+            let block_height = env::block_height();
+            let (last_block_height, count) = self
+                .zone_block_mints
+                .get(&zone_key)
+                .unwrap_or((block_height, 0));
+            let count = if last_block_height == block_height {
+                count
+            } else {
+                0
+            };
+            require!(
+                count < throttle.max_per_block,
+                RoveError::MintThrottled.detail("zone mint limit of this block is reached")
+            );
+            self.zone_block_mints
+                .insert(&zone_key, &(block_height, count + 1));
+        }
+
+        if throttle.max_per_account > 0 {
+            let ring_key = format!("{}:{}", zone_key, account_id);
+            let capacity = throttle.max_per_account as usize;
+            let mut ring = self.account_mint_rings.get(&ring_key).unwrap_or_default();
+            // the throttle was changed since the last mint
+            if ring.timestamps.len() > capacity {
+                ring = MintRing::default();
+            }
+
+            let now = env::block_timestamp();
+            if ring.timestamps.len() < capacity {
+                ring.timestamps.push(now);
+            } else {
+                let oldest = ring.timestamps[ring.head as usize];
+                require!(
+                    now - oldest >= throttle.window_sec as u64 * 1_000_000_000,
+                    RoveError::MintThrottled
+                        .detail("account mint limit of this zone is reached, try again later")
+                );
+                ring.timestamps[ring.head as usize] = now;
+                ring.head = (ring.head + 1) % capacity as u32;
+            }
+            self.account_mint_rings.insert(&ring_key, &ring);
+        }
+    }
+
+    // the checks of internal_throttle_mint without counting the mint, for callbacks that
+    // refund the deposit instead of panicking
+    pub(crate) fn internal_check_mint_throttle(
+        &self,
+        metaverse_id: &String,
+        zone_index: u16,
+        account_id: &AccountId,
+    ) -> Result<(), RoveError> {
+        let zone_key = gen_zone_key(metaverse_id, zone_index);
+        let throttle = match self.mint_throttles.get(&zone_key) {
+            Some(throttle) => throttle,
+            None => return Ok(()),
+        };
+
+        if throttle.max_per_block > 0 {
+            if let Some((block_height, count)) = self.zone_block_mints.get(&zone_key) {
+                if block_height == env::block_height() && count >= throttle.max_per_block {
+                    return Err(RoveError::MintThrottled);
+                }
+            }
+        }
+
+        if throttle.max_per_account > 0 {
+            let ring_key = format!("{}:{}", zone_key, account_id);
+            let capacity = throttle.max_per_account as usize;
+            if let Some(ring) = self.account_mint_rings.get(&ring_key) {
+                if ring.timestamps.len() == capacity {
+                    let oldest = ring.timestamps[ring.head as usize];
+                    if env::block_timestamp() - oldest < throttle.window_sec as u64 * 1_000_000_000
+                    {
+                        return Err(RoveError::MintThrottled);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // take back the last mint counted by internal_throttle_mint for account_id, e.g. when
+    // the rock is burnt again
+    pub(crate) fn internal_unthrottle_mint(
+        &mut self,
+        metaverse_id: &String,
+        zone_index: u16,
+        account_id: &AccountId,
+    ) {
+        let zone_key = gen_zone_key(metaverse_id, zone_index);
+        if let Some((block_height, count)) = self.zone_block_mints.get(&zone_key) {
+            if block_height == env::block_height() && count > 0 {
+                self.zone_block_mints
+                    .insert(&zone_key, &(block_height, count - 1));
+            }
+        }
+
+        let ring_key = format!("{}:{}", zone_key, account_id);
+        if let Some(mut ring) = self.account_mint_rings.get(&ring_key) {
+            // newest timestamp is right before head
+            ring.timestamps.rotate_left(ring.head as usize);
+            ring.timestamps.pop();
+            ring.head = 0;
+            if ring.timestamps.is_empty() {
+                self.account_mint_rings.remove(&ring_key);
+            } else {
+                self.account_mint_rings.insert(&ring_key, &ring);
+            }
+        }
+    }
+}
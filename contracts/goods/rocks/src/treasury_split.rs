@@ -0,0 +1,151 @@
+use crate::*;
+
+// gas bounds the number of payouts of a single fee transfer
+pub const MAX_TREASURY_BENEFICIARIES: usize = 10;
+
+// platform wallet receiving `weight` / total weight of every treasury payout
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TreasuryBeneficiary {
+    pub account_id: AccountId,
+    pub weight: u32,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// admin splits treasury payouts between platform wallets (e.g. ops, DAO, buyback) by weight,
+    /// the rounding dust goes to the first one. An empty list pays everything to treasury_id.
+    /// Beneficiaries are paid in native NEAR, the treasury must settle in native NEAR first
+    #[payable]
+    pub fn set_treasury_beneficiaries(&mut self, beneficiaries: Vec<TreasuryBeneficiary>) {
+        self.assert_admin_only();
+        require!(
+            beneficiaries.is_empty() || self.treasury_settlement == TreasurySettlement::Native,
+            RoveError::InvalidArgument
+                .detail("treasury beneficiaries are paid in native NEAR only")
+        );
+        require!(
+            beneficiaries.len() <= MAX_TREASURY_BENEFICIARIES,
+            RoveError::TooManyItems.detail("too many treasury beneficiaries")
+        );
+        for (index, beneficiary) in beneficiaries.iter().enumerate() {
+            require!(
+                beneficiary.weight > 0,
+                RoveError::InvalidArgument.detail("weight must be > 0")
+            );
+            require!(
+                beneficiaries[..index]
+                    .iter()
+                    .all(|other| other.account_id != beneficiary.account_id),
+                RoveError::InvalidArgument
+                    .detail(format!("duplicate beneficiary {}", beneficiary.account_id))
+            );
+        }
+
+        // kept in the contract state, at most MAX_TREASURY_BENEFICIARIES entries
+        self.treasury_beneficiaries = beneficiaries;
+    }
+
+    pub fn get_treasury_beneficiaries(&self) -> Vec<TreasuryBeneficiary> {
+        self.treasury_beneficiaries.clone()
+    }
+
+    /// shares of `amount` each beneficiary would receive from a treasury payout
+    pub fn get_treasury_split(&self, amount: U128) -> Vec<(AccountId, U128)> {
+        self.internal_treasury_split(amount.0)
+            .into_iter()
+            .map(|(account_id, share)| (account_id, U128(share)))
+            .collect()
+    }
+
+    // every fee transfer to the platform goes through here, the treasury settlement only
+    // applies without beneficiaries
+    pub(crate) fn internal_pay_treasury(&mut self, amount: Balance) {
+        if self.treasury_beneficiaries.is_empty() {
+            self.internal_payout(self.treasury_id.clone(), amount);
+            return;
+        }
+        for (account_id, share) in self.internal_treasury_split(amount) {
+            if share > 0 {
+                self.internal_native_payout(account_id, share);
+            }
+        }
+    }
+
+    fn internal_treasury_split(&self, amount: Balance) -> Vec<(AccountId, Balance)> {
+        if self.treasury_beneficiaries.is_empty() {
+            return vec![(self.treasury_id.clone(), amount)];
+        }
+        let total_weight: u128 = self
+            .treasury_beneficiaries
+            .iter()
+            .map(|beneficiary| beneficiary.weight as u128)
+            .sum();
+        let mut shares: Vec<(AccountId, Balance)> = self
+            .treasury_beneficiaries
+            .iter()
+            .map(|beneficiary| {
+                (
+                    beneficiary.account_id.clone(),
+                    amount / total_weight * beneficiary.weight as u128
+                        + amount % total_weight * beneficiary.weight as u128 / total_weight,
+                )
+            })
+            .collect();
+        let dust = amount - shares.iter().map(|(_, share)| share).sum::<Balance>();
+        shares[0].1 += dust;
+        shares
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::test_utils::{accounts, get_created_receipts};
+    use near_sdk::{testing_env, ONE_NEAR};
+
+    use crate::tests::{context, setup, transfers_to};
+    use crate::*;
+
+    fn beneficiaries() -> Vec<TreasuryBeneficiary> {
+        vec![
+            TreasuryBeneficiary {
+                account_id: accounts(2),
+                weight: 3,
+            },
+            TreasuryBeneficiary {
+                account_id: accounts(4),
+                weight: 1,
+            },
+        ]
+    }
+
+    #[test]
+    fn beneficiaries_are_paid_in_native_near() {
+        let mut contract = setup(0);
+        contract.treasury_beneficiaries = beneficiaries();
+        // settlement set before beneficiaries were guarded
+        contract.treasury_settlement = TreasurySettlement::Wnear {
+            wnear_id: "wrap.near".parse().unwrap(),
+        };
+        testing_env!(context(accounts(0), 0).build());
+        contract.internal_pay_treasury(4 * ONE_NEAR);
+
+        assert_eq!(transfers_to(&accounts(2)), vec![3 * ONE_NEAR]);
+        assert_eq!(transfers_to(&accounts(4)), vec![ONE_NEAR]);
+        assert!(get_created_receipts()
+            .iter()
+            .all(|receipt| receipt.receiver_id.as_str() != "wrap.near"));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR5000")]
+    fn wnear_settlement_is_rejected_with_beneficiaries() {
+        let mut contract = setup(0);
+        testing_env!(context(accounts(0), 1).build());
+        contract.set_treasury_beneficiaries(beneficiaries());
+        testing_env!(context(accounts(2), 1).build());
+        contract.set_treasury_settlement(TreasurySettlement::Wnear {
+            wnear_id: "wrap.near".parse().unwrap(),
+        });
+    }
+}
@@ -2,11 +2,40 @@ use crate::*;
 use near_sdk::{
     serde::{Deserialize, Serialize},
 };
-use near_sdk::json_types::U128;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{U128, U64};
 
-//defines the payout type we'll be returning as a part of the royalty standards.
+// contract configuration returned by get_config
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
-pub struct Payout {
-    pub payout: HashMap<AccountId, U128>,
+pub struct ContractConfig {
+    pub admin_id: AccountId,
+    pub operator_id: AccountId,
+    pub treasury_id: AccountId,
+    pub init_imo_fee: U128,
+    pub rock_purchase_fee: u32,
+    pub fee_dust_policy: DustPolicy,
+    pub version: String,
+}
+
+// build metadata embedded at compile time by build.rs
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VersionInfo {
+    pub name: String,
+    pub version: String,
+    pub git_commit: String,
+    pub rustc_version: String,
+    pub build_target: String,
+    pub build_profile: String,
+}
+
+// a rock bought by an account, returned by get_purchases_for_account
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Purchase {
+    pub token_id: TokenId,
+    pub metaverse_id: String,
+    pub price: U128,
+    pub timestamp: U64,
 }
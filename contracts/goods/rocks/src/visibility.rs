@@ -0,0 +1,116 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner hides a zone to prepare and price it privately (e.g. for a surprise drop)
+    /// or makes it visible again. Rocks of a hidden zone are only minted by the metaverse owner
+    /// and get_zones and get_zone_summaries leave the zone out
+    #[payable]
+    pub fn set_zone_visibility(&mut self, metaverse_id: String, zone_index: u16, visible: bool) {
+        self.assert_metaverse_owner(&metaverse_id);
+        self.assert_zone_exist(&metaverse_id, zone_index);
+        if self.internal_is_zone_visible(&metaverse_id, zone_index) == visible {
+            return;
+        }
+        // only hidden zones are stored, a zone without an entry is visible
+        let initial_storage_usage = env::storage_usage();
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        if visible {
+            self.zone_visibility.remove(&zone_key);
+        } else {
+            self.zone_visibility.insert(&zone_key, &false);
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+
+        let zone_visibility_log: EventLog = EventLog {
+            standard: "imo_zone_visibility".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::ZoneVisibilityUpdate(vec![ZoneVisibilityLog {
+                metaverse_id,
+                zone_index,
+                visible,
+                memo: None,
+            }]),
+        };
+        zone_visibility_log.emit();
+    }
+
+    /// zones of a metaverse by zone_index, hidden zones are only listed with include_hidden
+    pub fn get_zones(&self, metaverse_id: String, include_hidden: Option<bool>) -> Vec<Zone> {
+        let include_hidden = include_hidden.unwrap_or(false);
+        let mut zones: Vec<Zone> = self
+            .assert_metaverse_exist(&metaverse_id)
+            .zones
+            .into_values()
+            .filter(|zone| {
+                include_hidden || self.internal_is_zone_visible(&metaverse_id, zone.zone_index)
+            })
+            .collect();
+        zones.sort_by_key(|zone| zone.zone_index);
+        zones
+    }
+
+    pub fn is_zone_visible(&self, metaverse_id: String, zone_index: u16) -> bool {
+        self.assert_zone_exist(&metaverse_id, zone_index);
+        self.internal_is_zone_visible(&metaverse_id, zone_index)
+    }
+
+    pub(crate) fn internal_is_zone_visible(&self, metaverse_id: &String, zone_index: u16) -> bool {
+        self.zone_visibility
+            .get(&gen_zone_key(metaverse_id, zone_index))
+            .unwrap_or(true)
+    }
+
+    // rocks of a hidden zone are minted by the metaverse owner only. The operator (import) and
+    // this contract (callbacks of a checked call) are let through
+    pub(crate) fn assert_zone_visible(&self, metaverse_id: &String, zone: &Zone) {
+        if self.internal_is_zone_visible(metaverse_id, zone.zone_index) {
+            return;
+        }
+        let caller_id = env::predecessor_account_id();
+        require!(
+            self.metaverse_owners.get(metaverse_id) == Some(caller_id.clone())
+                || caller_id == self.tokens.owner_id
+                || caller_id == env::current_account_id(),
+            RoveError::ZoneHidden.as_str()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::test_utils::accounts;
+    use near_sdk::{testing_env, ONE_NEAR};
+
+    use crate::tests::{context, public_zone, setup, METAVERSE_ID};
+    use crate::*;
+
+    #[test]
+    fn hidden_zone_is_stored_until_visible_again() {
+        let mut contract = setup(0);
+        testing_env!(context(accounts(3), ONE_NEAR).build());
+        contract.add_zone(METAVERSE_ID.to_string(), public_zone(2, 1, 10));
+        let zone_key = gen_zone_key(&METAVERSE_ID.to_string(), 2);
+        assert!(contract.zone_visibility.get(&zone_key).is_none());
+
+        contract.set_zone_visibility(METAVERSE_ID.to_string(), 2, false);
+        assert!(!contract.is_zone_visible(METAVERSE_ID.to_string(), 2));
+        assert!(contract
+            .get_zones(METAVERSE_ID.to_string(), None)
+            .iter()
+            .all(|zone| zone.zone_index != 2));
+        assert_eq!(
+            contract.get_zones(METAVERSE_ID.to_string(), Some(true)),
+            vec![public_zone(2, 1, 10)]
+        );
+
+        contract.set_zone_visibility(METAVERSE_ID.to_string(), 2, true);
+        assert!(contract.is_zone_visible(METAVERSE_ID.to_string(), 2));
+        assert!(contract.zone_visibility.get(&zone_key).is_none());
+    }
+}
@@ -0,0 +1,130 @@
+use near_contract_standards::non_fungible_token::core::NonFungibleTokenReceiver;
+
+use crate::*;
+
+// `msg` of nft_transfer_call when a voucher NFT is traded in for a rock
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VoucherMintArgs {
+    pub metaverse_id: String,
+    pub zone_index: u16,
+    pub rock_index: u128,
+    pub token_metadata: TokenMetadata,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner sets (or removes with None) the nft collection accepted as voucher for a zone
+    #[payable]
+    pub fn set_voucher_collection(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        collection_id: Option<AccountId>,
+    ) {
+        self.assert_metaverse_owner(&metaverse_id);
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(
+            zone.mint_policy == MintPolicy::PublicFixedPrice,
+            RoveError::InvalidZoneType.as_str()
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let mut vouchers = self
+            .voucher_collections
+            .get(&metaverse_id)
+            .unwrap_or_default();
+        match collection_id.clone() {
+            Some(collection_id) => vouchers.insert(zone_index, collection_id),
+            None => vouchers.remove(&zone_index),
+        };
+        self.voucher_collections.insert(&metaverse_id, &vouchers);
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+
+        let set_voucher_log: EventLog = EventLog {
+            standard: "public_imo_set_voucher_collection".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::ImoSetVoucherCollection(vec![ImoSetVoucherCollectionLog {
+                metaverse_id,
+                zone_index,
+                collection_id: collection_id.map(|id| id.to_string()),
+                memo: None,
+            }]),
+        };
+
+        set_voucher_log.emit();
+    }
+
+    pub fn get_voucher_collection(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+    ) -> Option<AccountId> {
+        self.voucher_collections
+            .get(&metaverse_id)
+            .and_then(|vouchers| vouchers.get(&zone_index).cloned())
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenReceiver for Contract {
+    /// voucher NFT is locked in this contract and the rock in `msg` is minted to its previous owner.
+    /// Panic on any invalid input, so the voucher collection returns the NFT to its owner.
+    #[allow(unused_variables)]
+    fn nft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        previous_owner_id: AccountId,
+        token_id: TokenId,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        let args: VoucherMintArgs = near_sdk::serde_json::from_str(&msg)
+            .unwrap_or_else(|_| env::panic_str(RoveError::InvalidVoucherArgs.as_str()));
+        let collection_id = env::predecessor_account_id();
+        let voucher_collection = self
+            .get_voucher_collection(args.metaverse_id.clone(), args.zone_index)
+            .unwrap_or_else(|| env::panic_str(RoveError::NoZoneVoucher.as_str()));
+        require!(
+            voucher_collection == collection_id,
+            RoveError::InvalidCollection.detail("voucher collection is invalid")
+        );
+
+        let (_zone, rock_token_id) =
+            self.assert_rock_mintable(&args.metaverse_id, args.zone_index, args.rock_index);
+
+        // voucher is the payment: contract's account pays storage cost
+        self._mint(
+            args.metaverse_id.clone(),
+            args.zone_index,
+            rock_token_id.clone(),
+            previous_owner_id.clone(),
+            args.token_metadata,
+            U128(0),
+            0,
+        );
+
+        let redeem_voucher_log: EventLog = EventLog {
+            standard: "public_imo_redeem_voucher".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::ImoRedeemVoucher(vec![ImoRedeemVoucherLog {
+                metaverse_id: args.metaverse_id,
+                zone_index: args.zone_index,
+                collection_id: collection_id.to_string(),
+                voucher_token_id: token_id,
+                owner_id: previous_owner_id.to_string(),
+                token_id: rock_token_id,
+                memo: None,
+            }]),
+        };
+
+        redeem_voucher_log.emit();
+
+        // keep the voucher
+        PromiseOrValue::Value(false)
+    }
+}
@@ -0,0 +1,401 @@
+use near_contract_standards::non_fungible_token::approval::NonFungibleTokenApproval;
+use near_contract_standards::non_fungible_token::core::{
+    NonFungibleTokenCore, NonFungibleTokenResolver,
+};
+use near_sdk::{ext_contract, Gas};
+
+use crate::*;
+
+pub const GAS_FOR_NFT_APPROVE: Gas = Gas(10_000_000_000_000);
+
+// expiry of one approval, it only applies while the account holds that approval_id
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ApprovalExpiry {
+    pub approval_id: u64,
+    pub expires_at: U64,
+}
+
+// one approval of nft_approvals
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenApproval {
+    pub approval_id: u64,
+    // set when given with nft_approve_with_expiry
+    pub expires_at: Option<U64>,
+    // transfers with an expired approval are rejected
+    pub expired: bool,
+}
+
+#[ext_contract(ext_approval_receiver)]
+pub trait NonFungibleTokenApprovalReceiver {
+    fn nft_on_approve(
+        &mut self,
+        token_id: TokenId,
+        owner_id: AccountId,
+        approval_id: u64,
+        msg: String,
+    );
+}
+
+// standard core and approval methods, transfers involving a denied account or using an expired
+// approval are rejected
+#[near_bindgen]
+impl NonFungibleTokenCore for Contract {
+    #[payable]
+    fn nft_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) {
+        self.assert_transfer_allowed(&token_id, &receiver_id);
+        self.assert_approval_not_expired(&token_id, &env::predecessor_account_id());
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        self.tokens
+            .nft_transfer(receiver_id.clone(), token_id.clone(), approval_id, memo);
+        self.internal_update_holding(&token_id, Some(&owner_id), Some(&receiver_id));
+        emit_account_activity(vec![AccountActivityLog::new(
+            &owner_id,
+            ActivityAction::Transfer,
+            &token_id,
+            Some(&receiver_id),
+            None,
+        )]);
+    }
+
+    #[payable]
+    fn nft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        self.assert_transfer_allowed(&token_id, &receiver_id);
+        self.assert_approval_not_expired(&token_id, &env::predecessor_account_id());
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        // nft_transfer_call transfers before calling the receiver, nft_resolve_transfer counts a
+        // returned token
+        self.internal_update_holding(&token_id, Some(&owner_id), Some(&receiver_id));
+        emit_account_activity(vec![AccountActivityLog::new(
+            &owner_id,
+            ActivityAction::Transfer,
+            &token_id,
+            Some(&receiver_id),
+            None,
+        )]);
+        self.tokens
+            .nft_transfer_call(receiver_id, token_id, approval_id, memo, msg)
+    }
+
+    fn nft_token(&self, token_id: TokenId) -> Option<Token> {
+        self.tokens.nft_token(token_id)
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenResolver for Contract {
+    #[private]
+    fn nft_resolve_transfer(
+        &mut self,
+        previous_owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approved_account_ids: Option<HashMap<AccountId, u64>>,
+    ) -> bool {
+        let owner_id = self.tokens.owner_by_id.get(&token_id);
+        let transferred = self.tokens.nft_resolve_transfer(
+            previous_owner_id,
+            receiver_id,
+            token_id.clone(),
+            approved_account_ids,
+        );
+        let resolved_owner_id = self.tokens.owner_by_id.get(&token_id);
+        if resolved_owner_id != owner_id {
+            self.internal_update_holding(&token_id, owner_id.as_ref(), resolved_owner_id.as_ref());
+            // the receiver returned the token
+            if let (Some(owner_id), Some(resolved_owner_id)) = (&owner_id, &resolved_owner_id) {
+                emit_account_activity(vec![AccountActivityLog::new(
+                    owner_id,
+                    ActivityAction::Transfer,
+                    &token_id,
+                    Some(resolved_owner_id),
+                    None,
+                )]);
+            }
+        }
+        transferred
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenApproval for Contract {
+    #[payable]
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        msg: Option<String>,
+    ) -> Option<Promise> {
+        self.internal_approve(token_id, account_id, None, msg)
+    }
+
+    #[payable]
+    fn nft_revoke(&mut self, token_id: TokenId, account_id: AccountId) {
+        self.tokens.nft_revoke(token_id.clone(), account_id.clone());
+        if let Some(mut expiries) = self.approval_expiries.get(&token_id) {
+            if expiries.remove(&account_id).is_some() {
+                self.internal_save_approval_expiries(&token_id, expiries);
+            }
+        }
+    }
+
+    #[payable]
+    fn nft_revoke_all(&mut self, token_id: TokenId) {
+        self.tokens.nft_revoke_all(token_id.clone());
+        self.approval_expiries.remove(&token_id);
+    }
+
+    fn nft_is_approved(
+        &self,
+        token_id: TokenId,
+        approved_account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool {
+        !self.internal_is_approval_expired(&token_id, &approved_account_id)
+            && self
+                .tokens
+                .nft_is_approved(token_id, approved_account_id, approval_id)
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// owner approves `account_id` (e.g. a marketplace listing) until `expires_at`, a timestamp
+    /// in nanoseconds. Transfers with an expired approval are rejected. Same deposit as nft_approve
+    #[payable]
+    pub fn nft_approve_with_expiry(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        expires_at: U64,
+        msg: Option<String>,
+    ) -> Option<Promise> {
+        require!(
+            expires_at.0 > env::block_timestamp(),
+            RoveError::InvalidArgument.detail("expires_at must be in the future")
+        );
+        self.internal_approve(token_id, account_id, Some(expires_at), msg)
+    }
+
+    /// expiry of the approval of `account_id`, None if it does not expire
+    pub fn get_approval_expiry(&self, token_id: TokenId, account_id: AccountId) -> Option<U64> {
+        self.internal_approval_expiry(&token_id, &account_id)
+            .map(|expiry| expiry.expires_at)
+    }
+
+    /// expiries of the current approvals of a token, expired ones included
+    pub fn get_approval_expiries(&self, token_id: TokenId) -> HashMap<AccountId, U64> {
+        let approved_account_ids = self
+            .tokens
+            .approvals_by_id
+            .as_ref()
+            .and_then(|by_id| by_id.get(&token_id))
+            .unwrap_or_default();
+        self.approval_expiries
+            .get(&token_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(account_id, expiry)| {
+                approved_account_ids.get(account_id) == Some(&expiry.approval_id)
+            })
+            .map(|(account_id, expiry)| (account_id, expiry.expires_at))
+            .collect()
+    }
+
+    /// every approval of a token by approved account with its approval_id and expiry, None if
+    /// the token does not exist
+    pub fn nft_approvals(&self, token_id: TokenId) -> Option<HashMap<AccountId, TokenApproval>> {
+        self.tokens.owner_by_id.get(&token_id)?;
+        let approved_account_ids = self
+            .tokens
+            .approvals_by_id
+            .as_ref()
+            .and_then(|by_id| by_id.get(&token_id))
+            .unwrap_or_default();
+        let expiries = self.get_approval_expiries(token_id);
+        Some(
+            approved_account_ids
+                .into_iter()
+                .map(|(account_id, approval_id)| {
+                    let expires_at = expiries.get(&account_id).cloned();
+                    let expired = expires_at
+                        .map(|expires_at| env::block_timestamp() >= expires_at.0)
+                        .unwrap_or(false);
+                    (
+                        account_id,
+                        TokenApproval {
+                            approval_id,
+                            expires_at,
+                            expired,
+                        },
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// whether `account_id` may transfer the token with its approval (matching `approval_id`
+    /// when given), false for unknown tokens and expired approvals instead of panicking
+    pub fn is_approved(
+        &self,
+        token_id: TokenId,
+        account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool {
+        self.nft_approvals(token_id)
+            .and_then(|approvals| approvals.get(&account_id).cloned())
+            .map(|approval| {
+                !approval.expired
+                    && approval_id
+                        .map(|approval_id| approval_id == approval.approval_id)
+                        .unwrap_or(true)
+            })
+            .unwrap_or(false)
+    }
+
+    // same as the standard nft_approve, the approval storage and its expiry are both charged
+    fn internal_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        expires_at: Option<U64>,
+        msg: Option<String>,
+    ) -> Option<Promise> {
+        assert_at_least_one_yocto();
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        require!(
+            env::predecessor_account_id() == owner_id,
+            RoveError::NotTokenOwner.as_str()
+        );
+        self.assert_not_denied(&owner_id);
+        self.assert_not_denied(&account_id);
+
+        let initial_storage_usage = env::storage_usage();
+        let approvals_by_id = self.tokens.approvals_by_id.as_mut().unwrap();
+        let next_approval_id_by_id = self.tokens.next_approval_id_by_id.as_mut().unwrap();
+        let mut approved_account_ids = approvals_by_id.get(&token_id).unwrap_or_default();
+        let approval_id = next_approval_id_by_id.get(&token_id).unwrap_or(1u64);
+        approved_account_ids.insert(account_id.clone(), approval_id);
+        approvals_by_id.insert(&token_id, &approved_account_ids);
+        next_approval_id_by_id.insert(&token_id, &(approval_id + 1));
+
+        // expiries of revoked or replaced approvals are dropped
+        let mut expiries: HashMap<AccountId, ApprovalExpiry> = self
+            .approval_expiries
+            .get(&token_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(account_id, expiry)| {
+                approved_account_ids.get(account_id) == Some(&expiry.approval_id)
+            })
+            .collect();
+        if let Some(expires_at) = expires_at {
+            expiries.insert(
+                account_id.clone(),
+                ApprovalExpiry {
+                    approval_id,
+                    expires_at,
+                },
+            );
+        }
+        self.internal_save_approval_expiries(&token_id, expiries);
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                owner_id.clone(),
+            );
+        }
+        emit_account_activity(vec![AccountActivityLog::new(
+            &owner_id,
+            ActivityAction::List,
+            &token_id,
+            Some(&account_id),
+            None,
+        )]);
+
+        msg.map(|msg| {
+            ext_approval_receiver::nft_on_approve(
+                token_id,
+                owner_id,
+                approval_id,
+                msg,
+                account_id,
+                0,
+                env::prepaid_gas() - GAS_FOR_NFT_APPROVE,
+            )
+        })
+    }
+
+    fn internal_save_approval_expiries(
+        &mut self,
+        token_id: &TokenId,
+        expiries: HashMap<AccountId, ApprovalExpiry>,
+    ) {
+        if expiries.is_empty() {
+            self.approval_expiries.remove(token_id);
+        } else {
+            self.approval_expiries.insert(token_id, &expiries);
+        }
+    }
+
+    // expiry of the current approval of account_id, expiries of previous approvals are ignored
+    fn internal_approval_expiry(
+        &self,
+        token_id: &TokenId,
+        account_id: &AccountId,
+    ) -> Option<ApprovalExpiry> {
+        let approval_id = self
+            .tokens
+            .approvals_by_id
+            .as_ref()
+            .and_then(|by_id| by_id.get(token_id))
+            .and_then(|approved_account_ids| approved_account_ids.get(account_id).cloned())?;
+        self.approval_expiries
+            .get(token_id)
+            .and_then(|expiries| expiries.get(account_id).cloned())
+            .filter(|expiry| expiry.approval_id == approval_id)
+    }
+
+    pub(crate) fn internal_is_approval_expired(
+        &self,
+        token_id: &TokenId,
+        account_id: &AccountId,
+    ) -> bool {
+        self.internal_approval_expiry(token_id, account_id)
+            .map(|expiry| env::block_timestamp() >= expiry.expires_at.0)
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn assert_approval_not_expired(&self, token_id: &TokenId, account_id: &AccountId) {
+        require!(
+            !self.internal_is_approval_expired(token_id, account_id),
+            RoveError::ApprovalExpired.as_str()
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// admin denies accounts (e.g. sanctioned ones) from minting, transferring, receiving and
+    /// approving rocks, `reason` is logged with the event
+    #[payable]
+    pub fn deny_accounts(&mut self, account_ids: Vec<AccountId>, reason: Option<String>) {
+        self.assert_admin_only();
+        require!(!account_ids.is_empty(), RoveError::EmptyBatch.as_str());
+
+        let initial_storage_usage = env::storage_usage();
+        for account_id in account_ids.iter() {
+            self.denied_accounts.insert(account_id);
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+
+        emit_deny_list_log(EventLogVariant::AccountsDenied, account_ids, reason);
+    }
+
+    /// admin removes accounts from the deny list
+    #[payable]
+    pub fn allow_accounts(&mut self, account_ids: Vec<AccountId>, reason: Option<String>) {
+        self.assert_admin_only();
+        require!(!account_ids.is_empty(), RoveError::EmptyBatch.as_str());
+        for account_id in account_ids.iter() {
+            self.denied_accounts.remove(account_id);
+        }
+
+        emit_deny_list_log(EventLogVariant::AccountsAllowed, account_ids, reason);
+    }
+
+    pub fn is_denied(&self, account_id: AccountId) -> bool {
+        self.denied_accounts.contains(&account_id)
+    }
+
+    pub fn get_denied_accounts(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<AccountId> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        self.denied_accounts
+            .iter()
+            .skip(start_index as usize)
+            .take(limit)
+            .collect()
+    }
+
+    pub(crate) fn assert_not_denied(&self, account_id: &AccountId) {
+        require!(
+            !self.denied_accounts.contains(account_id),
+            RoveError::AccountDenied.detail(account_id)
+        );
+    }
+
+    // the caller, the owner of the token and the receiver must all be allowed, and the receiver
+    // eligible in zones restricting transfers
+    pub(crate) fn assert_transfer_allowed(&self, token_id: &TokenId, receiver_id: &AccountId) {
+        self.assert_not_denied(&env::predecessor_account_id());
+        self.assert_not_denied(receiver_id);
+        self.assert_transfer_receiver(token_id, receiver_id);
+        if let Some(owner_id) = self.tokens.owner_by_id.get(token_id) {
+            self.assert_not_denied(&owner_id);
+        }
+    }
+}
+
+fn emit_deny_list_log(
+    variant: fn(Vec<DenyListLog>) -> EventLogVariant,
+    account_ids: Vec<AccountId>,
+    reason: Option<String>,
+) {
+    let deny_list_log: EventLog = EventLog {
+        standard: "deny_list".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![DenyListLog {
+            account_ids: account_ids
+                .into_iter()
+                .map(|account_id| account_id.to_string())
+                .collect(),
+            memo: reason,
+        }]),
+    };
+    deny_list_log.emit();
+}
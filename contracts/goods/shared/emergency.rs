@@ -0,0 +1,120 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+// delay between queueing and executing an emergency action: 24h in nanoseconds
+pub const EMERGENCY_TIMELOCK: u64 = 24 * 60 * 60 * 1_000_000_000;
+pub const EMERGENCY_ACTION_REVOKE_ALL_APPROVALS: &str = "revoke_all_approvals";
+
+fn gen_emergency_action_key(action: &str, token_id: &TokenId) -> String {
+    format!("{}:{}", action, token_id)
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Admin only, timelocked: the first call queues the action, a call after EMERGENCY_TIMELOCK
+    /// removes every approval of token_id and refunds the released storage to the token owner
+    #[payable]
+    pub fn revoke_all_approvals(&mut self, token_id: TokenId) {
+        self.assert_admin_only();
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+
+        if !self.internal_emergency_action_ready(EMERGENCY_ACTION_REVOKE_ALL_APPROVALS, &token_id) {
+            return;
+        }
+
+        if let Some(approvals_by_id) = &mut self.tokens.approvals_by_id {
+            if let Some(approved_account_ids) = approvals_by_id.remove(&token_id) {
+                refund_approved_account_ids(owner_id, &approved_account_ids);
+            }
+        }
+        self.approval_expiries.remove(&token_id);
+        emit_emergency_action_log(
+            EventLogVariant::EmergencyActionExecuted,
+            EMERGENCY_ACTION_REVOKE_ALL_APPROVALS,
+            &token_id,
+            env::block_timestamp(),
+        );
+    }
+
+    #[payable]
+    pub fn cancel_emergency_action(&mut self, action: String, token_id: TokenId) {
+        self.assert_admin_only();
+        let executable_at = self
+            .emergency_actions
+            .remove(&gen_emergency_action_key(&action, &token_id))
+            .unwrap_or_else(|| env::panic_str(RoveError::EmergencyNotQueued.as_str()));
+        emit_emergency_action_log(
+            EventLogVariant::EmergencyActionCancelled,
+            &action,
+            &token_id,
+            executable_at,
+        );
+    }
+
+    // timestamp (nanoseconds) from which the queued action can be executed
+    pub fn get_emergency_action(&self, action: String, token_id: TokenId) -> Option<U64> {
+        self.emergency_actions
+            .get(&gen_emergency_action_key(&action, &token_id))
+            .map(U64)
+    }
+
+    // queue the action on first call, return true once its timelock has passed
+    fn internal_emergency_action_ready(&mut self, action: &str, token_id: &TokenId) -> bool {
+        let key = gen_emergency_action_key(action, token_id);
+        match self.emergency_actions.get(&key) {
+            None => {
+                let initial_storage_usage = env::storage_usage();
+                let executable_at = env::block_timestamp() + EMERGENCY_TIMELOCK;
+                self.emergency_actions.insert(&key, &executable_at);
+                refund_deposit_to_account(
+                    env::storage_usage() - initial_storage_usage,
+                    env::predecessor_account_id(),
+                );
+                emit_emergency_action_log(
+                    EventLogVariant::EmergencyActionQueued,
+                    action,
+                    token_id,
+                    executable_at,
+                );
+                false
+            }
+            Some(executable_at) => {
+                require!(
+                    env::block_timestamp() >= executable_at,
+                    RoveError::EmergencyTimelocked.detail(format!(
+                        "emergency action is timelocked until {}",
+                        executable_at
+                    ))
+                );
+                self.emergency_actions.remove(&key);
+                true
+            }
+        }
+    }
+}
+
+fn emit_emergency_action_log(
+    variant: fn(Vec<EmergencyActionLog>) -> EventLogVariant,
+    action: &str,
+    token_id: &TokenId,
+    executable_at: u64,
+) {
+    let emergency_action_log: EventLog = EventLog {
+        standard: "emergency_action".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![EmergencyActionLog {
+            action: action.to_string(),
+            token_id: token_id.to_string(),
+            admin_id: env::predecessor_account_id().to_string(),
+            executable_at: U64(executable_at),
+            memo: None,
+        }]),
+    };
+
+    emergency_action_log.emit();
+}
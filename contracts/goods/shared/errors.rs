@@ -0,0 +1,256 @@
+use std::fmt;
+
+// Stable error codes shared by every Rove goods contract (keep this file identical in all of
+// them). Panic messages read `ERR{code}: {message}` so clients can map codes to localized text:
+// 1xxx access, 2xxx deposit and payout, 3xxx metaverse and zone, 4xxx token and sale, 5xxx arguments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoveError {
+    Unauthorized,
+    NotMetaverseOwner,
+    NotCoreTeam,
+    NotTokenOwner,
+    NotApproved,
+    NotRelayer,
+    NotCallback,
+    AlreadyInitialized,
+    NotCollectionHolder,
+    NotInAllowlist,
+    ImportFinalized,
+    ContractNotLocked,
+    AccountDenied,
+    NotInAirdrop,
+    NotZoneReceiver,
+    NotCampaignEligible,
+    NotInitialized,
+    DepositRequired,
+    NotEnoughDeposit,
+    NotEnoughGas,
+    PayoutNotFound,
+    TooManyPayoutReceivers,
+    NotEnoughRelayFund,
+    NotEnoughSponsorship,
+    DepositTooLarge,
+    MetaverseNotFound,
+    MetaverseAlreadyExists,
+    ZoneNotFound,
+    ZoneAlreadyExists,
+    InvalidZone,
+    InvalidZoneType,
+    InvalidRockIndex,
+    MissingZonePrice,
+    InvalidCollection,
+    NoZoneAllowlist,
+    NoZoneVoucher,
+    MetadataNotEditable,
+    NoHostingFee,
+    HostingNotOverdue,
+    MapCommitmentNotSet,
+    MintThrottled,
+    ZoneNotSoldOut,
+    RockCapReached,
+    SlugTaken,
+    SlugAuctionNotFound,
+    SlugAuctionEnded,
+    SlugAuctionNotEnded,
+    InstallmentsNotEnabled,
+    RaffleActive,
+    RaffleNotFound,
+    RaffleClosed,
+    RaffleNotClosed,
+    RaffleAlreadyEntered,
+    RaffleAlreadyDrawn,
+    CollectionChangeNotFound,
+    AirdropActive,
+    AirdropNotFound,
+    AirdropAlreadyClaimed,
+    OperatorMigrationPending,
+    OperatorMigrationNotFound,
+    MetaverseLimitReached,
+    CampaignNotFound,
+    CampaignExhausted,
+    ZoneHidden,
+    TokenNotFound,
+    TokenAlreadyExists,
+    InvalidTokenId,
+    MintPending,
+    CheckerNotReady,
+    CheckerFailed,
+    CollectionTokenUsed,
+    NftTypeNotFound,
+    MaxSupplyReached,
+    SaleNotLive,
+    InvalidSaleStateTransition,
+    NoUpgradeRecipe,
+    InvalidUpgradeTokens,
+    SameOwner,
+    InvalidNonce,
+    RelayLimitReached,
+    RelayerNotSet,
+    InvalidVoucherArgs,
+    EscrowNotFound,
+    EscrowLocked,
+    RefundWindowOver,
+    RefundNotAllowed,
+    AlreadyFlagged,
+    FlagRateLimited,
+    EmergencyNotQueued,
+    EmergencyTimelocked,
+    MetadataFrozen,
+    InstallmentNotFound,
+    InstallmentOverdue,
+    InstallmentNotOverdue,
+    RegionNotFound,
+    ApprovalExpired,
+    RockOnHold,
+    FaucetNotEnabled,
+    FaucetLimitReached,
+    QuoteKeyNotSet,
+    QuoteExpired,
+    InvalidQuoteSignature,
+    InvalidArgument,
+    ValueTooLong,
+    FieldNotEditable,
+    TooManyItems,
+    EmptyBatch,
+}
+
+impl RoveError {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoveError::Unauthorized => "ERR1000: unauthorized",
+            RoveError::NotMetaverseOwner => "ERR1001: only metaverse owner can call this function",
+            RoveError::NotCoreTeam => "ERR1002: only core team can call this mint",
+            RoveError::NotTokenOwner => "ERR1003: caller does not own the token",
+            RoveError::NotApproved => "ERR1004: sender is not approved",
+            RoveError::NotRelayer => "ERR1005: caller must be the relayer",
+            RoveError::NotCallback => "ERR1006: this is a callback method",
+            RoveError::AlreadyInitialized => "ERR1007: contract is already initialized",
+            RoveError::NotCollectionHolder => {
+                "ERR1008: caller does not hold an nft of the zone collection"
+            }
+            RoveError::NotInAllowlist => "ERR1009: caller is not in the zone allowlist",
+            RoveError::ImportFinalized => "ERR1010: state import is finalized",
+            RoveError::ContractNotLocked => "ERR1011: contract account still has access keys",
+            RoveError::AccountDenied => "ERR1012: account is on the deny list",
+            RoveError::NotInAirdrop => "ERR1013: caller is not allocated this rock in the airdrop",
+            RoveError::NotZoneReceiver => {
+                "ERR1014: account is not an eligible receiver of the zone"
+            }
+            RoveError::NotCampaignEligible => "ERR1015: caller is not eligible for the campaign",
+            RoveError::NotInitialized => "ERR1016: contract is not initialized",
+            RoveError::DepositRequired => {
+                "ERR2000: requires attached deposit of at least 1 yoctoNEAR"
+            }
+            RoveError::NotEnoughDeposit => "ERR2001: not enough attached deposit",
+            RoveError::NotEnoughGas => "ERR2002: not enough prepaid gas",
+            RoveError::PayoutNotFound => "ERR2003: payout_id not found",
+            RoveError::TooManyPayoutReceivers => {
+                "ERR2004: market cannot payout to that many receivers"
+            }
+            RoveError::NotEnoughRelayFund => "ERR2005: not enough relay fund",
+            RoveError::NotEnoughSponsorship => "ERR2006: not enough sponsorship balance",
+            RoveError::DepositTooLarge => "ERR2007: attached deposit is too large",
+            RoveError::MetaverseNotFound => "ERR3000: metaverse_id not found",
+            RoveError::MetaverseAlreadyExists => "ERR3001: metaverse already exists",
+            RoveError::ZoneNotFound => "ERR3002: zone_index not found",
+            RoveError::ZoneAlreadyExists => "ERR3003: zone_index already exists",
+            RoveError::InvalidZone => "ERR3004: zone is invalid",
+            RoveError::InvalidZoneType => "ERR3005: type_zone is invalid",
+            RoveError::InvalidRockIndex => "ERR3006: rock index is invalid",
+            RoveError::MissingZonePrice => "ERR3007: missing price for public zone",
+            RoveError::InvalidCollection => "ERR3008: collection is invalid",
+            RoveError::NoZoneAllowlist => "ERR3009: zone does not have allowlist",
+            RoveError::NoZoneVoucher => "ERR3010: zone does not accept voucher",
+            RoveError::MetadataNotEditable => "ERR3011: metaverse does not allow metadata updates",
+            RoveError::NoHostingFee => "ERR3012: metaverse does not have hosting fee",
+            RoveError::HostingNotOverdue => "ERR3013: hosting is not overdue",
+            RoveError::MapCommitmentNotSet => "ERR3014: map commitment is not set",
+            RoveError::MintThrottled => "ERR3015: mint limit is reached, try again later",
+            RoveError::ZoneNotSoldOut => "ERR3016: zone is not sold out",
+            RoveError::RockCapReached => "ERR3017: metaverse rock cap is reached",
+            RoveError::SlugTaken => "ERR3018: slug is already taken",
+            RoveError::SlugAuctionNotFound => "ERR3019: slug auction not found",
+            RoveError::SlugAuctionEnded => "ERR3020: slug auction has ended",
+            RoveError::SlugAuctionNotEnded => "ERR3021: slug auction has not ended yet",
+            RoveError::InstallmentsNotEnabled => {
+                "ERR3022: metaverse does not offer installment purchases"
+            }
+            RoveError::RaffleActive => "ERR3023: rock is reserved for a raffle",
+            RoveError::RaffleNotFound => "ERR3024: zone does not have a raffle",
+            RoveError::RaffleClosed => "ERR3025: raffle is closed",
+            RoveError::RaffleNotClosed => "ERR3026: raffle is not closed yet",
+            RoveError::RaffleAlreadyEntered => "ERR3027: account already entered the raffle",
+            RoveError::RaffleAlreadyDrawn => "ERR3028: raffle is already drawn",
+            RoveError::CollectionChangeNotFound => {
+                "ERR3029: metaverse does not have a pending collection change"
+            }
+            RoveError::AirdropActive => "ERR3030: zone is reserved for an airdrop",
+            RoveError::AirdropNotFound => "ERR3031: zone does not have an airdrop",
+            RoveError::AirdropAlreadyClaimed => "ERR3032: airdrop is already claimed",
+            RoveError::OperatorMigrationPending => {
+                "ERR3033: tokens of the previous operator are still migrating"
+            }
+            RoveError::OperatorMigrationNotFound => "ERR3034: no operator token migration",
+            RoveError::MetaverseLimitReached => "ERR3035: metaverse limit is reached",
+            RoveError::CampaignNotFound => "ERR3036: zone does not have a campaign",
+            RoveError::CampaignExhausted => "ERR3037: campaign budget is spent",
+            RoveError::ZoneHidden => "ERR3038: zone is hidden",
+            RoveError::TokenNotFound => "ERR4000: token not found",
+            RoveError::TokenAlreadyExists => "ERR4001: token already exists",
+            RoveError::InvalidTokenId => "ERR4002: token_id has wrong format",
+            RoveError::MintPending => "ERR4003: token is being minted, try again later",
+            RoveError::CheckerNotReady => "ERR4004: nft checker is not ready",
+            RoveError::CheckerFailed => "ERR4005: nft checker failed",
+            RoveError::CollectionTokenUsed => {
+                "ERR4006: collection token is already used in this zone"
+            }
+            RoveError::NftTypeNotFound => "ERR4007: nft_type_id not found",
+            RoveError::MaxSupplyReached => "ERR4008: max supply is reached",
+            RoveError::SaleNotLive => "ERR4009: sale is not live",
+            RoveError::InvalidSaleStateTransition => "ERR4010: invalid sale state transition",
+            RoveError::NoUpgradeRecipe => "ERR4011: no upgrade recipe for target type",
+            RoveError::InvalidUpgradeTokens => "ERR4012: tokens do not match the upgrade recipe",
+            RoveError::SameOwner => "ERR4013: current and next owner must differ",
+            RoveError::InvalidNonce => "ERR4014: invalid nonce",
+            RoveError::RelayLimitReached => "ERR4015: relayed mint daily limit reached",
+            RoveError::RelayerNotSet => "ERR4016: relayer is not set",
+            RoveError::InvalidVoucherArgs => "ERR4017: msg is invalid voucher mint args",
+            RoveError::EscrowNotFound => "ERR4018: token does not have escrowed sale",
+            RoveError::EscrowLocked => "ERR4019: escrow can not be released yet",
+            RoveError::RefundWindowOver => "ERR4020: refund window is over",
+            RoveError::RefundNotAllowed => "ERR4021: rock is not owned by the buyer anymore",
+            RoveError::AlreadyFlagged => "ERR4022: token is already flagged by this account",
+            RoveError::FlagRateLimited => "ERR4023: flag_token is rate limited",
+            RoveError::EmergencyNotQueued => "ERR4024: emergency action is not queued",
+            RoveError::EmergencyTimelocked => "ERR4025: emergency action is timelocked",
+            RoveError::MetadataFrozen => "ERR4026: token metadata is frozen",
+            RoveError::InstallmentNotFound => "ERR4027: token does not have an installment plan",
+            RoveError::InstallmentOverdue => "ERR4028: installment plan is overdue",
+            RoveError::InstallmentNotOverdue => "ERR4029: installment plan is not overdue",
+            RoveError::RegionNotFound => "ERR4030: token is not a region",
+            RoveError::ApprovalExpired => "ERR4031: approval has expired",
+            RoveError::RockOnHold => "ERR4032: rock is on hold",
+            RoveError::FaucetNotEnabled => "ERR4033: faucet mode is not enabled",
+            RoveError::FaucetLimitReached => "ERR4034: faucet mint limit of the account reached",
+            RoveError::QuoteKeyNotSet => "ERR4035: price quote key is not set",
+            RoveError::QuoteExpired => "ERR4036: price quote has expired",
+            RoveError::InvalidQuoteSignature => "ERR4037: price quote signature is invalid",
+            RoveError::InvalidArgument => "ERR5000: invalid argument",
+            RoveError::ValueTooLong => "ERR5001: value is too long",
+            RoveError::FieldNotEditable => "ERR5002: field is not editable",
+            RoveError::TooManyItems => "ERR5003: too many items",
+            RoveError::EmptyBatch => "ERR5004: batch is empty",
+        }
+    }
+
+    // `ERR{code}: {message} ({detail})`, for messages that carry values
+    pub fn detail(&self, detail: impl fmt::Display) -> String {
+        format!("{} ({})", self.as_str(), detail)
+    }
+}
+
+impl fmt::Display for RoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,212 @@
+use near_sdk::json_types::U64;
+
+use crate::*;
+
+// longest refund window a metaverse can offer (30 days)
+pub const MAX_ESCROW_WINDOW_SEC: u32 = 30 * 24 * 3600;
+
+// proceeds of a primary sale held until release_at, the buyer may return the rock before that
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscrowedSale {
+    pub metaverse_id: String,
+    pub buyer_id: AccountId,
+    pub treasury_amount: U128,
+    pub metaverse_owner_amount: U128,
+    pub release_at: U64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner enables (or disables with None) the refund window of its primary sales
+    #[payable]
+    pub fn set_escrow_window(&mut self, metaverse_id: String, window_sec: Option<u32>) {
+        self.assert_metaverse_owner(&metaverse_id);
+
+        let initial_storage_usage = env::storage_usage();
+        match window_sec {
+            Some(window_sec) => {
+                require!(
+                    0 < window_sec && window_sec <= MAX_ESCROW_WINDOW_SEC,
+                    RoveError::InvalidArgument.detail(format!(
+                        "window_sec must be in (0, {}]",
+                        MAX_ESCROW_WINDOW_SEC
+                    ))
+                );
+                self.escrow_windows.insert(&metaverse_id, &window_sec)
+            }
+            None => self.escrow_windows.remove(&metaverse_id),
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    pub fn get_escrow_window(&self, metaverse_id: String) -> Option<u32> {
+        self.escrow_windows.get(&metaverse_id)
+    }
+
+    pub fn get_escrowed_sale(&self, token_id: TokenId) -> Option<EscrowedSale> {
+        self.escrowed_sales.get(&token_id)
+    }
+
+    /// buyer returns a rock within the refund window, the rock goes to the metaverse owner
+    /// and the price without the storage cost is refunded
+    #[payable]
+    pub fn request_refund(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        let escrowed_sale = self
+            .escrowed_sales
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::EscrowNotFound.as_str()));
+        require!(
+            env::block_timestamp() < escrowed_sale.release_at.0,
+            RoveError::RefundWindowOver.as_str()
+        );
+        let buyer_id = env::predecessor_account_id();
+        require!(
+            buyer_id == escrowed_sale.buyer_id,
+            RoveError::Unauthorized.as_str()
+        );
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        require!(owner_id == buyer_id, RoveError::RefundNotAllowed.as_str());
+
+        self.escrowed_sales.remove(&token_id);
+        let metaverse_owner = self
+            .metaverse_owners
+            .get(&escrowed_sale.metaverse_id)
+            .unwrap();
+        if let Some(approved_account_ids) = self
+            .tokens
+            .approvals_by_id
+            .as_mut()
+            .and_then(|by_id| by_id.remove(&token_id))
+        {
+            refund_approved_account_ids(buyer_id.clone(), &approved_account_ids);
+        }
+        self.internal_transfer_token(&token_id, &buyer_id, &metaverse_owner);
+
+        let amount = escrowed_sale.treasury_amount.0 + escrowed_sale.metaverse_owner_amount.0;
+        if amount > 0 {
+            self.internal_payout(buyer_id.clone(), amount);
+        }
+        self.internal_sub_metaverse_volume(&escrowed_sale.metaverse_id, amount);
+
+        let nft_transfer_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                authorized_id: None,
+                old_owner_id: buyer_id.to_string(),
+                new_owner_id: metaverse_owner.to_string(),
+                token_ids: vec![token_id.clone()],
+                memo: Some(String::from("request_refund")),
+            }]),
+        };
+        nft_transfer_log.emit();
+        emit_account_activity(vec![AccountActivityLog::new(
+            &buyer_id,
+            ActivityAction::Transfer,
+            &token_id,
+            Some(&metaverse_owner),
+            None,
+        )]);
+        emit_escrow_log(EventLogVariant::EscrowRefunded, token_id, &escrowed_sale);
+    }
+
+    /// pay out a sale past its refund window, callable by anyone
+    pub fn finalize_escrow(&mut self, token_id: TokenId) {
+        require!(
+            self.internal_release_escrow(&token_id),
+            RoveError::EscrowLocked.as_str()
+        );
+    }
+
+    // hold the proceeds of a primary sale when the metaverse has a refund window,
+    // returns false when the proceeds must be paid out now
+    pub(crate) fn internal_escrow_sale(
+        &mut self,
+        metaverse_id: &String,
+        token_id: &TokenId,
+        buyer_id: &AccountId,
+        treasury_amount: Balance,
+        metaverse_owner_amount: Balance,
+    ) -> bool {
+        let window_sec = match self.escrow_windows.get(metaverse_id) {
+            Some(window_sec) => window_sec,
+            None => return false,
+        };
+
+        // storage of the escrow is fronted by this contract until it is released or refunded
+        let escrowed_sale = EscrowedSale {
+            metaverse_id: metaverse_id.clone(),
+            buyer_id: buyer_id.clone(),
+            treasury_amount: U128(treasury_amount),
+            metaverse_owner_amount: U128(metaverse_owner_amount),
+            release_at: U64(env::block_timestamp() + window_sec as u64 * 1_000_000_000),
+        };
+        self.escrowed_sales.insert(token_id, &escrowed_sale);
+        emit_escrow_log(
+            EventLogVariant::EscrowHeld,
+            token_id.clone(),
+            &escrowed_sale,
+        );
+        true
+    }
+
+    // pay out the escrowed sale of token_id once its refund window is over
+    pub(crate) fn internal_release_escrow(&mut self, token_id: &TokenId) -> bool {
+        let escrowed_sale = match self.escrowed_sales.get(token_id) {
+            Some(escrowed_sale) => escrowed_sale,
+            None => return false,
+        };
+        if env::block_timestamp() < escrowed_sale.release_at.0 {
+            return false;
+        }
+
+        self.escrowed_sales.remove(token_id);
+        if escrowed_sale.treasury_amount.0 > 0 {
+            self.internal_pay_treasury(escrowed_sale.treasury_amount.0);
+        }
+        if escrowed_sale.metaverse_owner_amount.0 > 0 {
+            let metaverse_owner = self
+                .metaverse_owners
+                .get(&escrowed_sale.metaverse_id)
+                .unwrap();
+            self.internal_payout(metaverse_owner, escrowed_sale.metaverse_owner_amount.0);
+        }
+        emit_escrow_log(
+            EventLogVariant::EscrowReleased,
+            token_id.clone(),
+            &escrowed_sale,
+        );
+        true
+    }
+}
+
+fn emit_escrow_log(
+    variant: fn(Vec<EscrowLog>) -> EventLogVariant,
+    token_id: TokenId,
+    escrowed_sale: &EscrowedSale,
+) {
+    let escrow_log: EventLog = EventLog {
+        standard: "escrow".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![EscrowLog {
+            token_id,
+            buyer_id: escrowed_sale.buyer_id.to_string(),
+            amount: U128(escrowed_sale.treasury_amount.0 + escrowed_sale.metaverse_owner_amount.0),
+            release_at: escrowed_sale.release_at,
+            memo: None,
+        }]),
+    };
+
+    escrow_log.emit();
+}
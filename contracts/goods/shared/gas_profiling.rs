@@ -0,0 +1,37 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /// operator turns on (or off) the gas checkpoints of mint_rock, the nft checker callback
+    /// and init_metaverse, e.g. while out-of-gas reports of users are diagnosed
+    #[payable]
+    pub fn set_gas_profiling(&mut self, enabled: bool) {
+        self.assert_operator_only();
+        self.gas_profiling = enabled;
+    }
+
+    pub fn get_gas_profiling(&self) -> bool {
+        self.gas_profiling
+    }
+
+    // with gas_profiling on, log the gas and storage used so far by `method`, so out-of-gas
+    // reports can be diagnosed from the receipt logs without reproducing the call
+    pub(crate) fn gas_checkpoint(&self, method: &str, checkpoint: &str) {
+        if !self.gas_profiling {
+            return;
+        }
+        let gas_checkpoint_log: EventLog = EventLog {
+            standard: "gas_profiling".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::GasCheckpoint(vec![GasCheckpointLog {
+                method: method.to_string(),
+                checkpoint: checkpoint.to_string(),
+                used_gas: U64(env::used_gas().0),
+                prepaid_gas: U64(env::prepaid_gas().0),
+                storage_usage: U64(env::storage_usage()),
+                memo: None,
+            }]),
+        };
+        gas_checkpoint_log.emit();
+    }
+}
@@ -0,0 +1,165 @@
+use crate::*;
+
+// longest hold of a rock (90 days in nanoseconds), a longer promo renews it
+pub const MAX_HOLD_DURATION: u64 = 90 * 24 * 60 * 60 * 1_000_000_000;
+pub const MAX_HOLD_ROCKS: usize = 100;
+
+#[near_bindgen]
+impl Contract {
+    /// operator takes unminted rocks out of public sale until `until_timestamp` (nanoseconds),
+    /// e.g. for a marketing promo or while a dispute is resolved. Holding a held rock again
+    /// replaces its expiry. Only the operator can mint a held rock
+    #[payable]
+    pub fn hold_rocks(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        indices: Vec<u128>,
+        until_timestamp: U64,
+        reason: Option<String>,
+    ) {
+        self.assert_operator_only();
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(!indices.is_empty(), RoveError::EmptyBatch.as_str());
+        require!(
+            indices.len() <= MAX_HOLD_ROCKS,
+            RoveError::TooManyItems.detail("too many rocks to hold")
+        );
+        let now = env::block_timestamp();
+        require!(
+            now < until_timestamp.0 && until_timestamp.0 <= now + MAX_HOLD_DURATION,
+            RoveError::InvalidArgument.detail("until_timestamp must be in the next 90 days")
+        );
+        let tokens_minted = self.tokens_minted.get(&metaverse_id).unwrap_or_default();
+
+        let initial_storage_usage = env::storage_usage();
+        for rock_index in indices.iter() {
+            require!(
+                zone.rock_index_from <= *rock_index && *rock_index <= zone.rock_index_to,
+                RoveError::InvalidRockIndex.as_str()
+            );
+            let token_id = rock_token_id(&metaverse_id, zone_index, *rock_index);
+            require!(
+                !tokens_minted.contains_key(&token_id),
+                RoveError::TokenAlreadyExists.detail(&token_id)
+            );
+            self.rock_holds.insert(&token_id, &until_timestamp.0);
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+
+        emit_rock_hold_log(
+            EventLogVariant::RocksHeld,
+            metaverse_id,
+            zone_index,
+            indices,
+            Some(until_timestamp),
+            reason,
+        );
+    }
+
+    /// operator puts held rocks back on sale before their hold expires
+    #[payable]
+    pub fn release_rocks(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        indices: Vec<u128>,
+        reason: Option<String>,
+    ) {
+        self.assert_operator_only();
+        require!(!indices.is_empty(), RoveError::EmptyBatch.as_str());
+        require!(
+            indices.len() <= MAX_HOLD_ROCKS,
+            RoveError::TooManyItems.detail("too many rocks to release")
+        );
+        for rock_index in indices.iter() {
+            self.rock_holds
+                .remove(&rock_token_id(&metaverse_id, zone_index, *rock_index));
+        }
+
+        emit_rock_hold_log(
+            EventLogVariant::RocksReleased,
+            metaverse_id,
+            zone_index,
+            indices,
+            None,
+            reason,
+        );
+    }
+
+    /// end of the hold of an unminted rock, None when it is on sale
+    pub fn get_rock_hold(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        rock_index: u128,
+    ) -> Option<U64> {
+        self.internal_rock_hold(&rock_token_id(&metaverse_id, zone_index, rock_index))
+            .map(U64)
+    }
+
+    /// held rock indexes of a zone in ascending order, paginated like get_unminted_rocks
+    pub fn get_held_rocks(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<u128> {
+        let zone = self.assert_zone_exist(&metaverse_id, zone_index);
+        let start_index = from_index
+            .map(u128::from)
+            .unwrap_or_default()
+            .max(zone.rock_index_from);
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        (start_index..=zone.rock_index_to)
+            .filter(|rock_index| {
+                self.internal_rock_hold(&rock_token_id(&metaverse_id, zone_index, *rock_index))
+                    .is_some()
+            })
+            .take(limit)
+            .collect()
+    }
+
+    // holds expire by themselves, an expired entry is ignored and dropped at the mint
+    pub(crate) fn internal_rock_hold(&self, token_id: &TokenId) -> Option<u64> {
+        self.rock_holds
+            .get(token_id)
+            .filter(|until_timestamp| env::block_timestamp() < *until_timestamp)
+    }
+
+    pub(crate) fn assert_rock_not_held(&self, token_id: &TokenId) {
+        require!(
+            self.internal_rock_hold(token_id).is_none()
+                || env::predecessor_account_id() == self.tokens.owner_id,
+            RoveError::RockOnHold.as_str()
+        );
+    }
+}
+
+fn emit_rock_hold_log(
+    variant: fn(Vec<RockHoldLog>) -> EventLogVariant,
+    metaverse_id: String,
+    zone_index: u16,
+    rock_indices: Vec<u128>,
+    until_timestamp: Option<U64>,
+    reason: Option<String>,
+) {
+    let rock_hold_log: EventLog = EventLog {
+        standard: "rock_hold".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![RockHoldLog {
+            metaverse_id,
+            zone_index,
+            rock_indices,
+            until_timestamp,
+            memo: reason,
+        }]),
+    };
+    rock_hold_log.emit();
+}
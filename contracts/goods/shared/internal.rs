@@ -0,0 +1,40 @@
+use near_sdk::require;
+use crate::*;
+
+pub(crate) fn assert_at_least_one_yocto() {
+    require!(
+        env::attached_deposit() >= 1,
+        RoveError::DepositRequired.as_str()
+    )
+}
+
+// token id has format {metaverse_id}:{zone_index}:{rock_index}
+pub(crate) fn metaverse_id_of_token(token_id: &TokenId) -> String {
+    let token_id_parts: Vec<&str> = token_id.split(':').collect();
+    require!(
+        token_id_parts.len() == 3,
+        RoveError::InvalidTokenId.as_str()
+    );
+    token_id_parts[0].to_string()
+}
+
+pub(crate) fn gen_zone_key(metaverse_id: &String, zone_index: u16) -> String {
+    format!("{}:{}", metaverse_id, zone_index)
+}
+
+// zone key ({metaverse_id}:{zone_index}) of the zone a token was minted in
+pub(crate) fn zone_key_of_token(token_id: &TokenId) -> String {
+    let token_id_parts: Vec<&str> = token_id.split(':').collect();
+    require!(
+        token_id_parts.len() == 3,
+        RoveError::InvalidTokenId.as_str()
+    );
+    let zone_index: u16 = token_id_parts[1]
+        .parse()
+        .unwrap_or_else(|_| env::panic_str(RoveError::InvalidTokenId.as_str()));
+    gen_zone_key(&token_id_parts[0].to_string(), zone_index)
+}
+
+pub(crate) fn zone_rock_size(zone: &Zone) -> u128 {
+    zone.rock_index_to - zone.rock_index_from + 1
+}
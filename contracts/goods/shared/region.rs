@@ -0,0 +1,280 @@
+use crate::*;
+
+pub const MAX_REGION_ROCKS: usize = 100;
+
+#[near_bindgen]
+impl Contract {
+    /// owner wraps rocks of one metaverse into a region token, the rocks are held by the
+    /// contract until unwrap_region. At least 1 yoctoNEAR plus storage must be attached
+    #[payable]
+    pub fn wrap_region(
+        &mut self,
+        token_ids: Vec<TokenId>,
+        token_metadata: TokenMetadata,
+    ) -> TokenId {
+        assert_at_least_one_yocto();
+        require!(
+            token_ids.len() >= 2,
+            RoveError::InvalidArgument.detail("a region needs at least 2 rocks")
+        );
+        require!(
+            token_ids.len() <= MAX_REGION_ROCKS,
+            RoveError::TooManyItems.detail("too many rocks in region")
+        );
+        let owner_id = env::predecessor_account_id();
+        self.assert_not_denied(&owner_id);
+        let metaverse_id = metaverse_id_of_token(&token_ids[0]);
+        let contract_id = env::current_account_id();
+
+        let initial_storage_usage = env::storage_usage();
+        for (index, token_id) in token_ids.iter().enumerate() {
+            require!(
+                !token_ids[..index].contains(token_id),
+                RoveError::InvalidArgument.detail(format!("duplicate token_id {}", token_id))
+            );
+            require!(
+                self.regions.get(token_id).is_none(),
+                RoveError::InvalidArgument.detail("a region can not be wrapped")
+            );
+            require!(
+                metaverse_id_of_token(token_id) == metaverse_id,
+                RoveError::InvalidArgument.detail("rocks of a region must be in one metaverse")
+            );
+            let token_owner_id = self
+                .tokens
+                .owner_by_id
+                .get(token_id)
+                .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+            require!(
+                token_owner_id == owner_id,
+                RoveError::NotTokenOwner.as_str()
+            );
+
+            self.internal_release_escrow(token_id);
+            if let Some(approved_account_ids) = self
+                .tokens
+                .approvals_by_id
+                .as_mut()
+                .and_then(|by_id| by_id.remove(token_id))
+            {
+                refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+            }
+            self.internal_transfer_token(token_id, &owner_id, &contract_id);
+        }
+
+        let region_id = format!("{}:region:{}", metaverse_id, self.next_region_id);
+        self.next_region_id += 1;
+        self.tokens.internal_mint_with_refund(
+            region_id.clone(),
+            owner_id.clone(),
+            Some(token_metadata),
+            None,
+        );
+        self.regions.insert(&region_id, &token_ids);
+        for token_id in token_ids.iter() {
+            self.wrapped_rocks.insert(token_id, &region_id);
+        }
+        self.internal_update_holding(&region_id, None, Some(&owner_id));
+        refund_deposit_to_account(
+            env::storage_usage() - initial_storage_usage,
+            owner_id.clone(),
+        );
+
+        let nft_transfer_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                authorized_id: None,
+                old_owner_id: owner_id.to_string(),
+                new_owner_id: contract_id.to_string(),
+                token_ids: token_ids.clone(),
+                memo: Some(String::from("wrap_region")),
+            }]),
+        };
+        nft_transfer_log.emit();
+        let nft_mint_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftMint(vec![NftMintLog {
+                owner_id: owner_id.to_string(),
+                token_ids: vec![region_id.clone()],
+                memo: Some(String::from("wrap_region")),
+            }]),
+        };
+        nft_mint_log.emit();
+        let mut activities: Vec<AccountActivityLog> = token_ids
+            .iter()
+            .map(|token_id| {
+                AccountActivityLog::new(
+                    &owner_id,
+                    ActivityAction::Transfer,
+                    token_id,
+                    Some(&contract_id),
+                    None,
+                )
+            })
+            .collect();
+        activities.push(AccountActivityLog::new(
+            &owner_id,
+            ActivityAction::Mint,
+            &region_id,
+            None,
+            None,
+        ));
+        emit_account_activity(activities);
+        emit_region_log(
+            EventLogVariant::RegionWrapped,
+            &region_id,
+            &owner_id,
+            token_ids,
+        );
+
+        region_id
+    }
+
+    /// region owner burns the region token and gets its rocks back
+    #[payable]
+    pub fn unwrap_region(&mut self, region_id: TokenId) -> Vec<TokenId> {
+        assert_one_yocto();
+        let token_ids = self
+            .regions
+            .get(&region_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::RegionNotFound.as_str()));
+        let owner_id = env::predecessor_account_id();
+        self.assert_not_denied(&owner_id);
+        let region_owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&region_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+        require!(
+            region_owner_id == owner_id,
+            RoveError::NotTokenOwner.as_str()
+        );
+
+        // burn the region token
+        self.internal_update_holding(&region_id, Some(&owner_id), None);
+        self.tokens.owner_by_id.remove(&region_id);
+        if let Some(token_metadata_by_id) = &mut self.tokens.token_metadata_by_id {
+            token_metadata_by_id.remove(&region_id);
+        }
+        if let Some(tokens_per_owner) = &mut self.tokens.tokens_per_owner {
+            let mut owner_tokens = tokens_per_owner
+                .get(&owner_id)
+                .unwrap_or_else(|| env::panic_str(RoveError::TokenNotFound.as_str()));
+            owner_tokens.remove(&region_id);
+            if owner_tokens.is_empty() {
+                tokens_per_owner.remove(&owner_id);
+            } else {
+                tokens_per_owner.insert(&owner_id, &owner_tokens);
+            }
+        }
+        if let Some(approved_account_ids) = self
+            .tokens
+            .approvals_by_id
+            .as_mut()
+            .and_then(|by_id| by_id.remove(&region_id))
+        {
+            refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+        }
+        if let Some(next_approval_id_by_id) = &mut self.tokens.next_approval_id_by_id {
+            next_approval_id_by_id.remove(&region_id);
+        }
+        self.frozen_metadata.remove(&region_id);
+        self.gift_messages.remove(&region_id);
+        self.regions.remove(&region_id);
+
+        let contract_id = env::current_account_id();
+        for token_id in token_ids.iter() {
+            self.wrapped_rocks.remove(token_id);
+            self.internal_transfer_token(token_id, &contract_id, &owner_id);
+        }
+
+        let nft_burn_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                owner_id: owner_id.to_string(),
+                token_ids: vec![region_id.clone()],
+                memo: Some(String::from("unwrap_region")),
+            }]),
+        };
+        nft_burn_log.emit();
+        let nft_transfer_log: EventLog = EventLog {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_METADATA_SPEC.to_string(),
+            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                authorized_id: None,
+                old_owner_id: contract_id.to_string(),
+                new_owner_id: owner_id.to_string(),
+                token_ids: token_ids.clone(),
+                memo: Some(String::from("unwrap_region")),
+            }]),
+        };
+        nft_transfer_log.emit();
+        emit_account_activity(
+            token_ids
+                .iter()
+                .map(|token_id| {
+                    AccountActivityLog::new(
+                        &contract_id,
+                        ActivityAction::Transfer,
+                        token_id,
+                        Some(&owner_id),
+                        None,
+                    )
+                })
+                .collect(),
+        );
+        emit_region_log(
+            EventLogVariant::RegionUnwrapped,
+            &region_id,
+            &owner_id,
+            token_ids.clone(),
+        );
+
+        token_ids
+    }
+
+    /// rocks wrapped in a region
+    pub fn get_region_tokens(
+        &self,
+        region_id: TokenId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<TokenId> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        self.regions
+            .get(&region_id)
+            .unwrap_or_default()
+            .into_iter()
+            .skip(start_index as usize)
+            .take(limit)
+            .collect()
+    }
+
+    /// region a rock is wrapped in
+    pub fn get_region_of_rock(&self, token_id: TokenId) -> Option<TokenId> {
+        self.wrapped_rocks.get(&token_id)
+    }
+}
+
+fn emit_region_log(
+    variant: fn(Vec<RegionLog>) -> EventLogVariant,
+    region_id: &TokenId,
+    owner_id: &AccountId,
+    token_ids: Vec<TokenId>,
+) {
+    let region_log: EventLog = EventLog {
+        standard: "region".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![RegionLog {
+            region_id: region_id.clone(),
+            owner_id: owner_id.to_string(),
+            token_ids,
+            memo: None,
+        }]),
+    };
+    region_log.emit();
+}
@@ -0,0 +1,275 @@
+use near_sdk::json_types::U64;
+use near_sdk::{ext_contract, Gas, PromiseResult};
+
+use crate::*;
+
+pub const GAS_FOR_RESOLVE_PAYOUT: Gas = Gas(5_000_000_000_000);
+pub const GAS_FOR_NEAR_DEPOSIT: Gas = Gas(5_000_000_000_000);
+pub const GAS_FOR_NEAR_WITHDRAW: Gas = Gas(10_000_000_000_000);
+pub const GAS_FOR_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+pub const GAS_FOR_RESOLVE_NEAR_WITHDRAW: Gas = Gas(5_000_000_000_000);
+pub const GAS_FOR_RESOLVE_FT_PAYOUT: Gas = Gas(25_000_000_000_000);
+pub const GAS_FOR_RESOLVE_WRAP_PAYOUT: Gas = Gas(45_000_000_000_000);
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FailedPayout {
+    pub payout_id: u64,
+    pub receiver_id: AccountId,
+    pub amount: U128,
+    pub failed_at: U64,
+    // set when the amount is held as wNEAR of this token instead of native NEAR
+    pub wnear_id: Option<AccountId>,
+}
+
+// how the treasury receives its share of proceeds
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TreasurySettlement {
+    Native,
+    // proceeds are wrapped and sent with ft_transfer, treasury must be registered on wnear_id
+    Wnear { wnear_id: AccountId },
+}
+
+#[ext_contract(ext_wnear)]
+pub trait Wnear {
+    fn near_deposit(&mut self);
+    fn near_withdraw(&mut self, amount: U128);
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+#[ext_contract(ext_payout_resolver)]
+pub trait PayoutResolver {
+    fn resolve_payout(&mut self, receiver_id: AccountId, amount: U128);
+    fn resolve_wrap_payout(&mut self, receiver_id: AccountId, amount: U128, wnear_id: AccountId);
+    fn resolve_ft_payout(&mut self, receiver_id: AccountId, amount: U128, wnear_id: AccountId);
+    fn resolve_near_withdraw(&mut self, receiver_id: AccountId, amount: U128, wnear_id: AccountId);
+}
+
+#[near_bindgen]
+impl Contract {
+    // This is callback function of internal_payout (private, CAN NOT CALL DIRECTLY)
+    #[private]
+    pub fn resolve_payout(&mut self, receiver_id: AccountId, amount: U128) {
+        require!(
+            env::promise_results_count() == 1,
+            RoveError::NotCallback.as_str()
+        );
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            return;
+        }
+
+        // the transferred amount came back to this account, keep it claimable by receiver_id
+        self.internal_record_failed_payout(receiver_id, amount, None);
+    }
+
+    // This is callback function of near_deposit for wNEAR payouts (private, CAN NOT CALL DIRECTLY)
+    #[private]
+    pub fn resolve_wrap_payout(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        wnear_id: AccountId,
+    ) {
+        require!(
+            env::promise_results_count() == 1,
+            RoveError::NotCallback.as_str()
+        );
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => self.internal_ft_payout(receiver_id, amount, wnear_id),
+            // the deposit of near_deposit came back to this account
+            _ => self.internal_record_failed_payout(receiver_id, amount, None),
+        }
+    }
+
+    // This is callback function of ft_transfer for wNEAR payouts (private, CAN NOT CALL DIRECTLY)
+    #[private]
+    pub fn resolve_ft_payout(&mut self, receiver_id: AccountId, amount: U128, wnear_id: AccountId) {
+        require!(
+            env::promise_results_count() == 1,
+            RoveError::NotCallback.as_str()
+        );
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            return;
+        }
+
+        // unwrap the wNEAR left on this account, the payout is retried in the current settlement mode
+        ext_wnear::near_withdraw(amount, wnear_id.clone(), 1, GAS_FOR_NEAR_WITHDRAW).then(
+            ext_payout_resolver::resolve_near_withdraw(
+                receiver_id,
+                amount,
+                wnear_id,
+                env::current_account_id(),
+                0,
+                GAS_FOR_RESOLVE_NEAR_WITHDRAW,
+            ),
+        );
+    }
+
+    // This is callback function of near_withdraw for wNEAR payouts (private, CAN NOT CALL DIRECTLY)
+    #[private]
+    pub fn resolve_near_withdraw(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        wnear_id: AccountId,
+    ) {
+        require!(
+            env::promise_results_count() == 1,
+            RoveError::NotCallback.as_str()
+        );
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => {
+                self.internal_record_failed_payout(receiver_id, amount, None)
+            }
+            // the amount is still wNEAR on this account, it is owed and retried as wNEAR
+            _ => self.internal_record_failed_payout(receiver_id, amount, Some(wnear_id)),
+        }
+    }
+
+    /// treasury opts into receiving its proceeds as native NEAR or as wNEAR, wNEAR is not
+    /// available while the proceeds are split between treasury beneficiaries
+    #[payable]
+    pub fn set_treasury_settlement(&mut self, mode: TreasurySettlement) {
+        assert_at_least_one_yocto();
+        require!(
+            env::predecessor_account_id() == self.treasury_id,
+            RoveError::Unauthorized.as_str()
+        );
+        require!(
+            mode == TreasurySettlement::Native || self.treasury_beneficiaries.is_empty(),
+            RoveError::InvalidArgument
+                .detail("treasury beneficiaries are paid in native NEAR only")
+        );
+        self.treasury_settlement = mode;
+    }
+
+    pub fn get_treasury_settlement(&self) -> TreasurySettlement {
+        self.treasury_settlement.clone()
+    }
+
+    /// send a failed payout again, callable by its receiver or the operator
+    #[payable]
+    pub fn retry_payout(&mut self, payout_id: u64) {
+        assert_at_least_one_yocto();
+        let failed_payout = self
+            .failed_payouts
+            .get(&payout_id)
+            .unwrap_or_else(|| env::panic_str(RoveError::PayoutNotFound.as_str()));
+        let caller_id = env::predecessor_account_id();
+        require!(
+            caller_id == failed_payout.receiver_id || caller_id == self.tokens.owner_id,
+            RoveError::Unauthorized.as_str()
+        );
+
+        self.failed_payouts.remove(&payout_id);
+        emit_payout_log(EventLogVariant::PayoutRetried, &failed_payout);
+        match failed_payout.wnear_id {
+            Some(wnear_id) => {
+                self.internal_ft_payout(failed_payout.receiver_id, failed_payout.amount, wnear_id)
+            }
+            None => self.internal_payout(failed_payout.receiver_id, failed_payout.amount.0),
+        }
+    }
+
+    pub fn get_failed_payouts(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<FailedPayout> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        self.failed_payouts
+            .values()
+            .skip(start_index as usize)
+            .take(limit)
+            .collect()
+    }
+
+    // transfer amount to receiver_id, a failed transfer is recorded in failed_payouts instead of lost
+    pub(crate) fn internal_payout(&mut self, receiver_id: AccountId, amount: Balance) {
+        if let TreasurySettlement::Wnear { wnear_id } = self.treasury_settlement.clone() {
+            if receiver_id == self.treasury_id {
+                ext_wnear::near_deposit(wnear_id.clone(), amount, GAS_FOR_NEAR_DEPOSIT).then(
+                    ext_payout_resolver::resolve_wrap_payout(
+                        receiver_id,
+                        U128(amount),
+                        wnear_id,
+                        env::current_account_id(),
+                        0,
+                        GAS_FOR_RESOLVE_WRAP_PAYOUT,
+                    ),
+                );
+                return;
+            }
+        }
+        self.internal_native_payout(receiver_id, amount);
+    }
+
+    // transfer amount to receiver_id as native NEAR whatever the treasury settlement
+    pub(crate) fn internal_native_payout(&mut self, receiver_id: AccountId, amount: Balance) {
+        Promise::new(receiver_id.clone()).transfer(amount).then(
+            ext_payout_resolver::resolve_payout(
+                receiver_id,
+                U128(amount),
+                env::current_account_id(),
+                0,
+                GAS_FOR_RESOLVE_PAYOUT,
+            ),
+        );
+    }
+
+    // send wNEAR held by this account to receiver_id
+    fn internal_ft_payout(&mut self, receiver_id: AccountId, amount: U128, wnear_id: AccountId) {
+        ext_wnear::ft_transfer(
+            receiver_id.clone(),
+            amount,
+            None,
+            wnear_id.clone(),
+            1,
+            GAS_FOR_FT_TRANSFER,
+        )
+        .then(ext_payout_resolver::resolve_ft_payout(
+            receiver_id,
+            amount,
+            wnear_id,
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_FT_PAYOUT,
+        ));
+    }
+
+    pub(crate) fn internal_record_failed_payout(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        wnear_id: Option<AccountId>,
+    ) {
+        let payout_id = self.next_payout_id;
+        self.next_payout_id += 1;
+        let failed_payout = FailedPayout {
+            payout_id,
+            receiver_id,
+            amount,
+            failed_at: U64(env::block_timestamp()),
+            wnear_id,
+        };
+        self.failed_payouts.insert(&payout_id, &failed_payout);
+        emit_payout_log(EventLogVariant::PayoutFailed, &failed_payout);
+    }
+}
+
+fn emit_payout_log(variant: fn(Vec<PayoutLog>) -> EventLogVariant, failed_payout: &FailedPayout) {
+    let payout_log: EventLog = EventLog {
+        standard: "payout".to_string(),
+        version: "1.0.0".to_string(),
+        event: variant(vec![PayoutLog {
+            payout_id: failed_payout.payout_id,
+            receiver_id: failed_payout.receiver_id.to_string(),
+            amount: failed_payout.amount,
+            memo: None,
+        }]),
+    };
+
+    payout_log.emit();
+}
@@ -0,0 +1,162 @@
+use crate::*;
+
+pub const MAX_ZONE_RECEIVERS_PER_CALL: usize = 100;
+
+// zone whose rocks can only be owned by the eligible receivers set by the metaverse owner
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ZoneReceiverRestriction {
+    // also reject transfers of the zone rocks to accounts that are not eligible
+    pub on_transfer: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// metaverse owner restricts (or lifts with None) who may receive the rocks of a zone, e.g.
+    /// KYC'd buyers of a regulated partner district. Unlike minter allowlists this applies to
+    /// the receiver of the mint, and to transfers when `on_transfer` is set
+    #[payable]
+    pub fn set_zone_receiver_restriction(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        restriction: Option<ZoneReceiverRestriction>,
+    ) {
+        self.assert_metaverse_owner(&metaverse_id);
+        self.assert_zone_exist(&metaverse_id, zone_index);
+
+        let initial_storage_usage = env::storage_usage();
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        match restriction {
+            Some(restriction) => self
+                .zone_receiver_restrictions
+                .insert(&zone_key, &restriction),
+            None => self.zone_receiver_restrictions.remove(&zone_key),
+        };
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    /// metaverse owner adds eligible receivers of a zone, at least 1 yoctoNEAR plus storage
+    /// must be attached
+    #[payable]
+    pub fn add_zone_receivers(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        account_ids: Vec<AccountId>,
+    ) {
+        self.assert_metaverse_owner(&metaverse_id);
+        self.assert_zone_exist(&metaverse_id, zone_index);
+        require!(!account_ids.is_empty(), RoveError::EmptyBatch.as_str());
+        require!(
+            account_ids.len() <= MAX_ZONE_RECEIVERS_PER_CALL,
+            RoveError::TooManyItems.detail("too many account_ids")
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        for account_id in account_ids.iter() {
+            self.zone_receivers
+                .insert(&gen_zone_receiver_key(&zone_key, account_id));
+        }
+        if env::storage_usage() > initial_storage_usage {
+            refund_deposit_to_account(
+                env::storage_usage() - initial_storage_usage,
+                env::predecessor_account_id(),
+            );
+        }
+    }
+
+    /// metaverse owner removes eligible receivers of a zone, rocks they own are not affected
+    #[payable]
+    pub fn remove_zone_receivers(
+        &mut self,
+        metaverse_id: String,
+        zone_index: u16,
+        account_ids: Vec<AccountId>,
+    ) {
+        self.assert_metaverse_owner(&metaverse_id);
+        require!(!account_ids.is_empty(), RoveError::EmptyBatch.as_str());
+        require!(
+            account_ids.len() <= MAX_ZONE_RECEIVERS_PER_CALL,
+            RoveError::TooManyItems.detail("too many account_ids")
+        );
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        for account_id in account_ids.iter() {
+            self.zone_receivers
+                .remove(&gen_zone_receiver_key(&zone_key, account_id));
+        }
+    }
+
+    pub fn get_zone_receiver_restriction(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+    ) -> Option<ZoneReceiverRestriction> {
+        self.zone_receiver_restrictions
+            .get(&gen_zone_key(&metaverse_id, zone_index))
+    }
+
+    /// whether account_id may receive rocks of the zone, always true without a restriction
+    pub fn is_zone_receiver(
+        &self,
+        metaverse_id: String,
+        zone_index: u16,
+        account_id: AccountId,
+    ) -> bool {
+        let zone_key = gen_zone_key(&metaverse_id, zone_index);
+        self.zone_receiver_restrictions.get(&zone_key).is_none()
+            || self
+                .zone_receivers
+                .contains(&gen_zone_receiver_key(&zone_key, &account_id))
+    }
+
+    // checked at mint whatever on_transfer is
+    pub(crate) fn assert_zone_receiver(
+        &self,
+        metaverse_id: &str,
+        zone_index: u16,
+        receiver_id: &AccountId,
+    ) {
+        require!(
+            self.is_zone_receiver(metaverse_id.to_string(), zone_index, receiver_id.clone()),
+            RoveError::NotZoneReceiver.detail(receiver_id)
+        );
+    }
+
+    // a region carries the restrictions of the rocks wrapped in it, the contract itself
+    // (installments, regions) is always eligible
+    pub(crate) fn assert_transfer_receiver(&self, token_id: &TokenId, receiver_id: &AccountId) {
+        if *receiver_id == env::current_account_id() {
+            return;
+        }
+        let token_ids = self
+            .regions
+            .get(token_id)
+            .unwrap_or_else(|| vec![token_id.clone()]);
+        for token_id in token_ids.iter() {
+            let zone_key = zone_key_of_token(token_id);
+            let on_transfer = self
+                .zone_receiver_restrictions
+                .get(&zone_key)
+                .map(|restriction| restriction.on_transfer)
+                .unwrap_or(false);
+            require!(
+                !on_transfer
+                    || self
+                        .zone_receivers
+                        .contains(&gen_zone_receiver_key(&zone_key, receiver_id)),
+                RoveError::NotZoneReceiver.detail(receiver_id)
+            );
+        }
+    }
+}
+
+fn gen_zone_receiver_key(zone_key: &String, account_id: &AccountId) -> String {
+    format!("{}:{}", zone_key, account_id)
+}
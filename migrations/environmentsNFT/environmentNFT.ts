@@ -1,7 +1,8 @@
 import * as fs from "fs";
 
 const nearAPI = require("near-api-js");
-const {connect, utils, KeyPair} = nearAPI;
+const {connect, utils, KeyPair, transactions} = nearAPI;
+const BN = require("bn.js");
 
 const nearConfig = require("../../near.config");
 
@@ -79,6 +80,25 @@ class EnvironmentNFT {
         }
     }
 
+    // deploy the new code and migrate the stored state in a single transaction, so the
+    // contract is never left with code that cannot read its state
+    async upgrade(wasmFile: string, contractAccountID: string) {
+        this.near = await connect(this.config);
+        try {
+            const contractAccount = await this.near.account(contractAccountID);
+            const response = await contractAccount.signAndSendTransaction({
+                receiverId: contractAccountID,
+                actions: [
+                    transactions.deployContract(fs.readFileSync(wasmFile)),
+                    transactions.functionCall("migrate", {}, new BN("300000000000000"), new BN("0")),
+                ],
+            });
+            console.log("upgrade on:", response.transaction.hash);
+        } catch (e) {
+            console.log(e);
+        }
+    }
+
     async init(contractAccountId: string, account: any, adminId: string, operatorId: string, treasuryId: string, contractMetadata: any) {
         const contract = new nearAPI.Contract(account, contractAccountId, {
             viewMethods: ['nft_metadata'],
@@ -224,7 +244,7 @@ class EnvironmentNFT {
             });
             const response = await contract.update_token_price({
                 args: {
-                    updated_price_in_string: utils.format.parseNearAmount(newTokenPrice)
+                    updated_price: utils.format.parseNearAmount(newTokenPrice)
                 },
                 amount: "1"
             });
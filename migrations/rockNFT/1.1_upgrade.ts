@@ -12,9 +12,7 @@ import {RockNFT} from "./rockNFT";
         }
         const wasm = process.argv[2];
         const contractAccountId = process.argv[3];
-        await nft.deploy(wasm, contractAccountId, "", "", "",
-            "", 0, "",
-            false);
+        await nft.upgrade(wasm, contractAccountId);
         console.log("Upgraded contract on contractAccountId:", contractAccountId)
     } catch (e) {
         console.log(e);
@@ -10,7 +10,7 @@ import {RockNFT} from "./rockNFT";
             console.log("wrong network");
             return;
         }
-        const typeZone = process.argv[2] || "";
+        const mintPolicy = process.argv[2] || ""; // core_team, holder_gated or public_fixed_price
         const zoneIndex = process.argv[3] || "";
         const collectionAddress = process.argv[4] || "";
         const rockIndexFrom = process.argv[5] || "";
@@ -22,7 +22,7 @@ import {RockNFT} from "./rockNFT";
         const attachDeposit = process.argv[11] || process.env.DEPOSIT || "";
 
         await nft.addZone(signerAccount, contractAccount, metaverseID, parseInt(zoneIndex),
-            parseInt(typeZone, 10),
+            mintPolicy,
             parseInt(rockIndexFrom, 10),
             parseInt(rockIndexTo, 10),
             price,
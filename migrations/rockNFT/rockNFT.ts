@@ -1,7 +1,8 @@
 import * as fs from "fs";
 
 const nearAPI = require("near-api-js");
-const {connect, utils, KeyPair} = nearAPI;
+const {connect, utils, KeyPair, transactions} = nearAPI;
+const BN = require("bn.js");
 const nearConfig = require("../../near.config");
 
 class RockNFT {
@@ -77,6 +78,25 @@ class RockNFT {
         }
     }
 
+    // deploy the new code and migrate the stored state in a single transaction, so the
+    // contract is never left with code that cannot read its state
+    async upgrade(wasmFile: string, contractAccountID: string) {
+        this.near = await connect(this.config);
+        try {
+            const contractAccount = await this.near.account(contractAccountID);
+            const response = await contractAccount.signAndSendTransaction({
+                receiverId: contractAccountID,
+                actions: [
+                    transactions.deployContract(fs.readFileSync(wasmFile)),
+                    transactions.functionCall("migrate", {}, new BN("300000000000000"), new BN("0")),
+                ],
+            });
+            console.log("upgrade on:", response.transaction.hash);
+        } catch (e) {
+            console.log(e);
+        }
+    }
+
     async initMetaverse(signerAccountId: string, contractAccountID: string, metaverseID: string, totalSupply: number, price: string, attachedDeposit: string) {
         this.near = await connect(this.config);
         try {
@@ -92,7 +112,7 @@ class RockNFT {
                     price: utils.format.parseNearAmount(price),
                     core_team_addr: '',
                     collection_addr: '',
-                    type_zone: 3,
+                    mint_policy: 'public_fixed_price',
                     rock_index_from: 2,
                     rock_index_to: totalSupply + 1,
                 }
@@ -179,7 +199,7 @@ class RockNFT {
     }
 
     async addZone(signerAccountId: string, contractAccountID: string, metaverseID: string, zoneIndex: number,
-                  typeZone: number,
+                  mintPolicy: string,
                   rockIndexFrom: number, rockIndexTo: number,
                   price: string, collectionAddress: string,  attachedDeposit: string) {
         this.near = await connect(this.config);
@@ -196,7 +216,7 @@ class RockNFT {
                     price: utils.format.parseNearAmount(price),
                     core_team_addr: '',
                     collection_addr: collectionAddress,
-                    type_zone: typeZone,
+                    mint_policy: mintPolicy,
                     rock_index_from: rockIndexFrom,
                     rock_index_to: rockIndexTo,
                 }
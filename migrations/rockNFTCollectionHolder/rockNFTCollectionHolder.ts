@@ -1,7 +1,8 @@
 import * as fs from "fs";
 
 const nearAPI = require("near-api-js");
-const {connect, utils, KeyPair} = nearAPI;
+const {connect, utils, KeyPair, transactions} = nearAPI;
+const BN = require("bn.js");
 const nearConfig = require("../../near.config");
 
 class RockNFTCollectionHolder {
@@ -78,6 +79,25 @@ class RockNFTCollectionHolder {
         }
     }
 
+    // deploy the new code and migrate the stored state in a single transaction, so the
+    // contract is never left with code that cannot read its state
+    async upgrade(wasmFile: string, contractAccountID: string) {
+        this.near = await connect(this.config);
+        try {
+            const contractAccount = await this.near.account(contractAccountID);
+            const response = await contractAccount.signAndSendTransaction({
+                receiverId: contractAccountID,
+                actions: [
+                    transactions.deployContract(fs.readFileSync(wasmFile)),
+                    transactions.functionCall("migrate", {}, new BN("300000000000000"), new BN("0")),
+                ],
+            });
+            console.log("upgrade on:", response.transaction.hash);
+        } catch (e) {
+            console.log(e);
+        }
+    }
+
     async initMetaverse(signerAccountId: string, contractAccountID: string, metaverseID: string, totalSupply: number,
                         price: string, collectionAddress: string, attachedDeposit: string) {
         this.near = await connect(this.config);
@@ -94,9 +114,9 @@ class RockNFTCollectionHolder {
                     price: utils.format.parseNearAmount(price),
                     core_team_addr: '',
                     collection_addr: collectionAddress,
-                    type_zone: 2,
+                    mint_policy: 'holder_gated',
                     rock_index_from: 2,
-                    rock_index_to: 501, // contract dont use this argument
+                    rock_index_to: 501, // must not exceed get_collection_max_zone_size + 1
                 }
             }
             console.log("call initMetaverse with args", args);
@@ -109,7 +129,7 @@ class RockNFTCollectionHolder {
     }
 
     async addZone(signerAccountId: string, contractAccountID: string, metaverseID: string, zoneIndex: number,
-                typeZone: number,
+                mintPolicy: string,
                 rockIndexFrom: number, rockIndexTo: number,
                 price: string, collectionAddress: string,  attachedDeposit: string) {
         this.near = await connect(this.config);
@@ -126,7 +146,7 @@ class RockNFTCollectionHolder {
                     price: utils.format.parseNearAmount(price),
                     core_team_addr: '',
                     collection_addr: collectionAddress,
-                    type_zone: typeZone,
+                    mint_policy: mintPolicy,
                     rock_index_from: rockIndexFrom,
                     rock_index_to: rockIndexTo,
                 }